@@ -0,0 +1,92 @@
+use crate::profile::Profile;
+use crate::strings::{fill, tr};
+
+// --- Achievements ---
+// People (never bots) earn achievements for standout games, kept with the
+// rest of their profile as an `achievements = ...` line. Each is announced
+// with a banner the moment it's unlocked, and only the first time.
+
+/// Codes this long or longer count for `Achievement::LongCode`.
+const LONG_CODE: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Achievement {
+    QuickSolve, // Cracked a code in three guesses or fewer
+    HatTrick,   // Won three multi-player games in a row
+    NoHints,    // Won a multi-player game without a hint
+    LongCode,   // Cracked a code of six digits or more
+}
+
+/// How a player cracked their code, for checking what it earns.
+#[derive(Debug, Clone, Copy)]
+pub struct Performance {
+    pub guesses: u32,
+    pub hints: u32,
+    pub code_length: usize,
+    pub streak: Option<usize>, // Multi-player wins in a row including this one, for an outright win
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 4] = [Achievement::QuickSolve, Achievement::HatTrick, Achievement::NoHints, Achievement::LongCode];
+
+    /// The name used in profile files.
+    pub fn id(self) -> &'static str {
+        match self {
+            Achievement::QuickSolve => "quick_solve",
+            Achievement::HatTrick => "hat_trick",
+            Achievement::NoHints => "no_hints",
+            Achievement::LongCode => "long_code",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Achievement> {
+        Achievement::ALL.into_iter().find(|a| a.id() == id)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Achievement::QuickSolve => tr("achievement.quick_solve"),
+            Achievement::HatTrick => tr("achievement.hat_trick"),
+            Achievement::NoHints => tr("achievement.no_hints"),
+            Achievement::LongCode => tr("achievement.long_code"),
+        }
+    }
+
+    /// What it takes, for the banner.
+    pub fn describe(self) -> &'static str {
+        match self {
+            Achievement::QuickSolve => tr("achievement.quick_solve_about"),
+            Achievement::HatTrick => tr("achievement.hat_trick_about"),
+            Achievement::NoHints => tr("achievement.no_hints_about"),
+            Achievement::LongCode => tr("achievement.long_code_about"),
+        }
+    }
+
+    pub fn earned_by(self, performance: &Performance) -> bool {
+        match self {
+            Achievement::QuickSolve => performance.guesses <= 3,
+            Achievement::HatTrick => performance.streak.is_some_and(|streak| streak >= 3),
+            Achievement::NoHints => performance.streak.is_some() && performance.hints == 0,
+            Achievement::LongCode => performance.code_length >= LONG_CODE,
+        }
+    }
+}
+
+/// Adds whatever `performance` earns to `profile` and returns the
+/// achievements new to it, so only those are announced.
+pub fn unlock(profile: &mut Profile, performance: &Performance) -> Vec<Achievement> {
+    let new: Vec<Achievement> = Achievement::ALL.into_iter().filter(|a| a.earned_by(performance) && !profile.achievements.contains(a)).collect();
+    profile.achievements.extend(&new);
+    new
+}
+
+/// Prints the banner for achievements `name` has just unlocked.
+pub fn announce(name: &str, unlocked: &[Achievement]) {
+    if unlocked.is_empty() {
+        return;
+    }
+    outln!("\n🏅🏅🏅 {} 🏅🏅🏅", tr("achievement.banner"));
+    for achievement in unlocked {
+        outln!("{}", fill("achievement.unlocked", &[("name", &name), ("achievement", &achievement.name()), ("about", &achievement.describe())]));
+    }
+}
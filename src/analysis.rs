@@ -0,0 +1,287 @@
+use clap::Args;
+
+use crate::code::{calculate_score, format_code, is_solved, parse_guess, solved_score, Guess, Score};
+use crate::config::Config;
+use crate::feedback::{DpNotation, FeedbackRenderer};
+use crate::solver::{all_codes, expected_remaining, fewest_remaining_guess, filter_candidates, partition_sizes, score_index, Strategy, SCORE_SLOTS};
+use crate::strings::{fill, tr};
+
+// --- Solver Analysis ---
+// Command-line tooling for studying the solver strategies: trace how one
+// cracks a particular secret, play it against every possible secret and
+// report how many guesses it needs, or compare all strategies side by side.
+
+/// How a strategy fares over the whole code space.
+#[derive(Debug, Clone)]
+pub struct StrategyReport {
+    pub strategy: Strategy,
+    pub counts: Vec<usize>, // counts[n] is the number of secrets cracked on guess n + 1
+}
+
+impl StrategyReport {
+    pub fn secrets(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    pub fn average(&self) -> f64 {
+        let total_guesses: usize = self.counts.iter().enumerate().map(|(i, n)| (i + 1) * n).sum();
+        total_guesses as f64 / self.secrets() as f64
+    }
+
+    pub fn worst_case(&self) -> usize {
+        self.counts.len()
+    }
+}
+
+/// Plays `strategy` against `secret`, returning each guess with the
+/// number of candidates left after its feedback.
+pub fn trace_solve(strategy: Strategy, secret: &Guess) -> Vec<(Guess, usize)> {
+    let codes = all_codes();
+    let mut candidates = codes.clone();
+    let mut steps = Vec::new();
+
+    loop {
+        let guess = strategy.choose(&candidates, &codes).expect("the secret is always a candidate");
+        let score = calculate_score(&guess, secret);
+        candidates.retain(|candidate| calculate_score(&guess, candidate) == score);
+        steps.push((guess, candidates.len()));
+
+        if is_solved(score) {
+            return steps;
+        }
+    }
+}
+
+/// Walks the strategy's decision tree, counting how many secrets are cracked on each guess.
+fn tally_guess_counts(strategy: Strategy, candidates: &[Guess], codes: &[Guess], depth: usize, counts: &mut Vec<usize>) {
+    let guess = strategy.choose(candidates, codes).expect("a branch is never empty");
+
+    // Split the candidates by the feedback this guess would get
+    let mut branches: Vec<Vec<Guess>> = vec![Vec::new(); SCORE_SLOTS];
+    for candidate in candidates {
+        branches[score_index(calculate_score(&guess, candidate))].push(*candidate);
+    }
+
+    if counts.len() <= depth {
+        counts.resize(depth + 1, 0);
+    }
+
+    for (index, branch) in branches.iter().enumerate() {
+        if branch.is_empty() {
+            continue;
+        }
+        if index == score_index(solved_score()) {
+            counts[depth] += 1; // The guess was the secret itself
+        } else {
+            tally_guess_counts(strategy, branch, codes, depth + 1, counts);
+        }
+    }
+}
+
+/// Plays `strategy` against every possible secret.
+pub fn evaluate_strategy(strategy: Strategy) -> StrategyReport {
+    let codes = all_codes();
+    let mut counts = Vec::new();
+    tally_guess_counts(strategy, &codes, &codes, 0, &mut counts);
+    StrategyReport { strategy, counts }
+}
+
+/// Prints a strategy's path to one secret.
+fn print_trace(strategy: Strategy, secret: &Guess) {
+    outln!("{}", fill("analyze.trace", &[("strategy", &strategy.id()), ("secret", &format_code(secret))]));
+    for (turn, (guess, remaining)) in trace_solve(strategy, secret).iter().enumerate() {
+        let feedback = DpNotation.render(calculate_score(guess, secret));
+        outln!("{}", fill("analyze.step", &[("number", &(turn + 1)), ("guess", &format_code(guess)), ("score", &feedback), ("count", remaining)]));
+    }
+}
+
+/// Prints how many guesses a strategy needs across every possible secret.
+fn print_overview(strategy: Strategy) {
+    outln!("{}", fill("analyze.overview", &[("strategy", &strategy.id()), ("count", &all_codes().len())]));
+    outln!("{}", tr("analyze.building"));
+
+    let report = evaluate_strategy(strategy);
+    for (i, n) in report.counts.iter().enumerate() {
+        outln!("{}", fill("analyze.depth", &[("count", &(i + 1)), ("secrets", &format!("{:>5}", n))]));
+    }
+    outln!("{}", fill("analyze.summary", &[("average", &format!("{:.3}", report.average())), ("worst", &report.worst_case())]));
+}
+
+/// Prints every strategy's results in one table.
+fn print_comparison() {
+    outln!("{}", fill("analyze.comparison", &[("count", &all_codes().len())]));
+    outln!("{}", tr("analyze.building_all"));
+
+    let reports: Vec<StrategyReport> = Strategy::ALL.into_iter().map(evaluate_strategy).collect();
+    let longest = reports.iter().map(StrategyReport::worst_case).max().unwrap_or(0);
+
+    out!("{:<10}", tr("analyze.guesses"));
+    for report in &reports {
+        out!("{:>10}", report.strategy.id());
+    }
+    outln!();
+    for depth in 0..longest {
+        out!("{:<10}", depth + 1);
+        for report in &reports {
+            out!("{:>10}", report.counts.get(depth).copied().unwrap_or(0));
+        }
+        outln!();
+    }
+    out!("{:<10}", tr("analyze.average"));
+    for report in &reports {
+        out!("{:>10.3}", report.average());
+    }
+    outln!();
+    out!("{:<10}", tr("analyze.worst"));
+    for report in &reports {
+        out!("{:>10}", report.worst_case());
+    }
+    outln!();
+}
+
+// --- Post-Game Review ---
+// Looks back over a finished player's guesses. Each guess is rated by how many
+// candidates it could be expected to rule out, against the best guess that
+// was available at the time, so a lucky guess isn't mistaken for a good one.
+
+/// How one guess compared with the best available at that point.
+#[derive(Debug, Clone)]
+pub struct TurnReview {
+    pub guess: Guess,
+    pub score: Score,
+    pub before: usize,         // Candidates before the guess
+    pub after: usize,          // Candidates actually left by its feedback (0 once solved)
+    pub expected: f64,         // Candidates the guess leaves on average
+    pub best: Guess,           // The guess that leaves the fewest on average
+    pub best_expected: f64,
+}
+
+impl TurnReview {
+    /// The share of the best guess's expected eliminations this guess achieved, from 0.0 to 1.0.
+    pub fn efficiency(&self) -> f64 {
+        let best = self.before as f64 - self.best_expected;
+        if best <= 0.0 {
+            return 1.0;
+        }
+        ((self.before as f64 - self.expected) / best).clamp(0.0, 1.0)
+    }
+}
+
+/// Reviews a player's guesses, in order. `given` are clues the player had
+/// before their first guess, such as a handicap's free clues.
+pub fn review_turns(given: &[(Guess, Score)], turns: &[(Guess, Score)]) -> Vec<TurnReview> {
+    let codes = all_codes();
+    let mut candidates = codes.clone();
+    for (guess, score) in given {
+        filter_candidates(&mut candidates, guess, *score);
+    }
+    let mut reviews = Vec::new();
+
+    for &(guess, score) in turns {
+        let before = candidates.len();
+        let expected = expected_remaining(&partition_sizes(&guess, &candidates));
+        // Before any feedback every code splits the space the same way
+        let (best, best_expected) = if before == codes.len() {
+            (guess, expected)
+        } else {
+            fewest_remaining_guess(&candidates, &codes).unwrap_or((guess, expected))
+        };
+
+        filter_candidates(&mut candidates, &guess, score);
+        let after = if is_solved(score) { 0 } else { candidates.len() };
+        reviews.push(TurnReview { guess, score, before, after, expected, best, best_expected });
+    }
+    reviews
+}
+
+/// The average efficiency over a game, from 0.0 to 1.0. A guess made before
+/// any feedback is left out, since every opening is as good as any other.
+pub fn overall_efficiency(reviews: &[TurnReview]) -> f64 {
+    let space = all_codes().len();
+    let rated: Vec<f64> = reviews.iter().filter(|r| r.before < space).map(TurnReview::efficiency).collect();
+    if rated.is_empty() {
+        return 1.0; // Cracked on the first guess
+    }
+    rated.iter().sum::<f64>() / rated.len() as f64
+}
+
+fn efficiency_rating(efficiency: f64) -> &'static str {
+    match efficiency {
+        e if e >= 0.95 => tr("analyze.solver_grade"),
+        e if e >= 0.85 => tr("analyze.sharp"),
+        e if e >= 0.70 => tr("analyze.solid"),
+        e if e >= 0.50 => tr("analyze.getting_there"),
+        _ => tr("analyze.room_to_improve"),
+    }
+}
+
+/// Prints the review screen for one player's game.
+pub fn print_review(player: &str, given: &[(Guess, Score)], turns: &[(Guess, Score)]) {
+    outln!("{}", fill("analyze.review", &[("name", &player)]));
+    outln!("{}", tr("analyze.reviewing"));
+
+    let reviews = review_turns(given, turns);
+    outln!("\n{:<5} {:<6} {:<9} {:>7} {:>7} {:>9}   {:<6} {:>9} {:>6}",
+             tr("analyze.col_turn"), tr("analyze.col_guess"), tr("analyze.col_feedback"), tr("analyze.col_before"), tr("analyze.col_after"),
+             tr("analyze.col_avg_left"), tr("analyze.col_best"), tr("analyze.col_avg_left"), tr("analyze.col_rating"));
+    for (i, r) in reviews.iter().enumerate() {
+        let best = if r.best == r.guess { tr("analyze.same").to_string() } else { format_code(&r.best) };
+        outln!("{:<5} {:<6} {:<9} {:>7} {:>7} {:>9.1}   {:<6} {:>9.1} {:>5.0}%",
+                 i + 1, format_code(&r.guess), DpNotation.render(r.score), r.before, r.after,
+                 r.expected, best, r.best_expected, r.efficiency() * 100.0);
+    }
+
+    let efficiency = overall_efficiency(&reviews);
+    outln!("{}", fill("analyze.efficiency", &[("percent", &format!("{:.0}", efficiency * 100.0)), ("rating", &efficiency_rating(efficiency))]));
+    outln!("{}", tr("analyze.avg_left_note"));
+}
+
+/// The options of `analyze`.
+#[derive(Debug, Args)]
+pub struct AnalyzeArgs {
+    /// Follow the solver against this secret, instead of playing it against every one
+    pub code: Option<String>,
+    /// Play every strategy against every secret and compare them
+    #[arg(long, conflicts_with_all = ["code", "strategy"])]
+    pub compare: bool,
+    /// The solver to study
+    #[arg(long, value_enum, default_value_t = Strategy::Minimax)]
+    pub strategy: Strategy,
+    /// Threads the solver searches with (0 for one per core)
+    #[arg(long, value_name = "N")]
+    pub threads: Option<usize>,
+}
+
+/// The most secrets a whole decision tree is built over: every 4-digit one.
+/// Longer codes have too many for the tree to finish in reasonable time.
+const MAX_TREE_SECRETS: usize = 5040;
+
+/// Turns down a decision tree over more secrets than `MAX_TREE_SECRETS`.
+fn check_tree_size() -> Result<(), String> {
+    let secrets = all_codes().len();
+    if secrets > MAX_TREE_SECRETS {
+        return Err(fill("analyze.too_many", &[("count", &secrets)]));
+    }
+    Ok(())
+}
+
+/// Entry point for the `analyze` subcommand.
+pub fn run_analysis(args: &AnalyzeArgs) -> Result<(), String> {
+    if let Some(threads) = args.threads {
+        Config::set_current(Config { solver_threads: threads, ..Config::current() });
+    }
+    if args.compare {
+        check_tree_size()?;
+        print_comparison();
+        return Ok(());
+    }
+
+    match &args.code {
+        Some(code) => print_trace(args.strategy, &parse_guess(code).map_err(|e| format!("Invalid code '{}': {}", code, e))?),
+        None => {
+            check_tree_size()?;
+            print_overview(args.strategy);
+        }
+    }
+    Ok(())
+}
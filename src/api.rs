@@ -0,0 +1,556 @@
+use std::fmt::Write;
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::code::{calculate_score, code_length, format_code, generate_secret, is_solved, parse_guess, Guess, Score};
+use crate::game::{best_score, score_json};
+use crate::http::{read_request, write_response, Request, Response};
+use crate::json::{parse_object, Json};
+use crate::room::{room_code, session_token, unique_name};
+use crate::rules::Rules;
+use crate::stats::record_results;
+use crate::store::{new_game_id, now_timestamp, GameResult, StoreSet};
+use crate::strings::{fill, tr};
+use crate::transcript::Transcript;
+
+// --- HTTP API ---
+// `serve --http` runs Individual Secrets games for web and mobile frontends,
+// as JSON over HTTP, so they needn't speak the socket protocol `host` does:
+//
+//   POST    /games                {"seats": 3}      Open a game (2 seats if none are given)
+//   GET     /games                                  The games waiting for players
+//   GET     /games/<id>                             A game as it stands
+//   POST    /games/<id>/players   {"name": "Ann"}   Take a seat, getting a token back
+//   POST    /games/<id>/guesses   {"guess": "0123"} Guess on your turn, getting the score back
+//   DELETE  /games/<id>/players                     Leave the game
+//   GET     /metrics                                How the server is doing, for Prometheus
+//
+// The token goes in an `Authorization: Bearer <token>` header and says who is
+// asking. Given with `GET /games/<id>`, it adds a `you` field with the
+// player's own guesses and scores, which nobody else gets to see; everyone
+// sees how many guesses each player has made and who has finished where.
+// A game is:
+//
+//   {"id", "status": "waiting"|"playing"|"over", "seats", "length", "round", "turn", "moves",
+//    "players": [{"name", "guesses", "place", "left"}], "you": {"name", "history", "secret"}}
+//
+// `length` is how many digits each secret and guess has, as `serve` was
+// started with. `turn` is the name of the player to guess next, or null,
+// and the player's own `secret` is only given once the game is over.
+// `moves` goes up with every change, so a client can poll the game and only
+// redraw when it moves.
+// An error comes as {"error": message} with a 4xx status.
+//
+// The game starts once every seat is taken, with everyone's secret dealt.
+// Turns go round in seat order and players who crack their code in the same
+// round share a place, as in a hosted game. A player who doesn't guess
+// within two minutes loses the turn. Games live in memory only; finished
+// ones are cleared away after an hour, and so is a waiting or playing game
+// in which nobody has joined, left or guessed for half an hour. A game
+// everyone saw through is recorded in the stats like any other.
+//
+// `/metrics` answers in Prometheus's text format, without a token. It has
+// the games in memory by status, the players seated in games that aren't
+// over, and running totals of games started, guesses made and the time
+// and rounds that finished games took. The totals count every game since
+// the server started, including the ones cleared away since.
+//
+// Each connection is read and answered on a thread of its own, so a client
+// that's slow to send its request only holds up itself; the requests
+// themselves are handled one at a time, in the order they arrive. At most
+// 64 connections are open at once.
+
+pub const DEFAULT_HTTP_PORT: u16 = 8080;
+
+/// The most connections open at once.
+const MAX_CONNECTIONS: usize = 64;
+
+/// How long a player has for each guess before the turn passes.
+const TURN_TIME: Duration = Duration::from_secs(120);
+
+/// How long a game that's over is kept for its players to look at.
+const KEEP_FINISHED: Duration = Duration::from_secs(3600);
+
+/// How long a game that isn't over may go without anyone joining, leaving
+/// or guessing before it's taken for abandoned.
+const KEEP_IDLE: Duration = Duration::from_secs(30 * 60);
+
+/// The most games the server holds at once.
+const MAX_GAMES: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Waiting,
+    Playing,
+    Over,
+}
+
+impl Status {
+    fn id(self) -> &'static str {
+        match self {
+            Status::Waiting => "waiting",
+            Status::Playing => "playing",
+            Status::Over => "over",
+        }
+    }
+}
+
+/// One seat of a game on the server.
+struct Player {
+    name: String,
+    token: String,
+    secret: Guess,
+    history: Vec<(Guess, Score)>,
+    place: Option<usize>,
+    left: bool,
+}
+
+impl Player {
+    fn playing(&self) -> bool {
+        self.place.is_none() && !self.left
+    }
+}
+
+struct Game {
+    id: String,
+    seats: usize,
+    status: Status,
+    players: Vec<Player>,
+    turn: usize, // Index of the player to guess next, while playing
+    round: u32,
+    moves: u64,
+    turn_started: Instant,
+    active_at: Instant, // When a player last joined, left or guessed
+    started_at: Instant,
+    ended_at: Option<Instant>,
+    rank_to_assign: usize,
+    last_ranked_round: u32,
+    transcript: Transcript,
+}
+
+impl Game {
+    fn new(id: String, seats: usize) -> Game {
+        let now = Instant::now();
+        Game {
+            id,
+            seats,
+            status: Status::Waiting,
+            players: Vec::new(),
+            turn: 0,
+            round: 1,
+            moves: 0,
+            turn_started: now,
+            active_at: now,
+            started_at: now,
+            ended_at: None,
+            rank_to_assign: 1,
+            last_ranked_round: 0,
+            transcript: Transcript::default(),
+        }
+    }
+
+    /// How long the game has run, or ran if it's over.
+    fn length(&self) -> Duration {
+        self.ended_at.unwrap_or_else(Instant::now).duration_since(self.started_at)
+    }
+
+    fn player_with(&self, token: Option<&str>) -> Option<usize> {
+        let token = token?;
+        self.players.iter().position(|p| p.token == token)
+    }
+
+    fn start(&mut self) {
+        for player in &mut self.players {
+            player.secret = generate_secret();
+        }
+        self.status = Status::Playing;
+        self.started_at = Instant::now();
+        self.turn_started = self.started_at;
+        outln!("{}", fill("api.begins", &[("id", &self.id), ("names", &self.players.iter().map(|p| p.name.as_str()).collect::<Vec<_>>().join(", "))]));
+    }
+
+    /// Moves the turn on to the next player still guessing, starting a new
+    /// round on the way past the last seat, or ends the game if nobody is.
+    fn advance(&mut self, stores: &mut StoreSet) {
+        self.moves += 1;
+        self.turn_started = Instant::now();
+        let count = self.players.len();
+        let Some(step) = (1..=count).find(|step| self.players[(self.turn + step) % count].playing()) else {
+            self.finish(stores);
+            return;
+        };
+        if self.turn + step >= count {
+            self.round += 1;
+        }
+        self.turn = (self.turn + step) % count;
+    }
+
+    /// Passes every turn whose time has run out since the game was last looked at.
+    fn catch_up(&mut self, stores: &mut StoreSet) {
+        while self.status == Status::Playing && self.turn_started.elapsed() >= TURN_TIME {
+            let due = self.turn_started + TURN_TIME;
+            self.advance(stores);
+            self.turn_started = due;
+        }
+    }
+
+    fn finish(&mut self, stores: &mut StoreSet) {
+        self.status = Status::Over;
+        self.ended_at = Some(Instant::now());
+        outln!("{}", fill("api.over", &[("id", &self.id), ("count", &self.round)]));
+        // Like a hosted game, only a game everyone saw through counts towards the stats
+        if self.players.iter().any(|p| p.left) {
+            return;
+        }
+        let (game, timestamp) = (new_game_id(), now_timestamp());
+        let results = self
+            .players
+            .iter()
+            .map(|p| GameResult {
+                game,
+                timestamp,
+                mode: "http".to_string(),
+                player: p.name.clone(),
+                rank: p.place.unwrap_or(self.players.len()),
+                field_size: self.players.len(),
+                guesses: p.history.len() as u32,
+                rules: Rules::default().record_key(),
+            })
+            .collect();
+        record_results(stores, results, &self.transcript.moves);
+    }
+
+    /// The game as `viewer` (a player's index, if the token was theirs) may see it.
+    fn to_json(&self, viewer: Option<usize>) -> Json {
+        let players = self
+            .players
+            .iter()
+            .map(|p| Json::Object(vec![("name", p.name.as_str().into()), ("guesses", p.history.len().into()), ("place", p.place.into()), ("left", p.left.into())]))
+            .collect();
+        let mut fields = vec![
+            ("id", self.id.as_str().into()),
+            ("status", self.status.id().into()),
+            ("seats", self.seats.into()),
+            ("length", code_length().into()),
+            ("round", self.round.into()),
+            ("turn", (self.status == Status::Playing).then(|| self.players[self.turn].name.as_str()).into()),
+            ("moves", self.moves.into()),
+            ("players", Json::List(players)),
+        ];
+        if let Some(player) = viewer.map(|i| &self.players[i]) {
+            let history = player
+                .history
+                .iter()
+                .map(|(guess, score)| Json::Object(vec![("guess", format_code(guess).into()), ("right_place", score.0.into()), ("wrong_place", score.1.into())]))
+                .collect();
+            let mut you = vec![("name", player.name.as_str().into()), ("history", Json::List(history))];
+            // The secret is no longer anyone's to crack once the game is over
+            if self.status == Status::Over {
+                you.push(("secret", format_code(&player.secret).into()));
+            }
+            fields.push(("you", Json::Object(you)));
+        }
+        Json::Object(fields)
+    }
+}
+
+/// Reads a request's body as a JSON object; an empty body has no fields.
+fn body_fields(request: &Request) -> Result<Vec<(String, Json)>, Response> {
+    if request.body.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    parse_object(&request.body).map_err(|e| Response::error(400, &format!("The body isn't a JSON object: {}.", e)))
+}
+
+fn field<'a>(fields: &'a [(String, Json)], key: &str) -> Option<&'a Json> {
+    fields.iter().find(|(k, _)| k == key).map(|(_, value)| value)
+}
+
+/// What the games cleared from memory add to the server's running totals.
+#[derive(Default)]
+struct Totals {
+    started: u64,
+    finished: u64,
+    guesses: u64,
+    seconds: f64,
+    rounds: u64,
+}
+
+/// Adds one metric, with its help and type lines, in Prometheus's text
+/// format. Each sample is a suffix or labels for the name, and its value.
+fn metric(out: &mut String, name: &str, kind: &str, help: &str, samples: &[(&str, f64)]) {
+    let _ = writeln!(out, "# HELP {} {}\n# TYPE {} {}", name, help, name, kind);
+    for (suffix, value) in samples {
+        let _ = writeln!(out, "{}{} {}", name, suffix, value);
+    }
+}
+
+/// The games on the server, and how it answers requests about them.
+#[derive(Default)]
+struct Server {
+    games: Vec<Game>,
+    cleared: Totals,
+}
+
+impl Server {
+    fn game(&mut self, id: &str) -> Result<&mut Game, Response> {
+        self.games.iter_mut().find(|g| g.id.eq_ignore_ascii_case(id)).ok_or_else(|| Response::error(404, "There's no game with that id."))
+    }
+
+    fn handle(&mut self, request: &Request, stores: &mut StoreSet) -> Response {
+        if request.method == "OPTIONS" {
+            return Response::empty();
+        }
+        self.clear_away();
+        let path: Vec<&str> = request.path.split('/').filter(|part| !part.is_empty()).collect();
+        let result = match (request.method.as_str(), path.as_slice()) {
+            ("GET", ["games"]) => Ok(self.list()),
+            ("POST", ["games"]) => self.create(request),
+            ("GET", ["games", id]) => self.show(id, request, stores),
+            ("POST", ["games", id, "players"]) => self.join(id, request),
+            ("DELETE", ["games", id, "players"]) => self.leave(id, request, stores),
+            ("POST", ["games", id, "guesses"]) => self.guess(id, request, stores),
+            ("GET", ["metrics"]) => Ok(self.metrics()),
+            (_, ["games"] | ["games", _] | ["games", _, "players" | "guesses"] | ["metrics"]) => Err(Response::error(405, "That method isn't allowed here.")),
+            _ => Err(Response::error(404, "There's nothing at that path.")),
+        };
+        result.unwrap_or_else(|response| response)
+    }
+
+    fn list(&self) -> Response {
+        let waiting = self
+            .games
+            .iter()
+            .filter(|g| g.status == Status::Waiting)
+            .map(|g| Json::Object(vec![("id", g.id.as_str().into()), ("seats", g.seats.into()), ("players", Json::List(g.players.iter().map(|p| p.name.as_str().into()).collect()))]))
+            .collect();
+        Response::json(200, Json::Object(vec![("games", Json::List(waiting))]))
+    }
+
+    fn create(&mut self, request: &Request) -> Result<Response, Response> {
+        let fields = body_fields(request)?;
+        let seats = match field(&fields, "seats") {
+            None => 2,
+            Some(Json::Number(n)) if (2..=8).contains(n) => *n as usize,
+            Some(_) => return Err(Response::error(400, "Seats must be a number from 2 to 8.")),
+        };
+        if self.games.len() >= MAX_GAMES {
+            return Err(Response::error(503, "The server has as many games as it can hold; try again later."));
+        }
+        let id = loop {
+            let id = room_code();
+            if !self.games.iter().any(|g| g.id == id) {
+                break id;
+            }
+        };
+        outln!("{}", fill("api.open", &[("id", &id), ("seats", &seats)]));
+        let game = Game::new(id, seats);
+        let response = Response::json(201, game.to_json(None));
+        self.games.push(game);
+        Ok(response)
+    }
+
+    /// Clears away finished games once their players have had time to
+    /// look, and games their players have abandoned, leaving what they add
+    /// up to in the totals.
+    fn clear_away(&mut self) {
+        let done = |g: &Game| match g.ended_at {
+            Some(at) => at.elapsed() >= KEEP_FINISHED,
+            None => g.active_at.elapsed() >= KEEP_IDLE,
+        };
+        let (cleared, kept): (Vec<Game>, Vec<Game>) = std::mem::take(&mut self.games).into_iter().partition(done);
+        self.games = kept;
+        for game in cleared {
+            if game.status != Status::Over {
+                outln!("{}", fill("api.abandoned", &[("id", &game.id)]));
+            }
+            self.cleared.started += u64::from(game.status != Status::Waiting);
+            self.cleared.guesses += game.players.iter().map(|p| p.history.len() as u64).sum::<u64>();
+            if game.status == Status::Over {
+                self.cleared.finished += 1;
+                self.cleared.seconds += game.length().as_secs_f64();
+                self.cleared.rounds += u64::from(game.round);
+            }
+        }
+    }
+
+    fn metrics(&self) -> Response {
+        let count = |status: Status| self.games.iter().filter(|g| g.status == status).count() as f64;
+        let finished: Vec<&Game> = self.games.iter().filter(|g| g.status == Status::Over).collect();
+        let started = self.cleared.started as f64 + count(Status::Playing) + count(Status::Over);
+        let seated = self.games.iter().filter(|g| g.status != Status::Over).flat_map(|g| &g.players).filter(|p| !p.left).count() as f64;
+        let guesses = self.cleared.guesses + self.games.iter().flat_map(|g| &g.players).map(|p| p.history.len() as u64).sum::<u64>();
+        let games = (self.cleared.finished + finished.len() as u64) as f64;
+        let seconds = self.cleared.seconds + finished.iter().map(|g| g.length().as_secs_f64()).sum::<f64>();
+        let rounds = self.cleared.rounds + finished.iter().map(|g| u64::from(g.round)).sum::<u64>();
+
+        let mut out = String::new();
+        metric(
+            &mut out,
+            "sgg_games",
+            "gauge",
+            "Games held in memory, by status.",
+            &[("{status=\"waiting\"}", count(Status::Waiting)), ("{status=\"playing\"}", count(Status::Playing)), ("{status=\"over\"}", count(Status::Over))],
+        );
+        metric(&mut out, "sgg_players_seated", "gauge", "Players in a seat of a game that isn't over.", &[("", seated)]);
+        metric(&mut out, "sgg_games_started_total", "counter", "Games started since the server did.", &[("", started)]);
+        metric(&mut out, "sgg_guesses_total", "counter", "Guesses made since the server started.", &[("", guesses as f64)]);
+        metric(&mut out, "sgg_game_duration_seconds", "summary", "How long finished games took, from start to end.", &[("_sum", seconds), ("_count", games)]);
+        metric(&mut out, "sgg_game_rounds", "summary", "How many rounds finished games took.", &[("_sum", rounds as f64), ("_count", games)]);
+        Response { status: 200, content_type: "text/plain; version=0.0.4", body: out }
+    }
+
+    fn show(&mut self, id: &str, request: &Request, stores: &mut StoreSet) -> Result<Response, Response> {
+        let game = self.game(id)?;
+        game.catch_up(stores);
+        Ok(Response::json(200, game.to_json(game.player_with(request.bearer()))))
+    }
+
+    fn join(&mut self, id: &str, request: &Request) -> Result<Response, Response> {
+        let fields = body_fields(request)?;
+        let wanted = match field(&fields, "name") {
+            Some(Json::Text(name)) => name.clone(),
+            None => String::new(),
+            Some(_) => return Err(Response::error(400, "Name must be text.")),
+        };
+        let game = self.game(id)?;
+        if game.status != Status::Waiting {
+            return Err(Response::error(409, "That game has already started."));
+        }
+        let taken: Vec<&str> = game.players.iter().map(|p| p.name.as_str()).collect();
+        let name = unique_name(&wanted, &taken);
+        let token = session_token();
+        game.players.push(Player { name: name.clone(), token: token.clone(), secret: Guess::default(), history: Vec::new(), place: None, left: false });
+        game.moves += 1;
+        game.active_at = Instant::now();
+        outln!("{}", fill("api.seated", &[("name", &name), ("id", &game.id), ("count", &game.players.len()), ("seats", &game.seats)]));
+        if game.players.len() == game.seats {
+            game.start();
+        }
+        let viewer = game.players.len() - 1;
+        Ok(Response::json(201, Json::Object(vec![("player", name.into()), ("token", token.into()), ("game", game.to_json(Some(viewer)))])))
+    }
+
+    fn leave(&mut self, id: &str, request: &Request, stores: &mut StoreSet) -> Result<Response, Response> {
+        let game = self.game(id)?;
+        game.catch_up(stores);
+        let i = seated(game, request)?;
+        outln!("{}", fill("api.left", &[("name", &game.players[i].name), ("id", &game.id)]));
+        game.active_at = Instant::now();
+        match game.status {
+            Status::Waiting => {
+                game.players.remove(i);
+                game.moves += 1;
+            }
+            Status::Playing => {
+                game.players[i].left = true;
+                if game.turn == i {
+                    game.advance(stores);
+                } else {
+                    game.moves += 1;
+                    if !game.players.iter().any(Player::playing) {
+                        game.finish(stores);
+                    }
+                }
+            }
+            Status::Over => return Err(Response::error(409, "That game is already over.")),
+        }
+        Ok(Response::json(200, game.to_json(None)))
+    }
+
+    fn guess(&mut self, id: &str, request: &Request, stores: &mut StoreSet) -> Result<Response, Response> {
+        let fields = body_fields(request)?;
+        let game = self.game(id)?;
+        game.catch_up(stores);
+        let i = seated(game, request)?;
+        if game.status != Status::Playing || game.turn != i {
+            return Err(Response::error(409, "It isn't your turn."));
+        }
+        let text = match field(&fields, "guess") {
+            Some(Json::Text(text)) => text,
+            _ => return Err(Response::error(400, "Guess must be text, such as \"0123\".")),
+        };
+        let guess = parse_guess(text.trim()).map_err(|message| Response::error(400, &message))?;
+
+        let score = calculate_score(&guess, &game.players[i].secret);
+        game.players[i].history.push((guess, score));
+        game.active_at = Instant::now();
+        game.transcript.record(game.round, &game.players[i].name, guess, score, game.started_at.elapsed());
+        if is_solved(score) {
+            // Everyone who cracks their code in the same round shares a place
+            if game.round != game.last_ranked_round && game.last_ranked_round != 0 {
+                game.rank_to_assign = game.players.iter().filter(|p| p.place.is_some()).count() + 1;
+            }
+            game.last_ranked_round = game.round;
+            game.players[i].place = Some(game.rank_to_assign);
+            outln!("{}", fill("api.cracked", &[("name", &game.players[i].name), ("id", &game.id)]));
+        }
+        game.advance(stores);
+
+        let player = &game.players[i];
+        Ok(Response::json(
+            200,
+            Json::Object(vec![
+                ("guess", format_code(&guess).into()),
+                ("right_place", score.0.into()),
+                ("wrong_place", score.1.into()),
+                ("best", best_score(&player.history).map_or(Json::Null, score_json)),
+                ("cracked", is_solved(score).into()),
+                ("place", player.place.into()),
+                ("game", game.to_json(Some(i))),
+            ]),
+        ))
+    }
+}
+
+/// The index of the player whose token came with `request`.
+fn seated(game: &Game, request: &Request) -> Result<usize, Response> {
+    let token = request.bearer().ok_or_else(|| Response::error(401, "Send your token in an Authorization: Bearer header."))?;
+    let i = game.player_with(Some(token)).ok_or_else(|| Response::error(403, "That token isn't for a seat in this game."))?;
+    if game.players[i].left {
+        return Err(Response::error(403, "You've left this game."));
+    }
+    Ok(i)
+}
+
+/// Runs `serve --http [--port <port>]`, answering requests until stopped.
+pub fn run_serve(port: u16, stores: &mut StoreSet) -> Result<(), String> {
+    let listener = TcpListener::bind(("0.0.0.0", port)).map_err(|e| fill("net.listen_failed", &[("port", &port), ("error", &e)]))?;
+    outln!("{}", tr("api.serving"));
+    outln!("{}", fill("api.hint", &[("port", &port)]));
+    // Connections are read on threads of their own, and their requests answered here
+    let (requests, incoming) = mpsc::channel::<(Request, Sender<Response>)>();
+    thread::spawn(move || {
+        let open = Arc::new(AtomicUsize::new(0));
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            if open.load(Ordering::SeqCst) >= MAX_CONNECTIONS {
+                let _ = write_response(&mut stream, &Response::error(503, "The server is busy. Try again shortly."));
+                continue;
+            }
+            open.fetch_add(1, Ordering::SeqCst);
+            let (open, requests) = (Arc::clone(&open), requests.clone());
+            thread::spawn(move || {
+                let response = match read_request(&mut stream) {
+                    Ok(request) => {
+                        let (answer, answered) = mpsc::channel();
+                        let _ = requests.send((request, answer));
+                        answered.recv().unwrap_or_else(|_| Response::error(503, "The server is stopping."))
+                    }
+                    Err(response) => response,
+                };
+                let _ = write_response(&mut stream, &response);
+                open.fetch_sub(1, Ordering::SeqCst);
+            });
+        }
+    });
+    let mut server = Server::default();
+    for (request, answer) in incoming {
+        let _ = answer.send(server.handle(&request, stores));
+    }
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use clap::Args;
+
+use crate::code::{format_code, is_solved, parse_feedback, parse_guess, Guess, Score};
+use crate::config::Config;
+use crate::console::prompt;
+use crate::feedback::player_notation;
+use crate::solver::{all_codes, filter_candidates, Strategy};
+use crate::strings::{fill, tr};
+
+// --- Solver Assistant ---
+// `solve` helps with a game played somewhere else, on paper or against
+// another program: type each guess with the feedback it got, and the solver
+// says how many codes still fit and what it would guess next.
+
+/// Candidates are listed in full once there are this few.
+const LIST_LIMIT: usize = 12;
+
+/// The options of `solve`.
+#[derive(Debug, Args)]
+pub struct SolveArgs {
+    /// The solver whose suggestions are given
+    #[arg(long, value_enum, default_value_t = Strategy::Minimax)]
+    pub strategy: Strategy,
+    /// Threads the solver searches with (0 for one per core)
+    #[arg(long, value_name = "N")]
+    pub threads: Option<usize>,
+}
+
+/// Reads one line of play: `<guess> <D,P>`, or just `<D,P>` for the suggested guess.
+fn parse_play(input: &str, suggestion: &Guess) -> Result<(Guess, Score), String> {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    match words.as_slice() {
+        [guess, feedback] => {
+            let guess = parse_guess(guess)?;
+            parse_feedback(feedback).map(|score| (guess, score))
+        }
+        _ => parse_feedback(input).map(|score| (*suggestion, score)),
+    }
+}
+
+/// Prints how many codes are left, listing them when there are only a few.
+fn print_candidates(candidates: &[Guess]) {
+    match candidates.len() {
+        1 => outln!("{}", fill("solve.one_left", &[("code", &format_code(&candidates[0]))])),
+        n if n <= LIST_LIMIT => {
+            let list: Vec<String> = candidates.iter().map(format_code).collect();
+            outln!("{}", fill("solve.listed", &[("count", &n), ("codes", &list.join(", "))]));
+        }
+        n => outln!("{}", fill("solve.count", &[("count", &n)])),
+    }
+}
+
+/// Entry point for the `solve` subcommand.
+pub fn run_solve(args: &SolveArgs) -> Result<(), String> {
+    let strategy = args.strategy;
+    if let Some(threads) = args.threads {
+        Config::set_current(Config { solver_threads: threads, ..Config::current() });
+    }
+
+    outln!("{}", fill("solve.title", &[("strategy", &strategy.id())]));
+    outln!("{}", tr("solve.intro"));
+    outln!("{}", tr("solve.intro_more"));
+
+    let codes = all_codes();
+    let mut clues: Vec<(Guess, Score)> = Vec::new();
+    loop {
+        let mut candidates = codes.clone();
+        for (guess, score) in &clues {
+            filter_candidates(&mut candidates, guess, *score);
+        }
+        let suggestion = strategy.choose(&candidates, &codes).expect("the code space is never empty");
+        outln!("{}", fill("solve.suggestion", &[("number", &(clues.len() + 1)), ("guess", &format_code(&suggestion))]));
+
+        let input = prompt(tr("solve.prompt"));
+        match input.as_str() {
+            "/quit" => return Ok(()),
+            "/undo" => match clues.pop() {
+                Some((guess, score)) => outln!("{}", fill("solve.undone", &[("guess", &format_code(&guess)), ("score", &player_notation().render(score))])),
+                None => outln!("{}", tr("solve.nothing_to_undo")),
+            },
+            _ => match parse_play(&input, &suggestion) {
+                Ok((guess, score)) if is_solved(score) => {
+                    outln!("{}", fill("solve.cracked", &[("guess", &format_code(&guess)), ("count", &(clues.len() + 1))]));
+                    return Ok(());
+                }
+                Ok((guess, score)) => {
+                    let mut narrowed = candidates;
+                    filter_candidates(&mut narrowed, &guess, score);
+                    if narrowed.is_empty() {
+                        outln!("{}", fill("solve.no_fit", &[("guess", &format_code(&guess)), ("score", &player_notation().render(score))]));
+                        continue;
+                    }
+                    clues.push((guess, score));
+                    print_candidates(&narrowed);
+                }
+                Err(message) => outln!("{}", message),
+            },
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use std::fmt;
+
+use rand::seq::{IndexedRandom, IteratorRandom};
+
+use crate::code::{feedback_pair, game_rng, is_solved, Guess, Score};
+use crate::solver::{all_codes, entropy_guess, filter_candidates, minimax_guess, Deductions};
+use crate::strings::{fill, tr};
+
+// --- Computer Opponents ---
+
+/// How strongly a bot plays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,   // Guesses any valid code at random
+    Medium, // Guesses at random among codes consistent with its feedback
+    Hard,   // Plays Knuth's minimax strategy over the whole code space
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Medium => "Medium",
+            Difficulty::Hard => "Hard",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A bot's playing style, layered on top of its difficulty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Personality {
+    Standard, // Plays its difficulty's strategy as-is
+    Cautious, // Prefers information-gathering guesses until the answer is nearly certain
+    Gambler,  // Stops gathering information early and goes for possible secrets
+}
+
+impl Personality {
+    pub const ALL: [Personality; 3] = [Personality::Standard, Personality::Cautious, Personality::Gambler];
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Personality::Standard => "plays its level's usual strategy",
+            Personality::Cautious => "gathers information before committing",
+            Personality::Gambler => "takes a shot at possible secrets early",
+        }
+    }
+
+    /// The strategy parameters behind the personality.
+    fn style(self) -> Style {
+        match self {
+            Personality::Standard => Style { explore_above: None, gamble_at_or_below: 0 },
+            Personality::Cautious => Style { explore_above: Some(2), gamble_at_or_below: 0 },
+            Personality::Gambler => Style { explore_above: None, gamble_at_or_below: 60 },
+        }
+    }
+}
+
+impl fmt::Display for Personality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Personality::Standard => "Standard",
+            Personality::Cautious => "Cautious",
+            Personality::Gambler => "Gambler",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Parameters that bend a difficulty's strategy.
+#[derive(Debug, Clone, Copy)]
+struct Style {
+    explore_above: Option<usize>, // Play the most informative guess (candidate or not) while more than this many codes remain
+    gamble_at_or_below: usize,    // With this many candidates or fewer, just guess one of them at random
+}
+
+/// How many codes a weaker bot considers when looking for an informative guess.
+const EXPLORE_SAMPLE: usize = 150;
+
+/// A computer player working out its own secret code.
+#[derive(Debug, Clone)]
+pub struct Bot {
+    pub difficulty: Difficulty,
+    pub personality: Personality,
+    candidates: Vec<Guess>, // Codes still consistent with every piece of feedback
+    tried: Vec<Guess>,      // Easy bots at least avoid repeating themselves
+}
+
+impl Bot {
+    pub fn new(difficulty: Difficulty) -> Self {
+        Bot::with_personality(difficulty, Personality::Standard)
+    }
+
+    pub fn with_personality(difficulty: Difficulty, personality: Personality) -> Self {
+        Bot { difficulty, personality, candidates: all_codes(), tried: Vec::new() }
+    }
+
+    /// How many codes the bot still considers possible.
+    pub fn candidates_left(&self) -> usize {
+        self.candidates.len()
+    }
+
+    /// Chooses the bot's next guess.
+    pub fn next_guess(&self) -> Guess {
+        let mut rng = game_rng();
+        let style = self.personality.style();
+        let remaining = self.candidates.len();
+
+        if remaining <= style.gamble_at_or_below {
+            return *self.candidates.choose(&mut rng).expect("the secret is always a candidate");
+        }
+        if let Some(threshold) = style.explore_above
+            && remaining > threshold
+        {
+            return self.informative_guess();
+        }
+        self.difficulty_guess()
+    }
+
+    /// The guess the bot's difficulty level would make on its own.
+    fn difficulty_guess(&self) -> Guess {
+        let mut rng = game_rng();
+        match self.difficulty {
+            Difficulty::Easy => {
+                let untried: Vec<Guess> = all_codes().into_iter().filter(|c| !self.tried.contains(c)).collect();
+                *untried.choose(&mut rng).expect("there are always untried codes left")
+            }
+            Difficulty::Medium => *self.candidates.choose(&mut rng).expect("the secret is always a candidate"),
+            // Before any feedback every opening is equivalent by symmetry, so skip the search
+            Difficulty::Hard if self.tried.is_empty() => *self.candidates.choose(&mut rng).expect("the code space is never empty"),
+            Difficulty::Hard => minimax_guess(&self.candidates, &all_codes()).expect("the secret is always a candidate"),
+        }
+    }
+
+    /// The most informative guess the bot can find. Hard bots search every
+    /// code; weaker bots only weigh a random sample plus a few of their
+    /// candidates, so they explore less well.
+    fn informative_guess(&self) -> Guess {
+        let mut rng = game_rng();
+        // Before any feedback every opening is equivalent by symmetry
+        if self.tried.is_empty() {
+            return *self.candidates.choose(&mut rng).expect("the code space is never empty");
+        }
+        match self.difficulty {
+            Difficulty::Hard => entropy_guess(&self.candidates, &all_codes()),
+            Difficulty::Easy | Difficulty::Medium => {
+                let mut pool: Vec<Guess> = all_codes().into_iter().choose_multiple(&mut rng, EXPLORE_SAMPLE);
+                pool.extend(self.candidates.iter().take(EXPLORE_SAMPLE / 3));
+                entropy_guess(&self.candidates, &pool)
+            }
+        }
+        .expect("the secret is always a candidate")
+    }
+
+    /// Updates the bot's knowledge with the feedback for one of its guesses.
+    pub fn observe(&mut self, guess: &Guess, score: Score) {
+        self.tried.push(*guess);
+        filter_candidates(&mut self.candidates, guess, score);
+    }
+
+    /// Beginner bots talk through what they learn, which doubles as a lesson for anyone watching.
+    pub fn is_chatty(&self) -> bool {
+        self.difficulty == Difficulty::Easy
+    }
+
+    /// Like `observe`, but also returns a few sentences explaining what the
+    /// feedback ruled in or out.
+    pub fn observe_and_explain(&mut self, guess: &Guess, score: Score) -> Vec<String> {
+        let before = Deductions::from_candidates(&self.candidates);
+        self.observe(guess, score);
+        let after = Deductions::from_candidates(&self.candidates);
+        explain(guess, score, &before, &after, self.candidates.len())
+    }
+}
+
+// --- Bot Chatter ---
+
+/// Joins digits as "3", "3 and 7" or "1, 2 and 4".
+fn digit_list(digits: &[u8]) -> String {
+    let names: Vec<String> = digits.iter().map(|d| d.to_string()).collect();
+    match names.split_last() {
+        Some((last, rest)) if !rest.is_empty() => fill("bot.digit_list", &[("rest", &rest.join(", ")), ("last", last)]),
+        Some((last, _)) => last.clone(),
+        None => String::new(),
+    }
+}
+
+/// Describes the difference between what the bot knew before and after a guess.
+fn explain(guess: &Guess, score: Score, before: &Deductions, after: &Deductions, remaining: usize) -> Vec<String> {
+    if is_solved(score) {
+        return vec![tr("bot.solved").to_string()];
+    }
+
+    let (total, _) = feedback_pair(score);
+    let mut remarks = Vec::new();
+
+    let newly = |now: &[bool; 10], then: &[bool; 10]| -> Vec<u8> {
+        (0..10u8).filter(|&d| now[d as usize] && !then[d as usize]).collect()
+    };
+    let dropped = newly(&after.absent, &before.absent);
+    let (dropped_guessed, dropped_other): (Vec<u8>, Vec<u8>) = dropped.iter().partition(|d| guess.contains(d));
+    let confirmed = newly(&after.present, &before.present);
+
+    if total == 0 {
+        remarks.push(fill("bot.none_match", &[("digits", &digit_list(guess))]));
+    } else if !dropped_guessed.is_empty() {
+        let id = if dropped_guessed.len() == 1 { "bot.drop_one" } else { "bot.drop_many" };
+        remarks.push(fill(id, &[("digits", &digit_list(&dropped_guessed))]));
+    }
+    if !dropped_other.is_empty() {
+        if total as usize == guess.len() {
+            remarks.push(tr("bot.all_matched").to_string());
+        } else {
+            remarks.push(fill("bot.rules_out", &[("digits", &digit_list(&dropped_other))]));
+        }
+    }
+    if !confirmed.is_empty() && (total as usize) < guess.len() {
+        remarks.push(fill("bot.confirmed", &[("digits", &digit_list(&confirmed))]));
+    }
+    for (position, (now, then)) in after.fixed.iter().zip(&before.fixed).enumerate() {
+        if let (Some(digit), None) = (now, then) {
+            remarks.push(fill("bot.fixed", &[("digit", &digit), ("position", &(position + 1))]));
+        }
+    }
+
+    if remaining == 1 {
+        remarks.push(tr("bot.one_left").to_string());
+    } else if remarks.is_empty() {
+        remarks.push(fill("bot.nothing_certain", &[("count", &remaining)]));
+    }
+    remarks
+}
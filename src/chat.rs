@@ -0,0 +1,195 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::code::{calculate_score, format_code, generate_secret, is_solved, parse_guess};
+use crate::console::{clear_screen, guest_session, prompt};
+use crate::feedback::player_notation;
+use crate::locale::Locale;
+use crate::session::{Session, Solve};
+use crate::strings::{fill, tr};
+use crate::theme::paint_feedback;
+use crate::vote::{PollResult, VoteAggregator, VoteConfig};
+
+// --- Chat Plays Mode ---
+// A whole chat/stream audience shares one secret. A chat bridge feeds lines
+// of the form `user: message` (from this terminal or a file/FIFO), messages
+// like `!guess 1234` or a bare `1234` count as votes, and the
+// `VoteAggregator` decides which guess actually gets played.
+
+/// How often the vote window is checked while waiting for chat lines.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Asks for a number of seconds, keeping the default on an empty answer.
+fn get_seconds(label: &str, default: Duration) -> Duration {
+    loop {
+        let input = prompt(&fill("chat.seconds_prompt", &[("label", &label), ("default", &default.as_secs())]));
+        if input.is_empty() {
+            return default;
+        }
+        match input.parse::<u64>() {
+            Ok(n) => return Duration::from_secs(n),
+            Err(_) => outln!("{}", tr("chat.seconds_invalid")),
+        }
+    }
+}
+
+/// Asks the host for the vote settings, with Enter accepting each default.
+fn get_vote_config() -> VoteConfig {
+    let defaults = VoteConfig::default();
+    outln!("{}", tr("chat.settings_title"));
+
+    let window = get_seconds(tr("chat.window"), defaults.window);
+    let min_votes = loop {
+        let input = prompt(&fill("chat.min_votes_prompt", &[("default", &defaults.min_votes)]));
+        if input.is_empty() {
+            break defaults.min_votes;
+        }
+        match input.parse::<usize>() {
+            Ok(n) if n >= 1 => break n,
+            _ => outln!("{}", tr("chat.min_votes_invalid")),
+        }
+    };
+    let user_cooldown = get_seconds(tr("chat.user_cooldown"), defaults.user_cooldown);
+    let round_cooldown = get_seconds(tr("chat.round_cooldown"), defaults.round_cooldown);
+
+    VoteConfig { window, min_votes, user_cooldown, round_cooldown }
+}
+
+/// Starts a thread that forwards chat lines from stdin or a followed file.
+fn spawn_chat_reader(path: Option<String>) -> io::Result<Receiver<String>> {
+    let (sender, receiver) = mpsc::channel();
+
+    match path {
+        None => {
+            thread::spawn(move || {
+                for line in io::stdin().lock().lines().map_while(Result::ok) {
+                    if sender.send(line).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        Some(path) => {
+            let mut reader = BufReader::new(File::open(path)?);
+            thread::spawn(move || {
+                // Follow the file like `tail -f`, so a bridge can keep appending to it
+                let mut line = String::new();
+                loop {
+                    line.clear();
+                    match reader.read_line(&mut line) {
+                        Ok(0) => thread::sleep(POLL_INTERVAL),
+                        Ok(_) => {
+                            if sender.send(line.trim_end().to_string()).is_err() {
+                                break;
+                            }
+                        }
+                        Err(_) => break,
+                    }
+                }
+            });
+        }
+    }
+
+    Ok(receiver)
+}
+
+/// Splits a `user: message` chat line, returning the user and their vote, if any.
+fn parse_chat_vote(line: &str) -> Option<(&str, &str)> {
+    let (user, message) = line.split_once(':')?;
+    let (user, message) = (user.trim(), message.trim());
+    if user.is_empty() {
+        return None;
+    }
+    let vote = message.strip_prefix("!guess").map(str::trim).unwrap_or(message);
+    Some((user, vote))
+}
+
+/// Runs one crowd game fed by a chat bridge.
+pub fn run_chat_mode(session: &mut Session) {
+    clear_screen();
+    outln!("{}", tr("chat.title"));
+    outln!("{}", tr("chat.intro"));
+
+    let config = get_vote_config();
+    // A guest session can only be fed from its own terminal
+    let path = if guest_session() {
+        String::new()
+    } else {
+        prompt(tr("chat.feed_prompt"))
+    };
+    let receiver = match spawn_chat_reader(if path.is_empty() { None } else { Some(path) }) {
+        Ok(receiver) => receiver,
+        Err(e) => {
+            outln!("{}", fill("chat.feed_failed", &[("error", &e)]));
+            return;
+        }
+    };
+
+    let secret = generate_secret();
+    let mut aggregator = VoteAggregator::new(config);
+    let started_at = Instant::now();
+    let mut guess_count: u64 = 0;
+    let mut waiting_announced = false; // Only nag once per stalled window
+
+    outln!("{}", tr("chat.open"));
+
+    loop {
+        match receiver.recv_timeout(POLL_INTERVAL) {
+            Ok(line) => {
+                if let Some((user, vote)) = parse_chat_vote(&line) {
+                    // Chatter that isn't a valid guess is simply ignored
+                    if let Ok(guess) = parse_guess(vote) {
+                        match aggregator.cast(user, guess, Instant::now()) {
+                            Ok(()) => outln!("{}", fill("chat.voted", &[("name", &user), ("guess", &format_code(&guess))])),
+                            Err(rejected) => outln!("⏳ {}: {}", user, rejected),
+                        }
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => {
+                outln!("{}", fill("chat.feed_closed", &[("secret", &format_code(&secret))]));
+                session.record_secret("chat", secret, None);
+                return;
+            }
+        }
+
+        match aggregator.poll(Instant::now()) {
+            PollResult::NeedMoreVotes { votes, needed } if !waiting_announced => {
+                waiting_announced = true;
+                outln!("{}", fill("chat.waiting", &[("votes", &votes), ("needed", &needed)]));
+            }
+            PollResult::Closed(tally) => {
+                waiting_announced = false;
+                guess_count += 1;
+
+                let score = calculate_score(&tally.winner, &secret);
+
+                outln!("\n======================================");
+                outln!("{}", fill("chat.crowd_guess", &[
+                    ("number", &guess_count),
+                    ("guess", &format_code(&tally.winner)),
+                    ("votes", &tally.winner_votes),
+                    ("total", &tally.total_votes),
+                ]));
+                outln!("{}", fill("chat.feedback", &[("score", &paint_feedback(score, &player_notation().render(score)))]));
+                outln!("======================================");
+
+                if is_solved(score) {
+                    let locale = Locale::current();
+                    outln!("{}", tr("chat.cracked"));
+                    outln!("{}", fill("chat.solved", &[("count", &locale.count(guess_count)), ("time", &locale.duration(started_at.elapsed()))]));
+                    outln!("{}", fill("chat.winners", &[("names", &tally.voters.join(", "))]));
+                    session.record_secret("chat", secret, Some(Solve { player: "Chat".to_string(), guesses: guess_count as u32 }));
+                    return;
+                }
+
+                outln!("{}", fill("chat.next_vote", &[("seconds", &aggregator.config().round_cooldown.as_secs())]));
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::code::Guess;
+use crate::config::Config;
+use crate::solver::{all_codes, entropy_guess, partition_entropy, partition_sizes};
+use crate::strings::{fill, tr};
+
+// --- Guess Coach ---
+// An optional voice that rates each guess straight after its feedback, to
+// help new players learn what makes a good probe. A guess is rated by how
+// much information its feedback could be expected to reveal (the entropy of
+// how it splits the remaining candidates), against the best guess available.
+
+/// Rates a guess from 0 to 100, given the candidates before it was made.
+pub fn rate_guess(guess: &Guess, candidates: &[Guess]) -> u8 {
+    let codes = all_codes();
+    // Before any feedback every code splits the space the same way
+    if candidates.len() == codes.len() {
+        return 100;
+    }
+
+    let bits = partition_entropy(&partition_sizes(guess, candidates));
+    let Some(best) = entropy_guess(candidates, &codes) else { return 0 };
+    let best_bits = partition_entropy(&partition_sizes(&best, candidates));
+    if best_bits <= 0.0 {
+        // Only one code is left: the only good guess is that code
+        return if candidates.contains(guess) { 100 } else { 0 };
+    }
+    ((bits / best_bits).min(1.0) * 100.0).round() as u8
+}
+
+fn comment(rating: u8) -> &'static str {
+    match rating {
+        95..=100 => tr("coach.excellent"),
+        80..=94 => tr("coach.good"),
+        60..=79 => tr("coach.decent"),
+        30..=59 => tr("coach.weak"),
+        _ => tr("coach.poor"),
+    }
+}
+
+/// If the coach is switched on, prints its rating of a guess. `candidates`
+/// are the codes that still fit the player's feedback before this guess.
+pub fn coach_guess(guess: &Guess, candidates: &[Guess]) {
+    if !Config::current().coach {
+        return;
+    }
+    let rating = rate_guess(guess, candidates);
+    outln!("{}", fill("coach.rating", &[("rating", &rating), ("comment", &comment(rating))]));
+}
@@ -0,0 +1,306 @@
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use rand::seq::SliceRandom;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::strings::{fill, tr};
+
+// --- Type Definitions ---
+
+/// The longest code this build plays.
+pub const MAX_CODE_LENGTH: usize = 6;
+
+/// A code of non-repeating digits, whether a secret or a guess at one. It
+/// reads as a slice of its digits, and is as cheap to copy as the digits are.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Guess {
+    digits: [u8; MAX_CODE_LENGTH], // Unused places stay 0, so equal codes compare equal
+    length: u8,
+}
+
+impl Guess {
+    /// The code made of `digits`, which must be at most `MAX_CODE_LENGTH` long.
+    pub fn from_digits(digits: &[u8]) -> Guess {
+        assert!(digits.len() <= MAX_CODE_LENGTH, "codes are at most {} digits", MAX_CODE_LENGTH);
+        let mut code = Guess { digits: [0; MAX_CODE_LENGTH], length: digits.len() as u8 };
+        code.digits[..digits.len()].copy_from_slice(digits);
+        code
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for Guess {
+    fn from(digits: [u8; N]) -> Guess {
+        Guess::from_digits(&digits)
+    }
+}
+
+impl Deref for Guess {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.digits[..self.length as usize]
+    }
+}
+
+impl DerefMut for Guess {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.digits[..self.length as usize]
+    }
+}
+
+impl<'a> IntoIterator for &'a Guess {
+    type Item = &'a u8;
+    type IntoIter = std::slice::Iter<'a, u8>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<const N: usize> PartialEq<[u8; N]> for Guess {
+    fn eq(&self, other: &[u8; N]) -> bool {
+        **self == *other
+    }
+}
+
+impl fmt::Debug for Guess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+// Score is internally represented as (Digits at Correct Position, Digits Correct but Wrong Position)
+pub type Score = (u8, u8);
+
+// --- Code Length ---
+// Every code in a game has the same length: 4 digits unless `code_length`
+// in the defaults file, `--length`, a seated player's profile or the game
+// being loaded or joined says otherwise. Like the seed, it's set for the
+// run rather than handed to every function that deals or reads a code.
+
+static CODE_LENGTH: AtomicUsize = AtomicUsize::new(4);
+static LENGTH_FIXED: AtomicBool = AtomicBool::new(false);
+
+/// The number of digits in the codes now in play.
+pub fn code_length() -> usize {
+    CODE_LENGTH.load(Ordering::Relaxed)
+}
+
+/// Makes codes `length` digits long from now on. The caller has checked
+/// `length` against `SUPPORTED_CODE_LENGTHS`.
+pub fn set_code_length(length: usize) {
+    CODE_LENGTH.store(length, Ordering::Relaxed);
+}
+
+/// Sets the length for the whole run, as `--length` does, so that players'
+/// preferred lengths leave it alone.
+pub fn fix_code_length(length: usize) {
+    set_code_length(length);
+    LENGTH_FIXED.store(true, Ordering::Relaxed);
+}
+
+/// Whether `--length` chose the length.
+pub fn code_length_fixed() -> bool {
+    LENGTH_FIXED.load(Ordering::Relaxed)
+}
+
+// --- Randomness ---
+// Everything random in play (secrets, free clues, bots' choices, who starts)
+// draws from `game_rng`. Normally that's fresh randomness each time; with
+// `--seed` the same seed deals the same game again. Individual Secrets games
+// start from a seed of their own and show it at the end, so any game can be
+// replayed for a bug report or a rematch. Stats encryption never uses it.
+
+/// The randomness stream, and the seed `--seed` gave for the next game.
+struct Seeding {
+    stream: Option<ChaCha8Rng>,
+    given: Option<u64>,
+}
+
+static SEED: Mutex<Seeding> = Mutex::new(Seeding { stream: None, given: None });
+
+/// Makes the whole run's randomness follow from `seed`.
+pub fn set_seed(seed: u64) {
+    *SEED.lock().unwrap() = Seeding { stream: Some(ChaCha8Rng::seed_from_u64(seed)), given: Some(seed) };
+}
+
+/// Starts a game's randomness from a seed of its own and returns it: the
+/// `--seed` one for the first game, then the next in line (or a fresh one
+/// without `--seed`). Playing with `--seed` set to it deals the game again.
+pub fn start_seeded_game() -> u64 {
+    let mut seeding = SEED.lock().unwrap();
+    let seed = match (seeding.given.take(), seeding.stream.as_mut()) {
+        (Some(seed), _) => seed,
+        (None, Some(stream)) => stream.next_u64(),
+        (None, None) => rand::rng().next_u64(),
+    };
+    seeding.stream = Some(ChaCha8Rng::seed_from_u64(seed));
+    seed
+}
+
+/// A random number generator for game play, the next one in line after `set_seed`.
+pub fn game_rng() -> ChaCha8Rng {
+    match SEED.lock().unwrap().stream.as_mut() {
+        Some(seeded) => ChaCha8Rng::seed_from_u64(seeded.next_u64()),
+        None => ChaCha8Rng::from_rng(&mut rand::rng()),
+    }
+}
+
+// --- Core Logic ---
+
+/// Generates a secret of `code_length()` non-repeating digits.
+/// The first digit is allowed to be 0.
+pub fn generate_secret() -> Guess {
+    let mut digits: Vec<u8> = (0..=9).collect();
+    let mut rng = game_rng();
+
+    // Shuffle the digits
+    digits.shuffle(&mut rng);
+
+    // Take the first few unique digits. Since they are shuffled, they are non-repeating.
+    Guess::from_digits(&digits[..code_length()])
+}
+
+/// Calculates the core matching score.
+/// Returns (Digits at Correct Position [Y], Digits Correct but Wrong Position).
+pub fn calculate_score(guess: &Guess, secret: &Guess) -> Score {
+    let mut correct_position = 0; // Digits at Correct Position (Y)
+    let mut total_correct_digits = 0; // Total Correct Digits (X)
+
+    // Use a frequency map for quick checking of digits present in the secret
+    let mut secret_counts: [bool; 10] = [false; 10];
+    for &digit in secret.iter() {
+        secret_counts[digit as usize] = true;
+    }
+
+    for (&g_digit, &s_digit) in guess.iter().zip(secret.iter()) {
+        let (g_digit, s_digit) = (g_digit as usize, s_digit as usize);
+
+        // Check for Digits at Correct Position (Y)
+        if g_digit == s_digit {
+            correct_position += 1;
+        }
+
+        // Check for Total Matches (X)
+        if secret_counts[g_digit] {
+            total_correct_digits += 1;
+        }
+    }
+
+    // Digits Correct but Wrong Position = Total Correct (X) - Correct Position (Y)
+    let correct_wrong_position = total_correct_digits - correct_position;
+
+    (correct_position, correct_wrong_position)
+}
+
+/// Converts an internal score into the (D,P) pair shown to players:
+/// D = Total Correct Digits, P = Digits at Correct Position.
+pub fn feedback_pair(score: Score) -> (u8, u8) {
+    (score.0 + score.1, score.0)
+}
+
+/// Returns true when the score means the secret has been cracked.
+pub fn is_solved(score: Score) -> bool {
+    score.0 as usize == code_length()
+}
+
+/// The score of a guess that cracks the code.
+pub fn solved_score() -> Score {
+    (code_length() as u8, 0)
+}
+
+// --- Parsing and Formatting ---
+
+/// Parses a guess of `code_length()` non-repeating digits.
+/// On failure, returns the message that should be shown to the player.
+pub fn parse_guess(input: &str) -> Result<Guess, String> {
+    if input.chars().count() != code_length() {
+        return Err(fill("error.guess_length", &[("length", &code_length())]));
+    }
+
+    let mut digits: Vec<u8> = Vec::new();
+    let mut seen_digits = [false; 10];
+
+    for c in input.chars() {
+        match c.to_digit(10) {
+            Some(d) => {
+                let d_u8 = d as u8;
+                // Check for repetition
+                if seen_digits[d_u8 as usize] {
+                    return Err(tr("error.guess_repeat").to_string());
+                }
+                seen_digits[d_u8 as usize] = true;
+                digits.push(d_u8);
+            }
+            None => return Err(tr("error.guess_digits").to_string()),
+        }
+    }
+
+    Ok(Guess::from_digits(&digits))
+}
+
+/// Parses feedback typed in the game's "D,P" notation (e.g. "3,1" or "3 1").
+/// Returns the matching internal Score, or the message to show on bad input.
+pub fn parse_feedback(input: &str) -> Result<Score, String> {
+    let parts: Vec<&str> = input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    if parts.len() != 2 {
+        return Err(tr("error.feedback_form").to_string());
+    }
+
+    let (total, position) = match (parts[0].parse::<u8>(), parts[1].parse::<u8>()) {
+        (Ok(d), Ok(p)) => (d, p),
+        _ => return Err(tr("error.feedback_numbers").to_string()),
+    };
+
+    let length = code_length() as u8;
+    if total > length || position > total {
+        return Err(fill("error.feedback_range", &[("length", &length)]));
+    }
+
+    // Every digit present with all but one in place is impossible.
+    if total == length && position + 1 == length {
+        return Err(fill("error.feedback_impossible", &[("total", &total), ("position", &position)]));
+    }
+
+    Ok((position, total - position))
+}
+
+/// Formats a code as its plain digit string (e.g. "0485").
+pub fn format_code(code: &Guess) -> String {
+    code.iter().map(|d| d.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(digits: &str) -> Guess {
+        Guess::from_digits(&digits.bytes().map(|b| b - b'0').collect::<Vec<u8>>())
+    }
+
+    #[test]
+    fn scores_count_digits_in_place_and_elsewhere() {
+        assert_eq!(calculate_score(&code("0123"), &code("0123")), (4, 0));
+        assert_eq!(calculate_score(&code("0123"), &code("3210")), (0, 4));
+        assert_eq!(calculate_score(&code("0123"), &code("0132")), (2, 2));
+        assert_eq!(calculate_score(&code("0123"), &code("4567")), (0, 0));
+        assert_eq!(calculate_score(&code("0145"), &code("1098")), (0, 2));
+        assert_eq!(calculate_score(&code("012"), &code("210")), (1, 2));
+        assert_eq!(calculate_score(&code("012345"), &code("012354")), (4, 2));
+    }
+
+    #[test]
+    fn codes_of_any_length_compare_by_their_digits() {
+        assert_eq!(code("0123"), code("0123"));
+        assert_ne!(code("0123"), code("01234"));
+        assert_eq!(format_code(&code("98765")), "98765");
+    }
+}
@@ -0,0 +1,376 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use std::thread;
+use std::time::Duration;
+
+use crate::console::{clear_screen, prompt};
+use crate::glyphs::OutputProfile;
+use crate::sound::SoundCues;
+use crate::store::data_dir;
+use crate::strings::{fill, tr, Language};
+use crate::theme::Theme;
+
+// --- Game Settings ---
+// Optional features are switched on in a small `key = value` file next to the
+// stats (`config.txt` in the data directory). A missing file means defaults;
+// unknown keys are ignored so older builds can read newer files.
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    /// After each guess, show how many codes still fit the player's feedback.
+    pub show_remaining_candidates: bool,
+    /// What everyone gets told once a player's private feedback has been hidden.
+    pub turn_announcement: Announcement,
+    /// Every guess must fit all the feedback the player has had so far.
+    pub hard_mode: bool,
+    /// Between turns, show each player's estimated chance of finishing next.
+    pub show_win_chances: bool,
+    /// After each human guess, rate how informative it was.
+    pub coach: bool,
+    /// How many threads the solver searches with; 0 means one per core.
+    pub solver_threads: usize,
+    /// The language for prompts and messages; None follows the environment.
+    pub language: Option<Language>,
+    /// Which decorative characters the terminal can show.
+    pub output: OutputProfile,
+    /// Seconds each human gets for a turn in Individual Secrets games; 0 means no limit.
+    pub turn_timer: u64,
+    /// Guesses each player gets in Individual Secrets games before they're out; 0 means no limit.
+    pub guess_limit: u32,
+    /// The colour theme by name; empty means the classic look.
+    pub theme: String,
+    /// Themes defined in the settings file with `theme.<name>.<part>` lines.
+    pub themes: Vec<Theme>,
+    /// Whether turns, cracked codes and the end of a game are signalled with a sound.
+    pub sound: SoundCues,
+    /// Output suited to screen readers: no tables, feedback in full sentences.
+    pub screen_reader: bool,
+    /// Taking back a guess with /undo needs another player's OK.
+    pub confirm_undo: bool,
+    /// After the final rankings, show the leaderboard from the stats.
+    pub show_leaderboard: bool,
+}
+
+/// How much of a turn is announced publicly in individual-secrets games.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Announcement {
+    #[default]
+    Nothing,   // The screen is simply cleared
+    Guessed,   // "Alice has guessed."
+    Feedback,  // "Alice got 2,1."
+    FullGuess, // "Alice guessed 0123 and got 2,1."
+}
+
+impl Announcement {
+    pub const ALL: [Announcement; 4] = [Announcement::Nothing, Announcement::Guessed, Announcement::Feedback, Announcement::FullGuess];
+
+    /// The value used in the settings file.
+    pub fn id(self) -> &'static str {
+        match self {
+            Announcement::Nothing => "nothing",
+            Announcement::Guessed => "guessed",
+            Announcement::Feedback => "feedback",
+            Announcement::FullGuess => "full",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Announcement> {
+        Announcement::ALL.into_iter().find(|a| a.id() == id.to_ascii_lowercase())
+    }
+
+    /// A description for the settings menu.
+    pub fn label(self) -> &'static str {
+        match self {
+            Announcement::Nothing => tr("announcement.nothing"),
+            Announcement::Guessed => tr("announcement.guessed"),
+            Announcement::Feedback => tr("announcement.feedback"),
+            Announcement::FullGuess => tr("announcement.full"),
+        }
+    }
+
+    /// The next option, for cycling through them in the menu.
+    pub fn next(self) -> Announcement {
+        let index = Announcement::ALL.iter().position(|&a| a == self).unwrap_or(0);
+        Announcement::ALL[(index + 1) % Announcement::ALL.len()]
+    }
+}
+
+static CURRENT: RwLock<Option<Config>> = RwLock::new(None);
+
+/// The number of cores the solver can use.
+pub fn available_threads() -> usize {
+    thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
+/// Parses "true"/"false" style values.
+fn parse_flag(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Some(true),
+        "false" | "no" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+impl Config {
+    /// Where the settings file lives, if there is a data directory.
+    pub fn path() -> Option<PathBuf> {
+        data_dir().map(|dir| dir.join("config.txt"))
+    }
+
+    /// Parses settings text. Blank lines and `#` comments are skipped; lines
+    /// that can't be understood are returned as warnings and otherwise ignored.
+    pub fn parse(text: &str) -> (Config, Vec<String>) {
+        let mut config = Config::default();
+        let mut warnings = Vec::new();
+        let mut theme_line = 0;
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                warnings.push(format!("line {}: expected `key = value`", number + 1));
+                continue;
+            };
+            let value = value.trim();
+            if let Some((name, part)) = key.trim().strip_prefix("theme.").and_then(|rest| rest.split_once('.')) {
+                let index = match config.themes.iter().position(|t| t.name == name) {
+                    Some(index) => index,
+                    None => {
+                        config.themes.push(Theme { name: name.to_string(), ..Theme::default() });
+                        config.themes.len() - 1
+                    }
+                };
+                if !config.themes[index].set_part(part, value) {
+                    warnings.push(format!("line {}: expected prompt, feedback, miss or cracked with a colour, or banner with dashes, boxed, or bold", number + 1));
+                }
+                continue;
+            }
+            match key.trim() {
+                "show_remaining_candidates" => match parse_flag(value) {
+                    Some(flag) => config.show_remaining_candidates = flag,
+                    None => warnings.push(format!("line {}: expected true or false", number + 1)),
+                },
+                "hard_mode" => match parse_flag(value) {
+                    Some(flag) => config.hard_mode = flag,
+                    None => warnings.push(format!("line {}: expected true or false", number + 1)),
+                },
+                "show_win_chances" => match parse_flag(value) {
+                    Some(flag) => config.show_win_chances = flag,
+                    None => warnings.push(format!("line {}: expected true or false", number + 1)),
+                },
+                "coach" => match parse_flag(value) {
+                    Some(flag) => config.coach = flag,
+                    None => warnings.push(format!("line {}: expected true or false", number + 1)),
+                },
+                "screen_reader" => match parse_flag(value) {
+                    Some(flag) => config.screen_reader = flag,
+                    None => warnings.push(format!("line {}: expected true or false", number + 1)),
+                },
+                "confirm_undo" => match parse_flag(value) {
+                    Some(flag) => config.confirm_undo = flag,
+                    None => warnings.push(format!("line {}: expected true or false", number + 1)),
+                },
+                "show_leaderboard" => match parse_flag(value) {
+                    Some(flag) => config.show_leaderboard = flag,
+                    None => warnings.push(format!("line {}: expected true or false", number + 1)),
+                },
+                "solver_threads" => match value.parse() {
+                    Ok(threads) => config.solver_threads = threads,
+                    Err(_) => warnings.push(format!("line {}: expected a number of threads (0 for automatic)", number + 1)),
+                },
+                "language" => match value {
+                    "auto" => config.language = None,
+                    tag => match Language::from_tag(tag) {
+                        Some(language) => config.language = Some(language),
+                        None => warnings.push(format!("line {}: expected auto, en, or de", number + 1)),
+                    },
+                },
+                "turn_timer" => match value.parse() {
+                    Ok(seconds) => config.turn_timer = seconds,
+                    Err(_) => warnings.push(format!("line {}: expected a number of seconds (0 for no limit)", number + 1)),
+                },
+                "guess_limit" => match value.parse() {
+                    Ok(limit) => config.guess_limit = limit,
+                    Err(_) => warnings.push(format!("line {}: expected a number of guesses (0 for no limit)", number + 1)),
+                },
+                "theme" => {
+                    config.theme = value.to_string();
+                    theme_line = number + 1;
+                }
+                "output" => match OutputProfile::from_id(value) {
+                    Some(output) => config.output = output,
+                    None => warnings.push(format!("line {}: expected emoji, unicode, or ascii", number + 1)),
+                },
+                "sound" => match SoundCues::from_id(value) {
+                    Some(sound) => config.sound = sound,
+                    None => warnings.push(format!("line {}: expected off, bell, or tones", number + 1)),
+                },
+                "turn_announcement" => match Announcement::from_id(value) {
+                    Some(announcement) => config.turn_announcement = announcement,
+                    None => warnings.push(format!("line {}: expected nothing, guessed, feedback, or full", number + 1)),
+                },
+                _ => {} // Unknown keys belong to other versions of the game
+            }
+        }
+        if !config.theme.is_empty() && Theme::by_name(&config, &config.theme).is_none() {
+            warnings.push(format!("line {}: there's no theme called {}", theme_line, config.theme));
+            config.theme.clear();
+        }
+        (config, warnings)
+    }
+
+    /// Renders the settings in the file format read by `parse`.
+    pub fn to_text(&self) -> String {
+        format!("# Secret Guessing Game settings\nshow_remaining_candidates = {}\nturn_announcement = {}\nhard_mode = {}\nshow_win_chances = {}\ncoach = {}\nsolver_threads = {}\nlanguage = {}\noutput = {}\nturn_timer = {}\nsound = {}\nscreen_reader = {}\nconfirm_undo = {}\nguess_limit = {}\ntheme = {}\nshow_leaderboard = {}\n{}",
+                self.show_remaining_candidates, self.turn_announcement.id(), self.hard_mode, self.show_win_chances, self.coach,
+                self.solver_threads, self.language.map_or("auto", Language::id), self.output.id(),
+                self.turn_timer, self.sound.id(), self.screen_reader, self.confirm_undo, self.guess_limit,
+                if self.theme.is_empty() { "classic" } else { &self.theme }, self.show_leaderboard,
+                self.themes.iter().map(Theme::to_lines).collect::<String>())
+    }
+
+    /// Loads the settings file, falling back to defaults if it's missing or
+    /// unreadable. Also returns any problems to tell the player about, which
+    /// can only be printed once the settings (and so the output profile) are in place.
+    pub fn load() -> (Config, Vec<String>) {
+        let Some(path) = Config::path() else { return (Config::default(), Vec::new()) };
+        match fs::read_to_string(&path) {
+            Ok(text) => {
+                let (config, warnings) = Config::parse(&text);
+                let problems = warnings
+                    .into_iter()
+                    .map(|warning| format!("⚠️ Ignoring setting in {} ({}).", path.display(), warning))
+                    .collect();
+                (config, problems)
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => (Config::default(), Vec::new()),
+            Err(e) => (Config::default(), vec![format!("⚠️ Couldn't read settings from {}: {}.", path.display(), e)]),
+        }
+    }
+
+    /// Writes the settings file, creating the data directory if needed.
+    pub fn save(&self) -> io::Result<()> {
+        let path = Config::path().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_text())
+    }
+
+    /// The settings in effect, loaded from disk on first use.
+    pub fn current() -> Config {
+        if let Some(config) = CURRENT.read().unwrap().as_ref() {
+            return config.clone();
+        }
+        let (config, problems) = Config::load();
+        *CURRENT.write().unwrap() = Some(config.clone());
+        for problem in problems {
+            outln!("{}", problem);
+        }
+        config
+    }
+
+    /// How long a human has for each turn, if turns are timed.
+    pub fn turn_limit(&self) -> Option<Duration> {
+        (self.turn_timer > 0).then(|| Duration::from_secs(self.turn_timer))
+    }
+
+    /// How many threads the solver should use, resolving 0 to the core count.
+    pub fn solver_threads(&self) -> usize {
+        match self.solver_threads {
+            0 => available_threads(),
+            n => n,
+        }
+    }
+
+    pub fn set_current(config: Config) {
+        *CURRENT.write().unwrap() = Some(config);
+    }
+}
+
+/// Lets the players toggle optional features; changes are saved straight away.
+pub fn settings_menu() {
+    loop {
+        clear_screen();
+        let mut config = Config::current();
+        let on_off = |flag: bool| if flag { tr("settings.on") } else { tr("settings.off") };
+
+        outln!("{}", tr("settings.title"));
+        outln!("{}", fill("settings.remaining", &[("value", &on_off(config.show_remaining_candidates))]));
+        outln!("{}", fill("settings.announcement", &[("value", &config.turn_announcement.label())]));
+        outln!("{}", fill("settings.hard_mode", &[("value", &on_off(config.hard_mode))]));
+        outln!("{}", fill("settings.win_chances", &[("value", &on_off(config.show_win_chances))]));
+        outln!("{}", fill("settings.coach", &[("value", &on_off(config.coach))]));
+        match config.solver_threads {
+            0 => outln!("{}", fill("settings.threads_auto", &[("count", &available_threads())])),
+            n => outln!("{}", fill("settings.threads", &[("count", &n)])),
+        }
+        let language = match config.language {
+            Some(language) => language.name().to_string(),
+            None => fill("settings.language_auto", &[("value", &Language::from_env().name())]),
+        };
+        outln!("{}", fill("settings.language", &[("value", &language)]));
+        outln!("{}", fill("settings.output", &[("value", &tr(config.output.label_id()))]));
+        match config.turn_timer {
+            0 => outln!("{}", tr("settings.timer_off")),
+            n => outln!("{}", fill("settings.timer", &[("count", &n)])),
+        }
+        outln!("{}", fill("settings.sound", &[("value", &tr(config.sound.label_id()))]));
+        outln!("{}", fill("settings.screen_reader", &[("value", &on_off(config.screen_reader))]));
+        outln!("{}", fill("settings.confirm_undo", &[("value", &on_off(config.confirm_undo))]));
+        match config.guess_limit {
+            0 => outln!("{}", tr("settings.guess_limit_off")),
+            n => outln!("{}", fill("settings.guess_limit", &[("count", &n)])),
+        }
+        outln!("{}", fill("settings.theme", &[("value", &Theme::current().name)]));
+        outln!("{}", fill("settings.leaderboard", &[("value", &on_off(config.show_leaderboard))]));
+        outln!("{}", tr("settings.back"));
+
+        match prompt(tr("settings.prompt")).as_str() {
+            "1" => config.show_remaining_candidates = !config.show_remaining_candidates,
+            "2" => config.turn_announcement = config.turn_announcement.next(),
+            "3" => config.hard_mode = !config.hard_mode,
+            "4" => config.show_win_chances = !config.show_win_chances,
+            "5" => config.coach = !config.coach,
+            "6" => match prompt(tr("settings.threads_prompt")).parse() {
+                Ok(threads) => config.solver_threads = threads,
+                Err(_) => continue,
+            },
+            // Cycles automatic -> English -> German -> automatic
+            "7" => config.language = match config.language {
+                None => Some(Language::ALL[0]),
+                Some(language) => Language::ALL.iter().position(|&l| l == language).and_then(|i| Language::ALL.get(i + 1)).copied(),
+            },
+            "8" => config.output = config.output.next(),
+            "9" => match prompt(tr("settings.timer_prompt")).parse() {
+                Ok(seconds) => config.turn_timer = seconds,
+                Err(_) => continue,
+            },
+            "10" => config.sound = config.sound.next(),
+            "11" => config.screen_reader = !config.screen_reader,
+            "12" => config.confirm_undo = !config.confirm_undo,
+            "13" => match prompt(tr("settings.guess_limit_prompt")).parse() {
+                Ok(limit) => config.guess_limit = limit,
+                Err(_) => continue,
+            },
+            "14" => {
+                let themes = Theme::all(&config);
+                let index = themes.iter().position(|t| t.name == Theme::current().name).unwrap_or(0);
+                config.theme = themes[(index + 1) % themes.len()].name.clone();
+            }
+            "15" => config.show_leaderboard = !config.show_leaderboard,
+            "16" => return,
+            _ => continue,
+        }
+
+        if let Err(e) = config.save() {
+            outln!("{}", fill("settings.save_failed", &[("error", &e)]));
+            prompt(tr("common.continue"));
+        }
+        Config::set_current(config);
+    }
+}
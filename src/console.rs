@@ -0,0 +1,636 @@
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::glyphs::finish;
+#[cfg(feature = "tui")]
+use crate::terminal::interactive;
+use crate::json;
+use crate::rankings::display_width;
+use crate::rules::print_rules;
+use crate::strings::{fill, tr};
+use crate::theme::Theme;
+#[cfg(unix)]
+use crate::terminal::{read_byte, read_byte_timeout, RawMode};
+use crate::terminal::{clear_from_row, clear_terminal, cursor_control, terminal_size, typed_input, write_row, EchoOff};
+
+// --- Output Style ---
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// Switches escape codes off (or back on) for the rest of the run, so the
+/// output is plain linear text for logs, pipes and dumb terminals.
+pub fn set_plain_output(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+/// True when escape codes are off: `--no-ansi` was given, or `TERM` is "dumb".
+pub fn plain_output() -> bool {
+    PLAIN.load(Ordering::Relaxed) || std::env::var("TERM").is_ok_and(|term| term == "dumb")
+}
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Switches to minimal output for the rest of the run (`--quiet`): no
+/// banners, separators or pauses, just prompts, feedback and rankings.
+pub fn set_quiet_output(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn quiet_output() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+static GUEST: AtomicBool = AtomicBool::new(false);
+
+/// Makes the rest of the run a remote guest's (`--guest`, which the telnet
+/// and SSH servers start every session with). The machine isn't the
+/// player's, so nothing asks for a file path, and the settings and stats
+/// encryption are left as the operator set them.
+pub fn set_guest_session(guest: bool) {
+    GUEST.store(guest, Ordering::Relaxed);
+}
+
+pub fn guest_session() -> bool {
+    GUEST.load(Ordering::Relaxed)
+}
+
+/// True for the lines `--quiet` leaves out: blank lines, rules drawn with
+/// `=`, `-` or `─`, and `--- Title ---` banners.
+fn is_decoration(line: &str) -> bool {
+    let line = line.trim();
+    line.chars().all(|c| matches!(c, '=' | '-' | '─' | '━')) || (line.starts_with("--- ") && line.ends_with(" ---"))
+}
+
+/// `text` without the lines `--quiet` leaves out. A trailing line without
+/// a newline (a prompt waiting for its answer) is kept whatever it holds.
+pub fn strip_decorations(text: &str, newline: bool) -> String {
+    let mut lines: Vec<&str> = text.split('\n').collect();
+    let open = if newline { None } else { lines.pop() };
+    let mut kept: Vec<&str> = lines.into_iter().filter(|line| !is_decoration(line)).collect();
+    kept.extend(open);
+    kept.join("\n")
+}
+
+// --- Pauses ---
+
+/// The length of every pause, when `--delay` sets one.
+static PAUSE: RwLock<Option<Duration>> = RwLock::new(None);
+
+/// Makes every pause in play last `length` for the rest of the run (zero for none).
+pub fn set_pause_length(length: Duration) {
+    *PAUSE.write().unwrap() = Some(length);
+}
+
+/// Waits a moment so something can be read, such as a bot's move: for
+/// `usual`, unless `--delay` gave another length. `--quiet` skips pauses.
+pub fn pause(usual: Duration) {
+    let length = PAUSE.read().unwrap().unwrap_or(usual);
+    if !length.is_zero() && !quiet_output() {
+        thread::sleep(length);
+    }
+}
+
+// --- User Input Helpers ---
+
+/// Clears the console screen using common ANSI escape codes. Consoles
+/// without them (older Windows) are cleared through the console API, and
+/// failing that the old screen is scrolled out of view. In plain output a
+/// blank line stands in for the cleared screen.
+pub fn clear_screen() {
+    FRAME.lock().unwrap().take();
+    if plain_output() {
+        outln!();
+        return;
+    }
+    if !clear_terminal() {
+        out!("{}", "\n".repeat(terminal_size().1 * 2));
+    }
+    io::stdout().flush().unwrap();
+}
+
+/// Reads input from the user.
+pub fn read_line() -> String {
+    read_line_with_history(&[])
+}
+
+/// Like `read_line`, but on a terminal Up and Down step through `history`
+/// (oldest first) to edit and send an earlier answer again.
+pub fn read_line_with_history(history: &[String]) -> String {
+    ROWS_SINCE_FRAME.fetch_add(1, Ordering::Relaxed); // The Enter key moves the cursor down a row
+    #[cfg(unix)]
+    if !plain_output() && let Some(raw) = RawMode::enable() {
+        let line = edit_line(history);
+        drop(raw);
+        return line.trim().to_string();
+    }
+    #[cfg(not(unix))]
+    let _ = history;
+    read_stdin_line()
+}
+
+/// Reads a whole line the usual way, through the terminal's own line buffer.
+/// Once piped input runs out nothing can answer the prompt, so the run ends.
+fn read_stdin_line() -> String {
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input).expect("Failed to read line") == 0 {
+        outln!();
+        eprintln!("{}", tr("input.ended"));
+        std::process::exit(0);
+    }
+    input.trim().to_string()
+}
+
+/// Answers `/help`, `/rules` and `/pause`, which work at every prompt
+/// without counting as an answer. Returns whether `input` was one of them.
+pub fn answer_command(input: &str) -> bool {
+    match input {
+        "/help" => outln!("{}", tr("help.commands")),
+        "/rules" => print_rules(),
+        "/pause" => pause_game(),
+        _ => return false,
+    }
+    true
+}
+
+// --- Pausing ---
+// `/pause` blanks the screen until someone presses Enter, for a hot-seat
+// game that's interrupted. Clocks are `Countdown`s, which stand still while
+// the game is paused.
+
+/// Time spent paused so far this run.
+static PAUSED: Mutex<Duration> = Mutex::new(Duration::ZERO);
+
+fn paused_time() -> Duration {
+    *PAUSED.lock().unwrap()
+}
+
+/// Blanks the screen and waits for Enter, keeping the time out of every clock.
+fn pause_game() {
+    let started = Instant::now();
+    clear_screen();
+    outln!("{}", tr("pause.paused"));
+    read_line();
+    *PAUSED.lock().unwrap() += started.elapsed();
+    clear_screen();
+    outln!("{}", tr("pause.resumed"));
+}
+
+/// A time limit that doesn't run while the game is paused.
+#[derive(Debug, Clone, Copy)]
+pub struct Countdown {
+    ends: Instant,
+    paused_before: Duration, // Pauses before the countdown started don't move it
+}
+
+impl Countdown {
+    pub fn new(limit: Duration) -> Countdown {
+        Countdown { ends: Instant::now() + limit, paused_before: paused_time() }
+    }
+
+    pub fn left(&self) -> Duration {
+        (self.ends + (paused_time() - self.paused_before)).saturating_duration_since(Instant::now())
+    }
+
+    pub fn expired(&self) -> bool {
+        self.left().is_zero()
+    }
+}
+
+/// Prints a prompt (without a newline) and reads the user's answer.
+pub fn prompt(message: &str) -> String {
+    prompt_with_history(message, &[])
+}
+
+/// Like `prompt`, with earlier answers in `history` to recall (see `read_line_with_history`).
+pub fn prompt_with_history(message: &str, history: &[String]) -> String {
+    // An empty message continues a prompt that's already been announced
+    if !message.is_empty() {
+        json::emit("prompt", vec![("text", message.trim().into())]);
+    }
+    loop {
+        let answer = ask(message, history);
+        if !answer_command(&answer) {
+            return answer;
+        }
+    }
+}
+
+/// Shows `message` and reads the answer to it, with `history` to recall.
+#[cfg(not(feature = "tui"))]
+fn ask(message: &str, history: &[String]) -> String {
+    show_prompt(message);
+    read_line_with_history(history)
+}
+
+/// Shows `message` and reads the answer to it. On a terminal the answer is
+/// typed into rustyline, with `history` (oldest first) behind Up and Down.
+#[cfg(feature = "tui")]
+fn ask(message: &str, history: &[String]) -> String {
+    use rustyline::error::ReadlineError;
+    use rustyline::DefaultEditor;
+
+    let editor = DefaultEditor::new().ok().filter(|_| interactive() && !plain_output());
+    let Some(mut editor) = editor else {
+        show_prompt(message);
+        return read_line_with_history(history);
+    };
+    for answer in history {
+        let _ = editor.add_history_entry(answer.as_str());
+    }
+    // rustyline draws the prompt's last line itself, so it can redraw it while editing
+    let (above, last) = message.rsplit_once('\n').map_or(("", message), |(above, last)| (above, last));
+    if !above.is_empty() {
+        show_prompt(&format!("{}\n", above));
+    }
+    ROWS_SINCE_FRAME.fetch_add(1, Ordering::Relaxed); // The Enter key moves the cursor down a row
+    match editor.readline(&finish(&Theme::current().prompt.paint(last))) {
+        Ok(line) => line.trim().to_string(),
+        // Ctrl+C ends the game, as it does at every other prompt
+        Err(ReadlineError::Interrupted) => std::process::exit(130),
+        Err(ReadlineError::Eof) => {
+            outln!();
+            eprintln!("{}", tr("input.ended"));
+            std::process::exit(0);
+        }
+        Err(_) => read_stdin_line(),
+    }
+}
+
+/// Prints a prompt without reading the answer, for input that's read elsewhere.
+pub fn show_prompt(message: &str) {
+    out!("{}", Theme::current().prompt.paint(message));
+    io::stdout().flush().unwrap();
+}
+
+/// Like `prompt`, but what's typed isn't shown, so a secret can be entered
+/// with others watching. Input that doesn't come from a terminal is read as usual.
+pub fn hidden_prompt(message: &str) -> String {
+    json::emit("prompt", vec![("text", message.trim().into())]);
+    out!("{}", Theme::current().prompt.paint(message));
+    io::stdout().flush().unwrap();
+    let Some(echo_off) = EchoOff::enable() else { return prompt("") };
+    let answer = read_stdin_line();
+    drop(echo_off);
+    outln!(); // The Enter key wasn't echoed either
+    if answer_command(&answer) {
+        return hidden_prompt(message);
+    }
+    answer
+}
+
+/// The clock shown in front of a timed prompt, in red for the last five
+/// seconds when `color` is allowed.
+fn timer_label(left: Duration, color: bool) -> String {
+    let seconds = left.as_secs() + u64::from(left.subsec_nanos() > 0);
+    let label = format!("[⏱️ {}s]", seconds);
+    if color && seconds <= 5 { format!("\x1b[31m{}\x1b[0m", label) } else { label }
+}
+
+/// Like `prompt`, but the answer must come before `deadline` runs out;
+/// returns None once time is up. On a terminal the clock in front of the
+/// prompt counts down every second while the player types. Elsewhere it's
+/// shown once, and an answer that arrives too late doesn't count.
+pub fn timed_prompt(message: &str, deadline: Countdown, history: &[String]) -> Option<String> {
+    #[cfg(unix)]
+    if !plain_output() && let Some(raw) = RawMode::enable() {
+        let answer = countdown_prompt(message, deadline, history);
+        drop(raw);
+        return match answer {
+            Some(answer) if answer_command(&answer) => timed_prompt(message, deadline, history),
+            answer => answer,
+        };
+    }
+    let answer = prompt_with_history(&format!("{} {}", timer_label(deadline.left(), false), message), history);
+    (!deadline.expired()).then_some(answer)
+}
+
+/// Reads a line key by key in raw mode, redrawing the prompt with the time left.
+#[cfg(unix)]
+fn countdown_prompt(message: &str, deadline: Countdown, history: &[String]) -> Option<String> {
+    let mut editor = LineEditor::new(history);
+    let mut keys = KeyDecoder::default();
+    loop {
+        let left = deadline.left();
+        if left.is_zero() {
+            outln!();
+            return None;
+        }
+        out!("\r\x1b[K{} {}", timer_label(left, true), Theme::current().prompt.paint(message));
+        editor.redraw_from_start();
+
+        // Wake up when the displayed second changes
+        let tick = Duration::from_nanos(u64::from(left.subsec_nanos()));
+        let Some(byte) = read_byte_timeout(if tick.is_zero() { Duration::from_secs(1) } else { tick }) else { continue };
+        if keys.feed(byte).is_some_and(|key| editor.apply(key)) {
+            outln!();
+            return Some(editor.line().trim().to_string());
+        }
+    }
+}
+
+/// Leaves the current player's feedback on screen until the keyboard has
+/// changed hands: the screen is cleared once `next` presses Enter. With a
+/// bot up next (None), the current player presses Enter when they're done.
+/// Piped input has nobody to hand over to, so it's skipped there.
+pub fn hand_over(next: Option<&str>) {
+    if !typed_input() {
+        return;
+    }
+    prompt(&hand_over_message(next));
+    clear_screen();
+}
+
+/// Like `hand_over`, but the current player may type `/undo` instead, which
+/// returns true and leaves the screen as it is.
+pub fn hand_over_or_undo(next: Option<&str>) -> bool {
+    if !typed_input() {
+        return false;
+    }
+    if prompt(&hand_over_message(next)) == "/undo" {
+        return true;
+    }
+    clear_screen();
+    false
+}
+
+fn hand_over_message(next: Option<&str>) -> String {
+    match next {
+        Some(name) => fill("common.hand_over", &[("name", &name)]),
+        None => tr("common.hide_turn").to_string(),
+    }
+}
+
+// --- Partial Redraws ---
+// Screens with a fixed layout (the full-screen frontend, the spectator view)
+// are drawn as frames. Rather than clearing the screen and printing it all
+// again, which flickers on slow terminals and remote sessions, each frame is
+// written over the last one in place: only the rows that changed are
+// rewritten, and whatever was printed below the old frame is blanked. When
+// the rows can't be trusted (the terminal was resized, a row wrapped, or
+// enough was printed since to scroll the frame away) the frame is drawn from
+// a cleared screen instead, as it is in plain output.
+
+/// The last frame left on screen: how many rows it took up, their text
+/// (empty for frames drawn by ratatui), and the terminal size it was drawn for.
+struct Frame {
+    height: usize,
+    rows: Vec<String>,
+    size: (usize, usize),
+}
+
+impl Frame {
+    /// True while the frame is still on screen as drawn, on a terminal of `size`.
+    fn intact(&self, size: (usize, usize)) -> bool {
+        self.size == size && self.height + ROWS_SINCE_FRAME.load(Ordering::Relaxed) < size.1
+    }
+}
+
+static FRAME: Mutex<Option<Frame>> = Mutex::new(None);
+
+/// Screen rows printed (or typed) since the last frame was drawn.
+static ROWS_SINCE_FRAME: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts the rows `text` takes up below the frame; called for everything printed.
+pub fn note_printed(text: &str, newline: bool) {
+    let rows = text.matches('\n').count() + usize::from(newline);
+    ROWS_SINCE_FRAME.fetch_add(rows, Ordering::Relaxed);
+}
+
+/// Draws a fixed layout from the top of the screen, one entry of `lines`
+/// per row, rewriting only the rows that differ from the last frame.
+pub fn draw_frame(lines: &[String]) {
+    if json::active() {
+        return;
+    }
+    let size = terminal_size();
+    // Banner styles can turn one line into several, so rows are counted after finishing
+    let rows: Vec<String> = lines.iter().flat_map(|line| finish(line).split('\n').map(str::to_string).collect::<Vec<_>>()).collect();
+    let fits = rows.len() < size.1 && rows.iter().all(|row| display_width(row) <= size.0);
+    let mut frame = FRAME.lock().unwrap();
+    let previous = frame.take().filter(|last| fits && last.intact(size));
+
+    if plain_output() || !cursor_control() || !fits {
+        drop(frame);
+        clear_screen();
+        let mut out = io::stdout().lock();
+        for row in &rows {
+            writeln!(out, "{}", row).unwrap();
+        }
+        out.flush().unwrap();
+        return;
+    }
+    let mut out = io::stdout().lock();
+    let previous = previous.map_or(Vec::new(), |last| last.rows);
+    if previous.is_empty() {
+        clear_from_row(&mut out, 0).unwrap();
+    }
+    for (i, row) in rows.iter().enumerate() {
+        if previous.get(i) != Some(row) {
+            // A full-width row covers the old one; erasing after it would take its last column
+            write_row(&mut out, i, row, display_width(row) < size.0).unwrap();
+        }
+    }
+    // Leave the cursor under the frame, with everything below it blanked
+    clear_from_row(&mut out, rows.len()).unwrap();
+    out.flush().unwrap();
+    ROWS_SINCE_FRAME.store(0, Ordering::Relaxed);
+    *frame = Some(Frame { height: rows.len(), rows, size });
+}
+
+/// True when the last frame is still on screen as drawn, so a frame drawn
+/// over it only has to rewrite what changed.
+#[cfg(feature = "tui")]
+pub fn frame_still_drawn(size: (usize, usize)) -> bool {
+    FRAME.lock().unwrap().as_ref().is_some_and(|last| last.intact(size))
+}
+
+/// Records a frame `height` rows tall that the caller drew at the top of
+/// the screen itself (the full-screen frontend draws through ratatui).
+#[cfg(feature = "tui")]
+pub fn note_frame(height: usize, size: (usize, usize)) {
+    ROWS_SINCE_FRAME.store(0, Ordering::Relaxed);
+    *FRAME.lock().unwrap() = Some(Frame { height, rows: Vec::new(), size });
+}
+
+// --- Line Editing ---
+// On a terminal, answers are typed into a small line editor rather than the
+// terminal's own line buffer: Backspace and Delete, Left/Right (and Home/End,
+// or Ctrl+A/Ctrl+E) to move within the line, Ctrl+U to clear everything
+// before the cursor, and Up/Down to recall earlier answers where a prompt
+// has some to offer (a player's own guesses, say). Piped input, plain
+// output and platforms without raw mode read whole lines as before.
+
+/// A key press, decoded from the bytes raw mode delivers.
+#[cfg(unix)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key {
+    Char(char),
+    Enter,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Home,
+    End,
+    Up,
+    Down,
+    ClearToStart,
+    Ignored, // Other control keys and escape sequences
+}
+
+/// Assembles key presses from raw input, one byte at a time.
+#[cfg(unix)]
+#[derive(Debug, Default)]
+struct KeyDecoder {
+    pending: Vec<u8>,
+}
+
+#[cfg(unix)]
+impl KeyDecoder {
+    /// Adds one byte of input, returning the key once all its bytes are in.
+    fn feed(&mut self, byte: u8) -> Option<Key> {
+        self.pending.push(byte);
+        let key = match self.pending.as_slice() {
+            [b'\n' | b'\r'] => Key::Enter,
+            [0x7f | 0x08] => Key::Backspace,
+            [0x01] => Key::Home,
+            [0x05] => Key::End,
+            [0x15] => Key::ClearToStart,
+            [0x1b] | [0x1b, b'[' | b'O'] => return None,
+            [0x1b, b'[' | b'O', b'A'] => Key::Up,
+            [0x1b, b'[' | b'O', b'B'] => Key::Down,
+            [0x1b, b'[' | b'O', b'C'] => Key::Right,
+            [0x1b, b'[' | b'O', b'D'] => Key::Left,
+            [0x1b, b'[' | b'O', b'H'] | [0x1b, b'[', b'1' | b'7', b'~'] => Key::Home,
+            [0x1b, b'[' | b'O', b'F'] | [0x1b, b'[', b'4' | b'8', b'~'] => Key::End,
+            [0x1b, b'[', b'3', b'~'] => Key::Delete,
+            // A sequence ends with a letter or `~`; parameters come before it
+            [0x1b, b'[', .., last] if last.is_ascii_digit() || *last == b';' => return None,
+            [0x1b, ..] => Key::Ignored,
+            bytes => match std::str::from_utf8(bytes) {
+                Ok(text) => text.chars().next().filter(|c| !c.is_control()).map_or(Key::Ignored, Key::Char),
+                Err(e) if e.error_len().is_none() => return None, // More of the character to come
+                Err(_) => Key::Ignored,
+            },
+        };
+        self.pending.clear();
+        Some(key)
+    }
+}
+
+/// The line being typed at a prompt, with the cursor's place in it.
+#[cfg(unix)]
+#[derive(Debug)]
+struct LineEditor<'a> {
+    text: Vec<char>,
+    cursor: usize,
+    history: &'a [String],
+    recalled: usize,  // The earlier answer on show; history.len() for the line being typed
+    draft: Vec<char>, // The line being typed, kept while earlier answers are on show
+    drawn: usize,     // Columns between the start of the line on screen and the terminal's cursor
+}
+
+#[cfg(unix)]
+impl<'a> LineEditor<'a> {
+    fn new(history: &'a [String]) -> LineEditor<'a> {
+        LineEditor { text: Vec::new(), cursor: 0, history, recalled: history.len(), draft: Vec::new(), drawn: 0 }
+    }
+
+    fn line(&self) -> String {
+        self.text.iter().collect()
+    }
+
+    fn replace_text(&mut self, text: Vec<char>) {
+        self.cursor = text.len();
+        self.text = text;
+    }
+
+    /// Applies a key press; returns true once the line is sent.
+    fn apply(&mut self, key: Key) -> bool {
+        match key {
+            Key::Enter => return true,
+            Key::Char(c) => {
+                self.text.insert(self.cursor, c);
+                self.cursor += 1;
+            }
+            Key::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.text.remove(self.cursor);
+            }
+            Key::Delete if self.cursor < self.text.len() => {
+                self.text.remove(self.cursor);
+            }
+            Key::Left => self.cursor = self.cursor.saturating_sub(1),
+            Key::Right => self.cursor = (self.cursor + 1).min(self.text.len()),
+            Key::Home => self.cursor = 0,
+            Key::End => self.cursor = self.text.len(),
+            Key::ClearToStart => {
+                self.text.drain(..self.cursor);
+                self.cursor = 0;
+            }
+            Key::Up if self.recalled > 0 => {
+                if self.recalled == self.history.len() {
+                    self.draft = self.text.clone();
+                }
+                self.recalled -= 1;
+                self.replace_text(self.history[self.recalled].chars().collect());
+            }
+            Key::Down if self.recalled < self.history.len() => {
+                self.recalled += 1;
+                let text = match self.history.get(self.recalled) {
+                    Some(answer) => answer.chars().collect(),
+                    None => std::mem::take(&mut self.draft),
+                };
+                self.replace_text(text);
+            }
+            _ => {}
+        }
+        false
+    }
+
+    /// Redraws the line in place, from wherever the terminal's cursor was left.
+    fn redraw(&mut self) {
+        if self.drawn > 0 {
+            print_raw(&format!("\x1b[{}D", self.drawn));
+        }
+        self.redraw_from_start();
+    }
+
+    /// Draws the line with the terminal's cursor at its start (just after the prompt).
+    fn redraw_from_start(&mut self) {
+        let before: String = self.text[..self.cursor].iter().collect();
+        let after: String = self.text[self.cursor..].iter().collect();
+        let back = display_width(&after);
+        let back = if back > 0 { format!("\x1b[{}D", back) } else { String::new() };
+        print_raw(&format!("{}{}\x1b[K{}", before, after, back));
+        self.drawn = display_width(&before);
+    }
+}
+
+/// Writes typed text as it is, without the output profile's substitutions.
+#[cfg(unix)]
+fn print_raw(text: &str) {
+    let mut stdout = io::stdout().lock();
+    write!(stdout, "{}", text).unwrap();
+    stdout.flush().unwrap();
+}
+
+/// Reads a line key by key in raw mode, with the editing keys above.
+#[cfg(unix)]
+fn edit_line(history: &[String]) -> String {
+    let mut editor = LineEditor::new(history);
+    let mut keys = KeyDecoder::default();
+    // End of input sends whatever was typed
+    while let Some(byte) = read_byte() {
+        let Some(key) = keys.feed(byte) else { continue };
+        if editor.apply(key) {
+            break;
+        }
+        editor.redraw();
+    }
+    print_raw("\n");
+    editor.line()
+}
@@ -0,0 +1,496 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+
+use crate::code::{calculate_score, format_code, generate_secret, is_solved, parse_guess, Guess, Score, MAX_CODE_LENGTH};
+use crate::console::{hidden_prompt, prompt};
+use crate::crypto::{chacha20_xor, constant_time_eq, from_hex, hmac_sha256, pbkdf2_sha256, random_bytes, to_hex};
+use crate::feedback::player_notation;
+use crate::locale::Locale;
+use crate::rankings::{display_width, pad};
+use crate::room::unique_name;
+use crate::rules::{GameSpec, Rules, Variant};
+use crate::snapshot::{code_field, parse_code_field, parse_score_field, score_field, Snapshot, SnapshotBody, SnapshotError};
+use crate::store::clean_field;
+use crate::strings::{fill, tr};
+
+// --- Play by File ---
+// A correspondence game of Individual Secrets lives in one file that the
+// players pass round: a shared folder, a Git repository or an email
+// attachment. Each player runs `turn <file>` when it's theirs, and the tool
+// does what the turn needs and writes the file back for the next player. The
+// file is an engine snapshot of kind "correspondence":
+//
+//   game    id, salt
+//   player  name, check              One per seat, in turn order; check is "-" until their first turn
+//   sealed  owner, secret, mac       A seat's secret, sealed by the seat after it
+//   guess   seat, number, code, mac
+//   score   seat, number, score, mac
+//
+// Whoever can read a secret could peek at it, so nobody ever holds their own.
+// Each seat's secret is dealt and sealed by the next seat in turn order,
+// under a key stretched from that player's passphrase, and that player's turn
+// scores the guess. So the feedback for a guess is waiting when its player's
+// turn comes round again, and a player who has cracked their code still takes
+// turns to score for the seat before them.
+//
+// Every record but the players' carries a MAC under its writer's key, and each
+// turn first checks everything its player wrote, so an edit to anyone's
+// records is caught on their next turn. What a file can't show is an older
+// copy passed on in place of the latest; version control or a shared folder's
+// history does.
+
+/// The options of `turn`.
+#[derive(Debug, Args)]
+pub struct TurnArgs {
+    /// The game's file; one that doesn't exist yet starts a new game
+    pub file: PathBuf,
+}
+
+/// How hard a passphrase is stretched into a seat's keys.
+const KDF_ITERATIONS: u32 = 100_000;
+
+/// Wrong passphrases allowed before a turn gives up.
+const PASSPHRASE_TRIES: usize = 3;
+
+#[derive(Debug, Clone)]
+struct Player {
+    name: String,
+    check: Option<String>, // Proves a passphrase is theirs, once they've chosen one
+}
+
+/// Something that happened in the game, kept in the order it did.
+#[derive(Debug, Clone)]
+enum Entry {
+    Sealed { owner: usize, secret: String, mac: String },
+    Guess { seat: usize, number: u32, code: Guess, mac: String },
+    Score { seat: usize, number: u32, score: Score, mac: String },
+}
+
+impl Entry {
+    /// The entry's fields, tag first, without its MAC.
+    fn fields(&self) -> Vec<String> {
+        match self {
+            Entry::Sealed { owner, secret, .. } => vec!["sealed".to_string(), owner.to_string(), secret.clone()],
+            Entry::Guess { seat, number, code, .. } => vec!["guess".to_string(), seat.to_string(), number.to_string(), code_field(code)],
+            Entry::Score { seat, number, score, .. } => vec!["score".to_string(), seat.to_string(), number.to_string(), score_field(*score)],
+        }
+    }
+
+    fn mac(&self) -> &str {
+        match self {
+            Entry::Sealed { mac, .. } | Entry::Guess { mac, .. } | Entry::Score { mac, .. } => mac,
+        }
+    }
+}
+
+/// A seat's keys, stretched from its player's passphrase.
+struct Keys {
+    seal: [u8; 32],
+    mac: [u8; 32],
+}
+
+impl Keys {
+    fn derive(passphrase: &str, salt: &str, seat: usize) -> Keys {
+        let mut material = [0u8; 64];
+        pbkdf2_sha256(passphrase.as_bytes(), format!("{}\t{}", salt, seat).as_bytes(), KDF_ITERATIONS, &mut material);
+        Keys { seal: material[..32].try_into().unwrap(), mac: material[32..].try_into().unwrap() }
+    }
+
+    /// The MAC of a record's `fields` in the game `id`.
+    fn mac(&self, id: &str, fields: &[String]) -> String {
+        to_hex(&hmac_sha256(&self.mac, format!("{}\t{}", id, fields.join("\t")).as_bytes()))
+    }
+
+    fn verify(&self, id: &str, fields: &[String], mac: &str) -> bool {
+        constant_time_eq(self.mac(id, fields).as_bytes(), mac.as_bytes())
+    }
+
+    /// Seals seat `owner`'s secret. A seat's key only ever seals the one
+    /// secret, so the owner's seat serves as the nonce.
+    fn seal(&self, owner: usize, secret: &Guess) -> String {
+        let mut bytes = *secret;
+        chacha20_xor(&self.seal, owner as u64, &mut bytes);
+        to_hex(&bytes)
+    }
+
+    fn unseal(&self, owner: usize, sealed: &str) -> Option<Guess> {
+        let mut bytes = Guess::from_digits(&from_hex(sealed).filter(|bytes| bytes.len() <= MAX_CODE_LENGTH)?);
+        chacha20_xor(&self.seal, owner as u64, &mut bytes);
+        Some(bytes)
+    }
+}
+
+/// The fields a seat's passphrase check is the MAC of.
+fn check_fields(seat: usize) -> Vec<String> {
+    vec!["check".to_string(), seat.to_string()]
+}
+
+fn parse_number<T: std::str::FromStr>(field: &str) -> Result<T, SnapshotError> {
+    field.parse().map_err(|_| SnapshotError::Malformed(format!("bad number '{}'", field)))
+}
+
+/// A game played by passing a file round.
+#[derive(Debug, Clone)]
+pub struct Correspondence {
+    id: String,
+    salt: String,
+    players: Vec<Player>,
+    entries: Vec<Entry>,
+}
+
+impl Correspondence {
+    fn new(names: Vec<String>) -> Correspondence {
+        Correspondence {
+            id: to_hex(&random_bytes::<8>()),
+            salt: to_hex(&random_bytes::<16>()),
+            players: names.into_iter().map(|name| Player { name, check: None }).collect(),
+            entries: Vec::new(),
+        }
+    }
+
+    fn name(&self, seat: usize) -> &str {
+        &self.players[seat].name
+    }
+
+    /// The seat that deals, seals and scores `seat`'s secret.
+    fn holder(&self, seat: usize) -> usize {
+        (seat + 1) % self.players.len()
+    }
+
+    /// The seat whose secret `holder` holds.
+    fn held(&self, holder: usize) -> usize {
+        (holder + self.players.len() - 1) % self.players.len()
+    }
+
+    fn writer(&self, entry: &Entry) -> usize {
+        match entry {
+            Entry::Sealed { owner, .. } => self.holder(*owner),
+            Entry::Guess { seat, .. } => *seat,
+            Entry::Score { seat, .. } => self.holder(*seat),
+        }
+    }
+
+    /// Seat `seat`'s guesses in order, each with its score once it has one.
+    fn guesses(&self, seat: usize) -> Vec<(u32, Guess, Option<Score>)> {
+        let score_of = |n: u32| {
+            self.entries.iter().find_map(|e| match e {
+                Entry::Score { seat: s, number, score, .. } if *s == seat && *number == n => Some(*score),
+                _ => None,
+            })
+        };
+        self.entries
+            .iter()
+            .filter_map(|e| match e {
+                Entry::Guess { seat: s, number, code, .. } if *s == seat => Some((*number, *code, score_of(*number))),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The guess of `seat`'s still waiting for a score, if there is one.
+    fn unscored(&self, seat: usize) -> Option<(u32, Guess)> {
+        self.guesses(seat).into_iter().find(|(_, _, score)| score.is_none()).map(|(number, code, _)| (number, code))
+    }
+
+    /// How many guesses `seat` took to crack their code, once they have.
+    fn cracked(&self, seat: usize) -> Option<u32> {
+        self.guesses(seat).into_iter().find(|(_, _, score)| score.is_some_and(is_solved)).map(|(number, _, _)| number)
+    }
+
+    fn sealed(&self, owner: usize) -> Option<&str> {
+        self.entries.iter().find_map(|e| match e {
+            Entry::Sealed { owner: o, secret, .. } if *o == owner => Some(secret.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Whether `seat` has anything to do: a guess to make or one to score.
+    fn needs_turn(&self, seat: usize) -> bool {
+        self.cracked(seat).is_none() || self.unscored(self.held(seat)).is_some()
+    }
+
+    /// Whose turn it is, going round from whoever wrote last, or None once the game is over.
+    fn whose_turn(&self) -> Option<usize> {
+        let seats = self.players.len();
+        let start = self.entries.last().map_or(0, |e| self.writer(e) + 1);
+        (0..seats).map(|k| (start + k) % seats).find(|&seat| self.needs_turn(seat))
+    }
+
+    /// Checks the entries could have been played in the order they're in.
+    fn check_order(&self) -> Result<(), SnapshotError> {
+        let seats = self.players.len();
+        if !(2..=8).contains(&seats) {
+            return Err(SnapshotError::Malformed(format!("{} players", seats)));
+        }
+        let out_of_order = |what: &str| Err(SnapshotError::Malformed(format!("{} out of order", what)));
+        let (mut guessed, mut scored, mut sealed, mut cracked) = (vec![0; seats], vec![0; seats], vec![false; seats], vec![false; seats]);
+        for entry in &self.entries {
+            match *entry {
+                Entry::Sealed { owner, .. } => {
+                    if owner >= seats || sealed[owner] {
+                        return out_of_order("a sealed secret");
+                    }
+                    sealed[owner] = true;
+                }
+                Entry::Guess { seat, number, .. } => {
+                    // A guess waits for the last one's score
+                    if seat >= seats || cracked[seat] || number != guessed[seat] + 1 || scored[seat] != guessed[seat] {
+                        return out_of_order("a guess");
+                    }
+                    guessed[seat] = number;
+                }
+                Entry::Score { seat, number, score, .. } => {
+                    if seat >= seats || !sealed[seat] || number != scored[seat] + 1 || number > guessed[seat] {
+                        return out_of_order("a score");
+                    }
+                    scored[seat] = number;
+                    cracked[seat] = is_solved(score);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The first thing `seat` wrote that doesn't carry their MAC any more, if anything.
+    fn tampered(&self, seat: usize, keys: &Keys) -> Option<String> {
+        self.entries.iter().filter(|e| self.writer(e) == seat).find(|e| !keys.verify(&self.id, &e.fields(), e.mac())).map(|e| match e {
+            Entry::Sealed { owner, .. } => fill("turn.tampered_secret", &[("name", &self.name(*owner))]),
+            Entry::Guess { number, .. } => fill("turn.tampered_guess", &[("number", number)]),
+            Entry::Score { seat, number, .. } => fill("turn.tampered_score", &[("name", &self.name(*seat)), ("number", number)]),
+        })
+    }
+
+    /// Adds an entry, with its MAC under its writer's `keys`.
+    fn push(&mut self, keys: &Keys, mut entry: Entry) {
+        let signed = keys.mac(&self.id, &entry.fields());
+        match &mut entry {
+            Entry::Sealed { mac, .. } | Entry::Guess { mac, .. } | Entry::Score { mac, .. } => *mac = signed,
+        }
+        self.entries.push(entry);
+    }
+
+    /// Reads the game at `path`, explaining what's wrong with it if it can't be played.
+    fn load(path: &Path) -> Result<Correspondence, String> {
+        let text = fs::read_to_string(path).map_err(|e| fill("turn.read_failed", &[("path", &path.display()), ("error", &e)]))?;
+        Correspondence::from_snapshot(&text).map_err(|e| fill("turn.unplayable", &[("path", &path.display()), ("error", &e)]))
+    }
+
+    /// Writes the game back to `path` (atomically, via a temporary file).
+    fn save(&self, path: &Path) -> io::Result<()> {
+        let temp = path.with_extension("tmp");
+        fs::write(&temp, self.to_snapshot())?;
+        fs::rename(&temp, path)
+    }
+}
+
+impl Snapshot for Correspondence {
+    const KIND: &'static str = "correspondence";
+
+    fn spec(&self) -> GameSpec {
+        GameSpec::new(Variant::IndividualSecrets, Rules::default())
+    }
+
+    fn records(&self) -> Vec<Vec<String>> {
+        let mut records = vec![vec!["game".to_string(), self.id.clone(), self.salt.clone()]];
+        for player in &self.players {
+            records.push(vec!["player".to_string(), clean_field(&player.name), player.check.clone().unwrap_or_else(|| "-".to_string())]);
+        }
+        for entry in &self.entries {
+            let mut record = entry.fields();
+            record.push(entry.mac().to_string());
+            records.push(record);
+        }
+        records
+    }
+
+    fn from_body(body: SnapshotBody) -> Result<Self, SnapshotError> {
+        let mut game = None;
+        let mut players = Vec::new();
+        let mut entries = Vec::new();
+        for record in &body.records {
+            match record.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+                ["game", id, salt] => game = Some((id.to_string(), salt.to_string())),
+                ["player", name, check] => players.push(Player { name: name.to_string(), check: (*check != "-").then(|| check.to_string()) }),
+                ["sealed", owner, secret, mac] => entries.push(Entry::Sealed { owner: parse_number(owner)?, secret: secret.to_string(), mac: mac.to_string() }),
+                ["guess", seat, number, code, mac] => entries.push(Entry::Guess {
+                    seat: parse_number(seat)?,
+                    number: parse_number(number)?,
+                    code: parse_code_field(code)?,
+                    mac: mac.to_string(),
+                }),
+                ["score", seat, number, score, mac] => entries.push(Entry::Score {
+                    seat: parse_number(seat)?,
+                    number: parse_number(number)?,
+                    score: parse_score_field(score)?,
+                    mac: mac.to_string(),
+                }),
+                _ => {} // Records from newer builds are skipped
+            }
+        }
+        let (id, salt) = game.ok_or_else(|| SnapshotError::Malformed("missing game record".to_string()))?;
+        let game = Correspondence { id, salt, players, entries };
+        game.check_order()?;
+        Ok(game)
+    }
+}
+
+/// Asks for `seat`'s passphrase, or has them choose one on their first turn.
+fn unlock(game: &mut Correspondence, seat: usize) -> Result<Keys, String> {
+    let name = game.name(seat).to_string();
+    let Some(check) = game.players[seat].check.clone() else {
+        outln!("{}", fill("turn.first_turn", &[("name", &name), ("other", &game.name(game.held(seat)))]));
+        loop {
+            let passphrase = hidden_prompt(tr("turn.passphrase"));
+            if passphrase.is_empty() {
+                outln!("{}", tr("turn.passphrase_empty"));
+            } else if hidden_prompt(tr("turn.passphrase_again")) != passphrase {
+                outln!("{}", tr("turn.passphrase_mismatch"));
+            } else {
+                let keys = Keys::derive(&passphrase, &game.salt, seat);
+                game.players[seat].check = Some(keys.mac(&game.id, &check_fields(seat)));
+                return Ok(keys);
+            }
+        }
+    };
+    for _ in 0..PASSPHRASE_TRIES {
+        let keys = Keys::derive(&hidden_prompt(&fill("turn.passphrase_prompt", &[("name", &name)])), &game.salt, seat);
+        if keys.verify(&game.id, &check_fields(seat), &check) {
+            return Ok(keys);
+        }
+        outln!("{}", tr("turn.passphrase_wrong"));
+    }
+    Err(fill("turn.passphrase_locked", &[("name", &name)]))
+}
+
+/// Shows how everyone is getting on.
+fn show_table(game: &Correspondence) {
+    let width = game.players.iter().map(|p| display_width(&p.name)).max().unwrap_or(0);
+    for seat in 0..game.players.len() {
+        let status = match game.cracked(seat) {
+            Some(guesses) => fill("turn.cracked_status", &[("count", &guesses)]),
+            None => fill("turn.guesses_status", &[("count", &game.guesses(seat).len())]),
+        };
+        outln!("  {}  {}", pad(game.name(seat), width), status);
+    }
+}
+
+/// Shows the final places: fewer guesses place higher, and the same number shares a place.
+fn show_results(game: &Correspondence) {
+    let locale = Locale::current();
+    let width = game.players.iter().map(|p| display_width(&p.name)).max().unwrap_or(0);
+    let mut finishers: Vec<(u32, usize)> = (0..game.players.len()).filter_map(|seat| game.cracked(seat).map(|guesses| (guesses, seat))).collect();
+    finishers.sort();
+    outln!("{}", tr("turn.over"));
+    for &(guesses, seat) in &finishers {
+        let place = 1 + finishers.iter().filter(|(other, _)| *other < guesses).count();
+        let secret = game.guesses(seat).get(guesses as usize - 1).map(|(_, code, _)| format_code(code)).unwrap_or_default();
+        outln!("{}", fill("turn.place", &[
+            ("place", &format!("{:>4}", locale.ordinal(place))),
+            ("name", &pad(game.name(seat), width)),
+            ("count", &guesses),
+            ("secret", &secret),
+        ]));
+    }
+}
+
+/// Plays whoever's turn it is in the game at `path`.
+fn play_turn(mut game: Correspondence, path: &Path) -> Result<(), String> {
+    let names: Vec<&str> = game.players.iter().map(|p| p.name.as_str()).collect();
+    outln!("{}", fill("turn.title", &[("names", &names.join(", "))]));
+    let Some(seat) = game.whose_turn() else {
+        show_results(&game);
+        return Ok(());
+    };
+    show_table(&game);
+    outln!("{}", fill("turn.whose_turn", &[("name", &game.name(seat))]));
+
+    let keys = unlock(&mut game, seat)?;
+    if let Some(what) = game.tampered(seat, &keys) {
+        return Err(fill("turn.tampered", &[("path", &path.display()), ("what", &what), ("name", &game.name(seat))]));
+    }
+
+    // Score the guess waiting on this seat, dealing the secret it's scored against first if need be
+    let held = game.held(seat);
+    if let Some((number, guess)) = game.unscored(held) {
+        let secret = match game.sealed(held) {
+            Some(sealed) => keys.unseal(held, sealed).ok_or_else(|| fill("turn.unreadable_secret", &[("name", &game.name(held))]))?,
+            None => {
+                let secret = generate_secret();
+                let sealed = keys.seal(held, &secret);
+                game.push(&keys, Entry::Sealed { owner: held, secret: sealed, mac: String::new() });
+                secret
+            }
+        };
+        let score = calculate_score(&guess, &secret);
+        game.push(&keys, Entry::Score { seat: held, number, score, mac: String::new() });
+        outln!("{}", fill("turn.scored", &[("name", &game.name(held)), ("number", &number)]));
+        if is_solved(score) {
+            outln!("{}", fill("turn.cracked_other", &[("name", &game.name(held))]));
+        }
+    }
+
+    if let Some(guesses) = game.cracked(seat) {
+        outln!("{}", fill("turn.cracked_own", &[("count", &guesses), ("name", &game.name(held))]));
+    } else {
+        let history = game.guesses(seat);
+        if !history.is_empty() {
+            outln!("{}", tr("turn.your_guesses"));
+            for (number, code, score) in history {
+                let score = score.map_or("-".to_string(), |score| player_notation().render(score));
+                outln!("  #{}  {}  {}", number, format_code(&code), score);
+            }
+        }
+        let code = loop {
+            match parse_guess(&prompt(tr("turn.guess_prompt"))) {
+                Ok(code) => break code,
+                Err(message) => outln!("{}", message),
+            }
+        };
+        let number = game.guesses(seat).len() as u32 + 1;
+        game.push(&keys, Entry::Guess { seat, number, code, mac: String::new() });
+    }
+
+    game.save(path).map_err(|e| fill("turn.write_back_failed", &[("path", &path.display()), ("error", &e)]))?;
+    match game.whose_turn() {
+        Some(next) => outln!("{}", fill("turn.saved", &[("path", &path.display()), ("name", &game.name(next))])),
+        None => show_results(&game),
+    }
+    Ok(())
+}
+
+/// Sets up a new game at `path`, if the player wants one, and plays its first turn.
+fn start_game(path: &Path) -> Result<(), String> {
+    outln!("{}", fill("turn.no_game", &[("path", &path.display())]));
+    if !prompt(tr("turn.start_prompt")).eq_ignore_ascii_case(tr("common.yes")) {
+        return Ok(());
+    }
+    let names = loop {
+        let answer = prompt(tr("turn.players_prompt"));
+        let wanted: Vec<&str> = answer.split(',').map(str::trim).filter(|name| !name.is_empty()).collect();
+        if (2..=8).contains(&wanted.len()) {
+            let mut names: Vec<String> = Vec::new();
+            for name in wanted {
+                let taken: Vec<&str> = names.iter().map(String::as_str).collect();
+                names.push(unique_name(name, &taken));
+            }
+            break names;
+        }
+        outln!("{}", tr("turn.player_count"));
+    };
+    let game = Correspondence::new(names);
+    game.save(path).map_err(|e| fill("turn.write_failed", &[("path", &path.display()), ("error", &e)]))?;
+    outln!("{}", fill("turn.first_player", &[("name", &game.name(0))]));
+    play_turn(game, path)
+}
+
+/// Entry point for the `turn` subcommand.
+pub fn run_turn(args: &TurnArgs) -> Result<(), String> {
+    let path = args.file.as_path();
+    if !path.exists() {
+        return start_game(path);
+    }
+    play_turn(Correspondence::load(path)?, path)
+}
@@ -0,0 +1,201 @@
+use chacha20::cipher::{KeyIvInit, StreamCipher};
+use chacha20::ChaCha20Legacy;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+// --- Cryptographic Building Blocks ---
+// The game's uses of cryptography, each a thin wrapper over a RustCrypto
+// crate, so the rest of the code says what it needs rather than how.
+//
+// Anything kept secret at rest (the encrypted stats store) goes through
+// `seal` and `open`: XChaCha20-Poly1305, whose 192-bit nonces are safe to
+// pick at random for every save. Keys come from passphrases through
+// PBKDF2-HMAC-SHA-256. `chacha20_xor` is the bare original ChaCha20 (64-bit
+// nonce) that play-by-file games seal secrets with; those records carry an
+// HMAC of their own, and nothing else should use it.
+
+/// Bytes `seal` adds to what it seals: the nonce in front and the tag behind.
+pub const SEAL_OVERHEAD: usize = NONCE_LEN + 16;
+
+const NONCE_LEN: usize = 24;
+
+/// One-shot SHA-256.
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+/// HMAC-SHA-256 (RFC 2104).
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut mac = <Hmac<Sha256> as KeyInit>::new_from_slice(key).expect("HMAC takes a key of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+/// PBKDF2-HMAC-SHA-256 (RFC 8018), filling `out`.
+pub fn pbkdf2_sha256(password: &[u8], salt: &[u8], iterations: u32, out: &mut [u8]) {
+    pbkdf2::pbkdf2_hmac::<Sha256>(password, salt, iterations, out);
+}
+
+/// Encrypts or decrypts `data` in place with the original ChaCha20 under
+/// `key` and `nonce`. Never reuse a (key, nonce) pair for two different
+/// messages, and MAC what it gives back.
+pub fn chacha20_xor(key: &[u8; 32], nonce: u64, data: &mut [u8]) {
+    ChaCha20Legacy::new(key.into(), &nonce.to_le_bytes().into()).apply_keystream(data);
+}
+
+/// Encrypts and authenticates `plaintext` under `key`, binding it to
+/// `associated` (which isn't encrypted, but must be the same to open it).
+pub fn seal(key: &[u8; 32], associated: &[u8], plaintext: &[u8]) -> Vec<u8> {
+    let nonce = random_bytes::<NONCE_LEN>();
+    let ciphertext = XChaCha20Poly1305::new(key.into())
+        .encrypt(&XNonce::from(nonce), Payload { msg: plaintext, aad: associated })
+        .expect("XChaCha20-Poly1305 seals any message that fits in memory");
+    [&nonce[..], &ciphertext].concat()
+}
+
+/// The plaintext `seal` was given, or None if `sealed` wasn't sealed under
+/// `key` with `associated`, or has been changed since.
+pub fn open(key: &[u8; 32], associated: &[u8], sealed: &[u8]) -> Option<Vec<u8>> {
+    if sealed.len() < SEAL_OVERHEAD {
+        return None;
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    let nonce: [u8; NONCE_LEN] = nonce.try_into().ok()?;
+    XChaCha20Poly1305::new(key.into()).decrypt(&XNonce::from(nonce), Payload { msg: ciphertext, aad: associated }).ok()
+}
+
+/// Compares two byte strings without leaking where they first differ.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+/// Fresh random bytes from `rand`'s cryptographically secure thread generator.
+pub fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    rand::rng().fill_bytes(&mut bytes);
+    bytes
+}
+
+/// Lower-case hexadecimal encoding.
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Reads hexadecimal back into bytes, or None if it isn't hex.
+pub fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len()).step_by(2).map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(text: &str) -> Vec<u8> {
+        from_hex(&text.replace(' ', "")).expect("test vectors are hex")
+    }
+
+    #[test]
+    fn sealed_data_opens_only_unchanged_and_with_its_key() {
+        let (key, other) = ([7; 32], [8; 32]);
+        let sealed = seal(&key, b"header", b"results");
+        assert_eq!(sealed.len(), b"results".len() + SEAL_OVERHEAD);
+        assert_eq!(open(&key, b"header", &sealed).as_deref(), Some(&b"results"[..]));
+
+        assert_eq!(open(&other, b"header", &sealed), None);
+        assert_eq!(open(&key, b"headers", &sealed), None);
+        let mut changed = sealed.clone();
+        changed[NONCE_LEN] ^= 1;
+        assert_eq!(open(&key, b"header", &changed), None);
+        assert_eq!(open(&key, b"header", &sealed[..SEAL_OVERHEAD - 1]), None);
+
+        // Every seal picks a fresh nonce
+        assert_ne!(seal(&key, b"header", b"results"), sealed);
+    }
+
+    #[test]
+    fn sha256_matches_fips_180() {
+        assert_eq!(sha256(b"abc").to_vec(), hex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"));
+        assert_eq!(
+            sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq").to_vec(),
+            hex("248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1")
+        );
+        assert_eq!(sha256(b"").to_vec(), hex("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"));
+    }
+
+    #[test]
+    fn hmac_sha256_matches_rfc_4231() {
+        assert_eq!(hmac_sha256(&[0x0b; 20], b"Hi There").to_vec(), hex("b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"));
+        assert_eq!(
+            hmac_sha256(b"Jefe", b"what do ya want for nothing?").to_vec(),
+            hex("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+        // A key longer than a block is hashed first
+        assert_eq!(
+            hmac_sha256(&[0xaa; 131], b"Test Using Larger Than Block-Size Key - Hash Key First").to_vec(),
+            hex("60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54")
+        );
+    }
+
+    #[test]
+    fn pbkdf2_sha256_matches_known_answers() {
+        // RFC 6070's inputs, with SHA-256 in place of SHA-1
+        let derive = |iterations| {
+            let mut out = [0u8; 32];
+            pbkdf2_sha256(b"password", b"salt", iterations, &mut out);
+            out.to_vec()
+        };
+        assert_eq!(derive(1), hex("120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b"));
+        assert_eq!(derive(2), hex("ae4d0c95af6b46d32d0adff928f06dd02a303f8ef3c251dfd6e2d85a95474c43"));
+        assert_eq!(derive(4096), hex("c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a"));
+
+        // RFC 7914 section 11, which takes more than one block of output
+        let mut out = [0u8; 64];
+        pbkdf2_sha256(b"passwd", b"salt", 1, &mut out);
+        assert_eq!(
+            out.to_vec(),
+            hex("55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783")
+        );
+    }
+
+    #[test]
+    fn chacha20_xor_matches_rfc_8439() {
+        // Section 2.4.2 starts at block 1, so block 0 is padded out and
+        // dropped. Its nonce is four zero bytes and then the 64-bit one.
+        let key: [u8; 32] = std::array::from_fn(|i| i as u8);
+        let plaintext = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+        let mut data = vec![0u8; 64];
+        data.extend_from_slice(plaintext);
+        chacha20_xor(&key, 0x4a00_0000, &mut data);
+        assert_eq!(
+            data[64..].to_vec(),
+            hex("6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0bf91b65c5524733ab8f593dabcd62b3571639d624e65152ab8f530c359f0861d8\
+                 07ca0dbf500d6a6156a38e088a22b65e52bc514d16ccf806818ce91ab77937365af90bbf74a35be6b40b8eedf2785e42874d")
+        );
+
+        // The same call again takes the encryption off
+        chacha20_xor(&key, 0x4a00_0000, &mut data);
+        assert_eq!(&data[64..], plaintext);
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        assert_eq!(to_hex(&[0x00, 0xab, 0xff]), "00abff");
+        assert_eq!(from_hex("00abff"), Some(vec![0x00, 0xab, 0xff]));
+        assert_eq!(from_hex("abc"), None);
+        assert_eq!(from_hex("zz"), None);
+    }
+
+    #[test]
+    fn constant_time_eq_compares_whole_strings() {
+        assert!(constant_time_eq(b"seal", b"seal"));
+        assert!(!constant_time_eq(b"seal", b"seam"));
+        assert!(!constant_time_eq(b"seal", b"seals"));
+    }
+}
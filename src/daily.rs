@@ -0,0 +1,145 @@
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::coach::coach_guess;
+use crate::code::{calculate_score, code_length, format_code, is_solved, set_code_length, Guess, Score};
+use crate::console::{clear_screen, prompt};
+use crate::feedback::{player_notation, EmojiNotation, FeedbackRenderer};
+use crate::game::{get_consistent_guess, track_candidates};
+use crate::locale::{civil_from_days, Locale};
+use crate::rules::Rules;
+use crate::session::{Session, Solve};
+use crate::solver::all_codes;
+use crate::stats::{celebrate, milestones, record_results, streaks};
+use crate::store::{new_game_id, now_timestamp, GameResult, StoreSet};
+use crate::strings::{fill, tr};
+use crate::theme::paint_feedback;
+
+// --- Daily Challenge ---
+// Everyone gets the same secret on the same (UTC) day. The secret is derived
+// from the day number with a small self-contained generator rather than
+// `rand`, so the puzzle stays identical across builds and platforms. It is
+// always played under the standard rules, whatever length is set, so the
+// puzzle and its results are the same for everyone.
+
+/// Day number of puzzle #1 (2025-01-01, counted in days since 1970-01-01).
+const DAILY_EPOCH_DAY: u64 = 20_089;
+
+/// Mixed into the day number so daily secrets don't follow an obvious pattern.
+const DAILY_SALT: u64 = 0x5EC2_E7C0_DE5A_17ED;
+
+/// Today's day number (UTC), in days since 1970-01-01.
+pub fn today() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() / 86_400
+}
+
+/// The puzzle number shown in results, counted from the first daily challenge.
+pub fn puzzle_number(day: u64) -> u64 {
+    day.saturating_sub(DAILY_EPOCH_DAY) + 1
+}
+
+/// SplitMix64: a tiny, well-known generator whose output never changes.
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Derives the secret for a given day. Same day, same secret, everywhere.
+pub fn daily_secret(day: u64) -> Guess {
+    let mut state = day ^ DAILY_SALT;
+    let mut pool: Vec<u8> = (0..=9).collect();
+    let mut secret = Vec::with_capacity(Rules::STANDARD.code_length);
+    for _ in 0..Rules::STANDARD.code_length {
+        let index = (splitmix64(&mut state) % pool.len() as u64) as usize;
+        secret.push(pool.remove(index));
+    }
+    Guess::from_digits(&secret)
+}
+
+/// Builds the shareable result text for a finished daily challenge.
+pub fn share_text(day: u64, scores: &[Score]) -> String {
+    let (year, month, date) = civil_from_days(day as i64);
+    let date = format!("{}-{:02}-{:02}", year, month, date);
+    let mut text = fill("daily.share_title", &[("number", &puzzle_number(day)), ("date", &date)]) + "\n";
+    text.push_str(&fill("daily.share_cracked", &[("count", &Locale::current().count(scores.len() as u64))]));
+    text.push('\n');
+
+    for score in scores {
+        text.push_str(&EmojiNotation.render(*score));
+        text.push('\n');
+    }
+    text
+}
+
+/// Plays today's daily challenge and prints the shareable result grid.
+pub fn run_daily(stores: &mut StoreSet, session: &mut Session) {
+    clear_screen();
+    let length = code_length();
+    set_code_length(Rules::STANDARD.code_length);
+    let day = today();
+    let secret = daily_secret(day);
+
+    outln!("{}", fill("daily.title", &[("number", &puzzle_number(day))]));
+    outln!("{}", tr("daily.intro"));
+
+    let player = prompt(tr("common.name_prompt"));
+    let started_at = Instant::now();
+    let mut scores: Vec<Score> = Vec::new();
+    let mut candidates = all_codes();
+    let mut clues: Vec<(Guess, Score)> = Vec::new();
+
+    loop {
+        let Some(guess) = get_consistent_guess(&player, &clues) else { continue };
+        let score = calculate_score(&guess, &secret);
+        scores.push(score);
+        clues.push((guess, score));
+
+        outln!("{}", fill("daily.feedback", &[
+            ("guess", &format_code(&guess)),
+            ("score", &paint_feedback(score, &player_notation().render(score))),
+            ("emoji", &EmojiNotation.render(score)),
+        ]));
+        coach_guess(&guess, &candidates);
+        track_candidates(&mut candidates, &guess, score);
+
+        if is_solved(score) {
+            break;
+        }
+    }
+
+    let found = milestones(stores.store_for(1).results(), "daily", &Rules::STANDARD.record_key(), &player, scores.len() as u32, &[]);
+    celebrate(&player, &found);
+    outln!("{}", fill("daily.cracked", &[("name", &player), ("time", &Locale::current().duration(started_at.elapsed()))]));
+
+    session.record_secret("daily", secret, Some(Solve { player: player.clone(), guesses: scores.len() as u32 }));
+    record_results(stores, vec![GameResult {
+        game: new_game_id(),
+        timestamp: now_timestamp(),
+        mode: "daily".to_string(),
+        player: player.clone(),
+        rank: 1,
+        field_size: 1,
+        guesses: scores.len() as u32,
+        rules: Rules::STANDARD.record_key(),
+    }], &[]);
+
+    // Group games may be kept in the club store, so the streaks look at both
+    let mut history = stores.personal.results().to_vec();
+    if let Some(club) = &stores.club {
+        history.extend_from_slice(club.results());
+    }
+    let streak = streaks(&history, &player);
+    let id = if streak.days == 1 { "daily.streak_one" } else { "daily.streak" };
+    outln!("{}", fill(id, &[
+        ("days", &streak.days),
+        ("best_days", &streak.best_days),
+        ("wins", &streak.wins),
+        ("best_wins", &streak.best_wins),
+    ]));
+
+    outln!("{}", tr("daily.share"));
+    out!("{}", share_text(day, &scores));
+    set_code_length(length);
+}
@@ -0,0 +1,298 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use crate::glyphs::OutputProfile;
+use crate::rules::SUPPORTED_CODE_LENGTHS;
+use crate::strings::Language;
+
+// --- Defaults File ---
+// `config.toml` in the config directory holds the values the command-line
+// options would otherwise have to give every time. An `SGG_*` environment
+// variable for a key wins over the file (handy in CI scripts and containers),
+// and options given on the command line win over both. Only a small part of
+// TOML is understood: `key = value` lines with strings, numbers, booleans
+// and arrays of strings.
+
+/// File name of the defaults file inside the config directory.
+pub const DEFAULTS_FILE: &str = "config.toml";
+
+/// What `config init` writes: every key, commented out at its usual value.
+const TEMPLATE: &str = r#"# Secret Guessing Game defaults
+#
+# The game reads this file at start-up. Options given on the command line
+# win over the values here. Remove the # in front of a line to use it.
+
+# Digits in each secret code, from 3 to 6, as with --length.
+# code_length = 4
+
+# Seconds the game pauses after a bot's move and similar moments, as with
+# --delay. 0 means no pauses.
+# delay = 1
+
+# The colour theme: classic, bright, mono, or one defined in the settings.
+# theme = "classic"
+
+# The language of messages, such as "en" or "de", as with --lang.
+# language = "en"
+
+# Which symbols to draw: "emoji", "unicode" (no emoji) or "ascii".
+# output = "emoji"
+
+# Names offered for the human seats, in seat order.
+# names = ["Ann", "Bob"]
+
+# Plain text without escape codes, as with --no-ansi.
+# no_ansi = false
+"#;
+
+/// The environment variable for each key. Lists are separated by commas.
+pub const ENV_VARS: &[(&str, &str)] = &[
+    ("SGG_CODE_LENGTH", "code_length"),
+    ("SGG_DELAY", "delay"),
+    ("SGG_THEME", "theme"),
+    ("SGG_LANG", "language"),
+    ("SGG_OUTPUT", "output"),
+    ("SGG_NAMES", "names"),
+    ("SGG_NO_ANSI", "no_ansi"),
+];
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Defaults {
+    pub code_length: Option<usize>,
+    /// The length of every pause, as `--delay` gives it.
+    pub delay: Option<Duration>,
+    pub theme: Option<String>,
+    /// A language tag, as `--lang` takes it.
+    pub language: Option<String>,
+    pub output: Option<OutputProfile>,
+    /// Default names for seats 1, 2, ... when they're played by people.
+    pub names: Vec<String>,
+    /// Escape codes off, as `--no-ansi` does; None leaves them to the terminal.
+    pub no_ansi: Option<bool>,
+}
+
+/// A value on the right of `=`.
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Text(String),
+    Number(f64),
+    Flag(bool),
+    List(Vec<Value>),
+}
+
+static CURRENT: RwLock<Option<Defaults>> = RwLock::new(None);
+
+/// The per-platform directory the defaults file lives in.
+pub fn config_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME").filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir).join("secret-guessing-game"));
+    }
+    if cfg!(windows) && let Some(dir) = std::env::var_os("APPDATA") {
+        return Some(PathBuf::from(dir).join("secret-guessing-game"));
+    }
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config/secret-guessing-game"))
+}
+
+/// Reads a string starting just after its opening quote, returning it and
+/// the rest of the line. Basic strings (`"..."`) take backslash escapes;
+/// literal strings (`'...'`) don't.
+fn parse_string(text: &str, quote: char) -> Result<(String, &str), String> {
+    let mut out = String::new();
+    let mut chars = text.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            c if c == quote => return Ok((out, &text[i + 1..])),
+            '\\' if quote == '"' => match chars.next().map(|(_, c)| c) {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some('"') => out.push('"'),
+                Some('\\') => out.push('\\'),
+                _ => return Err("unknown escape in a string".to_string()),
+            },
+            c => out.push(c),
+        }
+    }
+    Err("a string is missing its closing quote".to_string())
+}
+
+/// Reads one value from the start of `text`, returning it and what's left.
+fn parse_value(text: &str) -> Result<(Value, &str), String> {
+    let text = text.trim_start();
+    if let Some(rest) = text.strip_prefix('"') {
+        return parse_string(rest, '"').map(|(s, rest)| (Value::Text(s), rest));
+    }
+    if let Some(rest) = text.strip_prefix('\'') {
+        return parse_string(rest, '\'').map(|(s, rest)| (Value::Text(s), rest));
+    }
+    if let Some(mut rest) = text.strip_prefix('[') {
+        let mut items = Vec::new();
+        loop {
+            rest = rest.trim_start();
+            if let Some(after) = rest.strip_prefix(']') {
+                return Ok((Value::List(items), after));
+            }
+            let (item, after) = parse_value(rest)?;
+            items.push(item);
+            rest = after.trim_start();
+            if let Some(after) = rest.strip_prefix(',') {
+                rest = after;
+            } else if !rest.starts_with(']') {
+                return Err("expected , or ] in a list".to_string());
+            }
+        }
+    }
+    let end = text.find([',', ']', '#', ' ', '\t']).unwrap_or(text.len());
+    let (word, rest) = text.split_at(end);
+    match word {
+        "" => Err("a value is missing".to_string()),
+        "true" => Ok((Value::Flag(true), rest)),
+        "false" => Ok((Value::Flag(false), rest)),
+        _ => word.replace('_', "").parse().map(|n| (Value::Number(n), rest)).map_err(|_| format!("can't read the value {}", word)),
+    }
+}
+
+impl Defaults {
+    /// Where the defaults file lives, if there is a config directory.
+    pub fn path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join(DEFAULTS_FILE))
+    }
+
+    /// Parses the defaults file. Keys with values that don't fit are
+    /// returned as warnings and otherwise ignored, as are unknown keys.
+    pub fn parse(text: &str) -> (Defaults, Vec<String>) {
+        let mut defaults = Defaults::default();
+        let mut warnings = Vec::new();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') {
+                warnings.push(format!("line {}: tables aren't used, so every key goes at the top", number + 1));
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                warnings.push(format!("line {}: expected `key = value`", number + 1));
+                continue;
+            };
+            let value = match parse_value(value) {
+                Ok((value, rest)) if rest.trim().is_empty() || rest.trim_start().starts_with('#') => value,
+                Ok(_) => {
+                    warnings.push(format!("line {}: unexpected text after the value", number + 1));
+                    continue;
+                }
+                Err(problem) => {
+                    warnings.push(format!("line {}: {}", number + 1, problem));
+                    continue;
+                }
+            };
+            if let Err(problem) = defaults.set(key.trim(), value) {
+                warnings.push(format!("line {}: {}", number + 1, problem));
+            }
+        }
+        (defaults, warnings)
+    }
+
+    /// Sets one key, explaining what was expected if the value doesn't fit.
+    /// Unknown keys are ignored: they belong to other versions of the game.
+    fn set(&mut self, key: &str, value: Value) -> Result<(), String> {
+        match (key, value) {
+            ("code_length", Value::Number(n)) if SUPPORTED_CODE_LENGTHS.contains(&(n as usize)) && n.fract() == 0.0 => self.code_length = Some(n as usize),
+            ("code_length", _) => return Err(format!("expected a whole number of digits from {} to {}", SUPPORTED_CODE_LENGTHS.start(), SUPPORTED_CODE_LENGTHS.end())),
+            ("delay", Value::Number(seconds)) if seconds >= 0.0 && seconds.is_finite() => self.delay = Some(Duration::from_secs_f64(seconds)),
+            ("delay", _) => return Err("expected a number of seconds, such as 0 or 0.5".to_string()),
+            ("theme", Value::Text(name)) => self.theme = Some(name),
+            ("theme", _) => return Err("expected the name of a theme in quotes".to_string()),
+            ("language", Value::Text(tag)) if Language::is_known(&tag) => self.language = Some(tag),
+            ("language", _) => return Err("expected \"en\", \"de\" or \"fr\"".to_string()),
+            ("output", Value::Text(id)) if OutputProfile::from_id(&id).is_some() => self.output = OutputProfile::from_id(&id),
+            ("output", _) => return Err("expected \"emoji\", \"unicode\", or \"ascii\"".to_string()),
+            ("names", Value::List(items)) if items.iter().all(|item| matches!(item, Value::Text(_))) => {
+                self.names = items.into_iter().filter_map(|item| if let Value::Text(name) = item { Some(name.trim().to_string()) } else { None }).collect();
+            }
+            ("names", _) => return Err("expected a list of names, such as [\"Ann\", \"Bob\"]".to_string()),
+            ("no_ansi", Value::Flag(flag)) => self.no_ansi = Some(flag),
+            // Environment variables are usually switched on with 1 and off with 0
+            ("no_ansi", Value::Number(n)) if n == 0.0 || n == 1.0 => self.no_ansi = Some(n == 1.0),
+            ("no_ansi", _) => return Err("expected true or false".to_string()),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Applies the `SGG_*` environment variables over the file's values,
+    /// returning a warning for each one that can't be used.
+    pub fn apply_env(&mut self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for &(var, key) in ENV_VARS {
+            let Some(raw) = std::env::var(var).ok().filter(|v| !v.trim().is_empty()) else { continue };
+            // Text needs no quotes in a variable, and a list is comma-separated
+            let value = match key {
+                "theme" | "language" | "output" => Ok(Value::Text(raw.trim().to_string())),
+                "names" => Ok(Value::List(raw.split(',').map(|name| Value::Text(name.trim().to_string())).collect())),
+                _ => match parse_value(&raw) {
+                    Ok((value, rest)) if rest.trim().is_empty() => Ok(value),
+                    Ok(_) => Err(format!("can't read the value {}", raw.trim())),
+                    Err(problem) => Err(problem),
+                },
+            };
+            if let Err(problem) = value.and_then(|value| self.set(key, value)) {
+                warnings.push(format!("⚠️ Ignoring {} ({}).", var, problem));
+            }
+        }
+        warnings
+    }
+
+    /// Loads the defaults file, then the environment variables over it; a
+    /// missing file means no defaults. Also returns any problems to tell the
+    /// player about.
+    pub fn load() -> (Defaults, Vec<String>) {
+        let (mut defaults, mut problems) = Defaults::load_file();
+        problems.extend(defaults.apply_env());
+        (defaults, problems)
+    }
+
+    /// Loads just the defaults file.
+    fn load_file() -> (Defaults, Vec<String>) {
+        let Some(path) = Defaults::path() else { return (Defaults::default(), Vec::new()) };
+        match fs::read_to_string(&path) {
+            Ok(text) => {
+                let (defaults, warnings) = Defaults::parse(&text);
+                let problems = warnings
+                    .into_iter()
+                    .map(|warning| format!("⚠️ Ignoring a default in {} ({}).", path.display(), warning))
+                    .collect();
+                (defaults, problems)
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => (Defaults::default(), Vec::new()),
+            Err(e) => (Defaults::default(), vec![format!("⚠️ Couldn't read defaults from {}: {}.", path.display(), e)]),
+        }
+    }
+
+    /// Writes a commented defaults file, unless there is one already and
+    /// `overwrite` is false. Returns where it was written.
+    pub fn init(overwrite: bool) -> io::Result<PathBuf> {
+        let path = Defaults::path().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+        if !overwrite && path.exists() {
+            return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists (add --force to replace it)", path.display())));
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, TEMPLATE)?;
+        Ok(path)
+    }
+
+    /// The defaults in effect; none until `set_current` is called.
+    pub fn current() -> Defaults {
+        CURRENT.read().unwrap().clone().unwrap_or_default()
+    }
+
+    pub fn set_current(defaults: Defaults) {
+        *CURRENT.write().unwrap() = Some(defaults);
+    }
+}
@@ -0,0 +1,225 @@
+use std::time::Duration;
+
+use crate::bot::{Bot, Difficulty, Personality};
+use crate::code::{calculate_score, code_length, feedback_pair, format_code, generate_secret, is_solved, Guess, Score};
+use crate::console::{clear_screen, prompt};
+use crate::feedback::player_notation;
+use crate::game::get_personality;
+use crate::rules::{GameSpec, Rules, Variant};
+use crate::strings::{fill, tr};
+use crate::terminal::KeyListener;
+
+// --- Bot-vs-Bot Games ---
+// Bots play full games on screen with a running commentary. The attract-mode
+// demo loops them until someone presses a key (for an unattended screen at an
+// event stand); the watch mode plays one game with a chosen line-up and full
+// narration, which is also a quick way to sanity-check the rules.
+
+/// Pause after each bot guess in the attract-mode demo.
+const TURN_DELAY: Duration = Duration::from_millis(1800);
+
+/// Pause on the result screen before the next demo game starts.
+const RESULT_DELAY: Duration = Duration::from_secs(6);
+
+/// The line-up for each attract-mode game, so viewers see the difficulty levels side by side.
+const LINE_UP: [(&str, Difficulty); 3] = [
+    ("Rookie Rita", Difficulty::Easy),
+    ("Steady Sam", Difficulty::Medium),
+    ("Clever Cleo", Difficulty::Hard),
+];
+
+struct DemoSeat {
+    name: String,
+    secret: Guess,
+    bot: Bot,
+    guesses: u32,
+}
+
+/// A short remark about a guess, based on its feedback and how much it narrowed things down.
+fn commentary(score: Score, before: usize, after: usize) -> String {
+    let (total, position) = feedback_pair(score);
+    if is_solved(score) {
+        return tr("demo.cracked").to_string();
+    }
+    if total == 0 {
+        return fill("demo.no_digits", &[("length", &code_length())]);
+    }
+    if total as usize == code_length() {
+        return tr("demo.all_digits").to_string();
+    }
+    if after == 1 {
+        return tr("demo.one_left").to_string();
+    }
+    if position > 0 && total == position {
+        return fill("demo.in_place", &[("count", &position)]);
+    }
+
+    let eliminated = before - after;
+    fill("demo.ruled_out", &[("eliminated", &eliminated), ("remaining", &after)])
+}
+
+/// How a bot-vs-bot game is set up and narrated.
+struct WatchOptions {
+    line_up: Vec<(String, Difficulty, Personality)>,
+    turn_delay: Duration,
+    narrate_all: bool, // Every bot explains its deductions, not only the chatty ones
+}
+
+/// Plays one bot-vs-bot game until the first code is cracked.
+/// Returns the winner's index, or None if a key was pressed along the way.
+fn play_watch_game(keys: &KeyListener, options: &WatchOptions) -> Option<usize> {
+    let mut seats: Vec<DemoSeat> = options
+        .line_up
+        .iter()
+        .map(|(name, difficulty, personality)| DemoSeat {
+            name: name.clone(),
+            secret: generate_secret(),
+            bot: Bot::with_personality(*difficulty, *personality),
+            guesses: 0,
+        })
+        .collect();
+    let width = seats.iter().map(|seat| seat.name.chars().count()).max().unwrap_or(0).max(12);
+
+    for seat in &seats {
+        match seat.bot.personality {
+            Personality::Standard => outln!("{}", fill("demo.bot", &[("name", &seat.name), ("difficulty", &seat.bot.difficulty)])),
+            personality => outln!("{}", fill("demo.bot_personality", &[("name", &seat.name), ("difficulty", &seat.bot.difficulty), ("personality", &personality)])),
+        }
+    }
+
+    let mut round = 1;
+    loop {
+        outln!("{}", fill("demo.round", &[("round", &round)]));
+
+        for (index, seat) in seats.iter_mut().enumerate() {
+            let guess = seat.bot.next_guess();
+            let before = seat.bot.candidates_left();
+            let score = calculate_score(&guess, &seat.secret);
+            let remarks = if options.narrate_all || seat.bot.is_chatty() { seat.bot.observe_and_explain(&guess, score) } else {
+                seat.bot.observe(&guess, score);
+                Vec::new()
+            };
+            seat.guesses += 1;
+
+            // Bots that explain themselves don't need the commentator as well
+            let line = fill("demo.guesses", &[
+                ("name", &format!("{:<width$}", seat.name)),
+                ("guess", &format_code(&guess)),
+                ("score", &player_notation().render(score)),
+            ]);
+            if remarks.is_empty() {
+                outln!("{}   {}", line, commentary(score, before, seat.bot.candidates_left()));
+            } else {
+                outln!("{}", line);
+                for remark in &remarks {
+                    outln!("{:<width$}   💬 {}", "", remark);
+                }
+            }
+
+            if keys.wait(options.turn_delay) {
+                return None;
+            }
+
+            if is_solved(score) {
+                outln!("{}", fill("demo.wins", &[("name", &seat.name), ("count", &seat.guesses), ("secret", &format_code(&seat.secret))]));
+                return Some(index);
+            }
+        }
+        round += 1;
+    }
+}
+
+/// Loops demo games until a key is pressed, then returns (to the main menu).
+pub fn run_demo() {
+    let keys = KeyListener::start();
+    let options = WatchOptions {
+        line_up: LINE_UP.iter().map(|&(name, difficulty)| (name.to_string(), difficulty, Personality::Standard)).collect(),
+        turn_delay: TURN_DELAY,
+        narrate_all: false,
+    };
+
+    loop {
+        clear_screen();
+        outln!("{}", tr("demo.title"));
+        if play_watch_game(&keys, &options).is_none() {
+            break;
+        }
+        outln!("{}", tr("demo.play_prompt"));
+        if keys.wait(RESULT_DELAY) {
+            break;
+        }
+    }
+    drop(keys); // Restore normal line input before the menus
+    clear_screen();
+}
+
+// --- Watch Mode ---
+
+/// Asks for the bots taking part.
+fn get_line_up() -> Vec<(String, Difficulty, Personality)> {
+    let count = loop {
+        match prompt(tr("watch.count_prompt")).parse::<usize>() {
+            Ok(n) if (2..=6).contains(&n) => break n,
+            _ => outln!("{}", tr("watch.count_invalid")),
+        }
+    };
+
+    (1..=count)
+        .map(|seat| loop {
+            let difficulty = match prompt(&fill("watch.difficulty_prompt", &[("number", &seat)])).as_str() {
+                "1" => Difficulty::Easy,
+                "2" => Difficulty::Medium,
+                "3" => Difficulty::Hard,
+                _ => {
+                    outln!("{}", tr("watch.choice_invalid"));
+                    continue;
+                }
+            };
+            let personality = get_personality();
+            let name = match personality {
+                Personality::Standard => format!("{} Bot {}", difficulty, seat),
+                personality => format!("{} {} Bot {}", personality, difficulty, seat),
+            };
+            break (name, difficulty, personality);
+        })
+        .collect()
+}
+
+/// Asks how long to pause after each guess.
+fn get_turn_delay() -> Duration {
+    loop {
+        match prompt(tr("watch.speed_prompt")).as_str() {
+            "1" => return Duration::from_secs(3),
+            "2" => return Duration::from_millis(1500),
+            "3" => return Duration::from_millis(300),
+            _ => outln!("{}", tr("watch.choice_invalid")),
+        }
+    }
+}
+
+/// Sets up and plays one fully narrated bot-vs-bot game.
+pub fn run_watch() {
+    clear_screen();
+    outln!("{}", tr("watch.title"));
+    outln!("{}", tr("watch.intro"));
+
+    let options = WatchOptions { line_up: get_line_up(), turn_delay: get_turn_delay(), narrate_all: true };
+    let rules = Rules::default();
+
+    clear_screen();
+    outln!("{}", tr("watch.running"));
+    outln!("{}", fill("watch.rules", &[
+        ("length", &rules.code_length),
+        ("repeats", &tr(if rules.allow_repeats { "watch.repeats" } else { "watch.no_repeats" })),
+        ("spec", &GameSpec::new(Variant::IndividualSecrets, rules)),
+    ]));
+
+    let keys = KeyListener::start();
+    if play_watch_game(&keys, &options).is_some() {
+        // Wait for the key here too, so the listener doesn't swallow the next menu input
+        outln!("{}", tr("watch.menu_prompt"));
+        while !keys.wait(RESULT_DELAY) {}
+    } else {
+        outln!("{}", tr("watch.stopped"));
+    }
+}
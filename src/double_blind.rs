@@ -0,0 +1,122 @@
+use std::time::Instant;
+
+use crate::code::{calculate_score, code_length, format_code, is_solved, parse_feedback, parse_guess, Guess, Score};
+use crate::console::{clear_screen, hidden_prompt, prompt};
+use crate::feedback::player_notation;
+use crate::game::get_player_guess;
+use crate::locale::Locale;
+use crate::session::{Session, Solve};
+use crate::strings::{fill, tr};
+use crate::theme::paint_feedback;
+
+// --- Double-Blind Mode ---
+// A human codemaker picks the secret and scores every guess by hand, like the
+// pencil-and-paper game. The engine knows the secret too and acts as referee:
+// any feedback that doesn't match the real score is flagged on the spot.
+
+/// Asks the codemaker to type their secret, using the same rules as a guess.
+fn get_codemaker_secret(codemaker: &str) -> Guess {
+    loop {
+        let input = hidden_prompt(&fill("double_blind.secret_prompt", &[("name", &codemaker), ("length", &code_length())]));
+
+        match parse_guess(&input) {
+            Ok(secret) => return secret,
+            Err(message) => outln!("{}", message),
+        }
+    }
+}
+
+/// Asks the codemaker to score a guess in the usual "D,P" notation.
+fn get_codemaker_feedback(codemaker: &str, guess: &Guess) -> Score {
+    loop {
+        let input = prompt(&fill("common.score_prompt", &[("name", &codemaker), ("guess", &format_code(guess))]));
+
+        match parse_feedback(&input) {
+            Ok(score) => return score,
+            Err(message) => outln!("{}", message),
+        }
+    }
+}
+
+/// Runs a single double-blind game between a human codemaker and codebreaker.
+pub fn run_double_blind(session: &mut Session) {
+    clear_screen();
+    outln!("{}", tr("double_blind.title"));
+    outln!("{}", tr("double_blind.intro"));
+
+    // 1. Setup Roles
+    let codemaker = prompt(tr("double_blind.codemaker_prompt"));
+    let codebreaker = prompt(tr("double_blind.codebreaker_prompt"));
+
+    outln!("{}", fill("double_blind.look_away", &[("codebreaker", &codebreaker), ("codemaker", &codemaker)]));
+    let secret = get_codemaker_secret(&codemaker);
+
+    // *** CLEAR SCREEN *** so the secret doesn't stay on display
+    clear_screen();
+
+    // 2. Game Loop Variables
+    let started_at = Instant::now();
+    let mut guess_count: u32 = 0;
+    let mut mistakes: u32 = 0;
+
+    loop {
+        guess_count += 1;
+
+        outln!("\n======================================");
+        outln!("{}", fill("double_blind.guess_header", &[("number", &guess_count), ("name", &codebreaker)]));
+        outln!("======================================");
+
+        let guess = match get_player_guess(&codebreaker) {
+            Some(g) => g,
+            None => continue,
+        };
+
+        // 3. Human scoring, then the engine's cross-check
+        let claimed = get_codemaker_feedback(&codemaker, &guess);
+        let actual = calculate_score(&guess, &secret);
+
+
+        if claimed != actual {
+            mistakes += 1;
+            outln!("{}", fill("double_blind.mistake", &[
+                ("name", &codemaker),
+                ("claimed", &player_notation().render(claimed)),
+                ("actual", &player_notation().render(actual)),
+            ]));
+        }
+
+        outln!("--------------------------------------");
+        outln!("{}", fill("game.feedback", &[("guess", &format_code(&guess)), ("score", &paint_feedback(actual, &player_notation().render(actual)))]));
+        outln!("--------------------------------------");
+
+        // 4. Check for Win Condition
+        if is_solved(actual) {
+            outln!("{}", tr("double_blind.guessed"));
+            outln!("{}", fill("double_blind.cracked", &[
+                ("codebreaker", &codebreaker),
+                ("codemaker", &codemaker),
+                ("secret", &format_code(&secret)),
+                ("count", &guess_count),
+            ]));
+            session.record_secret("double-blind", secret, Some(Solve { player: codebreaker.clone(), guesses: guess_count }));
+            break;
+        }
+    }
+
+    // --- REFEREE REPORT ---
+    let locale = Locale::current();
+    outln!("\n======================================");
+    outln!("{}", tr("double_blind.report"));
+    outln!("======================================");
+    if mistakes == 0 {
+        outln!("{}", fill("double_blind.no_mistakes", &[("name", &codemaker)]));
+    } else {
+        outln!("{}", fill("double_blind.mistakes", &[
+            ("name", &codemaker),
+            ("count", &locale.count(mistakes as u64)),
+            ("guesses", &locale.count(guess_count as u64)),
+        ]));
+    }
+    outln!("{}", fill("double_blind.length", &[("time", &locale.duration(started_at.elapsed()))]));
+    outln!("======================================");
+}
@@ -0,0 +1,242 @@
+use std::fmt;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::code::{code_length, format_code, parse_guess, Guess, Score};
+use crate::json::{parse_object, Json};
+use crate::strings::{fill, tr};
+
+// --- External Bots ---
+// A seat can be played by a bot program of anyone's making, in any language,
+// given with `--engine <command>`. The game starts the program when the seat's
+// first turn comes and talks to it over its standard input and output, one
+// JSON object per line:
+//
+//   game → bot  {"type":"hello","protocol":1,"name":"mybot","length":4,"digits":"0123456789","repeats":false,"time_limit_ms":5000}
+//   bot → game  {"type":"ready"}
+//   game → bot  {"type":"turn","round":3,"clues":[...],"history":[{"guess":"0123","right_place":1,"wrong_place":2}, ...],"time_limit_ms":5000}
+//   bot → game  {"guess":"4567"}
+//   game → bot  {"type":"end"}
+//
+// `clues` are the free clues from a handicap and `history` the seat's own
+// guesses, oldest first, each with its feedback; together they are everything
+// the bot may know, so a bot needn't remember anything between turns. Fields
+// the bot doesn't know should be ignored, as the game ignores any it sends.
+//
+// Each `turn` gets exactly one reply, which must come within the time limit.
+// One that comes too late, or that isn't a guess of `length` different digits,
+// loses the turn, just as a human's turn timer running out does; a late reply
+// is thrown away when it does come. A program that can't be started, doesn't say
+// it's ready within ten seconds, or stops partway hands its seat to a Medium
+// bot for the rest of the game. Whatever it writes to standard error shows up
+// in the terminal, which helps while writing one. After `end` it has a second
+// to exit before it is stopped.
+
+/// The version of the messages above, sent in `hello`.
+pub const PROTOCOL: u32 = 1;
+
+/// How long a bot has to answer each turn.
+pub const MOVE_TIME: Duration = Duration::from_secs(5);
+
+/// How long a newly started bot has to say it's ready.
+const START_TIME: Duration = Duration::from_secs(10);
+
+/// How long a bot has to exit once the game is over.
+const EXIT_WAIT: Duration = Duration::from_secs(1);
+
+/// Why a bot program didn't come up with a guess.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EngineError {
+    Launch(String),  // It couldn't be started, or never said it was ready
+    Gone,            // It stopped, or closed its end of the pipes
+    TimedOut,        // It didn't answer within the time limit
+    Invalid(String), // It answered with something other than a guess
+}
+
+impl EngineError {
+    /// True if the program can't play on, rather than just losing this turn.
+    pub fn is_fatal(&self) -> bool {
+        matches!(self, EngineError::Launch(_) | EngineError::Gone)
+    }
+}
+
+impl fmt::Display for EngineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EngineError::Launch(reason) => write!(f, "{}", fill("engine.launch_failed", &[("reason", reason)])),
+            EngineError::Gone => write!(f, "{}", tr("engine.gone")),
+            EngineError::TimedOut => write!(f, "{}", fill("engine.timed_out", &[("seconds", &MOVE_TIME.as_secs())])),
+            EngineError::Invalid(reason) => write!(f, "{}", fill("engine.invalid", &[("reason", reason)])),
+        }
+    }
+}
+
+/// A started program and its pipes.
+#[derive(Debug)]
+struct Running {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>, // What it prints, line by line, read on a thread of its own
+    late: u32, // Replies still to come for turns that ran out of time
+}
+
+impl Running {
+    fn send(&mut self, message: Json) -> Result<(), EngineError> {
+        writeln!(self.stdin, "{}", message).and_then(|()| self.stdin.flush()).map_err(|_| EngineError::Gone)
+    }
+
+    /// Waits up to `limit` for the reply to the latest message, read as a
+    /// JSON object. Late replies to earlier turns are skipped on the way.
+    fn receive(&mut self, limit: Duration) -> Result<Vec<(String, Json)>, EngineError> {
+        let deadline = Instant::now() + limit;
+        let line = loop {
+            let line = self.lines.recv_timeout(deadline.saturating_duration_since(Instant::now())).map_err(|e| match e {
+                RecvTimeoutError::Timeout => {
+                    self.late += 1;
+                    EngineError::TimedOut
+                }
+                RecvTimeoutError::Disconnected => EngineError::Gone,
+            })?;
+            if self.late == 0 {
+                break line;
+            }
+            self.late -= 1;
+        };
+        parse_object(&line).map_err(|e| EngineError::Invalid(format!("a line that isn't a JSON object ({})", e)))
+    }
+
+    /// Says goodbye, then gives the program a moment to exit before stopping it.
+    fn stop(mut self) {
+        let _ = self.send(Json::Object(vec![("type", "end".into())]));
+        drop(self.stdin);
+        let deadline = Instant::now() + EXIT_WAIT;
+        while Instant::now() < deadline {
+            if !matches!(self.child.try_wait(), Ok(None)) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// The text of one of a field's values, if it is text.
+fn text_of<'a>(fields: &'a [(String, Json)], key: &str) -> Option<&'a str> {
+    fields.iter().find(|(k, _)| k == key).and_then(|(_, value)| match value {
+        Json::Text(text) => Some(text.as_str()),
+        _ => None,
+    })
+}
+
+/// Scored codes as the protocol lists them.
+fn scored_list(codes: &[(Guess, Score)]) -> Json {
+    Json::List(
+        codes
+            .iter()
+            .map(|(code, score)| Json::Object(vec![("guess", format_code(code).into()), ("right_place", score.0.into()), ("wrong_place", score.1.into())]))
+            .collect(),
+    )
+}
+
+/// A seat's bot program, started on its first turn and stopped when dropped.
+#[derive(Debug)]
+pub struct Engine {
+    pub command: String,
+    running: Option<Running>,
+}
+
+impl Engine {
+    pub fn new(command: &str) -> Engine {
+        Engine { command: command.to_string(), running: None }
+    }
+
+    /// Starts the program for the seat called `name` and waits for it to be ready.
+    fn launch(&self, name: &str) -> Result<Running, EngineError> {
+        let mut words = self.command.split_whitespace();
+        let program = words.next().ok_or_else(|| EngineError::Launch("no command was given".to_string()))?;
+        let mut child = Command::new(program)
+            .args(words)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| EngineError::Launch(e.to_string()))?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            let _ = child.kill();
+            return Err(EngineError::Launch("its pipes couldn't be opened".to_string()));
+        };
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut running = Running { child, stdin, lines, late: 0 };
+        let hello = Json::Object(vec![
+            ("type", "hello".into()),
+            ("protocol", PROTOCOL.into()),
+            ("name", name.into()),
+            ("length", (code_length() as u8).into()),
+            ("digits", "0123456789".into()),
+            ("repeats", false.into()),
+            ("time_limit_ms", (MOVE_TIME.as_millis() as u64).into()),
+        ]);
+        let ready = running.send(hello).and_then(|()| running.receive(START_TIME));
+        match ready {
+            Ok(fields) if text_of(&fields, "type") == Some("ready") => Ok(running),
+            outcome => {
+                running.stop();
+                Err(EngineError::Launch(match outcome {
+                    Err(EngineError::TimedOut) => format!("it didn't say it was ready within {} seconds", START_TIME.as_secs()),
+                    Err(EngineError::Gone) => "it stopped before saying it was ready".to_string(),
+                    _ => "it answered the greeting with something other than ready".to_string(),
+                }))
+            }
+        }
+    }
+
+    /// Asks the program for its guess for round `round`, starting it first
+    /// if this is its first turn. After a fatal error the program is stopped.
+    pub fn next_guess(&mut self, name: &str, round: u32, clues: &[(Guess, Score)], history: &[(Guess, Score)]) -> Result<Guess, EngineError> {
+        if self.running.is_none() {
+            self.running = Some(self.launch(name)?);
+        }
+        let result = self.play(round, clues, history);
+        if result.as_ref().is_err_and(EngineError::is_fatal)
+            && let Some(running) = self.running.take()
+        {
+            running.stop();
+        }
+        result
+    }
+
+    fn play(&mut self, round: u32, clues: &[(Guess, Score)], history: &[(Guess, Score)]) -> Result<Guess, EngineError> {
+        let running = self.running.as_mut().ok_or(EngineError::Gone)?;
+        running.send(Json::Object(vec![
+            ("type", "turn".into()),
+            ("round", round.into()),
+            ("clues", scored_list(clues)),
+            ("history", scored_list(history)),
+            ("time_limit_ms", (MOVE_TIME.as_millis() as u64).into()),
+        ]))?;
+        let fields = running.receive(MOVE_TIME)?;
+        let text = text_of(&fields, "guess").ok_or_else(|| EngineError::Invalid("no \"guess\" in its reply".to_string()))?;
+        parse_guess(text).map_err(|_| EngineError::Invalid(format!("\"{}\", which isn't {} different digits", text, code_length())))
+    }
+}
+
+impl Drop for Engine {
+    fn drop(&mut self) {
+        if let Some(running) = self.running.take() {
+            running.stop();
+        }
+    }
+}
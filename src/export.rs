@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+
+use crate::code::format_code;
+use crate::locale::civil_from_days;
+use crate::replay::{replay_dir, replay_names, Replay};
+use crate::rules::describe_record_key;
+use crate::store::{format_game_id, GameResult, StoreSet};
+use crate::strings::fill;
+
+// --- CSV Export ---
+// `stats export --format csv` writes the saved history as two spreadsheet-
+// friendly files:
+//
+//   games.csv  One row per player per game, from the personal and club stores
+//   turns.csv  One row per guess, from the recorded replays (see replay.rs)
+//
+// The stats stores only keep results, so the turns come from the replays.
+// A game's rows in games.csv give its game id (the same in every store) and,
+// if it was replayed, the name of its replay, which is what the rows in
+// turns.csv give. Replays saved before game ids can't be matched to games.
+// Times are UTC, written so spreadsheets read them as dates.
+
+/// The options of `stats export`.
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    /// The file format, which so far is always csv
+    #[arg(long, value_parser = ["csv"])]
+    pub format: String,
+    /// The folder the files are written to
+    #[arg(long, value_name = "FOLDER", default_value = ".")]
+    pub out: PathBuf,
+}
+
+const GAMES_HEADER: &str = "store,game,replay,time,mode,rules,players,player,rank,guesses";
+
+const TURNS_HEADER: &str = "replay,round,player,guess,right_place,wrong_place,seconds_in";
+
+/// Quotes a field if a comma, quote or line break in it would otherwise split
+/// it. A field a spreadsheet would take for a formula (a player called
+/// `=HYPERLINK(...)`, say) gets a `'` in front, so it stays text.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) { format!("'{}", value) } else { value.to_string() };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_line(fields: &[String]) -> String {
+    fields.iter().map(|f| csv_field(f)).collect::<Vec<_>>().join(",")
+}
+
+/// `timestamp` (seconds since the Unix epoch) as `YYYY-MM-DD HH:MM:SS`.
+fn csv_time(timestamp: u64) -> String {
+    let (year, month, day) = civil_from_days((timestamp / 86_400) as i64);
+    format!("{}-{:02}-{:02} {:02}:{:02}:{:02}", year, month, day, (timestamp / 3600) % 24, (timestamp / 60) % 60, timestamp % 60)
+}
+
+/// The rows of games.csv for one store. `replays` names the replay of each
+/// game that has one, by game id.
+fn game_rows(label: &str, results: &[GameResult], replays: &HashMap<u64, String>) -> Vec<String> {
+    let mut rows = Vec::new();
+    for r in results {
+        rows.push(csv_line(&[
+            label.to_string(),
+            format_game_id(r.game),
+            replays.get(&r.game).cloned().unwrap_or_default(),
+            csv_time(r.timestamp),
+            r.mode.clone(),
+            describe_record_key(&r.rules),
+            r.field_size.to_string(),
+            r.player.clone(),
+            r.rank.to_string(),
+            r.guesses.to_string(),
+        ]));
+    }
+    rows
+}
+
+/// The rows of turns.csv for one replay.
+fn turn_rows(name: &str, replay: &Replay) -> Vec<String> {
+    replay
+        .moves
+        .iter()
+        .map(|m| {
+            csv_line(&[
+                name.to_string(),
+                m.round.to_string(),
+                m.player.clone(),
+                format_code(&m.guess),
+                m.score.0.to_string(),
+                m.score.1.to_string(),
+                format!("{:.1}", m.at.as_secs_f64()),
+            ])
+        })
+        .collect()
+}
+
+/// Writes `header` and `rows` to `path`, returning how many rows went in.
+fn write_csv(path: &Path, header: &str, rows: Vec<String>) -> Result<usize, String> {
+    let count = rows.len();
+    let mut text = vec![header.to_string()];
+    text.extend(rows);
+    text.push(String::new());
+    fs::write(path, text.join("\n")).map_err(|e| fill("common.write_failed", &[("path", &path.display()), ("error", &e)]))?;
+    Ok(count)
+}
+
+/// Entry point for `stats export`.
+pub fn run_export(args: &ExportArgs, stores: &StoreSet) -> Result<(), String> {
+    let out = &args.out;
+    fs::create_dir_all(out).map_err(|e| fill("common.create_failed", &[("path", &out.display()), ("error", &e)]))?;
+
+    let mut turns = Vec::new();
+    let mut replays = HashMap::new();
+    if let Some(dir) = replay_dir() {
+        for name in replay_names(&dir) {
+            match Replay::load(&dir.join(format!("{}.txt", name))) {
+                Ok(replay) => {
+                    turns.extend(turn_rows(&name, &replay));
+                    if let Some(game) = replay.game {
+                        replays.insert(game, name);
+                    }
+                }
+                Err(e) => outln!("{}", fill("export.skipped", &[("error", &e)])),
+            }
+        }
+    }
+
+    let mut games = game_rows("personal", stores.personal.results(), &replays);
+    if let Some(club) = &stores.club {
+        games.extend(game_rows("club", club.results(), &replays));
+    }
+    let games_path = out.join("games.csv");
+    let game_count = write_csv(&games_path, GAMES_HEADER, games)?;
+    outln!("{}", fill("export.games", &[("count", &game_count), ("path", &games_path.display())]));
+
+    let turns_path = out.join("turns.csv");
+    let turn_count = write_csv(&turns_path, TURNS_HEADER, turns)?;
+    outln!("{}", fill("export.turns", &[("count", &turn_count), ("path", &turns_path.display())]));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fields_a_spreadsheet_would_run_stay_text() {
+        assert_eq!(csv_field("=HYPERLINK(\"x\")"), "\"'=HYPERLINK(\"\"x\"\")\"");
+        for name in ["+1", "-2", "@SUM(A1)"] {
+            assert_eq!(csv_field(name), format!("'{}", name));
+        }
+        assert_eq!(csv_field("Ann, Ben"), "\"Ann, Ben\"");
+        assert_eq!(csv_field("Ann"), "Ann");
+    }
+}
@@ -0,0 +1,110 @@
+use crate::code::{code_length, feedback_pair, Score};
+use crate::config::Config;
+use crate::strings::{fill, tr};
+
+// --- Feedback Renderers ---
+// Every way of showing a Score to players goes through this trait, including
+// the game's own notations, so a frontend or plugin that supplies its own
+// rendering (pegs, colours, braille, speech...) gets exactly the same input.
+
+/// Turns a score into something a player can read (or hear).
+pub trait FeedbackRenderer {
+    /// A short, unique name for the notation (e.g. "dp").
+    fn id(&self) -> &str;
+
+    /// Renders one guess's score.
+    fn render(&self, score: Score) -> String;
+}
+
+/// The game's standard "D,P" notation: total correct digits, then digits in
+/// the correct position (e.g. "3,1").
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DpNotation;
+
+impl FeedbackRenderer for DpNotation {
+    fn id(&self) -> &str {
+        "dp"
+    }
+
+    fn render(&self, score: Score) -> String {
+        let (total, position) = feedback_pair(score);
+        format!("{},{}", total, position)
+    }
+}
+
+/// The spoiler-free tiles from the daily challenge's share grid: 🟩 for each
+/// digit in place, 🟨 for each digit elsewhere, ⬛ for the rest.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EmojiNotation;
+
+impl FeedbackRenderer for EmojiNotation {
+    fn id(&self) -> &str {
+        "emoji"
+    }
+
+    fn render(&self, score: Score) -> String {
+        let (total, position) = feedback_pair(score);
+        let misplaced = total - position;
+        let missing = code_length().saturating_sub(total as usize);
+        "🟩".repeat(position as usize) + &"🟨".repeat(misplaced as usize) + &"⬛".repeat(missing)
+    }
+}
+
+/// Pegs as on a Mastermind board: ● for each digit in place, ○ for each
+/// digit elsewhere, and · for the rest, so every score is as wide as the code.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PegNotation;
+
+impl FeedbackRenderer for PegNotation {
+    fn id(&self) -> &str {
+        "pegs"
+    }
+
+    fn render(&self, score: Score) -> String {
+        let (total, position) = feedback_pair(score);
+        let misplaced = total - position;
+        let missing = code_length().saturating_sub(total as usize);
+        "●".repeat(position as usize) + &"○".repeat(misplaced as usize) + &"·".repeat(missing)
+    }
+}
+
+/// Feedback spelled out for screen readers, e.g. "two digits correct, one
+/// in the right position", in the current language.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SentenceNotation;
+
+impl FeedbackRenderer for SentenceNotation {
+    fn id(&self) -> &str {
+        "sentence"
+    }
+
+    fn render(&self, score: Score) -> String {
+        const DIGITS: [&str; 6] = ["sentence.digits_0", "sentence.digits_1", "sentence.digits_2", "sentence.digits_3", "sentence.digits_4", "sentence.digits_5"];
+        const PLACED: [&str; 6] = ["sentence.placed_0", "sentence.placed_1", "sentence.placed_2", "sentence.placed_3", "sentence.placed_4", "sentence.placed_5"];
+        let (total, position) = feedback_pair(score);
+        if total == 0 {
+            return tr(DIGITS[0]).to_string();
+        }
+        // "All" once every digit of the code is covered, so a 4-digit code still reads "all digits correct"
+        let word = |count: u8, words: &[&'static str; 6], all: &'static str| {
+            if count as usize >= code_length() { tr(all) } else { tr(words[count as usize]) }
+        };
+        fill("sentence.feedback", &[("digits", &word(total, &DIGITS, "sentence.digits_all")), ("placed", &word(position, &PLACED, "sentence.placed_all"))])
+    }
+}
+
+/// Every notation that ships with the game.
+pub fn builtin_renderers() -> Vec<Box<dyn FeedbackRenderer>> {
+    vec![Box::new(DpNotation), Box::new(EmojiNotation), Box::new(PegNotation), Box::new(SentenceNotation)]
+}
+
+/// The notation feedback is shown to players in: "D,P", or full sentences
+/// with the screen-reader setting on.
+pub fn player_notation() -> &'static dyn FeedbackRenderer {
+    if Config::current().screen_reader { &SentenceNotation } else { &DpNotation }
+}
+
+/// Looks up a built-in notation by its id.
+pub fn renderer_by_id(id: &str) -> Option<Box<dyn FeedbackRenderer>> {
+    builtin_renderers().into_iter().find(|renderer| renderer.id() == id)
+}
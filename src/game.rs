@@ -0,0 +1,1293 @@
+use rand::seq::IndexedRandom;
+use rand::Rng;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use crate::achievement::{announce, unlock, Performance};
+use crate::analysis::print_review;
+use crate::bot::{Bot, Difficulty, Personality};
+use crate::coach::coach_guess;
+use crate::engine::Engine;
+use crate::code::{calculate_score, code_length, code_length_fixed, feedback_pair, format_code, game_rng, generate_secret, is_solved, parse_guess, set_code_length, start_seeded_game, Guess, Score};
+use crate::console::{clear_screen, guest_session, hand_over, hand_over_or_undo, pause, prompt, prompt_with_history, quiet_output, timed_prompt, Countdown};
+use crate::feedback::{player_notation, FeedbackRenderer, PegNotation};
+use crate::json::{self, Json};
+use crate::lobby::{run_lobby, take_preset_seats, Seat};
+use crate::locale::Locale;
+use crate::odds::win_chances;
+use crate::profile::{welcome, Profile};
+use crate::rankings::{display_width, pad, print_rankings, Finish, Standing};
+use crate::rating::Ratings;
+use crate::rules::Rules;
+use crate::save::{save_path, take_resumed_game, SavedGame};
+use crate::session::{GameRecord, Placement, Session, Solve};
+use crate::config::{Announcement, Config};
+use crate::sound::{self, Cue};
+use crate::spectator::{emit, GameEvent};
+use crate::solver::{all_codes, filter_candidates, find_violated_clue, suggest_guess};
+use crate::stats::{celebrate, comeback_weights, milestones, print_leaderboard, record_results, summarize, win_streak};
+use crate::store::{new_game_id, now_timestamp, GameResult, StoreSet};
+use crate::strings::{fill, tr};
+use crate::terminal::typed_input;
+use crate::theme::paint_feedback;
+use crate::replay::{record_replay, Replay};
+use crate::transcript::{offer_transcript, Transcript};
+use crate::tournament::run_rematch_bracket;
+
+// Player struct now holds their unique secret code
+#[derive(Debug)] // Required for debugging/printing complex structs
+pub struct Player {
+    pub name: String,
+    pub secret_code: Guess, // Each player has their own secret
+    pub rank: Option<usize>, // Stores the player's finishing position (1st, 2nd, etc.)
+    pub guesses: u32, // Number of guesses this player has made
+    pub cracked: bool, // True once the player has guessed their secret
+    pub candidates: Vec<Guess>, // Codes still consistent with this player's feedback
+    pub hints: u32, // Number of times the player asked for /hint
+    pub notes: Vec<String>, // Private reminders typed with /note, shown only on this player's turns
+    pub undos: u32, // Number of guesses taken back with /undo
+    pub head_start: Vec<(Guess, Score)>, // Free clues from a handicap, given before the first turn
+    pub history: Vec<(Guess, Score)>, // Every guess this player made, with its feedback
+    pub sits_out: u32, // Turns still to sit out from a late-start handicap
+    pub finished: Option<(u32, Duration)>, // Round and game time when they cracked their code or dropped out
+    pub bot: Option<Bot>, // Set for computer-controlled players
+    pub engine: Option<Engine>, // Set for seats played by an external bot program
+    pub profile: Option<Profile>, // A human's saved preferences, found by name
+}
+
+impl Player {
+    /// Seats a player from the lobby, dealing their secret and any free clues.
+    pub fn from_seat(seat: Seat) -> Player {
+        let mut bot = seat.bot.map(|(difficulty, personality)| Bot::with_personality(difficulty, personality));
+
+        // Generate a unique secret for this player
+        let secret_code = generate_secret();
+
+        // Free clues are random codes scored in advance; they don't count as guesses
+        let codes = all_codes();
+        let mut candidates = codes.clone();
+        let others: Vec<Guess> = codes.into_iter().filter(|&code| code != secret_code).collect();
+        let head_start: Vec<(Guess, Score)> = others
+            .choose_multiple(&mut game_rng(), seat.handicap.free_clues as usize)
+            .map(|&clue| (clue, calculate_score(&clue, &secret_code)))
+            .collect();
+        for &(clue, score) in &head_start {
+            filter_candidates(&mut candidates, &clue, score);
+            if let Some(bot) = &mut bot {
+                bot.observe(&clue, score);
+            }
+        }
+
+        let profile = if seat.bot.is_none() && seat.engine.is_none() { Profile::load(&seat.name) } else { None };
+        Player {
+            name: seat.name,
+            profile,
+            secret_code,
+            rank: None,
+            guesses: 0,
+            cracked: false,
+            candidates,
+            hints: 0,
+            notes: Vec::new(),
+            undos: 0,
+            head_start,
+            history: Vec::new(),
+            sits_out: seat.handicap.late_start as u32,
+            finished: None,
+            bot,
+            engine: seat.engine.as_deref().map(Engine::new),
+        }
+    }
+
+    /// Takes back the latest guess, as if it had never been made, and
+    /// returns it. The candidates are rebuilt from the clues that are left.
+    pub fn undo_last_guess(&mut self) -> Option<Guess> {
+        let (guess, _) = self.history.pop()?;
+        self.guesses -= 1;
+        self.undos += 1;
+        self.candidates = all_codes();
+        for (clue, score) in self.head_start.iter().chain(&self.history) {
+            filter_candidates(&mut self.candidates, clue, *score);
+        }
+        Some(guess)
+    }
+
+    pub fn is_bot(&self) -> bool {
+        self.bot.is_some() || self.engine.is_some()
+    }
+
+    /// Asks an external bot program for this turn's guess. Gives None for
+    /// seats that aren't played by one, and for one whose program just failed,
+    /// which a Medium bot takes over (as the message printed says). Otherwise
+    /// the guess, or a note saying why the program lost the turn.
+    pub fn engine_turn(&mut self, round: u32) -> Option<Result<Guess, String>> {
+        let engine = self.engine.as_mut()?;
+        match engine.next_guess(&self.name, round, &self.head_start, &self.history) {
+            Ok(guess) => Some(Ok(guess)),
+            Err(e) if e.is_fatal() => {
+                outln!("{}", fill("engine.replaced", &[("name", &self.name), ("reason", &e)]));
+                let mut bot = Bot::new(Difficulty::Medium);
+                for (clue, score) in self.head_start.iter().chain(&self.history) {
+                    bot.observe(clue, *score);
+                }
+                self.bot = Some(bot);
+                self.engine = None;
+                None
+            }
+            Err(e) => Some(Err(fill("engine.lost_turn", &[("name", &self.name), ("reason", &e)]))),
+        }
+    }
+}
+
+// --- Setup Prompts ---
+
+/// Asks whether a seat is played by a human or by a bot of some difficulty.
+pub fn get_seat_type(player_number: usize) -> Option<Difficulty> {
+    loop {
+        outln!("{}", fill("seat.type", &[("number", &player_number)]));
+        match prompt(tr("seat.type_prompt")).as_str() {
+            "" | "1" => return None,
+            "2" => return Some(Difficulty::Easy),
+            "3" => return Some(Difficulty::Medium),
+            "4" => return Some(Difficulty::Hard),
+            _ => outln!("{}", tr("seat.type_invalid")),
+        }
+    }
+}
+
+/// Asks which personality a bot seat should play with.
+pub fn get_personality() -> Personality {
+    loop {
+        for (i, personality) in Personality::ALL.iter().enumerate() {
+            outln!("  [{}] {}: {}", i + 1, personality, personality.description());
+        }
+        match prompt(tr("seat.personality_prompt")).as_str() {
+            "" => return Personality::Standard,
+            input => match input.parse::<usize>() {
+                Ok(n) if (1..=Personality::ALL.len()).contains(&n) => return Personality::ALL[n - 1],
+                _ => outln!("{}", fill("seat.personality_invalid", &[("max", &Personality::ALL.len())])),
+            },
+        }
+    }
+}
+
+/// Gets a valid non-repeating guess of `code_length()` digits.
+pub fn get_player_guess(player_name: &str) -> Option<Guess> {
+    loop {
+        let input = prompt(&fill("guess.prompt", &[("name", &player_name), ("length", &code_length())]));
+
+        match parse_guess(&input) {
+            Ok(guess) => return Some(guess),
+            Err(message) => outln!("{}", message),
+        }
+    }
+}
+
+/// In hard mode, checks that `guess` fits every clue in `history`, explaining
+/// the first one it breaks. Always true when hard mode is off.
+fn allowed_in_hard_mode(guess: &Guess, history: &[(Guess, Score)]) -> bool {
+    if !Config::current().hard_mode {
+        return true;
+    }
+    match find_violated_clue(guess, history) {
+        Some(violation) => {
+            outln!("{}", fill("guess.hard_mode", &[("guess", &format_code(guess)), ("reason", &violation)]));
+            false
+        }
+        None => true,
+    }
+}
+
+/// Like `get_player_guess`, but in hard mode the guess must also fit every
+/// clue in `history`; rejected guesses are asked for again.
+pub fn get_consistent_guess(player_name: &str, history: &[(Guess, Score)]) -> Option<Guess> {
+    loop {
+        let guess = get_player_guess(player_name)?;
+        if allowed_in_hard_mode(&guess, history) {
+            return Some(guess);
+        }
+    }
+}
+
+/// What a player did at the guess prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TurnChoice {
+    Guess(Guess),
+    TimedOut,       // The turn timer ran out
+    Skip,           // `/skip`: the turn was passed
+    Quit,           // `/quit`: the table agreed to end the game
+    Save(PathBuf),  // `/save <name>`: write the game there, then play the turn
+}
+
+/// The commands the guess prompt takes besides `/help` and `/rules` (which
+/// work at every prompt), with the string id of each one's description.
+const GUESS_COMMANDS: &[(&str, &str)] = &[
+    ("/hint", "commands.hint"),
+    ("/note <text>", "commands.note"),
+    ("/notes", "commands.notes"),
+    ("/history", "commands.history"),
+    ("/score", "commands.score"),
+    ("/skip", "commands.skip"),
+    ("/save <name>", "commands.save"),
+    ("/quit", "commands.quit"),
+];
+
+/// A slash command typed at the guess prompt instead of a guess.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum GuessCommand {
+    Hint,
+    Note(String),
+    Notes,
+    History,
+    Score,
+    Skip,
+    Save(String),
+    Quit,
+    Unknown(String),
+}
+
+impl GuessCommand {
+    /// Reads `input` as a command, or None if it doesn't start with a slash.
+    fn parse(input: &str) -> Option<GuessCommand> {
+        let rest = input.strip_prefix('/')?;
+        let (name, argument) = rest.split_once(' ').unwrap_or((rest, ""));
+        let command = match name.to_ascii_lowercase().as_str() {
+            "hint" => GuessCommand::Hint,
+            "note" => GuessCommand::Note(argument.trim().to_string()),
+            "notes" => GuessCommand::Notes,
+            "history" => GuessCommand::History,
+            "score" => GuessCommand::Score,
+            "skip" => GuessCommand::Skip,
+            "save" => GuessCommand::Save(argument.trim().to_string()),
+            "quit" => GuessCommand::Quit,
+            _ => GuessCommand::Unknown(format!("/{}", name)),
+        };
+        Some(command)
+    }
+}
+
+/// Lists what can be typed at the guess prompt instead of a guess.
+fn print_guess_commands() {
+    for (usage, description) in GUESS_COMMANDS {
+        outln!("  {}  {}", pad(usage, 13), tr(description));
+    }
+}
+
+/// Like `get_consistent_guess` for `player`, but the prompt also takes the
+/// commands in `GUESS_COMMANDS`: `/hint` suggests a guess from the codes
+/// still left (and adds to the player's hints), `/note` and `/notes` keep
+/// private notes, `/history` and `/score` show the player's board and the
+/// `scoreboard` again, and `/skip`, `/save` and `/quit` end the turn without a guess.
+/// With the turn timer on, running out of time ends it too. With a guess
+/// limit, the prompt counts `attempt` against it.
+pub fn get_player_guess_with_hints(player: &mut Player, attempt: u32, scoreboard: &[String]) -> TurnChoice {
+    let clues: Vec<(Guess, Score)> = player.head_start.iter().chain(&player.history).copied().collect();
+    if !player.notes.is_empty() {
+        outln!("{}", fill("guess.notes_reminder", &[("count", &player.notes.len())]));
+    }
+    if !clues.is_empty() && !quiet_output() {
+        print_digit_tracker(&player.candidates, &clues);
+    }
+    let message = match Config::current().guess_limit {
+        0 => fill("guess.prompt_hint", &[("name", &player.name), ("length", &code_length())]),
+        limit => {
+            if attempt >= limit {
+                outln!("{}", fill("guess.last_guess", &[("name", &player.name)]));
+            }
+            fill("guess.prompt_budget", &[("name", &player.name), ("number", &attempt), ("limit", &limit)])
+        }
+    };
+    // Up and Down at the prompt recall the player's own earlier guesses
+    let recall: Vec<String> = clues.iter().map(|(guess, _)| format_code(guess)).collect();
+    // The clock covers the whole turn, including hints and rejected guesses
+    let deadline = Config::current().turn_limit().map(Countdown::new);
+    loop {
+        let input = match deadline {
+            Some(deadline) => match timed_prompt(&message, deadline, &recall) {
+                Some(input) => input,
+                None => {
+                    outln!("{}", fill("guess.time_up", &[("name", &player.name)]));
+                    return TurnChoice::TimedOut;
+                }
+            },
+            None => prompt_with_history(&message, &recall),
+        };
+
+        let Some(command) = GuessCommand::parse(&input) else {
+            match parse_guess(&input) {
+                Ok(guess) if allowed_in_hard_mode(&guess, &clues) => return TurnChoice::Guess(guess),
+                Ok(_) => {}
+                Err(message) => outln!("{}", message),
+            }
+            continue;
+        };
+        match command {
+            GuessCommand::Hint => {
+                let suggestion = suggest_guess(&player.candidates);
+                player.hints += 1;
+                outln!("{}", fill("guess.hint", &[("guess", &format_code(&suggestion)), ("count", &player.candidates.len())]));
+            }
+            GuessCommand::Note(text) if text.is_empty() => outln!("{}", tr("guess.note_usage")),
+            GuessCommand::Note(text) => {
+                player.notes.push(text);
+                outln!("{}", tr("guess.note_added"));
+            }
+            GuessCommand::Notes => print_notes(&player.notes),
+            GuessCommand::History if clues.is_empty() => outln!("{}", tr("history.empty")),
+            GuessCommand::History => print_history_table(&player.head_start, &player.history),
+            GuessCommand::Score => {
+                for line in scoreboard {
+                    outln!("{}", line);
+                }
+            }
+            GuessCommand::Skip => {
+                outln!("{}", fill("guess.skipped", &[("name", &player.name)]));
+                return TurnChoice::Skip;
+            }
+            GuessCommand::Save(_) if guest_session() => outln!("{}", tr("save.guest")),
+            GuessCommand::Save(path) if path.is_empty() => outln!("{}", tr("guess.save_usage")),
+            GuessCommand::Save(name) => return TurnChoice::Save(save_path(&name)),
+            GuessCommand::Quit => {
+                if prompt(tr("guess.quit_confirm")).eq_ignore_ascii_case(tr("common.yes")) {
+                    return TurnChoice::Quit;
+                }
+                outln!("{}", tr("guess.quit_cancelled"));
+            }
+            GuessCommand::Unknown(name) => {
+                outln!("{}", fill("guess.unknown_command", &[("command", &name)]));
+                print_guess_commands();
+            }
+        }
+    }
+}
+
+/// Lists a player's own notes, oldest first.
+fn print_notes(notes: &[String]) {
+    if notes.is_empty() {
+        outln!("{}", tr("guess.notes_empty"));
+        return;
+    }
+    outln!("{}", tr("guess.notes_header"));
+    for (i, note) in notes.iter().enumerate() {
+        outln!("  {}. {}", i + 1, note);
+    }
+}
+
+/// Prints the digits 0-9 in one strip, marking the ones the player has
+/// already tried and the ones no code fitting their feedback contains.
+/// Screen readers get the two lists spelled out instead.
+fn print_digit_tracker(candidates: &[Guess], history: &[(Guess, Score)]) {
+    let absent = |digit: u8| !candidates.iter().any(|code| code.contains(&digit));
+    let tried = |digit: u8| history.iter().any(|(guess, _)| guess.contains(&digit));
+
+    if Config::current().screen_reader {
+        let list = |digits: Vec<String>| if digits.is_empty() { tr("guess.digits_none").to_string() } else { digits.join(", ") };
+        let tried = list((0..=9u8).filter(|&d| tried(d)).map(|d| d.to_string()).collect());
+        let absent = list((0..=9u8).filter(|&d| absent(d)).map(|d| d.to_string()).collect());
+        outln!("{}", fill("guess.digits_sentence", &[("tried", &tried), ("absent", &absent)]));
+        return;
+    }
+
+    let digits: Vec<String> = (0..=9u8)
+        .map(|digit| {
+            let mark = if absent(digit) { "✗" } else if tried(digit) { "·" } else { " " };
+            format!("{}{}", digit, mark)
+        })
+        .collect();
+    outln!("{}", fill("guess.digits", &[("digits", &digits.join(" "))]));
+}
+
+/// Narrows `candidates` with a guess's feedback and, if the setting is on,
+/// tells the player how many codes are left.
+pub fn track_candidates(candidates: &mut Vec<Guess>, guess: &Guess, score: Score) {
+    filter_candidates(candidates, guess, score);
+    if Config::current().show_remaining_candidates {
+        outln!("{}", fill("guess.remaining", &[("count", &candidates.len())]));
+    }
+}
+
+/// The name of the player after `current`, unless that seat is a bot and
+/// needs no keyboard.
+fn next_human(players: &[Player], current: usize) -> Option<&str> {
+    let next = &players[(current + 1) % players.len()];
+    (!next.is_bot()).then_some(next.name.as_str())
+}
+
+/// Prints a player's own clues so far as a board: head-start clues first,
+/// then each of their guesses with its feedback in pegs. For screen readers
+/// each clue is a sentence of its own instead.
+fn print_history_table(head_start: &[(Guess, Score)], history: &[(Guess, Score)]) {
+    if head_start.is_empty() && history.is_empty() {
+        return;
+    }
+    if Config::current().screen_reader {
+        for (clue, score) in head_start {
+            outln!("{}", fill("history.clue_sentence", &[("guess", &format_code(clue)), ("score", &player_notation().render(*score))]));
+        }
+        for (i, (guess, score)) in history.iter().enumerate() {
+            outln!("{}", fill("history.guess_sentence", &[("number", &(i + 1)), ("guess", &format_code(guess)), ("score", &player_notation().render(*score))]));
+        }
+        return;
+    }
+    // Columns as wide as their headings, which differ between languages
+    let guess_width = display_width(tr("history.guess")).max(4);
+    let pegs_width = display_width(tr("history.pegs")).max(4);
+    let rule = |left: &str, middle: &str, right: &str| {
+        format!("  {}{}{}{}{}{}{}", left, "─".repeat(5), middle, "─".repeat(guess_width + 2), middle, "─".repeat(pegs_width + 2), right)
+    };
+    let row = |number: &str, guess: &str, pegs: &str| {
+        let number = " ".repeat(3usize.saturating_sub(display_width(number))) + number;
+        format!("  │ {} │ {} │ {} │", number, pad(guess, guess_width), pad(pegs, pegs_width))
+    };
+
+    outln!("{}", rule("┌", "┬", "┐"));
+    outln!("{}", row("#", tr("history.guess"), tr("history.pegs")));
+    outln!("{}", rule("├", "┼", "┤"));
+    for (clue, score) in head_start {
+        outln!("{}", row("🎁", &format_code(clue), &PegNotation.render(*score)));
+    }
+    for (i, (guess, score)) in history.iter().enumerate() {
+        outln!("{}", row(&(i + 1).to_string(), &format_code(guess), &PegNotation.render(*score)));
+    }
+    outln!("{}", rule("└", "┴", "┘"));
+    outln!("{}", tr("history.legend"));
+}
+
+/// The best feedback in `history`: most digits in place, then most digits found.
+pub fn best_score(history: &[(Guess, Score)]) -> Option<Score> {
+    history.iter().map(|&(_, score)| score).max_by_key(|&score| (score.0, feedback_pair(score).0))
+}
+
+/// A score for `--output json`.
+pub fn score_json(score: Score) -> Json {
+    Json::Object(vec![("right_place", score.0.into()), ("wrong_place", score.1.into())])
+}
+
+/// Everyone's progress for `--output json`: players still guessing, in seat
+/// order, then those who are done.
+fn standings_json(players: &[Player], completed: &[Player]) -> Json {
+    let standings = players
+        .iter()
+        .chain(completed)
+        .map(|p| {
+            Json::Object(vec![
+                ("player", p.name.as_str().into()),
+                ("guesses", p.guesses.into()),
+                ("best", best_score(&p.history).map_or(Json::Null, score_json)),
+                ("place", p.rank.filter(|_| p.cracked).into()),
+            ])
+        })
+        .collect();
+    Json::List(standings)
+}
+
+/// Everyone's progress before a turn, with `current` (an index into
+/// `players`) about to move and finished players listed by rank.
+fn scoreboard_lines(players: &[Player], completed: &[Player], current: usize) -> Vec<String> {
+    let locale = Locale::current();
+    let width = players.iter().chain(completed).map(|p| display_width(&p.name)).max().unwrap_or(0);
+    let mut lines = vec![tr("scoreboard.title").to_string()];
+    for (i, p) in players.iter().enumerate() {
+        let marker = if i == current { "▶" } else { " " };
+        let best = best_score(&p.history).map_or("-".to_string(), |score| player_notation().render(score));
+        lines.push(format!(" {} {}  {}", marker, pad(&p.name, width), fill("scoreboard.playing", &[("count", &locale.count(p.guesses as u64)), ("best", &best)])));
+    }
+    let mut finished: Vec<&Player> = completed.iter().filter(|p| p.cracked).collect();
+    finished.sort_by_key(|p| p.rank);
+    for p in finished {
+        let place = p.rank.map_or("-".to_string(), |rank| locale.ordinal(rank));
+        lines.push(format!("   {}  {}", pad(&p.name, width), fill("scoreboard.finished", &[("place", &place), ("count", &locale.count(p.guesses as u64))])));
+    }
+    lines
+}
+
+/// With a guess limit, moves a player who has used every guess without
+/// cracking their code to `completed` (unranked, so they're listed as not
+/// finishing). Returns whether they were taken out.
+fn retire_if_out_of_guesses(players: &mut Vec<Player>, index: usize, completed: &mut Vec<Player>, round: u32, started_at: Instant) -> bool {
+    let limit = Config::current().guess_limit;
+    if limit == 0 || players[index].guesses < limit {
+        return false;
+    }
+    let mut player = players.remove(index);
+    player.finished = Some((round, started_at.elapsed()));
+    outln!("{}", fill("game.out_of_guesses", &[("name", &player.name), ("limit", &limit), ("secret", &format_code(&player.secret_code))]));
+    emit(GameEvent::OutOfGuesses { round, player: player.name.clone() });
+    json::emit("out", vec![("round", round.into()), ("player", player.name.as_str().into())]);
+    completed.push(player);
+    true
+}
+
+/// Checks an `/undo` with another human, if the setting asks for it. With
+/// no other human at the table there's nobody to ask, so it's allowed. A
+/// refused undo hands the keyboard over as usual.
+fn undo_allowed(players: &[Player], current: usize, guess: &str) -> bool {
+    let confirmer = players.iter().enumerate().find(|&(i, p)| i != current && !p.is_bot()).map(|(_, p)| p.name.as_str());
+    let (true, Some(confirmer)) = (Config::current().confirm_undo, confirmer) else { return true };
+    // The player who's up next is the natural one to ask
+    let confirmer = next_human(players, current).unwrap_or(confirmer);
+    let answer = prompt(&fill("undo.confirm", &[("name", &confirmer), ("player", &players[current].name), ("guess", &guess)]));
+    if answer.eq_ignore_ascii_case(tr("common.yes")) {
+        return true;
+    }
+    outln!("{}", tr("undo.refused"));
+    hand_over(next_human(players, current));
+    false
+}
+
+/// Tells the table about a turn whose feedback has just been hidden, as much as the settings allow.
+fn announce_turn(name: &str, guess: &Guess, score: Score) {
+    match Config::current().turn_announcement {
+        Announcement::Nothing => {}
+        Announcement::Guessed => outln!("{}", fill("announce.guessed", &[("name", &name)])),
+        Announcement::Feedback => outln!("{}", fill("announce.feedback", &[("name", &name), ("score", &player_notation().render(score))])),
+        Announcement::FullGuess => outln!("{}", fill("announce.full", &[("name", &name), ("guess", &format_code(guess)), ("score", &player_notation().render(score))])),
+    }
+}
+
+/// Prints each remaining player's estimated chance of taking the next place,
+/// with `next` about to move.
+fn show_win_chances(players: &[Player], next: usize, place: usize) {
+    let order: Vec<usize> = (0..players.len()).map(|offset| (next + offset) % players.len()).collect();
+    let candidates: Vec<&[Guess]> = order.iter().map(|&i| players[i].candidates.as_slice()).collect();
+    let chances = win_chances(&candidates, &mut rand::rng());
+
+    let width = players.iter().map(|p| p.name.chars().count()).max().unwrap_or(0);
+    outln!("{}", fill("chances.title", &[("place", &Locale::current().ordinal(place))]));
+    for (&i, chance) in order.iter().zip(chances) {
+        let bar = "█".repeat((chance * 20.0).round() as usize);
+        outln!("   {:<width$} {:>3.0}% {}", players[i].name, chance * 100.0, bar, width = width);
+    }
+}
+
+/// Prompts the user to select a starting player index (1-based) or 0 for random.
+pub fn get_starting_player_index(players: &[Player], results: &[GameResult]) -> usize {
+    let max_index = players.len();
+    loop {
+        outln!("{}", tr("start.title"));
+        // Print player options (1-based index)
+        for (i, player) in players.iter().enumerate() {
+            outln!("  [{}] {}", i + 1, player.name);
+        }
+        outln!("{}", tr("start.random"));
+        outln!("{}", tr("start.comeback"));
+
+        let input = prompt(tr("start.prompt"));
+        if input.eq_ignore_ascii_case("c") {
+            let names: Vec<&str> = players.iter().map(|p| p.name.as_str()).collect();
+            let weights = comeback_weights(results, &names);
+            let total: f64 = weights.iter().sum();
+            for (player, weight) in players.iter().zip(&weights) {
+                outln!("  {:<20} {}", player.name, fill("start.chance", &[("percent", &format!("{:>3.0}", weight / total * 100.0))]));
+            }
+            let indices: Vec<usize> = (0..max_index).collect();
+            let chosen = *indices
+                .choose_weighted(&mut game_rng(), |&i| weights[i])
+                .expect("comeback weights are always positive");
+            outln!("{}", fill("start.comeback_selected", &[("name", &players[chosen].name)]));
+            return chosen;
+        }
+        match input.parse::<usize>() {
+            Ok(0) => {
+                let mut rng = game_rng();
+                let random_index = rng.random_range(0..max_index);
+                outln!("{}", fill("start.random_selected", &[("name", &players[random_index].name)]));
+                return random_index;
+            }
+            Ok(n) if n >= 1 && n <= max_index => {
+                let start_index = n - 1; // Convert 1-based to 0-based
+                outln!("{}", fill("start.selected", &[("name", &players[start_index].name)]));
+                return start_index;
+            }
+            _ => {
+                outln!("{}", tr("start.invalid"));
+            }
+        }
+    }
+}
+
+/// Displays the post-game menu and handles the winner/game state.
+/// Returns true if the game should continue, false to quit or restart.
+/// What the table picked in the post-game menu.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PostGame {
+    Continue,
+    End,
+    Save(PathBuf), // Continue, once the game is saved there
+}
+
+/// Gives the winner their place and moves them to the completed players.
+fn retire_winner(players: &mut Vec<Player>, winner_index: usize, rank_to_assign: usize, completed_players: &mut Vec<Player>) {
+    // Assign the final rank before removing the player
+    if let Some(player) = players.get_mut(winner_index) {
+        player.rank = Some(rank_to_assign);
+    }
+
+    let winning_player = players.remove(winner_index);
+    let place = Locale::current().ordinal(winning_player.rank.unwrap_or(rank_to_assign));
+    outln!("{}", fill("post.removed", &[("name", &winning_player.name), ("place", &place)]));
+
+    // Move the ranked player to the completed list
+    completed_players.push(winning_player);
+}
+
+fn post_game_menu(
+    players: &mut Vec<Player>,
+    winner_index: usize,
+    rank_to_assign: usize,
+    completed_players: &mut Vec<Player>,
+) -> PostGame {
+    loop {
+        let name = &players[winner_index].name;
+        outln!("{}", tr("post.title"));
+        // Check if we're playing for the LAST spot.
+        if players.len() == 1 {
+            outln!("{}", fill("post.finish", &[("name", name)]));
+        } else {
+            outln!("{}", fill("post.continue", &[("name", name)]));
+        }
+
+        outln!("{}", tr("post.restart"));
+        outln!("{}", tr("post.quit"));
+        outln!("{}", fill("post.review", &[("name", name)]));
+        // Saving only makes sense with at least two left to play for the next place
+        let can_save = players.len() > 2 && !guest_session();
+        if can_save {
+            outln!("{}", tr("post.save"));
+        }
+
+        match prompt(tr("post.prompt")).as_str() {
+            "1" => {
+                retire_winner(players, winner_index, rank_to_assign, completed_players);
+
+                // Only end the game if the vector is now empty.
+                if players.is_empty() {
+                    return PostGame::End; // Signal run_game to break the loop
+                }
+                return PostGame::Continue; // Continue to the next round of the game loop
+            }
+            "2" => return PostGame::End, // Signal main to break and restart the whole main function process
+            "3" => {
+                outln!("{}", tr("post.goodbye"));
+                std::process::exit(0); // Explicitly exit the program
+            }
+            "4" => {
+                let player = &players[winner_index];
+                print_review(&player.name, &player.head_start, &player.history);
+                prompt(tr("post.back"));
+            }
+            input if can_save && let Some(GuessCommand::Save(name)) = GuessCommand::parse(input) => {
+                if name.is_empty() {
+                    outln!("{}", tr("guess.save_usage"));
+                    continue;
+                }
+                retire_winner(players, winner_index, rank_to_assign, completed_players);
+                return PostGame::Save(save_path(&name));
+            }
+            _ => {
+                outln!("{}", tr("post.invalid"));
+                pause(Duration::from_secs(1));
+                clear_screen();
+            }
+        }
+    }
+}
+
+// --- Debugging ---
+// `--debug-secrets` shows each player's secret on their turn, for trying out
+// rule variants. That would spoil a real game, so it only works with a single
+// person at the table unless `--i-know-what-im-doing` is given too.
+
+/// Who `--debug-secrets` is allowed to show secrets to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugSecrets {
+    Off,
+    SolePlayer, // Only when one person is playing, perhaps against bots
+    Always,     // Acknowledged with --i-know-what-im-doing
+}
+
+static DEBUG_SECRETS: RwLock<DebugSecrets> = RwLock::new(DebugSecrets::Off);
+
+pub fn set_debug_secrets(mode: DebugSecrets) {
+    *DEBUG_SECRETS.write().unwrap() = mode;
+}
+
+/// Deals this game's codes at the length the first returning player with a
+/// preference likes, unless `--length` chose one. Everyone's codes are the
+/// same length, so the table is told whose choice it was.
+pub fn use_preferred_length(seats: &[Seat]) {
+    if code_length_fixed() {
+        return;
+    }
+    let preference = seats
+        .iter()
+        .filter(|seat| seat.bot.is_none() && seat.engine.is_none())
+        .find_map(|seat| Profile::load(&seat.name)?.code_length.map(|length| (seat.name.as_str(), length)));
+    if let Some((name, length)) = preference
+        && length != code_length()
+    {
+        set_code_length(length);
+        outln!("{}", fill("profile.uses_length", &[("name", &name), ("length", &length)]));
+    }
+}
+
+/// Welcomes back each player who has a profile, with their stats.
+pub fn greet_profiles(players: &[Player], results: &[GameResult]) {
+    let summaries = summarize(results);
+    for p in players {
+        if let Some(profile) = &p.profile {
+            welcome(profile, summaries.get(&p.name));
+        }
+    }
+}
+
+/// Unlocks whatever a person's solve earns, saving it to their profile (made
+/// for them if they have none yet) and announcing anything new.
+fn award_achievements(player: &mut Player, performance: &Performance) {
+    let profile = player.profile.get_or_insert_with(|| Profile { name: player.name.clone(), ..Profile::default() });
+    let unlocked = unlock(profile, performance);
+    if unlocked.is_empty() {
+        return;
+    }
+    if let Err(e) = profile.save() {
+        outln!("{}", fill("profile.save_failed", &[("error", &e)]));
+    }
+    announce(&player.name, &unlocked);
+}
+
+/// Whether a game with these players shows their secrets, telling the
+/// table if `--debug-secrets` was refused.
+fn reveal_secrets(players: &[Player]) -> bool {
+    match *DEBUG_SECRETS.read().unwrap() {
+        DebugSecrets::Off => false,
+        DebugSecrets::Always => true,
+        DebugSecrets::SolePlayer if players.iter().filter(|p| !p.is_bot()).count() <= 1 => true,
+        DebugSecrets::SolePlayer => {
+            outln!("{}", tr("debug.refused"));
+            false
+        }
+    }
+}
+
+// --- Game Loop ---
+
+/// Encapsulates the entire game setup and main loop logic for easy restart.
+pub fn run_game(stores: &mut StoreSet, session: &mut Session) {
+    clear_screen();
+    outln!("{}", tr("game.title"));
+    if !quiet_output() {
+        outln!("{}", fill("game.intro_code", &[("length", &code_length())]));
+        outln!("{}", tr("game.intro_goal"));
+    }
+
+    // 1. Setup Players and Assign Individual Secrets, unless a saved game carries on
+    let game = match take_resumed_game() {
+        Some(saved) => {
+            outln!("{}", fill("save.resumed", &[("round", &saved.round), ("name", &saved.players[saved.current].name)]));
+            saved
+        }
+        None => {
+            let preset = take_preset_seats();
+            let quick_start = preset.is_some(); // Set up on the command line, without questions
+            // Ratings come from the multi-player games, wherever those are saved
+            let seats = preset.unwrap_or_else(|| run_lobby(&Ratings::from_results(stores.store_for(2).results())));
+            use_preferred_length(&seats);
+            let seed = start_seeded_game();
+            let players: Vec<Player> = seats.into_iter().map(Player::from_seat).collect();
+            if !quiet_output() {
+                outln!("{}", tr("game.secrets_ready"));
+            }
+
+            // 2. Determine Starting Player Index (the first seat, for a quick start)
+            let current = if quick_start { 0 } else { get_starting_player_index(&players, stores.store_for(players.len()).results()) };
+            SavedGame {
+                seed,
+                round: 1,
+                total_guesses: 0,
+                skipped_turns: 0,
+                rank_to_assign: 1,
+                last_assigned_round: 0,
+                current,
+                elapsed: Duration::ZERO,
+                players,
+                completed: Vec::new(),
+                transcript: Transcript::default(),
+            }
+        }
+    };
+
+    // 3. Game Loop Variables
+    let SavedGame {
+        seed,
+        round: mut round_number,           // Tracks full cycles (rounds)
+        mut total_guesses,                 // Tracks total guesses across all rounds
+        mut skipped_turns,                 // Turns passed by late-start handicaps, which still count towards rounds
+        mut rank_to_assign,                // The rank for the next *distinct* finisher (1st, 2nd, 3rd...)
+        mut last_assigned_round,           // The round number the most recent rank was achieved in.
+        current: mut current_player_index,
+        elapsed,
+        mut players,
+        completed: mut completed_players,  // Players who have finished the game
+        mut transcript,                    // Every guess, for the record offered at the end
+    } = game;
+    let num_players = players.len() + completed_players.len();
+    // For the game length shown with the final rankings, counting any time before a save
+    let started_at = Instant::now().checked_sub(elapsed).unwrap_or_else(Instant::now);
+
+    let reveal = reveal_secrets(&players);
+    if reveal {
+        outln!("{}", tr("debug.enabled"));
+    }
+    greet_profiles(&players, stores.store_for(num_players).results());
+
+    // *** CLEAR SCREEN ***
+    clear_screen();
+
+    emit(GameEvent::Started { players: players.iter().map(|p| p.name.clone()).collect() });
+    json::emit("start", vec![("players", Json::List(players.iter().map(|p| p.name.as_str().into()).collect())), ("seed", seed.into())]);
+
+    loop {
+        // Handle final player finishing the game
+        if players.is_empty() {
+             outln!("{}", tr("game.all_finished"));
+             break;
+        }
+
+        // Handle the last remaining player (auto-assignment of final rank). A
+        // player on their own is still racing nobody, so they play on until
+        // they crack it or give up.
+        if players.len() == 1 && !completed_players.is_empty() && players[0].rank.is_none() {
+            let last_player_index = 0;
+            // The last player automatically gets the current distinct rank
+            players[last_player_index].rank = Some(rank_to_assign);
+            players[last_player_index].finished = Some((round_number, started_at.elapsed()));
+            outln!("{}", tr("game.last_player_title"));
+            outln!("{}", fill("game.last_player", &[("name", &players[last_player_index].name), ("place", &Locale::current().ordinal(rank_to_assign))]));
+            emit(GameEvent::Finished { player: players[last_player_index].name.clone(), place: rank_to_assign, guesses: players[last_player_index].guesses });
+            json::emit("finish", vec![("player", players[last_player_index].name.as_str().into()), ("place", rank_to_assign.into()), ("guesses", players[last_player_index].guesses.into())]);
+
+            // Move the last player to the completed list and break
+            completed_players.append(&mut players);
+            break;
+        }
+
+        // Ensure the current_player_index is valid after a removal
+        if current_player_index >= players.len() {
+            current_player_index = 0;
+        }
+
+        // A late-start handicap passes the turn without a guess
+        if players[current_player_index].sits_out > 0 {
+            players[current_player_index].sits_out -= 1;
+            outln!("{}", fill("game.sits_out", &[("name", &players[current_player_index].name)]));
+            emit(GameEvent::SatOut { round: round_number, player: players[current_player_index].name.clone() });
+            json::emit("pass", vec![("round", round_number.into()), ("player", players[current_player_index].name.as_str().into()), ("reason", "sat_out".into())]);
+            pause(Duration::from_secs(1));
+            skipped_turns += 1;
+            if (total_guesses + skipped_turns).is_multiple_of(players.len() as u32) {
+                round_number += 1;
+            }
+            current_player_index = (current_player_index + 1) % players.len();
+            continue;
+        }
+
+        // Each human's turn looks the way their profile asks
+        Profile::apply(players[current_player_index].profile.as_ref());
+
+        // Quiet output leaves the progress displays to /score and /history
+        let quiet = quiet_output();
+        let scoreboard = scoreboard_lines(&players, &completed_players, current_player_index);
+        if !quiet {
+            for line in &scoreboard {
+                outln!("{}", line);
+            }
+        }
+
+        total_guesses += 1; // Increment guess counter first
+        players[current_player_index].guesses += 1;
+
+        if players.len() > 1 && Config::current().show_win_chances {
+            show_win_chances(&players, current_player_index, rank_to_assign);
+        }
+
+        let current_player = &mut players[current_player_index];
+
+        if !quiet {
+            outln!("\n======================================");
+            outln!("{}", fill("game.turn_header", &[("round", &round_number), ("name", &current_player.name)]));
+            outln!("======================================");
+        }
+        if reveal {
+            outln!("{}", fill("debug.secret", &[("name", &current_player.name), ("secret", &format_code(&current_player.secret_code))]));
+        }
+        json::emit("turn", vec![("round", round_number.into()), ("player", current_player.name.as_str().into()), ("bot", current_player.is_bot().into())]);
+
+        if !current_player.is_bot() {
+            sound::play(Cue::TurnStart);
+            if !quiet {
+                print_history_table(&current_player.head_start, &current_player.history);
+            }
+        }
+
+        let engine_turn = current_player.engine_turn(round_number);
+        let guess = match &current_player.bot {
+            Some(bot) => {
+                let guess = bot.next_guess();
+                if !quiet {
+                    outln!("{}", fill("game.bot_thinking", &[("name", &current_player.name)]));
+                    pause(Duration::from_secs(1));
+                }
+                outln!("{}", fill("game.bot_guesses", &[("name", &current_player.name), ("guess", &format_code(&guess))]));
+                guess
+            }
+            None => {
+                let attempt = current_player.guesses;
+                let choice = match engine_turn {
+                    Some(Ok(guess)) => {
+                        outln!("{}", fill("game.bot_guesses", &[("name", &current_player.name), ("guess", &format_code(&guess))]));
+                        TurnChoice::Guess(guess)
+                    }
+                    // A program that loses its turn is treated like a human whose timer ran out
+                    Some(Err(note)) => {
+                        outln!("{}", note);
+                        TurnChoice::TimedOut
+                    }
+                    None => get_player_guess_with_hints(current_player, attempt, &scoreboard),
+                };
+                match choice {
+                    TurnChoice::Guess(g) => g,
+                    TurnChoice::Quit => {
+                        // No guess was taken
+                        players[current_player_index].guesses -= 1;
+                        total_guesses -= 1;
+                        outln!("{}", tr("game.ended_early"));
+                        break;
+                    }
+                    TurnChoice::Save(path) => {
+                        // The save is of the game before this turn, which is then played as usual
+                        players[current_player_index].guesses -= 1;
+                        total_guesses -= 1;
+                        let saved = SavedGame {
+                            seed,
+                            round: round_number,
+                            total_guesses,
+                            skipped_turns,
+                            rank_to_assign,
+                            last_assigned_round,
+                            current: current_player_index,
+                            elapsed: started_at.elapsed(),
+                            players: std::mem::take(&mut players),
+                            completed: std::mem::take(&mut completed_players),
+                            transcript: std::mem::take(&mut transcript),
+                        };
+                        saved.save_to(&path);
+                        players = saved.players;
+                        completed_players = saved.completed;
+                        transcript = saved.transcript;
+                        continue;
+                    }
+                    choice => {
+                        // Out of time or passed: the turn is lost, but still counts towards the round
+                        if choice == TurnChoice::TimedOut {
+                            emit(GameEvent::TimedOut { round: round_number, player: players[current_player_index].name.clone() });
+                        }
+                        let reason = if choice == TurnChoice::TimedOut { "timed_out" } else { "skipped" };
+                        json::emit("pass", vec![("round", round_number.into()), ("player", players[current_player_index].name.as_str().into()), ("reason", reason.into())]);
+                        if players[current_player_index].is_bot() {
+                            pause(Duration::from_secs(2));
+                        } else {
+                            hand_over(next_human(&players, current_player_index));
+                        }
+                        if retire_if_out_of_guesses(&mut players, current_player_index, &mut completed_players, round_number, started_at) {
+                            continue;
+                        }
+                        if (total_guesses + skipped_turns).is_multiple_of(players.len() as u32) {
+                            round_number += 1;
+                        }
+                        current_player_index = (current_player_index + 1) % players.len();
+                        continue;
+                    }
+                }
+            }
+        };
+
+        // 4. Score and Feedback: Use the current player's unique secret code
+        let (y_score, c_score) = calculate_score(&guess, &current_player.secret_code);
+        let mut bot_remarks = Vec::new();
+        if let Some(bot) = &mut players[current_player_index].bot {
+            if bot.is_chatty() {
+                bot_remarks = bot.observe_and_explain(&guess, (y_score, c_score));
+            } else {
+                bot.observe(&guess, (y_score, c_score));
+            }
+        }
+        let current_player = &players[current_player_index];
+
+        // 5. Simplified Output (D = Total Correct Digits, P = Digits at Correct Position)
+        let guess_str = format_code(&guess);
+
+        outln!("--------------------------------------");
+        outln!("{}", fill("game.feedback", &[("guess", &guess_str), ("score", &paint_feedback((y_score, c_score), &player_notation().render((y_score, c_score))))]));
+        outln!("--------------------------------------");
+        for remark in &bot_remarks {
+            outln!("{}", fill("bot.remark", &[("name", &current_player.name), ("remark", remark)]));
+        }
+        if !current_player.is_bot() {
+            coach_guess(&guess, &current_player.candidates);
+        }
+        track_candidates(&mut players[current_player_index].candidates, &guess, (y_score, c_score));
+        players[current_player_index].history.push((guess, (y_score, c_score)));
+        let current_player = &players[current_player_index];
+        transcript.record(round_number, &current_player.name, guess, (y_score, c_score), started_at.elapsed());
+        emit(GameEvent::Turn {
+            round: round_number,
+            player: current_player.name.clone(),
+            guesses: current_player.guesses,
+            best: best_score(&current_player.history),
+        });
+        json::emit("guess", vec![
+            ("player", current_player.name.as_str().into()),
+            ("guess", guess_str.as_str().into()),
+            ("right_place", y_score.into()),
+            ("wrong_place", c_score.into()),
+        ]);
+        json::emit("score", vec![("standings", standings_json(&players, &completed_players))]);
+
+
+        // 6. Check for Win Condition (every digit in its correct position)
+        if is_solved((y_score, c_score)) {
+            let mut rank_to_assign_final: usize;
+
+            if round_number > last_assigned_round {
+                // New, distinct rank
+                rank_to_assign_final = rank_to_assign;
+                // Prepare rank for the NEXT distinct winner
+                rank_to_assign += 1;
+            } else {
+                // Tie: Assign the rank of the last winner (which is rank_to_assign - 1)
+                rank_to_assign_final = rank_to_assign.saturating_sub(1);
+                if rank_to_assign_final == 0 {
+                    rank_to_assign_final = 1;
+                }
+            }
+
+            // Update the winning round number after assigning the rank
+            last_assigned_round = round_number;
+            players[current_player_index].cracked = true;
+            players[current_player_index].finished = Some((round_number, started_at.elapsed()));
+            let current_player = &players[current_player_index];
+
+            // Only outright wins are checked against the stats for milestones
+            let found = if rank_to_assign_final == 1 {
+                let opponents: Vec<&str> = players
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, _)| i != current_player_index)
+                    .map(|(_, p)| p.name.as_str())
+                    .collect();
+                milestones(stores.store_for(num_players).results(), "individual", &Rules::default().record_key(), &current_player.name, current_player.guesses, &opponents)
+            } else {
+                Vec::new()
+            };
+            sound::play(Cue::Cracked);
+            celebrate(&current_player.name, &found);
+            let place = Locale::current().ordinal(rank_to_assign_final);
+            outln!("{}", fill("game.cracked", &[("name", &current_player.name), ("guess", &guess_str), ("place", &place)]));
+            emit(GameEvent::Finished { player: current_player.name.clone(), place: rank_to_assign_final, guesses: current_player.guesses });
+            json::emit("finish", vec![("player", current_player.name.as_str().into()), ("place", rank_to_assign_final.into()), ("guesses", current_player.guesses.into())]);
+
+            // Achievements are for people, and not for games with the secrets on show
+            if !current_player.is_bot() && !reveal {
+                let won = rank_to_assign_final == 1 && num_players > 1;
+                let performance = Performance {
+                    guesses: current_player.guesses,
+                    hints: current_player.hints,
+                    code_length: current_player.secret_code.len(),
+                    streak: won.then(|| win_streak(stores.store_for(num_players).results(), &current_player.name) + 1),
+                };
+                award_achievements(&mut players[current_player_index], &performance);
+            }
+
+            // Post-Game Menu
+            let choice = post_game_menu(&mut players, current_player_index, rank_to_assign_final, &mut completed_players);
+
+            if choice == PostGame::End {
+                break; // Exit the game loop
+            }
+
+            // Adjust the current player index since the vector was modified
+            current_player_index %= players.len();
+
+            // Clear screen after the menu selection
+            clear_screen();
+            if let PostGame::Save(path) = choice {
+                let saved = SavedGame {
+                    seed,
+                    round: round_number,
+                    total_guesses,
+                    skipped_turns,
+                    rank_to_assign,
+                    last_assigned_round,
+                    current: current_player_index,
+                    elapsed: started_at.elapsed(),
+                    players: std::mem::take(&mut players),
+                    completed: std::mem::take(&mut completed_players),
+                    transcript: std::mem::take(&mut transcript),
+                };
+                saved.save_to(&path);
+                players = saved.players;
+                completed_players = saved.completed;
+                transcript = saved.transcript;
+            }
+            continue; // Go to the next loop iteration (next player's turn)
+        }
+
+        // 7. Pause, clear screen, move to the next player, and check for round completion
+
+        if players[current_player_index].is_bot() {
+            // A bot's feedback reveals nothing private, so it only needs a moment on screen
+            pause(Duration::from_secs(2));
+        } else {
+            if typed_input() {
+                outln!("{}", fill("undo.offer", &[("guess", &guess_str)]));
+            }
+            if hand_over_or_undo(next_human(&players, current_player_index)) && undo_allowed(&players, current_player_index, &guess_str) {
+                // The turn is played again, so it mustn't count twice
+                let player = &mut players[current_player_index];
+                player.undo_last_guess();
+                transcript.take_back();
+                total_guesses -= 1;
+                outln!("{}", fill("undo.done", &[("player", &player.name), ("guess", &guess_str)]));
+                json::emit("undo", vec![("player", player.name.as_str().into()), ("guess", guess_str.as_str().into())]);
+                continue;
+            }
+            announce_turn(&players[current_player_index].name, &guess, (y_score, c_score));
+        }
+
+        // Like a finish, a player running out of guesses leaves the turn order
+        if retire_if_out_of_guesses(&mut players, current_player_index, &mut completed_players, round_number, started_at) {
+            continue;
+        }
+
+        // Check if a full round has been completed (total_guesses is a multiple of num_players)
+        if (total_guesses + skipped_turns).is_multiple_of(players.len() as u32) {
+            round_number += 1;
+        }
+
+        current_player_index = (current_player_index + 1) % players.len();
+    }
+
+    emit(GameEvent::Ended);
+    Profile::apply(None);
+    sound::play(Cue::GameEnd);
+    let rankings = completed_players
+        .iter()
+        .chain(&players)
+        .map(|p| Json::Object(vec![("player", p.name.as_str().into()), ("place", p.rank.filter(|_| p.cracked).into()), ("guesses", p.guesses.into())]))
+        .collect();
+    json::emit("rankings", vec![("complete", players.is_empty().into()), ("rankings", Json::List(rankings))]);
+
+    for p in completed_players.iter().chain(&players) {
+        let solve = p.cracked.then(|| Solve { player: p.name.clone(), guesses: p.guesses });
+        session.record_secret("individual", p.secret_code, solve);
+    }
+    session.record_game(GameRecord {
+        duration: started_at.elapsed(),
+        total_guesses,
+        placements: completed_players
+            .iter()
+            .chain(&players)
+            .map(|p| Placement { player: p.name.clone(), place: p.rank.filter(|_| p.cracked), guesses: p.guesses })
+            .collect(),
+    });
+
+    // A restart leaves players unfinished; only complete games count towards
+    // stats (and ratings), and only if nobody could see the secrets
+    let rated = players.is_empty() && !reveal;
+    let rating_changes = if rated {
+        let finishes: Vec<(&str, usize)> = completed_players.iter().map(|p| (p.name.as_str(), p.rank.unwrap_or(num_players))).collect();
+        Ratings::from_results(stores.store_for(num_players).results()).changes(&finishes)
+    } else {
+        BTreeMap::new()
+    };
+    // The replay is filed under the same id as the results
+    let game = new_game_id();
+    if rated {
+        let timestamp = now_timestamp();
+        let results = completed_players
+            .iter()
+            .map(|p| GameResult {
+                game,
+                timestamp,
+                mode: "individual".to_string(),
+                player: p.name.clone(),
+                rank: p.rank.unwrap_or(num_players),
+                field_size: num_players,
+                guesses: p.guesses,
+                rules: Rules::default().record_key(),
+            })
+            .collect();
+        record_results(stores, results, &transcript.moves);
+    }
+
+    // --- FINAL RANKING DISPLAY ---
+    // Players still in play after a restart are listed as not finishing this round
+    if !completed_players.is_empty() {
+        let standings: Vec<Standing> = completed_players
+            .iter()
+            .chain(&players)
+            .map(|p| {
+                let (round, time) = p.finished.unwrap_or((round_number, started_at.elapsed()));
+                let finish = match p.rank {
+                    Some(rank) if p.cracked => Finish::Placed(rank),
+                    _ => Finish::DidNotFinish { round },
+                };
+                Standing { name: p.name.clone(), finish, guesses: p.guesses, time, secret: p.secret_code, rating: rating_changes.get(&p.name).copied() }
+            })
+            .collect();
+        print_rankings(&standings);
+
+        let locale = Locale::current();
+        outln!("{}", fill("game.length", &[("time", &locale.duration(started_at.elapsed())), ("count", &locale.count(total_guesses as u64))]));
+        outln!("{}", fill("game.seed", &[("seed", &seed)]));
+
+        // Hints are listed openly so nobody's result looks better than it was
+        let hint_users: Vec<String> = completed_players
+            .iter()
+            .filter(|p| p.hints > 0)
+            .map(|p| format!("{} ({})", p.name, p.hints))
+            .collect();
+        if !hint_users.is_empty() {
+            outln!("{}", fill("game.hints_used", &[("players", &hint_users.join(", "))]));
+        }
+        let undo_users: Vec<String> = completed_players
+            .iter()
+            .filter(|p| p.undos > 0)
+            .map(|p| format!("{} ({})", p.name, p.undos))
+            .collect();
+        if !undo_users.is_empty() {
+            outln!("{}", fill("game.undos_used", &[("players", &undo_users.join(", "))]));
+        }
+        if Config::current().show_leaderboard {
+            print_leaderboard(stores.store_for(num_players));
+        }
+
+        record_replay(&Replay::new(seed, game, started_at.elapsed(), &standings, &transcript));
+
+        // JSON wrappers keep their own record of the events
+        if !json::active() {
+            offer_transcript(&transcript, &standings, seed, started_at.elapsed());
+        }
+
+        // A finished game with at least two people can carry on as a knockout
+        let humans = completed_players.iter().filter(|p| !p.is_bot()).count();
+        if players.is_empty() && humans >= 2
+            && prompt(tr("game.rematch_prompt")).eq_ignore_ascii_case(tr("common.yes"))
+        {
+            let bots: Vec<&str> = completed_players.iter().filter(|p| p.is_bot()).map(|p| p.name.as_str()).collect();
+            run_rematch_bracket(&standings, &bots, session);
+        }
+    }
+}
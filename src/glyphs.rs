@@ -0,0 +1,228 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::RwLock;
+
+use crate::config::Config;
+use crate::console::{note_printed, quiet_output, strip_decorations};
+use crate::json;
+use crate::theme::Theme;
+
+// --- Output Profiles ---
+// Some terminals can't draw emoji (the banner comes out as `ðŸŽ²`), and some
+// can't draw anything beyond ASCII. Everything the game prints passes through
+// `decorate` (by way of the `out!` and `outln!` macros in lib.rs), which swaps
+// each decorative character for one the chosen profile can show.
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputProfile {
+    #[default]
+    Emoji,   // Output exactly as written
+    Unicode, // Plain Unicode symbols instead of emoji
+    Ascii,   // Nothing outside ASCII
+}
+
+/// Set by the defaults file, which wins over the settings file.
+static OVERRIDE: RwLock<Option<OutputProfile>> = RwLock::new(None);
+
+/// The profile of the player whose turn it is, which wins over both.
+static PLAYER: RwLock<Option<OutputProfile>> = RwLock::new(None);
+
+impl OutputProfile {
+    pub const ALL: [OutputProfile; 3] = [OutputProfile::Emoji, OutputProfile::Unicode, OutputProfile::Ascii];
+
+    /// The name used in the settings file.
+    pub fn id(self) -> &'static str {
+        match self {
+            OutputProfile::Emoji => "emoji",
+            OutputProfile::Unicode => "unicode",
+            OutputProfile::Ascii => "ascii",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<OutputProfile> {
+        OutputProfile::ALL.into_iter().find(|p| p.id() == id.to_ascii_lowercase())
+    }
+
+    /// The string id of the profile's description in the settings menu.
+    pub fn label_id(self) -> &'static str {
+        match self {
+            OutputProfile::Emoji => "output.emoji",
+            OutputProfile::Unicode => "output.unicode",
+            OutputProfile::Ascii => "output.ascii",
+        }
+    }
+
+    /// The next profile, for cycling through them in the menu.
+    pub fn next(self) -> OutputProfile {
+        let index = OutputProfile::ALL.iter().position(|&p| p == self).unwrap_or(0);
+        OutputProfile::ALL[(index + 1) % OutputProfile::ALL.len()]
+    }
+
+    /// The profile in use: the current player's, then the defaults file, then the settings.
+    pub fn current() -> OutputProfile {
+        PLAYER.read().unwrap().or(*OVERRIDE.read().unwrap()).unwrap_or_else(|| Config::current().output)
+    }
+
+    /// Overrides the profile for the rest of the run.
+    pub fn set_current(profile: OutputProfile) {
+        *OVERRIDE.write().unwrap() = Some(profile);
+    }
+
+    /// Uses a player's own symbols until they're changed again (None for nobody's).
+    pub fn set_player(profile: Option<OutputProfile>) {
+        *PLAYER.write().unwrap() = profile;
+    }
+}
+
+/// Replacements for each decorative character: (character, Unicode profile, ASCII profile).
+const GLYPHS: &[(char, &str, &str)] = &[
+    // Emoji
+    ('🌐', "◍", "@"),
+    ('🎁', "◇", "+"),
+    ('🎆', "✺", "*"),
+    ('🎉', "✶", "*"),
+    ('🎓', "✎", ">"),
+    ('🎬', "▶", ">"),
+    ('🎲', "◆", "#"),
+    ('🏁', "⚑", ">"),
+    ('🏅', "✪", "*"),
+    ('🏆', "♛", "#"),
+    ('🐢', "~", "~"),
+    ('👀', "◎", "o"),
+    ('👋', "↩", "<-"),
+    ('👤', "☺", "@"),
+    ('💡', "☼", "?"),
+    ('💬', "»", ">"),
+    ('💾', "▣", "#"),
+    ('📁', "▤", "-"),
+    ('📅', "▦", "-"),
+    ('📈', "↗", "^"),
+    ('📊', "▥", "%"),
+    ('📜', "§", "-"),
+    ('📋', "▤", "-"),
+    ('📝', "✎", "-"),
+    ('📡', "≈", "~"),
+    ('📢', "»", ">"),
+    ('📺', "▭", "-"),
+    ('🔁', "⟲", "<>"),
+    ('🔌', "↯", "+"),
+    ('🔎', "⌕", "?"),
+    ('🔒', "■", "#"),
+    ('🔓', "□", "-"),
+    ('🔢', "#", "#"),
+    ('🔥', "✹", "!"),
+    ('🔬', "⌕", "?"),
+    ('🕵', "◉", "?"),
+    ('🖥', "▭", "-"),
+    ('🗳', "☐", "-"),
+    ('🚩', "⚑", "!"),
+    ('🚨', "‼", "!!"),
+    ('🚪', "▯", "-"),
+    ('🚫', "⊘", "x"),
+    ('🛑', "⊗", "x"),
+    ('🟨', "▒", "o"),
+    ('🟩', "█", "X"),
+    ('⬛', "░", "."),
+    ('🤖', "◆", "@"),
+    ('🥇', "①", "1"),
+    ('🧠', "◎", "*"),
+    ('🧊', "❄", "="),
+    ('🧪', "⚗", "~"),
+    ('🪑', "▪", "-"),
+    ('⏰', "◷", "!"),
+    ('⏱', "◷", "@"),
+    ('⏳', "⧗", "..."),
+    ('⏭', "»", ">>"),
+    ('⏸', "‖", "||"),
+    ('⚔', "⚔", "x"),
+    ('⚙', "⚙", "*"),
+    ('⚠', "⚠", "!"),
+    ('⚡', "ϟ", "!"),
+    ('✅', "✓", "+"),
+    ('❌', "✗", "x"),
+    ('❔', "?", "?"),
+    ('↩', "↩", "<-"),
+    ('↪', "↪", "->"),
+    // Drawing characters and punctuation, which only ASCII replaces
+    ('─', "─", "-"),
+    ('│', "│", "|"),
+    ('┌', "┌", "+"),
+    ('┐', "┐", "+"),
+    ('└', "└", "+"),
+    ('┘', "┘", "+"),
+    ('├', "├", "+"),
+    ('┤', "┤", "+"),
+    ('┬', "┬", "+"),
+    ('┴', "┴", "+"),
+    ('┼', "┼", "+"),
+    ('●', "●", "*"),
+    ('○', "○", "o"),
+    ('█', "█", "#"),
+    ('▶', "▶", ">"),
+    ('·', "·", "-"),
+    ('—', "—", "-"),
+    ('\u{a0}', "\u{a0}", " "),
+    ('\u{202f}', "\u{202f}", " "),
+    // Letters from the translations
+    ('ä', "ä", "ae"),
+    ('ö', "ö", "oe"),
+    ('ü', "ü", "ue"),
+    ('Ä', "Ä", "Ae"),
+    ('Ö', "Ö", "Oe"),
+    ('Ü', "Ü", "Ue"),
+    ('ß', "ß", "ss"),
+];
+
+/// `text` as the given profile would print it.
+pub fn decorate_as(profile: OutputProfile, text: &str) -> Cow<'_, str> {
+    if profile == OutputProfile::Emoji || text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match GLYPHS.iter().find(|&&(glyph, _, _)| glyph == c) {
+            Some(&(_, unicode, ascii)) => out.push_str(if profile == OutputProfile::Ascii { ascii } else { unicode }),
+            None if c == '\u{fe0f}' => {} // Emoji presentation selector
+            None if profile == OutputProfile::Ascii && !c.is_ascii() => out.push('?'),
+            None => out.push(c),
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// `text` as the current profile prints it.
+pub fn decorate(text: &str) -> Cow<'_, str> {
+    decorate_as(OutputProfile::current(), text)
+}
+
+/// `text` exactly as `out!` would show it: banners in the theme's style,
+/// then decorated for the current profile.
+pub fn finish(text: &str) -> String {
+    if text.contains("--- ") {
+        decorate(&Theme::current().style_banners(text)).into_owned()
+    } else {
+        decorate(text).into_owned()
+    }
+}
+
+/// Backs the crate's `out!` and `outln!`.
+pub fn print_decorated(args: fmt::Arguments, newline: bool) {
+    if json::active() {
+        return; // JSON events replace the usual text
+    }
+    let text = fmt::format(args);
+    let text = if quiet_output() {
+        let stripped = strip_decorations(&text, newline);
+        if newline && stripped.is_empty() {
+            return;
+        }
+        finish(&stripped)
+    } else {
+        finish(&text)
+    };
+    note_printed(&text, newline);
+    let mut stdout = io::stdout().lock();
+    let result = if newline { writeln!(stdout, "{}", text) } else { write!(stdout, "{}", text) };
+    result.expect("failed printing to stdout");
+}
@@ -0,0 +1,172 @@
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::time::{Duration, Instant};
+
+use crate::json::Json;
+
+// --- HTTP ---
+// Just enough HTTP/1.1 for the JSON API that `serve --http` offers: one
+// request per connection, a body only with `Content-Length`, and the
+// connection closed after the answer. Every answer allows any origin, so a
+// web page served from anywhere can call the API.
+
+/// The most a request's line and headers may take up.
+const MAX_HEAD: usize = 16 * 1024;
+
+/// The most a request's body may take up.
+const MAX_BODY: usize = 64 * 1024;
+
+/// How long a client has to send its whole request.
+const READ_WAIT: Duration = Duration::from_secs(5);
+
+/// How long an answer may take to go out.
+const WRITE_WAIT: Duration = Duration::from_secs(5);
+
+/// One request, as read from a connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String, // Without any query string
+    pub headers: Vec<(String, String)>, // Names in lower case
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+
+    /// The token from an `Authorization: Bearer <token>` header.
+    pub fn bearer(&self) -> Option<&str> {
+        let value = self.header("authorization")?;
+        let (scheme, token) = value.split_once(' ')?;
+        scheme.eq_ignore_ascii_case("bearer").then(|| token.trim())
+    }
+}
+
+/// An answer to send back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl Response {
+    pub fn json(status: u16, body: Json) -> Response {
+        Response { status, content_type: "application/json", body: body.to_string() }
+    }
+
+    /// An error, as `{"error": message}`.
+    pub fn error(status: u16, message: &str) -> Response {
+        Response::json(status, Json::Object(vec![("error", message.into())]))
+    }
+
+    /// An answer with nothing in it, as to a preflight request.
+    pub fn empty() -> Response {
+        Response { status: 204, content_type: "text/plain", body: String::new() }
+    }
+}
+
+/// The reason phrase that goes with a status code.
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        409 => "Conflict",
+        413 => "Content Too Large",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
+
+/// Reads one request from `stream`. A request that can't be read gives the
+/// error answer to send back instead.
+pub fn read_request(stream: &mut TcpStream) -> Result<Request, Response> {
+    // The wait is for the whole request, so trickling it in a byte at a time doesn't stretch it
+    let deadline = Instant::now() + READ_WAIT;
+    let too_slow = || Response::error(408, "The request took too long to arrive.");
+    let failed = |e: io::Error| match e.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => too_slow(),
+        _ => Response::error(400, "The request ended early."),
+    };
+    let read = |stream: &mut TcpStream, chunk: &mut [u8]| {
+        let left = deadline.checked_duration_since(Instant::now()).filter(|left| !left.is_zero()).ok_or_else(too_slow)?;
+        stream.set_read_timeout(Some(left)).map_err(|_| Response::error(400, "The connection couldn't be read."))?;
+        stream.read(chunk).map_err(failed)
+    };
+
+    // The head ends at the first blank line; anything read past it is the start of the body
+    let mut data = Vec::new();
+    let mut chunk = [0; 4096];
+    let head_end = loop {
+        if let Some(end) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end;
+        }
+        if data.len() > MAX_HEAD {
+            return Err(Response::error(413, "The request's headers are too long."));
+        }
+        match read(stream, &mut chunk)? {
+            0 => return Err(Response::error(400, "The request ended early.")),
+            n => data.extend_from_slice(&chunk[..n]),
+        }
+    };
+    let head = String::from_utf8_lossy(&data[..head_end]).into_owned();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split(' ');
+    let (Some(method), Some(target), Some(version)) = (request_line.next(), request_line.next(), request_line.next()) else {
+        return Err(Response::error(400, "The request line doesn't read."));
+    };
+    if !version.starts_with("HTTP/1.") {
+        return Err(Response::error(400, "Only HTTP/1.x is spoken here."));
+    }
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+
+    let length = match headers.iter().find(|(name, _)| name == "content-length") {
+        Some((_, value)) => value.parse::<usize>().map_err(|_| Response::error(400, "The Content-Length doesn't read."))?,
+        None => 0,
+    };
+    if length > MAX_BODY {
+        return Err(Response::error(413, "The request's body is too long."));
+    }
+    let mut body = data.split_off(head_end + 4);
+    while body.len() < length {
+        match read(stream, &mut chunk)? {
+            0 => return Err(Response::error(400, "The request ended early.")),
+            n => body.extend_from_slice(&chunk[..n]),
+        }
+    }
+    body.truncate(length);
+
+    Ok(Request {
+        method: method.to_string(),
+        path: target.split('?').next().unwrap_or_default().to_string(),
+        headers,
+        body: String::from_utf8(body).map_err(|_| Response::error(400, "The body isn't UTF-8."))?,
+    })
+}
+
+/// Sends `response` down `stream`, which is then done with.
+pub fn write_response(stream: &mut TcpStream, response: &Response) -> io::Result<()> {
+    stream.set_write_timeout(Some(WRITE_WAIT))?;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\nAccess-Control-Allow-Methods: GET, POST, DELETE, OPTIONS\r\nAccess-Control-Allow-Headers: Authorization, Content-Type\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason(response.status),
+        response.content_type,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
@@ -0,0 +1,344 @@
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::strings::fill;
+
+// --- JSON Output ---
+// With `--output json`, the game prints one JSON object per line for each
+// event of an Individual Secrets game instead of its usual text, so that
+// wrappers and bots can follow or drive a game. Input is read as usual. Each
+// object has an "event" field saying which of these it is:
+//
+//   start     players, seed                      The seats, in order, and the game's seed
+//   turn      round, player, bot                 A player is up
+//   prompt    text                               The game is waiting for a line of input
+//   guess     player, guess, right_place, wrong_place
+//   score     standings: [{player, guesses, best, place}]
+//   pass      round, player, reason              A turn went by without a guess
+//   undo      player, guess                      The guess was taken back
+//   out       round, player                      The guess limit ran out
+//   finish    player, place, guesses             A player cracked their code
+//   rankings  complete, rankings: [{player, place, guesses}]
+//
+// `best` is a {right_place, wrong_place} object or null, and `place` is null
+// for a player who hasn't finished.
+//
+// With `--event-log <path>` the same events (all but `prompt`, which is only
+// for whoever is driving the game) are also appended to a file as the game
+// runs, each with a "time" field in milliseconds since the Unix epoch. The
+// file is only ever added to, so it keeps every game of every run.
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Switches the usual text off and JSON events on for the rest of the run.
+pub fn enable() {
+    ACTIVE.store(true, Ordering::Relaxed);
+}
+
+/// True once `--output json` has been given.
+pub fn active() -> bool {
+    ACTIVE.load(Ordering::Relaxed)
+}
+
+static LOG: Mutex<Option<File>> = Mutex::new(None);
+
+/// Starts appending events to the log at `path`, creating it if needed.
+pub fn open_log(path: &Path) -> io::Result<()> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    *LOG.lock().unwrap() = Some(file);
+    Ok(())
+}
+
+/// A JSON value, enough for the events above.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(u64),
+    Text(String),
+    List(Vec<Json>),
+    Object(Vec<(&'static str, Json)>),
+}
+
+/// Writes `text` as a JSON string, quotes included.
+fn write_string(f: &mut fmt::Formatter<'_>, text: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in text.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(flag) => write!(f, "{}", flag),
+            Json::Number(n) => write!(f, "{}", n),
+            Json::Text(text) => write_string(f, text),
+            Json::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+impl From<&str> for Json {
+    fn from(text: &str) -> Json {
+        Json::Text(text.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(text: String) -> Json {
+        Json::Text(text)
+    }
+}
+
+impl From<bool> for Json {
+    fn from(flag: bool) -> Json {
+        Json::Bool(flag)
+    }
+}
+
+impl From<u8> for Json {
+    fn from(n: u8) -> Json {
+        Json::Number(n.into())
+    }
+}
+
+impl From<u32> for Json {
+    fn from(n: u32) -> Json {
+        Json::Number(n.into())
+    }
+}
+
+impl From<u64> for Json {
+    fn from(n: u64) -> Json {
+        Json::Number(n)
+    }
+}
+
+impl From<usize> for Json {
+    fn from(n: usize) -> Json {
+        Json::Number(n as u64)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Json {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+/// Prints one event line, if JSON output is on, and appends it to the event
+/// log, if there is one. A log that can't be written to is reported once and
+/// then dropped, so the game itself carries on.
+pub fn emit(event: &'static str, fields: Vec<(&'static str, Json)>) {
+    let mut log = LOG.lock().unwrap();
+    if !active() && log.is_none() {
+        return;
+    }
+    let mut fields: Vec<(&'static str, Json)> = std::iter::once(("event", Json::from(event))).chain(fields).collect();
+    if active() {
+        let mut stdout = io::stdout().lock();
+        writeln!(stdout, "{}", Json::Object(fields.clone())).and_then(|()| stdout.flush()).expect("failed printing to stdout");
+    }
+    if let Some(file) = log.as_mut().filter(|_| event != "prompt") {
+        let millis = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64;
+        fields.insert(1, ("time", millis.into()));
+        if let Err(e) = writeln!(file, "{}", Json::Object(fields)).and_then(|()| file.flush()) {
+            eprintln!("{}", fill("json.log_failed", &[("error", &e)]));
+            *log = None;
+        }
+    }
+}
+
+// --- JSON Input ---
+// External bots (see engine.rs) answer in JSON, so there is a reader too. It
+// takes one object per line and no more than those answers need: nested
+// objects are read but come back as null, since `Json` keys are fixed names,
+// and so do numbers that aren't whole and non-negative.
+
+/// Reads `line` as one JSON object, returning its fields in order.
+pub fn parse_object(line: &str) -> Result<Vec<(String, Json)>, String> {
+    let mut reader = Reader { chars: line.chars().collect(), at: 0 };
+    let fields = reader.object()?;
+    if reader.next().is_some() {
+        return Err("something follows the object".to_string());
+    }
+    Ok(fields)
+}
+
+/// Walks through the characters of a line of JSON.
+struct Reader {
+    chars: Vec<char>,
+    at: usize,
+}
+
+impl Reader {
+    /// The next character that isn't white space, without taking it.
+    fn peek(&mut self) -> Option<char> {
+        while self.chars.get(self.at).is_some_and(|c| c.is_whitespace()) {
+            self.at += 1;
+        }
+        self.chars.get(self.at).copied()
+    }
+
+    /// Takes the next character that isn't white space.
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.at += 1;
+        Some(c)
+    }
+
+    fn expect(&mut self, wanted: char) -> Result<(), String> {
+        match self.next() {
+            Some(c) if c == wanted => Ok(()),
+            Some(c) => Err(format!("expected '{}' but found '{}'", wanted, c)),
+            None => Err(format!("expected '{}' but the line ended", wanted)),
+        }
+    }
+
+    fn object(&mut self) -> Result<Vec<(String, Json)>, String> {
+        self.expect('{')?;
+        let mut fields = Vec::new();
+        if self.peek() == Some('}') {
+            self.at += 1;
+            return Ok(fields);
+        }
+        loop {
+            self.expect('"')?;
+            let key = self.string()?;
+            self.expect(':')?;
+            fields.push((key, self.value()?));
+            match self.next() {
+                Some(',') => {}
+                Some('}') => return Ok(fields),
+                _ => return Err("expected ',' or '}' after a field".to_string()),
+            }
+        }
+    }
+
+    fn list(&mut self) -> Result<Vec<Json>, String> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        if self.peek() == Some(']') {
+            self.at += 1;
+            return Ok(items);
+        }
+        loop {
+            items.push(self.value()?);
+            match self.next() {
+                Some(',') => {}
+                Some(']') => return Ok(items),
+                _ => return Err("expected ',' or ']' after an item".to_string()),
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        match self.peek() {
+            Some('{') => self.object().map(|_| Json::Null),
+            Some('[') => self.list().map(Json::List),
+            Some('"') => {
+                self.at += 1;
+                self.string().map(Json::Text)
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                let start = self.at;
+                while self.chars.get(self.at).is_some_and(|c| c.is_ascii_digit() || "+-.eE".contains(*c)) {
+                    self.at += 1;
+                }
+                let number: String = self.chars[start..self.at].iter().collect();
+                Ok(number.parse().map_or(Json::Null, Json::Number))
+            }
+            Some(_) => {
+                for (word, value) in [("true", Json::Bool(true)), ("false", Json::Bool(false)), ("null", Json::Null)] {
+                    if self.chars[self.at..].iter().copied().take(word.len()).eq(word.chars()) {
+                        self.at += word.len();
+                        return Ok(value);
+                    }
+                }
+                Err(format!("unexpected '{}'", self.chars[self.at]))
+            }
+            None => Err("the line ended early".to_string()),
+        }
+    }
+
+    /// Reads the rest of a string whose opening quote has been taken.
+    fn string(&mut self) -> Result<String, String> {
+        let mut text = String::new();
+        loop {
+            let Some(&c) = self.chars.get(self.at) else { return Err("a string isn't closed".to_string()) };
+            self.at += 1;
+            match c {
+                '"' => return Ok(text),
+                '\\' => {
+                    let Some(&escape) = self.chars.get(self.at) else { return Err("a string isn't closed".to_string()) };
+                    self.at += 1;
+                    match escape {
+                        'n' => text.push('\n'),
+                        't' => text.push('\t'),
+                        'r' => text.push('\r'),
+                        'b' => text.push('\u{8}'),
+                        'f' => text.push('\u{c}'),
+                        'u' => {
+                            let high = self.hex_escape()?;
+                            // A character outside the basic plane comes as a pair of escapes
+                            let code = if (0xD800..0xDC00).contains(&high) && self.chars[self.at..].starts_with(&['\\', 'u']) {
+                                self.at += 2;
+                                let low = self.hex_escape()?;
+                                if (0xDC00..0xE000).contains(&low) { 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00) } else { 0xFFFD }
+                            } else {
+                                high
+                            };
+                            text.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                        }
+                        other => text.push(other), // '"', '\\' and '/' stand for themselves
+                    }
+                }
+                c => text.push(c),
+            }
+        }
+    }
+
+    /// Reads the four hex digits of a `\u` escape.
+    fn hex_escape(&mut self) -> Result<u32, String> {
+        let digits: String = self.chars.iter().skip(self.at).take(4).collect();
+        let code = u32::from_str_radix(&digits, 16).ok().filter(|_| digits.len() == 4).ok_or("a \\u escape needs four hex digits")?;
+        self.at += 4;
+        Ok(code)
+    }
+}
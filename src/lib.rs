@@ -0,0 +1,74 @@
+// Everything the game shows goes through the output profile in glyphs.rs,
+// which themes banners, swaps glyphs the terminal can't draw and stays quiet
+// while JSON events are on. `out!` and `outln!` are `print!` and `println!`
+// with that routing; std's own macros write to stdout untouched.
+#[macro_export]
+macro_rules! out {
+    ($($arg:tt)*) => { $crate::glyphs::print_decorated(format_args!($($arg)*), false) };
+}
+
+#[macro_export]
+macro_rules! outln {
+    () => { $crate::glyphs::print_decorated(format_args!(""), true) };
+    ($($arg:tt)*) => { $crate::glyphs::print_decorated(format_args!($($arg)*), true) };
+}
+
+pub mod achievement;
+pub mod analysis;
+pub mod api;
+pub mod assistant;
+pub mod bot;
+pub mod chat;
+pub mod code;
+pub mod coach;
+pub mod config;
+pub mod console;
+pub mod correspondence;
+pub mod crypto;
+pub mod daily;
+pub mod defaults;
+pub mod demo;
+pub mod double_blind;
+pub mod engine;
+pub mod export;
+pub mod feedback;
+pub mod game;
+pub mod glyphs;
+pub mod http;
+pub mod json;
+pub mod lobby;
+pub mod locale;
+pub mod network;
+pub mod odds;
+pub mod practice;
+pub mod profile;
+pub mod protocol;
+pub mod rankings;
+pub mod rating;
+pub mod replay;
+pub mod reverse;
+pub mod room;
+pub mod rules;
+pub mod save;
+pub mod session;
+pub mod simulate;
+pub mod snapshot;
+pub mod solver;
+pub mod sound;
+pub mod spectator;
+#[cfg(feature = "ssh")]
+pub mod ssh;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod stats;
+pub mod store;
+pub mod strings;
+pub mod telnet;
+pub mod terminal;
+pub mod theme;
+pub mod time_attack;
+pub mod tournament;
+pub mod transcript;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod vote;
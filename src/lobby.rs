@@ -0,0 +1,235 @@
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::bot::{Difficulty, Personality};
+use crate::console::prompt;
+use crate::defaults::Defaults;
+use crate::game::{get_personality, get_seat_type};
+use crate::profile::edit_profile;
+use crate::rating::Ratings;
+use crate::strings::{fill, tr};
+
+// --- Game Lobby ---
+// Seats for an Individual Secrets game are set up here before anything is
+// dealt: who plays each seat (a person or a particular bot), what they're
+// called, and any handicap to even out a mixed table.
+
+/// The most seats a game can have.
+pub const MAX_SEATS: usize = 10;
+
+/// Optional adjustments for one seat.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Handicap {
+    pub free_clues: u8, // Random guesses scored for the seat before play begins
+    pub late_start: u8, // Turns the seat sits out at the start
+}
+
+impl Handicap {
+    pub fn describe(self) -> String {
+        let mut parts = Vec::new();
+        if self.free_clues > 0 {
+            parts.push(fill("handicap.free_clues", &[("count", &self.free_clues)]));
+        }
+        if self.late_start > 0 {
+            parts.push(fill("handicap.late_start", &[("count", &self.late_start)]));
+        }
+        if parts.is_empty() { tr("handicap.none").to_string() } else { parts.join(", ") }
+    }
+}
+
+/// One configured seat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Seat {
+    pub name: String,
+    pub bot: Option<(Difficulty, Personality)>, // None for a human
+    pub engine: Option<String>, // The command of an external bot program, which plays instead
+    pub handicap: Handicap,
+}
+
+impl Seat {
+    fn describe_player(&self) -> String {
+        if let Some(command) = &self.engine {
+            return fill("seat.engine", &[("command", command)]);
+        }
+        match self.bot {
+            None => tr("seat.human").to_string(),
+            Some((difficulty, Personality::Standard)) => format!("{} bot", difficulty),
+            Some((difficulty, personality)) => format!("{} bot, {}", difficulty, personality),
+        }
+    }
+}
+
+/// The name a seat gets if none is typed: people get the name the defaults
+/// file gives the seat, and bots are named after their settings.
+fn default_name(bot: Option<(Difficulty, Personality)>, seat_number: usize) -> String {
+    match bot {
+        None => Defaults::current()
+            .names
+            .get(seat_number - 1)
+            .filter(|name| !name.is_empty())
+            .cloned()
+            .unwrap_or_else(|| fill("seat.default_name", &[("number", &seat_number)])),
+        Some((difficulty, Personality::Standard)) => format!("{} Bot {}", difficulty, seat_number),
+        Some((difficulty, personality)) => format!("{} {} Bot {}", personality, difficulty, seat_number),
+    }
+}
+
+/// Asks who plays a seat.
+fn get_player(seat_number: usize) -> Option<(Difficulty, Personality)> {
+    get_seat_type(seat_number).map(|difficulty| (difficulty, get_personality()))
+}
+
+fn get_name(bot: Option<(Difficulty, Personality)>, seat_number: usize) -> String {
+    let default = default_name(bot, seat_number);
+    let name = prompt(&fill("seat.name_prompt", &[("number", &seat_number), ("default", &default)]));
+    if name.is_empty() { default } else { name }
+}
+
+/// Reads a small count, keeping `current` if the answer is blank.
+fn get_count(message: &str, current: u8) -> u8 {
+    loop {
+        match prompt(&fill("seat.count_prompt", &[("message", &message), ("current", &current)])).as_str() {
+            "" => return current,
+            input => match input.parse::<u8>() {
+                Ok(n) if n <= 3 => return n,
+                _ => outln!("{}", tr("seat.count_invalid")),
+            },
+        }
+    }
+}
+
+fn add_seat(seats: &mut Vec<Seat>) {
+    if seats.len() >= MAX_SEATS {
+        outln!("{}", fill("lobby.full", &[("max", &MAX_SEATS)]));
+        prompt(tr("common.continue"));
+        return;
+    }
+    let seat_number = seats.len() + 1;
+    let bot = get_player(seat_number);
+    let name = get_name(bot, seat_number);
+    seats.push(Seat { name, bot, engine: None, handicap: Handicap::default() });
+}
+
+fn edit_seat(seat: &mut Seat, seat_number: usize) {
+    loop {
+        outln!("{}", fill("edit.title", &[("number", &seat_number), ("name", &seat.name)]));
+        outln!("{}", fill("edit.player", &[("player", &seat.describe_player())]));
+        outln!("{}", fill("edit.name", &[("name", &seat.name)]));
+        outln!("{}", fill("edit.free_clues", &[("count", &seat.handicap.free_clues)]));
+        outln!("{}", fill("edit.late_start", &[("count", &seat.handicap.late_start)]));
+        outln!("{}", tr("edit.done"));
+
+        match prompt(tr("edit.prompt")).as_str() {
+            "1" => {
+                // A bot that still has its default name is renamed to match its new settings
+                let had_default_name = seat.name == default_name(seat.bot, seat_number);
+                seat.bot = get_player(seat_number);
+                seat.engine = None;
+                if had_default_name {
+                    seat.name = default_name(seat.bot, seat_number);
+                }
+            }
+            "2" => seat.name = get_name(seat.bot, seat_number),
+            "3" => seat.handicap.free_clues = get_count(tr("edit.free_clues_prompt"), seat.handicap.free_clues),
+            "4" => seat.handicap.late_start = get_count(tr("edit.late_start_prompt"), seat.handicap.late_start),
+            "5" => return,
+            _ => {}
+        }
+    }
+}
+
+/// Parses a lobby command such as "e 2" into its letter and seat index.
+fn parse_seat_command(input: &str, seats: usize) -> Option<(char, Option<usize>)> {
+    let mut parts = input.split_whitespace();
+    let letter = parts.next()?.chars().next()?.to_ascii_lowercase();
+    let index = match parts.next() {
+        Some(n) => Some(n.parse::<usize>().ok().filter(|&n| (1..=seats).contains(&n))? - 1),
+        None => None,
+    };
+    Some((letter, index))
+}
+
+fn print_lobby(seats: &[Seat], ratings: &Ratings) {
+    outln!("{}", tr("lobby.title"));
+    if seats.is_empty() {
+        outln!("{}", tr("lobby.empty"));
+    }
+    // Players with rated games so far have their rating next to their name
+    let names: Vec<String> = seats
+        .iter()
+        .map(|s| match ratings.rounded(&s.name) {
+            Some(rating) => fill("lobby.rated", &[("name", &s.name), ("rating", &rating)]),
+            None => s.name.clone(),
+        })
+        .collect();
+    let width = names.iter().map(|name| name.chars().count()).max().unwrap_or(0).max(4);
+    let player_width = seats.iter().map(|s| s.describe_player().chars().count()).max().unwrap_or(0).max(6);
+    for (i, (seat, name)) in seats.iter().zip(&names).enumerate() {
+        outln!("  {:>2}. {:<width$}  {:<player_width$}  {}",
+                 i + 1, name, seat.describe_player(), fill("lobby.handicap", &[("handicap", &seat.handicap.describe())]),
+                 width = width, player_width = player_width);
+    }
+    outln!("{}", tr("lobby.commands"));
+}
+
+/// Seats set up on the command line, which stand in for the lobby once.
+static PRESET: Mutex<Option<Vec<Seat>>> = Mutex::new(None);
+
+/// Human seats for `names`, with the default name wherever one is blank.
+pub fn human_seats(names: &[String]) -> Vec<Seat> {
+    names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| Seat { name: if name.is_empty() { default_name(None, i + 1) } else { name.clone() }, bot: None, engine: None, handicap: Handicap::default() })
+        .collect()
+}
+
+/// A seat played by the external bot program `command`. It's named after the
+/// script the command runs, if it names one (`python3 bots/smart.py` is
+/// "smart"), or else after the program.
+pub fn engine_seat(command: &str) -> Seat {
+    let words: Vec<&str> = command.split_whitespace().collect();
+    let script = words.iter().skip(1).rev().find(|word| Path::new(word).is_file()).or(words.first()).copied().unwrap_or(command);
+    let name = Path::new(script).file_stem().map_or_else(|| script.to_string(), |stem| stem.to_string_lossy().into_owned());
+    Seat { name, bot: None, engine: Some(command.to_string()), handicap: Handicap::default() }
+}
+
+/// Makes the next game start with `seats` instead of opening the lobby.
+pub fn preset_seats(seats: Vec<Seat>) {
+    *PRESET.lock().unwrap() = Some(seats);
+}
+
+/// The seats given with `preset_seats`, if no game has used them yet.
+pub fn take_preset_seats() -> Option<Vec<Seat>> {
+    PRESET.lock().unwrap().take()
+}
+
+/// Runs the lobby until the players start the game, returning the seats in
+/// order. `ratings` are shown next to the names.
+pub fn run_lobby(ratings: &Ratings) -> Vec<Seat> {
+    let mut seats: Vec<Seat> = Vec::new();
+    add_seat(&mut seats);
+
+    loop {
+        print_lobby(&seats, ratings);
+        let input = prompt(tr("lobby.prompt"));
+        match parse_seat_command(&input, seats.len()) {
+            Some(('a', None)) => add_seat(&mut seats),
+            Some(('e', Some(i))) => edit_seat(&mut seats[i], i + 1),
+            Some(('r', Some(i))) => {
+                seats.remove(i);
+            }
+            Some(('p', Some(i))) if seats[i].bot.is_none() && seats[i].engine.is_none() => edit_profile(&seats[i].name),
+            Some(('p', Some(_))) => {
+                prompt(tr("lobby.bot_profile"));
+            }
+            Some(('s', None)) if !seats.is_empty() => return seats,
+            Some(('s', None)) => {
+                prompt(tr("lobby.no_seats"));
+            }
+            _ => {
+                prompt(tr("lobby.unknown"));
+            }
+        }
+    }
+}
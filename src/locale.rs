@@ -0,0 +1,167 @@
+use std::sync::RwLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// --- Locale Selection ---
+// All counts, durations, and timestamps shown in reports go through these
+// helpers so the output follows one locale instead of whatever `Display`
+// happens to print. `Iso` is meant for machine-readable output.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    En,  // English: 12,345 | 10/14/2026 | 1st
+    De,  // German: 12.345 | 14.10.2026 | 1.
+    Fr,  // French: 12 345 | 14/10/2026 | 1er
+    Iso, // Machine output: 12345 | 2026-10-14T09:30:00Z | PT1M5S
+}
+
+static CURRENT: RwLock<Option<Locale>> = RwLock::new(None);
+
+impl Locale {
+    /// Parses a locale tag such as "de", "fr_FR.UTF-8", or "iso".
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let lang = tag.split(['_', '-', '.', '@']).next().unwrap_or("").to_ascii_lowercase();
+        match lang.as_str() {
+            "en" | "c" | "posix" => Some(Locale::En),
+            "de" => Some(Locale::De),
+            "fr" => Some(Locale::Fr),
+            "iso" => Some(Locale::Iso),
+            _ => None,
+        }
+    }
+
+    /// Picks a locale from the usual POSIX environment variables, defaulting to English.
+    pub fn from_env() -> Locale {
+        ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Locale::from_tag(&value))
+            .unwrap_or(Locale::En)
+    }
+
+    /// The locale used by all reporting code.
+    pub fn current() -> Locale {
+        if let Some(locale) = *CURRENT.read().unwrap() {
+            return locale;
+        }
+        let locale = Locale::from_env();
+        *CURRENT.write().unwrap() = Some(locale);
+        locale
+    }
+
+    /// Overrides the locale chosen from the environment.
+    pub fn set_current(locale: Locale) {
+        *CURRENT.write().unwrap() = Some(locale);
+    }
+
+    // --- Numbers ---
+
+    /// Formats a count with the locale's thousands separator.
+    pub fn count(self, n: u64) -> String {
+        let separator = match self {
+            Locale::En => ",",
+            Locale::De => ".",
+            Locale::Fr => "\u{202f}", // Narrow no-break space
+            Locale::Iso => "",
+        };
+
+        let digits = n.to_string();
+        let mut out = String::new();
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                out.push_str(separator);
+            }
+            out.push(c);
+        }
+        out
+    }
+
+    /// Formats a decimal number (e.g. an average) with a fixed number of places.
+    pub fn decimal(self, value: f64, places: usize) -> String {
+        let text = format!("{:.*}", places, value.abs());
+        let (whole, fraction) = text.split_once('.').unwrap_or((&text, ""));
+        let whole = self.count(whole.parse().unwrap_or(0));
+        let sign = if value < 0.0 && text.chars().any(|c| c.is_ascii_digit() && c != '0') { "-" } else { "" };
+
+        if fraction.is_empty() {
+            return format!("{}{}", sign, whole);
+        }
+        let point = match self {
+            Locale::De | Locale::Fr => ",",
+            Locale::En | Locale::Iso => ".",
+        };
+        format!("{}{}{}{}", sign, whole, point, fraction)
+    }
+
+    /// Formats a finishing position ("1st", "1.", "1er").
+    pub fn ordinal(self, n: usize) -> String {
+        match self {
+            Locale::En => {
+                let suffix = match (n % 10, n % 100) {
+                    (_, 11..=13) => "th",
+                    (1, _) => "st",
+                    (2, _) => "nd",
+                    (3, _) => "rd",
+                    _ => "th",
+                };
+                format!("{}{}", n, suffix)
+            }
+            Locale::De => format!("{}.", n),
+            Locale::Fr => if n == 1 { "1er".to_string() } else { format!("{}e", n) },
+            Locale::Iso => n.to_string(),
+        }
+    }
+
+    // --- Time ---
+
+    /// Formats an elapsed time: "1 h 02 min 05 s" for people, "PT1H2M5S" for machines.
+    pub fn duration(self, d: Duration) -> String {
+        let total = d.as_secs();
+        let (hours, minutes, seconds) = (total / 3600, (total / 60) % 60, total % 60);
+
+        if self == Locale::Iso {
+            let mut out = String::from("PT");
+            if hours > 0 { out.push_str(&format!("{}H", hours)); }
+            if minutes > 0 { out.push_str(&format!("{}M", minutes)); }
+            if seconds > 0 || total == 0 { out.push_str(&format!("{}S", seconds)); }
+            return out;
+        }
+
+        if hours > 0 {
+            format!("{} h {:02} min {:02} s", hours, minutes, seconds)
+        } else if minutes > 0 {
+            format!("{} min {:02} s", minutes, seconds)
+        } else {
+            format!("{} s", seconds)
+        }
+    }
+
+    /// Formats a point in time (in UTC, since std has no time zone database).
+    pub fn timestamp(self, time: SystemTime) -> String {
+        let secs = time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+        let (hour, minute, second) = ((secs / 3600) % 24, (secs / 60) % 60, secs % 60);
+
+        match self {
+            Locale::En => format!("{:02}/{:02}/{} {:02}:{:02} UTC", month, day, year, hour, minute),
+            Locale::De => format!("{:02}.{:02}.{} {:02}:{:02} UTC", day, month, year, hour, minute),
+            Locale::Fr => format!("{:02}/{:02}/{} {:02}:{:02} UTC", day, month, year, hour, minute),
+            Locale::Iso => format!("{}-{:02}-{:02}T{:02}:{:02}:{:02}Z", year, month, day, hour, minute, second),
+        }
+    }
+}
+
+/// Converts days since 1970-01-01 into a (year, month, day) civil date.
+/// This is Howard Hinnant's `civil_from_days` algorithm.
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
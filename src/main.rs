@@ -1,440 +1,566 @@
-use std::io::{self, Write};
-use rand::seq::SliceRandom;
-use rand::{thread_rng, Rng}; 
-use std::{thread, time::Duration};
-
-// --- Type Definitions ---
-type Guess = [u8; 4];
-// Score is internally represented as (Digits at Correct Position, Digits Correct but Wrong Position)
-type Score = (u8, u8); 
-
-// Player struct now holds their unique secret code
-#[derive(Debug)] // Required for debugging/printing complex structs
-struct Player {
-    name: String,
-    secret_code: Guess, // Each player has their own secret
-    rank: Option<usize>, // Stores the player's finishing position (1st, 2nd, etc.)
+use std::time::Duration;
+
+use clap::builder::PossibleValuesParser;
+use clap::{ArgGroup, Args, Parser, Subcommand};
+use guessing_game::code::{fix_code_length, set_code_length, set_seed};
+use guessing_game::console::{clear_screen, guest_session, pause, prompt, set_guest_session, set_pause_length, set_plain_output, set_quiet_output};
+use guessing_game::defaults::Defaults;
+use guessing_game::game::{set_debug_secrets, DebugSecrets};
+use guessing_game::glyphs::OutputProfile;
+use std::path::PathBuf;
+
+use guessing_game::session::{self, Session};
+use guessing_game::lobby::{engine_seat, human_seats, preset_seats, MAX_SEATS};
+use guessing_game::outln;
+use guessing_game::room::unique_name;
+use guessing_game::rules::SUPPORTED_CODE_LENGTHS;
+use guessing_game::save::{self, SavedGame};
+use guessing_game::store::StoreSet;
+use guessing_game::strings::{fill, tr, Language};
+use guessing_game::terminal::interactive;
+use guessing_game::theme::Theme;
+use guessing_game::{analysis, api, assistant, chat, config, correspondence, daily, demo, double_blind, export, game, json, network, practice, profile, replay, reverse, simulate, spectator, stats, telnet, time_attack, tournament};
+
+/// The modes `--mode` can start, with their choice in the main menu.
+const MODES: &[(&str, &str)] = &[
+    ("individual", "1"),
+    ("double-blind", "2"),
+    ("chat", "3"),
+    ("tournament", "4"),
+    ("daily", "5"),
+    ("time-attack", "6"),
+    ("practice", "7"),
+    ("watch", "8"),
+    ("reverse", "9"),
+];
+
+/// Runs the mode behind a main-menu choice. Returns false if the choice
+/// wasn't a mode (the stats and settings screens return to the menu).
+fn run_choice(choice: &str, stores: &mut StoreSet, session: &mut Session) -> bool {
+    match choice {
+        "1" => game::run_game(stores, session),
+        "2" => double_blind::run_double_blind(session),
+        "3" => chat::run_chat_mode(session),
+        "4" => tournament::run_tournament(session),
+        "5" => daily::run_daily(stores, session),
+        "6" => time_attack::run_time_attack(session),
+        "7" => practice::run_practice(session),
+        "8" => demo::run_watch(),
+        "9" => reverse::run_reverse(session),
+        // A guest may look at the stats, but the server's files are the operator's to manage
+        "10" if guest_session() => {
+            stats::print_stats(stores);
+            prompt(tr("common.continue"));
+            return false;
+        }
+        "11" if guest_session() => {
+            outln!("{}", tr("guest.unavailable"));
+            pause(Duration::from_secs(1));
+            return false;
+        }
+        "10" => {
+            stats::stats_menu(stores);
+            return false;
+        }
+        "11" => {
+            config::settings_menu();
+            return false;
+        }
+        _ => {
+            outln!("{}", tr("menu.invalid"));
+            pause(Duration::from_secs(1));
+            return false;
+        }
+    }
+    true
 }
 
-// --- Core Logic ---
-
-/// Generates a single 4-digit number with non-repeating digits.
-/// The first digit is allowed to be 0.
-fn generate_secret() -> Guess {
-    let mut digits: Vec<u8> = (0..=9).collect();
-    let mut rng = thread_rng();
-
-    // Shuffle the digits
-    digits.shuffle(&mut rng);
+/// Asks which mode to play and runs it.
+fn select_and_run_mode(stores: &mut StoreSet, session: &mut Session) {
+    loop {
+        clear_screen();
+        for id in [
+            "menu.title",
+            "menu.individual",
+            "menu.double_blind",
+            "menu.chat",
+            "menu.tournament",
+            "menu.daily",
+            "menu.time_attack",
+            "menu.practice",
+            "menu.watch",
+            "menu.reverse",
+        ] {
+            outln!("{}", tr(id));
+        }
+        let menu_prompt = if guest_session() {
+            outln!("{}", tr("menu.stats_view"));
+            tr("menu.prompt_guest")
+        } else {
+            outln!("{}", tr("menu.stats"));
+            outln!("{}", tr("menu.settings"));
+            tr("menu.prompt")
+        };
 
-    // Take the first four unique digits. Since they are shuffled, they are non-repeating.
-    [digits[0], digits[1], digits[2], digits[3]]
+        if run_choice(&prompt(menu_prompt), stores, session) {
+            return;
+        }
+    }
 }
 
+/// The options that apply whatever the command, given before or after it.
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Global options")]
+struct Options {
+    /// Save games with several players to this shared stats file [env: SGG_CLUB_STORE]
+    #[arg(long, global = true, value_name = "PATH")]
+    club: Option<PathBuf>,
+    /// Plain text, without escape codes
+    #[arg(long, global = true)]
+    no_ansi: bool,
+    /// Run as a guest, as the servers' sessions do
+    #[arg(long, global = true, hide = true)]
+    guest: bool,
+    /// Print text, or one JSON event per line
+    #[arg(long, global = true, value_name = "FORMAT", value_parser = ["text", "json"])]
+    output: Option<String>,
+    /// The language of prompts and messages, such as en or de
+    #[arg(long, global = true, value_name = "TAG")]
+    lang: Option<String>,
+    /// Leave out banners, boards and pauses
+    #[arg(long, global = true)]
+    quiet: bool,
+    /// Show each player's secret at the start of their turn
+    #[arg(long, global = true)]
+    debug_secrets: bool,
+    /// Let --debug-secrets show secrets with more than one person playing
+    #[arg(long, global = true)]
+    i_know_what_im_doing: bool,
+    /// The colour theme for this run, such as bright
+    #[arg(long, global = true, value_name = "NAME")]
+    theme: Option<String>,
+    /// Publish Individual Secrets games to this file for `spectate`
+    #[arg(long, global = true, value_name = "PATH")]
+    feed: Option<PathBuf>,
+    /// Append every engine event to this file as a JSON line
+    #[arg(long, global = true, value_name = "PATH")]
+    event_log: Option<PathBuf>,
+}
 
-/// Calculates the core matching score.
-/// Returns (Digits at Correct Position [Y], Digits Correct but Wrong Position).
-fn calculate_score(guess: &Guess, secret: &Guess) -> Score {
-    let mut correct_position = 0; // Digits at Correct Position (Y)
-    let mut total_correct_digits = 0; // Total Correct Digits (X)
+/// The options that set a game up without questions.
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Game options")]
+struct GameOptions {
+    /// Start an Individual Secrets game with this many human players
+    #[arg(long, global = true, value_name = "N", value_parser = clap::value_parser!(u8).range(1..=MAX_SEATS as i64))]
+    players: Option<u8>,
+    /// Start an Individual Secrets game with these players
+    #[arg(long, global = true, value_name = "A,B,C", value_delimiter = ',')]
+    names: Vec<String>,
+    /// Add a seat played by this bot program (more than once for more seats)
+    #[arg(long, global = true, value_name = "COMMAND")]
+    engine: Vec<String>,
+    /// Start this mode instead of showing the main menu
+    #[arg(long, global = true, value_name = "NAME", ignore_case = true, value_parser = PossibleValuesParser::new(MODES.iter().map(|(id, _)| *id)))]
+    mode: Option<String>,
+    /// Digits in a code
+    #[arg(long, global = true, value_name = "N", value_parser = parse_length)]
+    length: Option<usize>,
+    /// Seconds to pause after a bot's move and similar moments
+    #[arg(long, global = true, value_name = "SECONDS", value_parser = parse_delay)]
+    delay: Option<Duration>,
+    /// Deal the same secrets and draws as another run with this seed
+    #[arg(long, global = true, value_name = "NUMBER")]
+    seed: Option<u64>,
+}
 
-    // Use a frequency map for quick checking of digits present in the secret
-    let mut secret_counts: [bool; 10] = [false; 10];
-    for &digit in secret.iter() {
-        secret_counts[digit as usize] = true;
+/// Reads `--length`, which has to be a code length the game supports.
+fn parse_length(text: &str) -> Result<usize, String> {
+    match text.parse() {
+        Ok(n) if SUPPORTED_CODE_LENGTHS.contains(&n) => Ok(n),
+        _ => Err(format!("the number of digits must be from {} to {}", SUPPORTED_CODE_LENGTHS.start(), SUPPORTED_CODE_LENGTHS.end())),
     }
+}
 
-    for i in 0..4 {
-        let g_digit = guess[i] as usize;
-        let s_digit = secret[i] as usize;
-
-        // Check for Digits at Correct Position (Y)
-        if g_digit == s_digit {
-            correct_position += 1;
-        }
-
-        // Check for Total Matches (X)
-        if secret_counts[g_digit] {
-            total_correct_digits += 1;
-        }
+/// Reads `--delay`, a number of seconds such as 0 or 0.5.
+fn parse_delay(text: &str) -> Result<Duration, String> {
+    match text.parse::<f64>() {
+        Ok(seconds) if seconds >= 0.0 && seconds.is_finite() => Ok(Duration::from_secs_f64(seconds)),
+        _ => Err("the delay must be a number of seconds, such as 0 or 0.5".to_string()),
     }
+}
 
-    // Digits Correct but Wrong Position = Total Correct (X) - Correct Position (Y)
-    let correct_wrong_position = total_correct_digits - correct_position;
-
-    (correct_position, correct_wrong_position)
+/// What `stats` shows, besides the stats themselves.
+#[derive(Debug, Subcommand)]
+enum StatsCommand {
+    /// Show the leaderboard
+    Leaderboard,
+    /// Write the history as CSV files for a spreadsheet
+    Export(export::ExportArgs),
 }
 
+/// What `config` does.
+#[derive(Debug, Subcommand)]
+enum ConfigCommand {
+    /// Write a commented defaults file
+    Init {
+        /// Replace a defaults file that's already there
+        #[arg(long)]
+        force: bool,
+    },
+}
 
-// --- User Input Helpers ---
+/// Which server `serve` runs.
+#[derive(Debug, Args)]
+#[command(group(ArgGroup::new("server").required(true).args(["http", "telnet", "ssh"])))]
+struct ServeArgs {
+    /// Answer JSON requests from web and mobile frontends
+    #[arg(long)]
+    http: bool,
+    /// Take plain text connections from `nc` or telnet
+    #[arg(long)]
+    telnet: bool,
+    /// Let players in over SSH (builds with the ssh feature)
+    #[arg(long)]
+    ssh: bool,
+    /// The port to listen on, instead of the server's usual one
+    #[arg(long)]
+    port: Option<u16>,
+}
 
-/// Clears the console screen using common ANSI escape codes.
-fn clear_screen() {
-    // ANSI escape code for clearing the screen and moving cursor to home position
-    print!("\x1b[2J\x1b[H"); 
-    io::stdout().flush().unwrap();
+/// The subcommands. Without one the game is played, as it always was.
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Play through the menus. This is what runs without a command.
+    Play {
+        /// Carry on an Individual Secrets game saved with `/save`
+        #[arg(long, value_name = "NAME OR PATH")]
+        resume: Option<String>,
+    },
+    /// Get the solver's suggestions for a game played somewhere else.
+    Solve(assistant::SolveArgs),
+    /// Benchmark the solver strategies against random secrets.
+    Simulate(simulate::SimulateArgs),
+    /// Show the saved stats, the leaderboard, or write them to CSV files.
+    Stats {
+        #[command(subcommand)]
+        view: Option<StatsCommand>,
+    },
+    /// Study how a solver strategy cracks codes.
+    Analyze(analysis::AnalyzeArgs),
+    /// Play today's Daily Challenge.
+    Daily,
+    /// Watch bots play until a key is pressed, then carry on to the menus.
+    Demo,
+    /// Follow a game that's published with --feed.
+    Spectate(spectator::SpectateArgs),
+    /// Watch a recorded game again, or list the recordings.
+    Replay(replay::ReplayArgs),
+    /// Take your turn in a game played by passing a file round, or start one.
+    Turn(correspondence::TurnArgs),
+    /// Host an Individual Secrets game for players on other machines.
+    Host(network::HostArgs),
+    /// Take a seat in a game hosted with `host` (or just watch it), or find one on the local network.
+    Join(network::JoinArgs),
+    /// Run Individual Secrets games for web and mobile frontends, telnet or SSH.
+    Serve(ServeArgs),
+    /// Move a player's profile and results to another machine.
+    Profile {
+        #[command(subcommand)]
+        command: profile::ProfileCommand,
+    },
+    /// Play Individual Secrets full screen (builds with the tui feature).
+    Tui,
+    /// Write a commented defaults file.
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
 }
 
-/// Reads input from the user.
-fn read_line() -> String {
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).expect("Failed to read line");
-    input.trim().to_string()
+#[derive(Debug, Parser)]
+#[command(name = "MultiplayerGuessingGame", about = "A turn-based code guessing game for the terminal.", after_help = "The options are described in the README.")]
+struct Cli {
+    #[command(flatten)]
+    options: Options,
+    #[command(flatten)]
+    game: GameOptions,
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
-/// Gets a valid integer input for player count.
-fn get_player_count() -> u8 {
-    loop {
-        print!("Enter the number of players (1 to 10): ");
-        io::stdout().flush().unwrap();
-        match read_line().parse::<u8>() {
-            Ok(n) if n >= 1 && n <= 10 => return n,
-            _ => println!("Please enter a number between 1 and 10."),
-        }
+/// The `--club` path, falling back to the `SGG_CLUB_STORE` environment variable.
+fn club_path(options: &Options) -> Option<PathBuf> {
+    if let Some(path) = &options.club {
+        return Some(path.clone());
     }
+    std::env::var_os("SGG_CLUB_STORE").filter(|p| !p.is_empty()).map(PathBuf::from)
 }
 
-/// Gets a valid 4-digit, non-repeating number guess.
-fn get_player_guess(player_name: &str) -> Option<Guess> {
-    loop {
-        print!("{}, enter your 4-digit guess: ", player_name);
-        io::stdout().flush().unwrap();
-        let input = read_line();
-
-        if input.len() != 4 {
-            println!("Guess must be exactly 4 digits.");
-            continue;
-        }
-
-        let mut digits: Vec<u8> = Vec::new();
-        let mut seen_digits = [false; 10];
-        let mut valid = true;
-
-        for (_i, c) in input.chars().enumerate() {
-            match c.to_digit(10) {
-                Some(d) => {
-                    let d_u8 = d as u8;
-                    // Check for repetition
-                    if seen_digits[d_u8 as usize] {
-                        println!("Digits must not be repeated.");
-                        valid = false;
-                        break;
-                    }
-                    seen_digits[d_u8 as usize] = true;
-                    digits.push(d_u8);
-                },
-                None => {
-                    println!("Input contains non-digit characters.");
-                    valid = false;
-                    break;
-                }
-            }
-        }
-
-        if valid {
-            return Some([digits[0], digits[1], digits[2], digits[3]]);
-        }
+/// Applies `--lang <tag>`: the language of prompts and messages, and the
+/// locale numbers and dates are written in, where there is one for the tag.
+fn apply_language(options: &Options) {
+    let Some(tag) = &options.lang else { return };
+    if !Language::select(tag) {
+        usage_error(&format!("Unknown language '{}'. Available languages: en, de, fr (French number formats with English messages)", tag));
     }
 }
 
-/// Prompts the user to select a starting player index (1-based) or 0 for random.
-fn get_starting_player_index(players: &Vec<Player>) -> usize {
-    let max_index = players.len();
-    loop {
-        println!("\n--- Select Starting Player ---");
-        // Print player options (1-based index)
-        for (i, player) in players.iter().enumerate() {
-            println!("  [{}] {}", i + 1, player.name);
-        }
-        println!("  [0] Random selection");
-        print!("Enter selection (0, 1, 2, ...): ");
-        io::stdout().flush().unwrap();
-        
-        let input = read_line();
-        match input.parse::<usize>() {
-            Ok(0) => {
-                let mut rng = thread_rng();
-                let random_index = rng.gen_range(0..max_index);
-                println!("Randomly selected {} to start!", players[random_index].name);
-                return random_index;
-            }
-            Ok(n) if n >= 1 && n <= max_index => {
-                let start_index = n - 1; // Convert 1-based to 0-based
-                println!("Starting player is {}.", players[start_index].name);
-                return start_index;
-            }
-            _ => {
-                println!("Invalid selection. Please enter 0 for random, or a number corresponding to a player.");
-            }
+/// Applies the defaults file, before the command-line options that override it.
+fn apply_defaults() {
+    let (defaults, mut problems) = Defaults::load();
+    if let Some(tag) = &defaults.language {
+        Language::select(tag);
+    }
+    if let Some(name) = &defaults.theme {
+        let config = config::Config::current();
+        if Theme::by_name(&config, name).is_some() {
+            Theme::set_current(name);
+        } else {
+            problems.push(fill("config.default_theme_missing", &[("name", name)]));
         }
     }
+    if let Some(length) = defaults.code_length {
+        set_code_length(length);
+    }
+    if let Some(delay) = defaults.delay {
+        set_pause_length(delay);
+    }
+    if let Some(output) = defaults.output {
+        OutputProfile::set_current(output);
+    }
+    if let Some(no_ansi) = defaults.no_ansi {
+        set_plain_output(no_ansi);
+    }
+    for problem in problems {
+        outln!("{}", problem);
+    }
+    Defaults::set_current(defaults);
 }
 
-/// Displays the post-game menu and handles the winner/game state.
-/// Returns true if the game should continue, false to quit or restart.
-fn post_game_menu(
-    players: &mut Vec<Player>, 
-    winner_index: usize, 
-    rank_to_assign: usize,
-    completed_players: &mut Vec<Player>
-) -> bool {
-    
-    loop {
-        println!("\n--- Post-Game Menu ---");
-        // Check if we're playing for the LAST spot.
-        if players.len() == 1 {
-            println!("[1] Finish Game: Assign {} rank and view final menu.", players[winner_index].name);
-        } else {
-            println!("[1] Continue: Remove {} and play for next place.", players[winner_index].name);
-        }
-        
-        println!("[2] Restart: Start a new game with current players.");
-        println!("[3] Quit: Exit the program.");
-        print!("Enter your choice (1, 2, or 3): ");
-        io::stdout().flush().unwrap();
+/// Runs `config init [--force]`, which writes a commented defaults file.
+fn run_config(command: &ConfigCommand) -> Result<(), String> {
+    let ConfigCommand::Init { force } = command;
+    let path = Defaults::init(*force).map_err(|e| fill("config.write_failed", &[("error", &e)]))?;
+    outln!("{}", fill("config.written", &[("path", &path.display())]));
+    Ok(())
+}
 
-        match read_line().trim() {
-            "1" => {
-                // Assign the final rank before removing the player
-                if let Some(player) = players.get_mut(winner_index) {
-                    player.rank = Some(rank_to_assign);
-                }
-                
-                let winning_player = players.remove(winner_index);
-                println!("Removed {} (Rank {}) from active play.", 
-                         winning_player.name, 
-                         winning_player.rank.unwrap_or(rank_to_assign)
-                );
-                
-                // Move the ranked player to the completed list
-                completed_players.push(winning_player);
-                
-                // Only return false (end game) if the vector is now empty.
-                if players.is_empty() {
-                    return false; // Signal run_game to break the loop
-                }
-                return true; // Continue to the next round of the game loop
-            }
-            "2" => return false, // Signal main to break and restart the whole main function process
-            "3" => {
-                println!("Thank thank you for playing! Goodbye.");
-                std::process::exit(0); // Explicitly exit the program
-            }
-            _ => {
-                println!("Invalid input. Please enter 1, 2, or 3.");
-                thread::sleep(Duration::from_secs(1));
-                clear_screen();
-            }
-        }
+/// Applies `--theme <name>`: the colour theme for this run, whatever the settings say.
+fn apply_theme(options: &Options) {
+    let Some(name) = &options.theme else { return };
+    let config = config::Config::current();
+    if Theme::by_name(&config, name).is_none() {
+        let names: Vec<String> = Theme::all(&config).into_iter().map(|t| t.name).collect();
+        eprintln!("{}", fill("config.unknown_theme", &[("name", name), ("names", &names.join(", "))]));
+        std::process::exit(2);
     }
+    Theme::set_current(name);
 }
 
-/// Encapsulates the entire game setup and main loop logic for easy restart.
-fn run_game() {
-    clear_screen();
-    println!("--- 🎲 Multiplayer Code Guessing Game (Individual Secrets) ---");
-    println!("Each player has a unique, hidden 4-digit code (non-repeating digits, can start with 0).");
-    println!("Players take turns guessing their own secret. First to guess wins!");
-    
-    // 1. Setup Players and Assign Individual Secrets
-    let num_players_u8 = get_player_count();
-    let num_players = num_players_u8 as usize;
-    let mut players: Vec<Player> = Vec::new();
-
-    for i in 0..num_players_u8 {
-        print!("Enter name for Player {}: ", i + 1);
-        io::stdout().flush().unwrap();
-        let name = read_line();
-        
-        // Generate a unique secret for this player
-        let secret_code = generate_secret();
-        
-        // *** DEBUGGING PRINT ***
-        //println!("DEBUG: {}'s Secret Code is: {}{}{}{}", 
-                 name, secret_code[0], secret_code[1], secret_code[2], secret_code[3]);
-        
-        players.push(Player { name, secret_code, rank: None });
-    }
-
-    println!("\nAll secret codes have been generated. Let the guessing begin!");
-
-    // 2. Determine Starting Player Index
-    let mut current_player_index = get_starting_player_index(&players);
-
-    // *** CLEAR SCREEN ***
-    clear_screen();
-
-    // 3. Game Loop Variables
-    let mut round_number: u32 = 1; // Tracks full cycles (rounds)
-    let mut total_guesses: u32 = 0; // Tracks total guesses across all rounds
-    
-    // RANKING VARIABLES (For round-based tie ranking)
-    let mut rank_to_assign: usize = 1; // The rank for the next *distinct* finisher (1st, 2nd, 3rd...)
-    let mut last_assigned_round: u32 = 0; // The round number the most recent rank was achieved in.
-    
-    // List to hold players who have finished the game
-    let mut completed_players: Vec<Player> = Vec::new();
+/// Applies `--feed <path>`: Individual Secrets games publish their public
+/// progress there for `spectate` to show.
+fn open_spectator_feed(options: &Options) {
+    let Some(path) = &options.feed else { return };
+    if let Err(e) = spectator::open_feed(path) {
+        eprintln!("{}", fill("spectate.open_failed", &[("path", &path.display()), ("error", &e)]));
+        std::process::exit(2);
+    }
+}
 
-    loop {
-        // Handle final player finishing the game
-        if players.is_empty() {
-             println!("\nAll players have finished the game. Thanks for playing!");
-             break;
-        }
-        
-        // Handle the last remaining player (auto-assignment of final rank)
-        if players.len() == 1 && players[0].rank.is_none() {
-            let last_player_index = 0;
-            // The last player automatically gets the current distinct rank
-            players[last_player_index].rank = Some(rank_to_assign);
-            println!("\n--- Final Player Ranked ---");
-            println!("{} is automatically assigned {} place.", 
-                     players[last_player_index].name, rank_to_assign);
-
-            // Move the last player to the completed list and break
-            completed_players.extend(players.drain(..)); 
-            break;
-        }
-        
-        // Ensure the current_player_index is valid after a removal
-        if current_player_index >= players.len() {
-            current_player_index = 0;
-        }
+/// Applies `--event-log <path>`: every engine event is appended there as a JSON line.
+fn open_event_log(options: &Options) {
+    let Some(path) = &options.event_log else { return };
+    if let Err(e) = json::open_log(path) {
+        eprintln!("{}", fill("config.event_log_failed", &[("path", &path.display()), ("error", &e)]));
+        std::process::exit(2);
+    }
+}
 
-        let current_player = &players[current_player_index];
-        
-        total_guesses += 1; // Increment guess counter first
-
-        println!("\n======================================");
-        println!("ROUND {} | {}'s Guess", round_number, current_player.name);
-        println!("======================================");
-        
-        let guess = match get_player_guess(&current_player.name) {
-            Some(g) => g,
-            None => { 
-                current_player_index = (current_player_index + 1) % players.len();
-                continue; // Skip turn if input fails validation
-            },
-        };
+/// Exits with a command-line error, as every option does.
+fn usage_error(message: &str) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(2);
+}
 
-        // 4. Score and Feedback: Use the current player's unique secret code
-        let (y_score, c_score) = calculate_score(&guess, &current_player.secret_code);
-        
-        // Y = Digits at Correct Position
-        let y_correct_pos = y_score; 
-        
-        // X = Total Correct Digits (Y + C)
-        let x_total_correct = y_score + c_score;
-
-        // 5. Simplified Output
-        let guess_str = format!("{}{}{}{}", guess[0], guess[1], guess[2], guess[3]);
-
-        println!("--------------------------------------");
-        println!("Guess {}: Feedback (D,P) -> {},{}", guess_str, x_total_correct, y_correct_pos);
-        println!("--------------------------------------");
-
-
-        // 6. Check for Win Condition (4 correct positions)
-        if y_score == 4 {
-            let mut rank_to_assign_final: usize;
-
-            if round_number > last_assigned_round {
-                // New, distinct rank
-                rank_to_assign_final = rank_to_assign;
-                // Prepare rank for the NEXT distinct winner
-                rank_to_assign += 1;
-            } else {
-                // Tie: Assign the rank of the last winner (which is rank_to_assign - 1)
-                rank_to_assign_final = rank_to_assign.saturating_sub(1);
-                if rank_to_assign_final == 0 {
-                    rank_to_assign_final = 1;
-                }
-            }
-            
-            // Update the winning round number after assigning the rank
-            last_assigned_round = round_number;
-
-            println!("\n🎉🎉🎉 CODE GUESSED! 🎉🎉🎉");
-            println!("{} correctly guessed their secret code: {}. They finished in {} place!", 
-                     current_player.name, guess_str, rank_to_assign_final);
-            
-            // Post-Game Menu
-            let keep_playing = post_game_menu(&mut players, current_player_index, rank_to_assign_final, &mut completed_players);
-            
-            if !keep_playing {
-                break; // Exit the game loop
-            }
-            
-            // Adjust the current player index since the vector was modified
-            current_player_index = current_player_index % players.len(); 
-            
-            // Clear screen after the menu selection
-            clear_screen();
-            continue; // Go to the next loop iteration (next player's turn)
-        }
+/// Applies the options that set a game up without questions: `--players`,
+/// `--names`, `--engine`, `--length`, `--mode`, `--delay` and `--seed`.
+/// Returns the menu choice of the mode to start straight away, if one was
+/// asked for.
+fn apply_game_options(options: &GameOptions) -> Option<&'static str> {
+    if let Some(length) = options.length {
+        fix_code_length(length);
+    }
+    if let Some(delay) = options.delay {
+        set_pause_length(delay);
+    }
+    if let Some(seed) = options.seed {
+        set_seed(seed);
+    }
+    let mode = options.mode.as_ref().map(|name| {
+        let (_, choice) = MODES.iter().find(|(id, _)| id.eq_ignore_ascii_case(name)).expect("clap only takes the modes listed");
+        *choice
+    });
+
+    // Naming the players (or the bot programs) sets up an Individual Secrets table
+    let named: Vec<String> = options.names.iter().map(|name| name.trim().to_string()).collect();
+    let names = match (options.players.map(usize::from), named) {
+        (None, names) if names.is_empty() && options.engine.is_empty() => return mode,
+        (None, names) => names,
+        (Some(count), names) if names.is_empty() => vec![String::new(); count],
+        (Some(count), names) if count == names.len() => names,
+        (Some(count), names) => usage_error(&format!("--names gives {} name(s) for {} player(s).", names.len(), count)),
+    };
+    if names.len() + options.engine.len() > MAX_SEATS {
+        usage_error(&format!("A game has at most {} players.", MAX_SEATS));
+    }
+    if mode.is_some_and(|choice| choice != "1") {
+        usage_error("--players, --names and --engine set up Individual Secrets games, so they only go with --mode individual.");
+    }
+    let mut seats = human_seats(&names);
+    for command in &options.engine {
+        // Two copies of one program play under different names
+        let mut seat = engine_seat(command);
+        seat.name = unique_name(&seat.name, &seats.iter().map(|s| s.name.as_str()).collect::<Vec<_>>());
+        seats.push(seat);
+    }
+    preset_seats(seats);
+    Some("1")
+}
 
-        // 7. Pause, clear screen, move to the next player, and check for round completion
-        
-        println!("\n...Moving to next Player in 5 seconds...");
-        thread::sleep(Duration::from_secs(5));
-        
-        clear_screen();
+/// Applies `--debug-secrets`, which shows secrets in Individual Secrets games
+/// with one person playing, or in any game with `--i-know-what-im-doing`.
+fn apply_debug_secrets(options: &Options) {
+    if !options.debug_secrets {
+        return;
+    }
+    set_debug_secrets(if options.i_know_what_im_doing { DebugSecrets::Always } else { DebugSecrets::SolePlayer });
+}
 
-        // Check if a full round has been completed (total_guesses is a multiple of num_players)
-        if total_guesses % (players.len() as u32) == 0 {
-            round_number += 1;
-        }
-        
-        current_player_index = (current_player_index + 1) % players.len();
-    }
-    
-    // --- FINAL RANKING DISPLAY ---
-    if !completed_players.is_empty() {
-        println!("\n======================================");
-        println!("|         FINAL RANKINGS         |");
-        println!("======================================");
-        
-        // Sort the players by their assigned rank
-        completed_players.sort_by_key(|p| p.rank.unwrap_or(num_players)); 
-
-        for p in completed_players.iter() {
-            let rank_str = match p.rank {
-                Some(r) => format!("Rank {}", r),
-                None => "Unranked".to_string(),
-            };
-            let secret_str = format!("{}{}{}{}", p.secret_code[0], p.secret_code[1], p.secret_code[2], p.secret_code[3]);
-            println!("| {:<15} | {:<8} | Secret: {:<4} |", p.name, rank_str, secret_str);
-        }
-        println!("======================================");
+/// Ends a command-line tool's run, exiting with its error if it had one.
+fn finish_tool(result: Result<(), String>) {
+    if let Err(message) = result {
+        usage_error(&message);
     }
 }
 
-fn main() {
+/// Plays the game itself: the main menu (or the mode `--mode` asked for)
+/// and a new game each time round, until the players quit.
+fn play(club_path: Option<PathBuf>, mut quick_mode: Option<&'static str>) {
+    let mut stores = stats::open_stores(club_path);
+    let mut session = Session::new();
+
     loop {
-        run_game();
-        
+        // A mode named on the command line starts the first time round
+        match quick_mode.take() {
+            Some(choice) => {
+                run_choice(choice, &mut stores, &mut session);
+            }
+            None => select_and_run_mode(&mut stores, &mut session),
+        }
+
         // Check if we should restart or quit
-        println!("\n--- Game Over ---");
-        println!("[1] Start a New Game");
-        println!("[2] Quit Program");
-        print!("Enter choice (1 or 2): ");
-        io::stdout().flush().unwrap();
+        outln!("{}", tr("over.title"));
+        outln!("{}", tr("over.new"));
+        outln!("{}", tr("over.quit"));
 
-        match read_line().trim() {
+        match prompt(tr("over.prompt")).as_str() {
             "1" => {
-                // Continue the outer loop to call run_game() again
+                // Continue the outer loop to pick a mode again
                 clear_screen();
                 continue;
             }
             "2" => {
-                println!("Thank you for playing! Goodbye.");
+                session::show_trivia(&session, &stores);
+                session::show_summary(&session);
+                outln!("{}", tr("over.goodbye"));
                 break; // Exit main loop and terminate
             }
             _ => {
-                println!("Invalid input. Restarting the menu...");
-                thread::sleep(Duration::from_secs(1));
+                outln!("{}", tr("over.invalid"));
+                pause(Duration::from_secs(1));
                 clear_screen();
             }
         }
     }
 }
+
+fn main() {
+    let cli = Cli::parse();
+    let options = &cli.options;
+    let club_path = club_path(options);
+    set_guest_session(options.guest);
+    // JSON output replaces the usual text with one event per line, which
+    // only Individual Secrets games publish
+    if options.output.as_deref() == Some("json") {
+        json::enable();
+    }
+    apply_defaults();
+    apply_language(options);
+    // JSON output has no escape codes or line editing between the events
+    if options.no_ansi || json::active() {
+        set_plain_output(true);
+    }
+    // Nobody watches a pipe, so it gets neither escape codes nor pauses (unless --delay asks)
+    if !interactive() {
+        set_plain_output(true);
+        set_pause_length(Duration::ZERO);
+    }
+    if options.quiet {
+        set_quiet_output(true);
+    }
+    apply_debug_secrets(options);
+    apply_theme(options);
+    open_spectator_feed(options);
+    open_event_log(options);
+    let mut quick_mode = apply_game_options(&cli.game);
+
+    // Without a command the game is played, as it always was
+    let command = cli.command.unwrap_or(Command::Play { resume: None });
+    if let Command::Play { resume: Some(path) } = &command {
+        if quick_mode.is_some() {
+            usage_error("--resume carries on an Individual Secrets game, so it doesn't go with options that set up a new game.");
+        }
+        save::resume_game(SavedGame::load(path).unwrap_or_else(|message| usage_error(&message)));
+        quick_mode = Some("1");
+    }
+    if json::active() {
+        if !matches!(command, Command::Play { .. }) || quick_mode.is_some_and(|choice| choice != "1") {
+            usage_error("--output json plays Individual Secrets games, so it doesn't go with other modes or commands.");
+        }
+        quick_mode = Some("1");
+    }
+
+    match command {
+        Command::Play { .. } => play(club_path, quick_mode),
+        Command::Solve(args) => finish_tool(assistant::run_solve(&args)),
+        Command::Simulate(args) => finish_tool(simulate::run_simulation(&args)),
+        Command::Stats { view: None } => stats::print_stats(&stats::open_stores(club_path)),
+        Command::Stats { view: Some(StatsCommand::Leaderboard) } => stats::print_leaderboards(&stats::open_stores(club_path)),
+        Command::Stats { view: Some(StatsCommand::Export(args)) } => finish_tool(export::run_export(&args, &stats::open_stores(club_path))),
+        Command::Analyze(args) => finish_tool(analysis::run_analysis(&args)),
+        Command::Config { command } => finish_tool(run_config(&command)),
+        Command::Spectate(args) => finish_tool(spectator::run_spectator(&args)),
+        Command::Replay(args) => finish_tool(replay::run_replay(&args)),
+        Command::Turn(args) => finish_tool(correspondence::run_turn(&args)),
+        Command::Host(args) => finish_tool(network::run_host(&args, &mut stats::open_stores(club_path))),
+        Command::Join(args) => finish_tool(network::run_join(&args, &mut stats::open_stores(club_path))),
+        #[cfg(feature = "ssh")]
+        Command::Serve(ServeArgs { ssh: true, port, .. }) => finish_tool(guessing_game::ssh::run_ssh_serve(port.unwrap_or(guessing_game::ssh::DEFAULT_SSH_PORT))),
+        #[cfg(not(feature = "ssh"))]
+        Command::Serve(ServeArgs { ssh: true, .. }) => usage_error("This build doesn't include the SSH server. Run it with `cargo run --features ssh -- serve --ssh`."),
+        Command::Serve(ServeArgs { telnet: true, port, .. }) => finish_tool(telnet::run_telnet_serve(port.unwrap_or(telnet::DEFAULT_TELNET_PORT))),
+        Command::Serve(ServeArgs { port, .. }) => finish_tool(api::run_serve(port.unwrap_or(api::DEFAULT_HTTP_PORT), &mut stats::open_stores(club_path))),
+        Command::Profile { command } => finish_tool(profile::run_profile_command(&command, &mut stats::open_stores(club_path))),
+        Command::Daily => daily::run_daily(&mut stats::open_stores(club_path), &mut Session::new()),
+        // The demo runs until a key is pressed, then carries on into the normal menus
+        Command::Demo => {
+            demo::run_demo();
+            play(club_path, quick_mode);
+        }
+        #[cfg(feature = "tui")]
+        Command::Tui => guessing_game::tui::run_tui(&mut stats::open_stores(club_path), &mut Session::new()),
+        #[cfg(not(feature = "tui"))]
+        Command::Tui => usage_error("This build doesn't include the full-screen frontend. Run it with `cargo run --features tui -- tui`."),
+    }
+}
@@ -0,0 +1,1537 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, BufReader};
+use std::net::{IpAddr, Ipv4Addr, Shutdown, SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use clap::Args;
+
+use crate::code::{calculate_score, format_code, generate_secret, is_solved, parse_guess, set_code_length, start_seeded_game, Guess, Score};
+use crate::console::{answer_command, prompt, read_line, show_prompt};
+use crate::crypto::{from_hex, random_bytes, sha256, to_hex};
+use crate::room::{accept_clients, refuse, run_room, session_token, unique_name, version_mismatch, Client, Event, Watchers};
+use crate::feedback::player_notation;
+use crate::rankings::{print_rankings, render_rankings, Finish, Standing};
+use crate::rules::{describe_record_key, GameSpec, Rules, Variant};
+use crate::snapshot::{code_field, parse_code_field, parse_number_field, parse_score_field, score_field, Snapshot, SnapshotBody, SnapshotError};
+use crate::stats::record_results;
+use crate::protocol::{expect_greeting, receive, send, ClientMsg, ServerMsg, PROTOCOL_VERSION};
+use crate::store::{clean_field, data_dir, new_game_id, now_timestamp, GameResult, StoreSet};
+use crate::strings::{fill, tr};
+use crate::theme::paint_feedback;
+use crate::transcript::{Move, Transcript};
+
+// --- Network Play ---
+// `host` runs an Individual Secrets game for players on other machines, who
+// take their seats with `join <address>` and the room code the host passes on
+// (see `room` for the lobby they wait in). The host's copy of the game is the
+// one that counts: clients send what their player types and print what they're
+// sent, so nobody's secret or feedback leaves the host except to its owner and
+// to the one client standing by to take over (see Host Migration below). The
+// messages they exchange are in `protocol`.
+//
+// Turns go round in seat order as in the classic game, and players that
+// crack their code in the same round share a place. A player who drops out
+// (or types /quit) is out of the game, which carries on without them. A
+// player on another machine who leaves a prompt unanswered for five minutes
+// has that turn skipped, so one absent player can't stall everyone else.
+// Spectators hear everything a seat would but their own feedback, and each
+// guess with its score.
+//
+// Secrets are sealed so that nobody has to take the host's word for them. As
+// the game starts, every client is sent a `commit` for each seat: the SHA-256
+// of a fresh random salt and the seat's secret. At the end a `reveal` gives
+// the salt and secret themselves. The client checks every reveal against its
+// seal, and its own player's feedback against their secret, so a host that
+// swapped a secret or scored a guess wrongly partway through is caught.
+//
+// Chat goes out as plain `info` lines. It only ever carries what a player
+// typed after `/say`, and feedback never goes anywhere but to its owner.
+//
+// While seats are open the host also answers searches on the local network:
+// `join` without an address broadcasts a `sgg-find` datagram to the discovery
+// port, and every host waiting for players replies with a `sgg-game` one
+// (protocol version, host's name, port, open seats, game spec).
+
+/// The port `host` listens on and `join` connects to without one given.
+pub const DEFAULT_PORT: u16 = 7777;
+
+/// How many spectators a hosted game takes without `--spectators`.
+const DEFAULT_SPECTATORS: usize = 4;
+
+/// The UDP port hosts listen on for LAN searches.
+pub const DISCOVERY_PORT: u16 = DEFAULT_PORT;
+
+/// How long a player who loses their connection mid-game keeps their seat.
+pub const SEAT_HOLD: Duration = Duration::from_secs(60);
+
+/// How long a client's player has to answer a prompt before their turn is skipped.
+const ANSWER_WAIT: Duration = Duration::from_secs(300);
+
+/// How long `join` waits for hosts to answer a search.
+const DISCOVERY_WAIT: Duration = Duration::from_millis(1000);
+
+const DISCOVERY_QUERY: &str = "sgg-find";
+
+/// A game waiting for players, as its host describes it to a LAN search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Advert {
+    pub version: u32,
+    pub host: String,
+    pub port: u16,
+    pub open_seats: usize,
+    pub spec: GameSpec,
+}
+
+impl Advert {
+    pub fn to_datagram(&self) -> String {
+        format!("sgg-game\t{}\t{}\t{}\t{}\t{}", self.version, clean_field(&self.host), self.port, self.open_seats, self.spec)
+    }
+
+    /// Reads a reply, or None if it isn't one.
+    pub fn parse(text: &str) -> Option<Advert> {
+        match text.trim_end().split('\t').collect::<Vec<_>>().as_slice() {
+            ["sgg-game", version, host, port, open_seats, spec] => Some(Advert {
+                version: version.parse().ok()?,
+                host: host.to_string(),
+                port: port.parse().ok()?,
+                open_seats: open_seats.parse().ok()?,
+                spec: spec.parse().ok()?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// How a chat line reads to everyone else.
+pub fn chat_line(name: &str, text: &str) -> String {
+    format!("💬 {}: {}", name, text.trim())
+}
+
+/// The message in a `/say <message>` line, if it is one.
+fn said(line: &str) -> Option<&str> {
+    line.strip_prefix("/say").filter(|rest| rest.is_empty() || rest.starts_with(' ')).map(str::trim)
+}
+
+/// The seal on a secret: the SHA-256 of the salt and the secret's digits.
+pub fn commitment(salt: &str, secret: &Guess) -> String {
+    to_hex(&sha256(format!("{}:{}", salt, format_code(secret)).as_bytes()))
+}
+
+/// Prints the feedback for a guess, the way the classic game does.
+fn show_feedback(guess: &Guess, score: Score) {
+    outln!("{}", fill("game.feedback", &[("guess", &format_code(guess)), ("score", &paint_feedback(score, &player_notation().render(score)))]));
+}
+
+// --- Hosting ---
+
+/// A seat at the host's table: the host's own (no token) or a client's.
+struct Seat {
+    name: String,
+    token: Option<String>, // Claims a client's seat back after a lost connection
+    client: Option<Client>, // The client's connection, while there is one
+    secret: Guess,
+    salt: String, // Seals the secret in its commitment
+    guesses: u32,
+    history: Vec<(Guess, Score)>, // Their own guesses and feedback, to give back after a reconnect
+    rank: Option<usize>,
+    finished: Option<(u32, Duration)>, // Round and time they cracked their code or left
+    left: bool,
+    lost_at: Option<Instant>, // When the client's connection was lost, until they're back
+    skipped: bool,            // Their seat's hold ran out and their turns are being skipped
+}
+
+impl Seat {
+    fn playing(&self) -> bool {
+        self.finished.is_none()
+    }
+
+    /// Whether the player still has turns to take, or might come back to take them.
+    fn expected(&self) -> bool {
+        self.playing() && !self.skipped && !self.left
+    }
+
+    /// Whether the seat is played at this machine rather than by a client.
+    fn local(&self) -> bool {
+        self.token.is_none()
+    }
+
+    /// Sends `message` to a client seat. Returns false once the client is gone.
+    fn send(&mut self, message: &ServerMsg) -> bool {
+        match &mut self.client {
+            Some(client) if !self.left && self.lost_at.is_none() => send(&mut client.writer, message).is_ok(),
+            _ => self.local(),
+        }
+    }
+
+    /// Shows a line to this seat's player.
+    fn tell(&mut self, text: &str) {
+        if self.local() {
+            outln!("{}", text);
+        } else {
+            self.send(&ServerMsg::Info(text.to_string()));
+        }
+    }
+}
+
+/// The seal on every seat's secret, as sent at the start.
+fn commitments(seats: &[Seat]) -> Vec<ServerMsg> {
+    seats.iter().map(|s| ServerMsg::Commit { name: s.name.clone(), hash: commitment(&s.salt, &s.secret) }).collect()
+}
+
+/// The spectator that takes the game over if this host goes.
+struct Successor {
+    spectator: usize, // Its connection
+    address: Option<String>, // Where it takes connections, once it has said
+}
+
+/// What the host has set from the keyboard during the game (see Host Commands below).
+#[derive(Default)]
+struct Console {
+    paused: bool,
+    delay: Duration, // The wait before each turn
+    ended: bool,     // The host has called the game off
+}
+
+/// The host's end of the clients' connections once the game is under way,
+/// and the host's own keyboard.
+struct Inbox {
+    events: Receiver<Event>,
+    arrivals: BTreeMap<usize, TcpStream>, // Connections that haven't said who they are yet
+    watchers: Watchers,
+    successor: Option<Successor>,
+    handover: bool, // Whether a spectator stands by to take the game over (see Host Migration)
+    without_successor: bool, // Whether the host has been told nobody can take over
+    console: Console,
+}
+
+impl Inbox {
+    /// Who takes over if this host goes, once they're listening.
+    fn successor_notice(&self) -> Option<ServerMsg> {
+        let successor = self.successor.as_ref()?;
+        let address = successor.address.clone()?;
+        let ip = address.parse::<SocketAddr>().map_or_else(|_| address.clone(), |a| a.ip().to_string());
+        Some(ServerMsg::Successor { name: fill("net.spectator_at", &[("address", &ip)]), address })
+    }
+}
+
+/// Shows a line to every seat but `except`.
+fn broadcast(seats: &mut [Seat], text: &str, except: Option<usize>) {
+    for (i, seat) in seats.iter_mut().enumerate() {
+        if Some(i) != except {
+            seat.tell(text);
+        }
+    }
+}
+
+/// Like `broadcast`, and shows the line to the spectators too.
+fn announce(seats: &mut [Seat], watchers: &mut Watchers, text: &str, except: Option<usize>) {
+    broadcast(seats, text, except);
+    watchers.tell(text);
+}
+
+/// Passes a chat line from seat `from` on to everyone else.
+fn relay_chat(seats: &mut [Seat], watchers: &mut Watchers, from: usize, text: &str) {
+    if !text.is_empty() {
+        let line = chat_line(&seats[from].name, text);
+        announce(seats, watchers, &line, Some(from));
+    }
+}
+
+/// The seat whose client is connection `id`.
+fn seat_of(seats: &[Seat], id: usize) -> Option<usize> {
+    seats.iter().position(|s| s.client.as_ref().is_some_and(|c| c.id == id))
+}
+
+/// Notes that seat `i`'s connection is gone, holding their seat if they're still playing.
+fn lose_connection(seats: &mut [Seat], watchers: &mut Watchers, i: usize) {
+    if seats[i].lost_at.is_some() || seats[i].left {
+        return;
+    }
+    seats[i].lost_at = Some(Instant::now());
+    let text = if seats[i].playing() {
+        fill("net.lost_held", &[("name", &seats[i].name), ("seconds", &SEAT_HOLD.as_secs())])
+    } else {
+        fill("net.lost", &[("name", &seats[i].name)])
+    };
+    announce(seats, watchers, &text, Some(i));
+}
+
+/// Gives a player who has come back with their token their seat again,
+/// along with the feedback they'd had so far.
+fn rejoin(seats: &mut [Seat], watchers: &mut Watchers, mut writer: TcpStream, id: usize, version: u32, token: &str) {
+    if version != PROTOCOL_VERSION {
+        return refuse(writer, &version_mismatch(version));
+    }
+    let Some(i) = seats.iter().position(|s| s.token.as_deref() == Some(token)) else {
+        return refuse(writer, tr("net.no_seat"));
+    };
+    if seats[i].left {
+        return refuse(writer, tr("net.you_left"));
+    }
+    // A new connection wins over one the host hasn't noticed has died
+    if let Some(old) = seats[i].client.take() {
+        old.writer.shutdown(Shutdown::Both).ok();
+    }
+    let welcome = ServerMsg::Welcome { name: seats[i].name.clone(), token: token.to_string() };
+    if send(&mut writer, &welcome).is_err() || send(&mut writer, &ServerMsg::Start).is_err() {
+        return;
+    }
+    for message in commitments(seats) {
+        send(&mut writer, &message).ok();
+    }
+    seats[i].client = Some(Client { id, writer, token: token.to_string() });
+    seats[i].lost_at = None;
+    seats[i].skipped = false;
+    if !seats[i].history.is_empty() {
+        seats[i].tell(tr("net.welcome_back"));
+        for (guess, score) in seats[i].history.clone() {
+            seats[i].send(&ServerMsg::Feedback { guess, score });
+        }
+    }
+    let text = fill("net.back", &[("name", &seats[i].name)]);
+    announce(seats, watchers, &text, Some(i));
+}
+
+/// Deals with one thing a client did during the game. Returns the seat and
+/// text of any input, for whoever is waiting on it.
+fn handle_event(seats: &mut [Seat], inbox: &mut Inbox, event: Event) -> Option<(usize, String)> {
+    match event {
+        Event::Arrived { id, writer } => {
+            inbox.arrivals.insert(id, writer);
+            None
+        }
+        Event::Said { id, message: ClientMsg::Rejoin { version, token } } => {
+            let writer = inbox.arrivals.remove(&id)?;
+            rejoin(seats, &mut inbox.watchers, writer, id, version, &token);
+            if let Some(i) = seat_of(seats, id)
+                && let Some(notice) = inbox.successor_notice()
+            {
+                seats[i].send(&notice);
+            }
+            None
+        }
+        Event::Said { id, message: ClientMsg::Watch { version, room } } => {
+            let writer = inbox.arrivals.remove(&id)?;
+            if inbox.watchers.admit(id, writer, version, &room) {
+                inbox.watchers.send_to(id, &ServerMsg::Info(tr("net.under_way").to_string()));
+                for message in commitments(seats).into_iter().chain(inbox.successor_notice()) {
+                    inbox.watchers.send_to(id, &message);
+                }
+            }
+            None
+        }
+        Event::Said { id, message: ClientMsg::Hello { .. } } => {
+            refuse(inbox.arrivals.remove(&id)?, tr("net.started"));
+            None
+        }
+        Event::Said { id, message: ClientMsg::Listening(port) } => {
+            note_successor(seats, inbox, id, port);
+            None
+        }
+        Event::Said { id, message } => {
+            // Anything from a seat that has left, or been removed, is ignored
+            let i = seat_of(seats, id).filter(|&i| !seats[i].left)?;
+            match message {
+                ClientMsg::Input(text) => Some((i, text.trim().to_string())),
+                ClientMsg::Chat(text) => {
+                    relay_chat(seats, &mut inbox.watchers, i, &text);
+                    None
+                }
+                _ => None, // Nothing else is expected from a client mid-game
+            }
+        }
+        Event::Gone { id } => {
+            inbox.arrivals.remove(&id);
+            inbox.watchers.remove(id);
+            if let Some(i) = seat_of(seats, id) {
+                lose_connection(seats, &mut inbox.watchers, i);
+            }
+            None
+        }
+        Event::Typed(text) => {
+            if host_command(seats, inbox, &text) {
+                return None;
+            }
+            // A spectator that took the game over hosts it without a seat
+            let Some(i) = seats.iter().position(Seat::local) else {
+                outln!("{}", tr("net.hosting_unseated"));
+                return None;
+            };
+            match said(&text) {
+                Some(chat) => {
+                    relay_chat(seats, &mut inbox.watchers, i, chat);
+                    None
+                }
+                None => Some((i, text)),
+            }
+        }
+    }
+}
+
+/// Asks seat `i`'s player `question`. Returns false if their client has gone.
+fn put_question(seats: &mut [Seat], i: usize, question: &str) -> bool {
+    if seats[i].local() {
+        show_prompt(question);
+        true
+    } else {
+        seats[i].send(&ServerMsg::Prompt(question.to_string()))
+    }
+}
+
+/// Answers input from seat `from` that nobody asked for: while the game is
+/// held that's said, and the host is told it isn't their turn.
+fn stray_input(seats: &mut [Seat], console: &Console, from: usize) {
+    if console.paused {
+        seats[from].tell(tr("net.held"));
+    } else if seats[from].local() {
+        outln!("{}", tr("net.not_your_turn_host"));
+    }
+}
+
+/// Asks seat `i`'s player, or None if their connection is lost, they took
+/// too long to answer, the host removed them or the host ended the game.
+/// Everything else clients and the host do is dealt with while waiting for them.
+fn ask(seats: &mut [Seat], i: usize, question: &str, inbox: &mut Inbox) -> Option<String> {
+    if !put_question(seats, i, question) {
+        lose_connection(seats, &mut inbox.watchers, i);
+        return None;
+    }
+    let mut deadline = Instant::now() + ANSWER_WAIT;
+    loop {
+        // The host at this machine, and anyone while the game is held, can take their time
+        let event = if seats[i].local() || inbox.console.paused {
+            inbox.events.recv().ok()?
+        } else {
+            match inbox.events.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(event) => event,
+                Err(RecvTimeoutError::Timeout) => {
+                    seats[i].tell(tr("net.timed_out_you"));
+                    let text = fill("net.timed_out", &[("name", &seats[i].name), ("minutes", &(ANSWER_WAIT.as_secs() / 60))]);
+                    announce(seats, &mut inbox.watchers, &text, Some(i));
+                    return None;
+                }
+                Err(RecvTimeoutError::Disconnected) => return None,
+            }
+        };
+        let typed = matches!(event, Event::Typed(_));
+        let held = inbox.console.paused;
+        match handle_event(seats, inbox, event) {
+            Some((from, text)) if from == i && !inbox.console.paused => return Some(text),
+            Some((from, _)) => stray_input(seats, &inbox.console, from),
+            None => {}
+        }
+        if seats[i].lost_at.is_some() || seats[i].left || inbox.console.ended {
+            return None;
+        }
+        // The question is asked again once the game carries on, and after the
+        // host has used the keyboard on their own turn
+        if (typed && seats[i].local()) || (held && !inbox.console.paused) {
+            deadline = Instant::now() + ANSWER_WAIT;
+            if !put_question(seats, i, question) {
+                lose_connection(seats, &mut inbox.watchers, i);
+                return None;
+            }
+        }
+    }
+}
+
+/// Before a turn for seat `i`, whose connection is lost: waits out what's
+/// left of their seat's hold for them to come back. Returns whether they did.
+fn wait_for_rejoin(seats: &mut [Seat], i: usize, inbox: &mut Inbox) -> bool {
+    let Some(lost_at) = seats[i].lost_at else { return true };
+    let deadline = lost_at + SEAT_HOLD;
+    let left = deadline.saturating_duration_since(Instant::now()).as_secs();
+    if !seats[i].skipped && left > 0 {
+        announce(seats, &mut inbox.watchers, &fill("net.waiting_rejoin", &[("seconds", &left), ("name", &seats[i].name)]), Some(i));
+    }
+    while seats[i].lost_at.is_some() && !seats[i].skipped && !seats[i].left && !inbox.console.ended {
+        match inbox.events.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(event) => {
+                if let Some((from, _)) = handle_event(seats, inbox, event) {
+                    stray_input(seats, &inbox.console, from);
+                }
+            }
+            Err(_) => {
+                seats[i].skipped = true;
+                let text = fill("net.not_back", &[("name", &seats[i].name)]);
+                announce(seats, &mut inbox.watchers, &text, Some(i));
+            }
+        }
+    }
+    // Someone whose turns are being skipped can still come back between turns
+    while let Ok(event) = inbox.events.try_recv() {
+        if let Some((from, _)) = handle_event(seats, inbox, event) {
+            stray_input(seats, &inbox.console, from);
+        }
+    }
+    seats[i].lost_at.is_none() && !inbox.console.ended
+}
+
+/// Answers LAN searches for the game being hosted on `port` until its last
+/// seat is taken. Only one host per machine can do this; any others are
+/// still reachable by address.
+fn advertise(host: &str, port: u16, open_seats: Arc<AtomicUsize>) {
+    let socket = match UdpSocket::bind(("0.0.0.0", DISCOVERY_PORT)) {
+        Ok(socket) => socket,
+        Err(e) => {
+            outln!("{}", fill("net.no_discovery", &[("error", &e)]));
+            return;
+        }
+    };
+    socket.set_read_timeout(Some(Duration::from_millis(500))).ok();
+    let host = host.to_string();
+    thread::spawn(move || {
+        let mut buffer = [0; 512];
+        loop {
+            let open = open_seats.load(Ordering::Relaxed);
+            if open == 0 {
+                return;
+            }
+            let Ok((length, from)) = socket.recv_from(&mut buffer) else { continue };
+            if buffer[..length].starts_with(DISCOVERY_QUERY.as_bytes()) {
+                let advert = Advert {
+                    version: PROTOCOL_VERSION,
+                    host: host.clone(),
+                    port,
+                    open_seats: open,
+                    spec: GameSpec::new(Variant::IndividualSecrets, Rules::default()),
+                };
+                socket.send_to(advert.to_datagram().as_bytes(), from).ok();
+            }
+        }
+    });
+}
+
+/// Marks a seat as gone, telling the others.
+fn drop_seat(seats: &mut [Seat], watchers: &mut Watchers, i: usize, round: u32, started_at: Instant) {
+    // A player who typed /quit is still connected, and is let go properly
+    seats[i].tell(tr("net.left_you"));
+    seats[i].send(&ServerMsg::End);
+    seats[i].left = true;
+    seats[i].finished.get_or_insert((round, started_at.elapsed()));
+    let text = fill("net.left", &[("name", &seats[i].name)]);
+    announce(seats, watchers, &text, Some(i));
+}
+
+/// How a turn ended.
+enum Turn {
+    Guessed(Guess, Score),
+    Quit,
+    Lost,
+}
+
+/// Plays one turn for seat `i`: asks until there's a valid guess, then scores it.
+fn take_turn(seats: &mut [Seat], i: usize, round: u32, inbox: &mut Inbox) -> Turn {
+    let question = fill("net.guess_prompt", &[("round", &round)]);
+    loop {
+        let Some(input) = ask(seats, i, &question, inbox) else { return Turn::Lost };
+        if input == "/quit" {
+            return Turn::Quit;
+        }
+        match parse_guess(&input) {
+            Ok(guess) => {
+                let score = calculate_score(&guess, &seats[i].secret);
+                seats[i].guesses += 1;
+                seats[i].history.push((guess, score));
+                if seats[i].local() {
+                    show_feedback(&guess, score);
+                } else if !seats[i].send(&ServerMsg::Feedback { guess, score }) {
+                    // The guess stands, and they'll see its feedback when they're back
+                    lose_connection(seats, &mut inbox.watchers, i);
+                }
+                return Turn::Guessed(guess, score);
+            }
+            Err(message) => seats[i].tell(&message),
+        }
+    }
+}
+
+/// A game under way: what the host keeps, and what a successor takes over.
+struct Table {
+    seats: Vec<Seat>,
+    round: u32,
+    next: usize, // The seat whose turn comes next this round
+    rank_to_assign: usize,
+    last_ranked_round: u32,
+    started_at: Instant,
+    seed: u64,
+    transcript: Transcript,
+    room: String, // The room code and spectator limit, for spectators after a takeover
+    spectators: usize,
+}
+
+/// The options of `host`.
+#[derive(Debug, Args)]
+pub struct HostArgs {
+    /// The port players connect to
+    #[arg(long, default_value_t = DEFAULT_PORT)]
+    pub port: u16,
+    /// Seats in the game, counting the host's own
+    #[arg(long, value_name = "N", default_value_t = 2, value_parser = clap::value_parser!(u8).range(2..=8))]
+    pub seats: u8,
+    /// The most spectators the game takes (0 turns them away)
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_SPECTATORS)]
+    pub spectators: usize,
+    /// Let a spectator take the game over if the host drops out
+    #[arg(long)]
+    pub handover: bool,
+}
+
+/// Entry point for `host`.
+pub fn run_host(args: &HostArgs, stores: &mut StoreSet) -> Result<(), String> {
+    let (port, seat_count, spectator_cap, handover) = (args.port, usize::from(args.seats), args.spectators, args.handover);
+    if handover && spectator_cap == 0 {
+        return Err(tr("net.handover_needs_spectators").to_string());
+    }
+
+    let listener = TcpListener::bind(("0.0.0.0", port)).map_err(|e| fill("net.listen_failed", &[("port", &port), ("error", &e)]))?;
+    outln!("{}", tr("net.hosting"));
+    let name = unique_name(&prompt(tr("common.name_prompt")), &[]);
+    let open_seats = Arc::new(AtomicUsize::new(seat_count - 1));
+    advertise(&name, port, Arc::clone(&open_seats));
+    outln!("{}", fill("net.join_hint", &[("port", &port)]));
+
+    let (players, watchers, sender, events) = run_room(listener, &name, seat_count, spectator_cap, &open_seats);
+    let (typing, keyboard) = mpsc::channel();
+    read_typing(typing);
+    forward_typing(keyboard, sender);
+    let mut inbox = Inbox { events, arrivals: BTreeMap::new(), watchers, successor: None, handover, without_successor: false, console: Console::default() };
+    let mut seats: Vec<Seat> = players
+        .into_iter()
+        .map(|(name, client)| Seat {
+            name,
+            token: client.as_ref().map(|c| c.token.clone()),
+            client,
+            secret: Guess::default(),
+            salt: String::new(),
+            guesses: 0,
+            history: Vec::new(),
+            rank: None,
+            finished: None,
+            left: false,
+            lost_at: None,
+            skipped: false,
+        })
+        .collect();
+
+    let seed = start_seeded_game();
+    for seat in &mut seats {
+        seat.secret = generate_secret();
+        seat.salt = to_hex(&random_bytes::<16>());
+    }
+    let names: Vec<String> = seats.iter().map(|s| s.name.clone()).collect();
+    announce(&mut seats, &mut inbox.watchers, &fill("net.begins", &[("names", &names.join(", "))]), None);
+    for message in commitments(&seats) {
+        for seat in &mut seats {
+            seat.send(&message);
+        }
+        inbox.watchers.send(&message);
+    }
+    announce(&mut seats, &mut inbox.watchers, tr("net.sealed"), None);
+    if handover {
+        announce(&mut seats, &mut inbox.watchers, tr("net.handover"), None);
+    }
+
+    let table = Table {
+        seats,
+        round: 1,
+        next: 0,
+        rank_to_assign: 1,
+        last_ranked_round: 0,
+        started_at: Instant::now(),
+        seed,
+        transcript: Transcript::default(),
+        room: inbox.watchers.code().to_string(),
+        spectators: inbox.watchers.cap(),
+    };
+    play(table, inbox, stores);
+    Ok(())
+}
+
+/// Plays the game on from where `table` stands until nobody has turns left,
+/// then gives the rankings and reveals the secrets.
+fn play(mut table: Table, mut inbox: Inbox, stores: &mut StoreSet) {
+    outln!("{}", tr("net.host_commands"));
+    let mut first = table.next;
+    while table.seats.iter().any(Seat::expected) {
+        for i in first..table.seats.len() {
+            if !table.seats[i].playing() {
+                continue;
+            }
+            table.next = i;
+            between_turns(&mut table, &mut inbox);
+            if inbox.console.ended {
+                break;
+            }
+            if !table.seats[i].playing() {
+                continue;
+            }
+            if inbox.handover {
+                back_up(&mut table, &mut inbox);
+            }
+            if !wait_for_rejoin(&mut table.seats, i, &mut inbox) {
+                continue;
+            }
+            let (round, started_at) = (table.round, table.started_at);
+            let seats = &mut table.seats;
+            let text = fill("net.turn", &[("round", &round), ("name", &seats[i].name)]);
+            announce(seats, &mut inbox.watchers, &text, Some(i));
+            let turn = take_turn(seats, i, round, &mut inbox);
+            settle_removals(seats, round, started_at);
+            let (guess, score) = match turn {
+                Turn::Guessed(guess, score) => (guess, score),
+                Turn::Quit => {
+                    drop_seat(seats, &mut inbox.watchers, i, round, started_at);
+                    continue;
+                }
+                Turn::Lost => continue,
+            };
+            table.transcript.record(round, &seats[i].name, guess, score, started_at.elapsed());
+            // Spectators see the guess itself, which only gives a secret away once it's cracked
+            inbox.watchers.tell(&fill("net.watch_guess", &[("name", &seats[i].name), ("guess", &format_code(&guess)), ("score", &player_notation().render(score))]));
+            if is_solved(score) {
+                // Everyone who cracks their code in the same round shares a place
+                if round != table.last_ranked_round && table.last_ranked_round != 0 {
+                    table.rank_to_assign = seats.iter().filter(|s| s.rank.is_some()).count() + 1;
+                }
+                table.last_ranked_round = round;
+                seats[i].rank = Some(table.rank_to_assign);
+                seats[i].finished = Some((round, started_at.elapsed()));
+                let text = fill("net.cracked", &[("name", &seats[i].name), ("count", &seats[i].guesses)]);
+                announce(seats, &mut inbox.watchers, &text, None);
+            } else {
+                let text = fill("net.guessed", &[("name", &seats[i].name), ("number", &seats[i].guesses)]);
+                broadcast(seats, &text, Some(i));
+            }
+        }
+        if inbox.console.ended {
+            break;
+        }
+        first = 0;
+        table.round += 1;
+    }
+
+    let Table { mut seats, round, started_at, seed, transcript, .. } = table;
+    let standings: Vec<Standing> = seats
+        .iter()
+        .map(|s| {
+            let (round, time) = s.finished.unwrap_or((round, started_at.elapsed()));
+            let finish = match s.rank {
+                Some(rank) => Finish::Placed(rank),
+                None => Finish::DidNotFinish { round },
+            };
+            Standing { name: s.name.clone(), finish, guesses: s.guesses, time, secret: s.secret, rating: None }
+        })
+        .collect();
+    print_rankings(&standings);
+    let lines: Vec<String> = tr("rankings.title").lines().map(str::to_string).chain(render_rankings(&standings)).collect();
+    let reveals: Vec<ServerMsg> = seats.iter().map(|s| ServerMsg::Reveal { name: s.name.clone(), secret: s.secret, salt: s.salt.clone() }).collect();
+    for seat in seats.iter_mut().filter(|s| s.client.is_some()) {
+        for line in &lines {
+            seat.tell(line);
+        }
+        for reveal in &reveals {
+            seat.send(reveal);
+        }
+        seat.send(&ServerMsg::End);
+    }
+    // The rankings are already shown at this machine
+    for line in &lines {
+        inbox.watchers.send(&ServerMsg::Info(line.clone()));
+    }
+    for reveal in &reveals {
+        inbox.watchers.send(reveal);
+    }
+    inbox.watchers.send(&ServerMsg::End);
+
+    // Like the classic game, only a game everyone saw through counts towards the stats
+    if !inbox.console.ended && seats.iter().all(|s| !s.left && (s.rank.is_some() || s.lost_at.is_none())) {
+        let (game, timestamp) = (new_game_id(), now_timestamp());
+        let results = seats
+            .iter()
+            .map(|s| GameResult {
+                game,
+                timestamp,
+                mode: "network".to_string(),
+                player: s.name.clone(),
+                rank: s.rank.unwrap_or(seats.len()),
+                field_size: seats.len(),
+                guesses: s.guesses,
+                rules: Rules::default().record_key(),
+            })
+            .collect();
+        record_results(stores, results, &transcript.moves);
+    }
+    outln!("{}", fill("net.seed", &[("seed", &seed)]));
+}
+
+// --- Host Commands ---
+// The host runs the game from their own keyboard as well as playing in it.
+// Whatever they type goes through the same channel as the clients'
+// messages, so a command works at any moment: on their own turn, while
+// someone else is thinking, or while the game waits for a lost player.
+// Every command but /host is announced to the players and spectators.
+//
+//   /kick <name>       Removes a player, as if they'd left. They can't rejoin.
+//   /delay <seconds>   Waits that long before each turn, 0 for no wait.
+//   /hold, /resume     Stops the game until resumed. Players are told, and
+//                      whoever's turn it was is asked again afterwards.
+//   /end               Ends the game where it stands and reveals every secret.
+//                      A game ended early doesn't count towards the stats.
+//   /host              Lists these.
+//
+// (/pause is each player's own, and blanks their screen.) None of this is
+// part of the backup, so a successor starts with no delay and doesn't hold.
+
+/// The longest wait `/delay` sets.
+const MAX_TURN_DELAY: u64 = 60;
+
+/// Passes what the player at this machine types into the host's events.
+fn forward_typing(keyboard: Receiver<ClientEvent>, events: Sender<Event>) {
+    thread::spawn(move || {
+        for event in keyboard {
+            if let ClientEvent::Typed(text) = event
+                && events.send(Event::Typed(text)).is_err()
+            {
+                return;
+            }
+        }
+    });
+}
+
+/// Carries out a host command, if `text` is one. Returns whether it was.
+fn host_command(seats: &mut [Seat], inbox: &mut Inbox, text: &str) -> bool {
+    let (command, argument) = text.split_once(' ').map_or((text, ""), |(command, argument)| (command, argument.trim()));
+    let console = &mut inbox.console;
+    let announcement = match command {
+        "/host" => {
+            outln!("{}", tr("net.host_commands"));
+            return true;
+        }
+        "/kick" => return kick(seats, &mut inbox.watchers, argument),
+        "/delay" => match argument.parse::<u64>() {
+            Ok(seconds) if seconds <= MAX_TURN_DELAY => {
+                console.delay = Duration::from_secs(seconds);
+                if seconds == 0 { tr("net.delay_off").to_string() } else { fill("net.delay_on", &[("seconds", &seconds)]) }
+            }
+            _ => {
+                outln!("{}", fill("net.delay_usage", &[("max", &MAX_TURN_DELAY)]));
+                return true;
+            }
+        },
+        "/hold" if console.paused => {
+            outln!("{}", tr("net.already_held"));
+            return true;
+        }
+        "/hold" => {
+            console.paused = true;
+            tr("net.hold").to_string()
+        }
+        "/resume" if !console.paused => {
+            outln!("{}", tr("net.not_held"));
+            return true;
+        }
+        "/resume" => {
+            console.paused = false;
+            tr("net.resumed").to_string()
+        }
+        "/end" => {
+            console.ended = true;
+            tr("net.ended").to_string()
+        }
+        _ => return false,
+    };
+    announce(seats, &mut inbox.watchers, &announcement, None);
+    true
+}
+
+/// Removes the player called `name` from the game at the host's word.
+/// Returns true, having told the host if there's nobody to remove.
+fn kick(seats: &mut [Seat], watchers: &mut Watchers, name: &str) -> bool {
+    let Some(i) = seats.iter().position(|s| s.name.eq_ignore_ascii_case(name)) else {
+        outln!("{}", fill("net.kick_unknown", &[("name", &name)]));
+        return true;
+    };
+    if seats[i].local() {
+        outln!("{}", tr("net.kick_self"));
+        return true;
+    }
+    if seats[i].left {
+        outln!("{}", fill("net.kick_gone", &[("name", &seats[i].name)]));
+        return true;
+    }
+    seats[i].tell(tr("net.kicked_you"));
+    seats[i].send(&ServerMsg::End);
+    seats[i].left = true;
+    // The client goes once it has read the end; nothing more it sends is heeded
+    if let Some(client) = &seats[i].client {
+        client.writer.shutdown(Shutdown::Write).ok();
+    }
+    let text = fill("net.kicked", &[("name", &seats[i].name)]);
+    announce(seats, watchers, &text, Some(i));
+    true
+}
+
+/// Counts anyone the host has removed as finished as of `round`.
+fn settle_removals(seats: &mut [Seat], round: u32, started_at: Instant) {
+    for seat in seats.iter_mut().filter(|s| s.left) {
+        seat.finished.get_or_insert((round, started_at.elapsed()));
+    }
+}
+
+/// Before each turn: waits out the host's delay, and for as long as the
+/// game is held, dealing with everything else that happens meanwhile.
+fn between_turns(table: &mut Table, inbox: &mut Inbox) {
+    let until = Instant::now() + inbox.console.delay;
+    while !inbox.console.ended {
+        let event = if inbox.console.paused {
+            inbox.events.recv().ok()
+        } else {
+            inbox.events.recv_timeout(until.saturating_duration_since(Instant::now())).ok()
+        };
+        let Some(event) = event else { break };
+        if let Some((from, _)) = handle_event(&mut table.seats, inbox, event) {
+            stray_input(&mut table.seats, &inbox.console, from);
+        }
+    }
+    settle_removals(&mut table.seats, table.round, table.started_at);
+}
+
+// --- Host Migration ---
+// A game can outlive its host. With `host --handover`, at the top of every
+// turn the host sends one spectator, its successor, a `backup` of the whole
+// game: every seat with its token, secret, salt and guesses, and whose turn
+// it is. The successor answers with the port it has opened to take
+// connections on, and the host tells everyone where that is with `successor`.
+//
+// A client that loses the host tries the host again, then the successor, in
+// turn, for as long as its seat would be held. The successor gives the host a
+// few seconds to come back, then takes the game over from its last backup
+// and hosts it without a seat of its own: the old host's seat is left, the
+// turn that was under way starts again, and everyone else gets their seat
+// back with their token as after any lost connection. If the successor goes,
+// the host picks the spectator who has watched longest of those left.
+//
+// The backup holds every secret, so only a spectator is ever sent one: a
+// player with it could read the others' secrets. While nobody is watching
+// there's no successor, and a game whose host goes ends there, as it
+// always does without handover. The successor's machine is still trusted
+// as much as the host's, so everyone is told when the game starts that
+// handover is on, and hears who the successor is. The seals from the start
+// of the game still hold, so a successor that changes a secret is caught
+// when it is revealed.
+
+/// How long a successor keeps trying the host before taking the game over.
+const HOST_GRACE: Duration = Duration::from_secs(6);
+
+/// Makes sure a spectator is standing by to take the game over, and sends
+/// it the game as it stands. Players are never sent it.
+fn back_up(table: &mut Table, inbox: &mut Inbox) {
+    if inbox.successor.as_ref().is_none_or(|s| !inbox.watchers.contains(s.spectator)) {
+        inbox.successor = inbox.watchers.first().map(|spectator| Successor { spectator, address: None });
+    }
+    let Some(spectator) = inbox.successor.as_ref().map(|s| s.spectator) else {
+        if !inbox.without_successor {
+            outln!("{}", tr("net.no_successor"));
+            inbox.without_successor = true;
+        }
+        return;
+    };
+    inbox.without_successor = false;
+    let state = to_hex(table.to_snapshot().as_bytes());
+    if !inbox.watchers.send_to(spectator, &ServerMsg::Backup(state)) {
+        inbox.successor = None;
+    }
+}
+
+/// Notes the port spectator `id` takes connections on if it's the
+/// successor, and tells everyone where to find it.
+fn note_successor(seats: &mut [Seat], inbox: &mut Inbox, id: usize, port: u16) {
+    let Some(successor) = inbox.successor.as_mut().filter(|s| s.spectator == id) else { return };
+    // The successor is found at the address the host sees it at
+    let Some(ip) = inbox.watchers.address(id) else { return };
+    let address = SocketAddr::new(ip, port).to_string();
+    if successor.address.as_ref() == Some(&address) {
+        return;
+    }
+    successor.address = Some(address);
+    if let Some(notice) = inbox.successor_notice() {
+        for seat in seats.iter_mut() {
+            seat.send(&notice);
+        }
+        inbox.watchers.send(&notice);
+    }
+}
+
+/// A seat's finishing field, or "-" if it has none.
+fn optional_field<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(|| "-".to_string(), |v| v.to_string())
+}
+
+fn parse_optional_field<T: std::str::FromStr>(field: &str) -> Result<Option<T>, SnapshotError> {
+    if field == "-" { Ok(None) } else { parse_number_field(field).map(Some) }
+}
+
+impl Snapshot for Table {
+    const KIND: &'static str = "network";
+
+    fn spec(&self) -> GameSpec {
+        GameSpec::new(Variant::IndividualSecrets, Rules::default())
+    }
+
+    fn records(&self) -> Vec<Vec<String>> {
+        let mut records = vec![vec![
+            "game".to_string(),
+            self.round.to_string(),
+            self.next.to_string(),
+            self.rank_to_assign.to_string(),
+            self.last_ranked_round.to_string(),
+            self.started_at.elapsed().as_millis().to_string(),
+            self.seed.to_string(),
+            clean_field(&self.room),
+            self.spectators.to_string(),
+        ]];
+        for seat in &self.seats {
+            records.push(vec![
+                "seat".to_string(),
+                clean_field(&seat.name),
+                optional_field(seat.token.as_deref()),
+                code_field(&seat.secret),
+                clean_field(&seat.salt),
+                optional_field(seat.rank),
+                optional_field(seat.finished.map(|(round, _)| round)),
+                optional_field(seat.finished.map(|(_, at)| at.as_millis())),
+                if seat.left { "left" } else { "seated" }.to_string(),
+            ]);
+            for (guess, score) in &seat.history {
+                records.push(vec!["guess".to_string(), code_field(guess), score_field(*score)]);
+            }
+        }
+        for m in &self.transcript.moves {
+            records.push(vec![
+                "move".to_string(),
+                m.round.to_string(),
+                clean_field(&m.player),
+                code_field(&m.guess),
+                score_field(m.score),
+                m.at.as_millis().to_string(),
+            ]);
+        }
+        records
+    }
+
+    fn from_body(body: SnapshotBody) -> Result<Self, SnapshotError> {
+        let mut table: Option<Table> = None;
+        let mut seats: Vec<Seat> = Vec::new();
+        let mut moves = Vec::new();
+        for record in &body.records {
+            match record.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+                ["game", round, next, rank, last_round, elapsed, seed, room, spectators] => {
+                    let elapsed = Duration::from_millis(parse_number_field(elapsed)?);
+                    table = Some(Table {
+                        seats: Vec::new(),
+                        round: parse_number_field(round)?,
+                        next: parse_number_field(next)?,
+                        rank_to_assign: parse_number_field(rank)?,
+                        last_ranked_round: parse_number_field(last_round)?,
+                        started_at: Instant::now().checked_sub(elapsed).unwrap_or_else(Instant::now),
+                        seed: parse_number_field(seed)?,
+                        transcript: Transcript::default(),
+                        room: room.to_string(),
+                        spectators: parse_number_field(spectators)?,
+                    });
+                }
+                ["seat", name, token, secret, salt, rank, finished_round, finished_at, state] => {
+                    let finished = match (parse_optional_field(finished_round)?, parse_optional_field(finished_at)?) {
+                        (Some(round), Some(at)) => Some((round, Duration::from_millis(at))),
+                        _ => None,
+                    };
+                    seats.push(Seat {
+                        name: name.to_string(),
+                        token: (*token != "-").then(|| token.to_string()),
+                        client: None,
+                        secret: parse_code_field(secret)?,
+                        salt: salt.to_string(),
+                        guesses: 0,
+                        history: Vec::new(),
+                        rank: parse_optional_field(rank)?,
+                        finished,
+                        left: *state == "left",
+                        lost_at: None,
+                        skipped: false,
+                    });
+                }
+                ["guess", guess, score] => {
+                    let seat = seats.last_mut().ok_or_else(|| SnapshotError::Malformed("a guess comes before any seat".to_string()))?;
+                    seat.history.push((parse_code_field(guess)?, parse_score_field(score)?));
+                    seat.guesses += 1;
+                }
+                ["move", round, name, guess, score, at] => moves.push(Move {
+                    round: parse_number_field(round)?,
+                    player: name.to_string(),
+                    guess: parse_code_field(guess)?,
+                    score: parse_score_field(score)?,
+                    at: Duration::from_millis(parse_number_field(at)?),
+                }),
+                _ => {} // Records from newer builds are skipped
+            }
+        }
+        let mut table = table.ok_or_else(|| SnapshotError::Malformed("missing game record".to_string()))?;
+        if table.next >= seats.len() {
+            return Err(SnapshotError::Malformed("the next turn is for a seat that isn't there".to_string()));
+        }
+        table.seats = seats;
+        table.transcript.moves = moves;
+        Ok(table)
+    }
+}
+
+/// Carries the game on from the host's last `backup`, as its new host, once
+/// the host has gone. `token` is the seat of the player at this machine.
+fn take_over(listener: TcpListener, backup: &str, token: &str, keyboard: Receiver<ClientEvent>, stores: &mut StoreSet) -> Result<(), String> {
+    let text = from_hex(backup).and_then(|bytes| String::from_utf8(bytes).ok()).ok_or(tr("net.backup_unreadable"))?;
+    let mut table = Table::from_snapshot(&text).map_err(|e| fill("net.take_over_failed", &[("error", &e)]))?;
+    let now = Instant::now();
+    let mut old_host = String::new();
+    for seat in &mut table.seats {
+        match seat.token.as_deref() {
+            Some(mine) if mine == token => seat.token = None,
+            Some(_) => seat.lost_at = Some(now),
+            None => {
+                // Nobody can claim the old host's seat back
+                seat.token = Some(session_token());
+                seat.left = true;
+                seat.finished.get_or_insert((table.round, table.started_at.elapsed()));
+                old_host = seat.name.clone();
+            }
+        }
+    }
+    outln!("{}", fill("net.taken_over_you", &[("name", &old_host), ("seconds", &SEAT_HOLD.as_secs())]));
+
+    let (sender, events) = mpsc::channel();
+    accept_clients(listener, sender.clone());
+    // The client's reading thread still has the keyboard, and the old host's messages have stopped
+    forward_typing(keyboard, sender);
+    let mut watchers = Watchers::new(&table.room, table.spectators);
+    if !table.seats.iter().any(Seat::local) {
+        watchers.show_here();
+    }
+    let inbox = Inbox { events, arrivals: BTreeMap::new(), watchers, successor: None, handover: true, without_successor: false, console: Console::default() };
+    play(table, inbox, stores);
+    forget_rejoin();
+    Ok(())
+}
+
+// --- Joining ---
+
+/// Asks the local network which games are waiting for players, giving
+/// each with the address to join it at.
+pub fn find_games() -> io::Result<Vec<(SocketAddr, Advert)>> {
+    let socket = UdpSocket::bind(("0.0.0.0", 0))?;
+    socket.set_broadcast(true)?;
+    // Broadcasts don't always reach a host on this machine, so ask it directly too
+    let mut asked = false;
+    for target in [IpAddr::V4(Ipv4Addr::BROADCAST), IpAddr::V4(Ipv4Addr::LOCALHOST)] {
+        asked |= socket.send_to(format!("{}\t{}", DISCOVERY_QUERY, PROTOCOL_VERSION).as_bytes(), (target, DISCOVERY_PORT)).is_ok();
+    }
+    if !asked {
+        return Err(io::Error::new(io::ErrorKind::NotConnected, "no network to search"));
+    }
+
+    let mut games: Vec<(SocketAddr, Advert)> = Vec::new();
+    let deadline = Instant::now() + DISCOVERY_WAIT;
+    let mut buffer = [0; 512];
+    while let Some(left) = deadline.checked_duration_since(Instant::now()).filter(|d| !d.is_zero()) {
+        socket.set_read_timeout(Some(left))?;
+        let Ok((length, from)) = socket.recv_from(&mut buffer) else { break };
+        let Some(advert) = Advert::parse(&String::from_utf8_lossy(&buffer[..length])) else { continue };
+        // A host on this machine can answer both searches
+        if !games.iter().any(|(address, a)| a.host == advert.host && address.port() == advert.port) {
+            games.push((SocketAddr::new(from.ip(), advert.port), advert));
+        }
+    }
+    Ok(games)
+}
+
+/// Lists the games `find_games` turns up and lets the player pick one.
+fn choose_game() -> Result<Option<String>, String> {
+    outln!("{}", tr("net.searching"));
+    let games = find_games().map_err(|e| fill("net.search_failed", &[("error", &e)]))?;
+    if games.is_empty() {
+        outln!("{}", tr("net.none_found"));
+        return Ok(None);
+    }
+    outln!("{}", tr("net.games"));
+    for (i, (address, advert)) in games.iter().enumerate() {
+        let note = if advert.version > PROTOCOL_VERSION || advert.spec.check_supported().is_err() {
+            tr("net.needs_newer")
+        } else if advert.version < PROTOCOL_VERSION {
+            tr("net.host_needs_newer")
+        } else {
+            ""
+        };
+        outln!("{}", fill("net.game_row", &[
+            ("number", &(i + 1)),
+            ("host", &advert.host),
+            ("address", address),
+            ("rules", &describe_record_key(&advert.spec.rules.record_key())),
+            ("seats", &advert.open_seats),
+            ("note", &note),
+        ]));
+    }
+    loop {
+        let answer = prompt(tr("net.pick_prompt"));
+        if answer.is_empty() {
+            return Ok(None);
+        }
+        match answer.parse::<usize>().ok().and_then(|n| n.checked_sub(1)).and_then(|i| games.get(i)) {
+            Some((address, _)) => return Ok(Some(address.to_string())),
+            None => outln!("{}", fill("net.pick_invalid", &[("max", &games.len())])),
+        }
+    }
+}
+
+/// What a client keeps to check the host's secrets when they're revealed.
+#[derive(Default)]
+struct Seals {
+    commitments: BTreeMap<String, String>, // Each seat's seal, by name
+    revealed: Vec<(String, Guess, String)>, // Each seat's name, secret and salt, from the end
+    feedback: Vec<(Guess, Score)>, // The player's own feedback, checked against their secret
+}
+
+impl Seals {
+    fn commit(&mut self, name: String, hash: String) {
+        // A client that rejoins is sent the seals again, and they mustn't have changed
+        if let Some(old) = self.commitments.get(&name) {
+            if *old != hash {
+                outln!("{}", fill("net.seal_changed", &[("name", &name)]));
+            }
+            return;
+        }
+        self.commitments.insert(name, hash);
+    }
+
+    /// Checks the revealed secrets against their seals, and the feedback
+    /// `me` got (if they played) against their secret, saying how it went.
+    fn check(&self, me: Option<&str>) {
+        if self.revealed.is_empty() {
+            outln!("{}", tr("net.no_reveals"));
+            return;
+        }
+        let mut problems = Vec::new();
+        for (name, hash) in &self.commitments {
+            match self.revealed.iter().find(|(revealed, _, _)| revealed == name) {
+                None => problems.push(fill("net.not_revealed", &[("name", name)])),
+                Some((_, secret, salt)) if commitment(salt, secret) != *hash => {
+                    problems.push(fill("net.wrong_secret", &[("name", name), ("secret", &format_code(secret))]))
+                }
+                Some(_) => {}
+            }
+        }
+        for (name, _, _) in self.revealed.iter().filter(|(name, _, _)| !self.commitments.contains_key(name)) {
+            problems.push(fill("net.never_sealed", &[("name", name)]));
+        }
+        let mine = me.and_then(|me| self.revealed.iter().find(|(name, _, _)| name == me));
+        if let Some((_, secret, _)) = mine {
+            for (guess, score) in &self.feedback {
+                if calculate_score(guess, secret) != *score {
+                    problems.push(fill("net.wrong_feedback", &[("guess", &format_code(guess))]));
+                }
+            }
+        }
+        problems.dedup();
+        if problems.is_empty() {
+            outln!("{}", tr(if mine.is_some() { "net.seals_ok_feedback" } else { "net.seals_ok" }));
+        } else {
+            outln!("{}", tr("net.seals_bad"));
+            for problem in problems {
+                outln!("  - {}", problem);
+            }
+        }
+    }
+}
+
+/// The options of `join`.
+#[derive(Debug, Args)]
+pub struct JoinArgs {
+    /// Watch the game instead of taking a seat
+    #[arg(long)]
+    pub watch: bool,
+    /// The host's machine, with `:<port>` unless it's the default; without it the local network is searched
+    #[arg(value_name = "HOST[:PORT]")]
+    pub address: Option<String>,
+}
+
+/// Entry point for `join`.
+pub fn run_join(args: &JoinArgs, stores: &mut StoreSet) -> Result<(), String> {
+    let watching = args.watch;
+    let mut address = match &args.address {
+        None => match choose_game()? {
+            Some(address) => address,
+            None => return Ok(()),
+        },
+        Some(address) if address.contains(':') => address.clone(),
+        Some(address) => format!("{}:{}", address, DEFAULT_PORT),
+    };
+    let (reader, mut writer) = connect(&address)?;
+
+    outln!("{}", fill(if watching { "net.watching" } else { "net.joining" }, &[("address", &address)]));
+    let lost = |e: io::Error| fill("net.connection_lost", &[("error", &e)]);
+    let hello = match saved_rejoin(&address) {
+        _ if watching => ClientMsg::Watch { version: PROTOCOL_VERSION, room: prompt(tr("net.room_prompt")) },
+        Some((name, token)) if !prompt(&fill("net.rejoin_prompt", &[("name", &name)])).eq_ignore_ascii_case(tr("common.no")) => {
+            ClientMsg::Rejoin { version: PROTOCOL_VERSION, token }
+        }
+        saved => {
+            if saved.is_some() {
+                forget_rejoin();
+            }
+            let name = prompt(tr("common.name_prompt"));
+            let room = prompt(tr("net.room_prompt"));
+            ClientMsg::Hello { version: PROTOCOL_VERSION, name, room }
+        }
+    };
+    send(&mut writer, &hello).map_err(lost)?;
+
+    // The host's messages and the player's typing are read apart, so room
+    // news shows up while the player is typing a room command
+    let (sender, events) = mpsc::channel();
+    listen_to_host(reader, sender.clone());
+    read_typing(sender.clone());
+
+    let mut in_lobby = true;
+    let mut asked = false;
+    let mut left = false; // Whether the player typed /quit during the game
+    let mut token: Option<String> = None;
+    let mut seat_name: Option<String> = None;
+    let mut seals = Seals::default();
+    let mut successor: Option<(String, String)> = None; // Who takes over if the host goes, and where they are
+    let mut standby: Option<(TcpListener, String)> = None; // Where to take connections and the latest backup, if that's us
+    loop {
+        match events.recv().map_err(|_| tr("net.closed"))? {
+            ClientEvent::Host(Err(e)) => {
+                let comeback = match &token {
+                    Some(token) if !in_lobby => ClientMsg::Rejoin { version: PROTOCOL_VERSION, token: token.clone() },
+                    _ if watching && successor.is_some() => hello.clone(),
+                    _ => return Err(lost(e)),
+                };
+                // A send that fails is noticed here too, so only lost typing goes unsent
+                if let Some((listener, backup)) = standby.take() {
+                    outln!("{}", tr("net.checking_host"));
+                    match reconnect(std::slice::from_ref(&address), &comeback, &sender, HOST_GRACE) {
+                        Ok((back, _)) => {
+                            writer = back;
+                            standby = Some((listener, backup));
+                        }
+                        Err(_) => return take_over(listener, &backup, token.as_deref().unwrap_or_default(), events, stores),
+                    }
+                } else {
+                    outln!("{}", tr("net.reconnecting"));
+                    let addresses: Vec<String> = std::iter::once(address.clone()).chain(successor.iter().map(|(_, at)| at.clone())).collect();
+                    let (back, reached) = reconnect(&addresses, &comeback, &sender, SEAT_HOLD)?;
+                    if reached != address
+                        && let Some((name, _)) = successor.take()
+                    {
+                        outln!("{}", fill("net.taken_over", &[("name", &name)]));
+                        address = reached;
+                    }
+                    writer = back;
+                }
+                asked = false;
+            }
+            ClientEvent::Host(Ok(message)) => match message {
+                ServerMsg::Spec(spec) => {
+                    spec.check_supported().map_err(|e| fill("net.cant_join", &[("error", &e)]))?;
+                    set_code_length(spec.rules.code_length);
+                }
+                ServerMsg::Welcome { name, token: given } => {
+                    outln!("{}", fill("net.seated", &[("name", &name)]));
+                    save_rejoin(&address, &name, &given);
+                    token = Some(given);
+                    seat_name = Some(name);
+                }
+                ServerMsg::Refuse(reason) => {
+                    forget_rejoin();
+                    return Err(fill("net.refused", &[("reason", &reason)]));
+                }
+                ServerMsg::Info(text) => outln!("{}", text),
+                ServerMsg::Prompt(text) => {
+                    show_prompt(&text);
+                    asked = true;
+                }
+                ServerMsg::Start => {
+                    in_lobby = false;
+                    send(&mut writer, &ClientMsg::Started).ok();
+                }
+                ServerMsg::Commit { name, hash } => seals.commit(name, hash),
+                ServerMsg::Feedback { guess, score } => {
+                    show_feedback(&guess, score);
+                    seals.feedback.push((guess, score));
+                }
+                ServerMsg::Reveal { name, secret, salt } => seals.revealed.push((name, secret, salt)),
+                ServerMsg::Backup(_) if !watching => {} // Only spectators stand by
+                ServerMsg::Backup(state) => {
+                    // Picked to take over if the host goes, so be ready to take connections
+                    if standby.is_none()
+                        && let Ok(listener) = TcpListener::bind(("0.0.0.0", 0))
+                    {
+                        standby = Some((listener, String::new()));
+                    }
+                    if let Some((listener, backup)) = &mut standby {
+                        *backup = state;
+                        if let Ok(local) = listener.local_addr() {
+                            send(&mut writer, &ClientMsg::Listening(local.port())).ok();
+                        }
+                    }
+                }
+                ServerMsg::Successor { name, address: at } => {
+                    let news = successor.as_ref().is_none_or(|(known, _)| *known != name);
+                    // The successor is told apart by the port it takes connections on
+                    let listening = standby.as_ref().and_then(|(listener, _)| listener.local_addr().ok()).map(|local| local.port());
+                    if listening.is_none() || at.parse::<SocketAddr>().ok().map(|a| a.port()) != listening {
+                        standby = None; // Someone else was picked after all
+                        if news {
+                            outln!("{}", fill("net.successor", &[("name", &name)]));
+                        }
+                    } else if news {
+                        outln!("{}", tr("net.successor_you"));
+                    }
+                    successor = Some((name, at));
+                }
+                ServerMsg::End => {
+                    forget_rejoin();
+                    // A player who leaves gets no reveals, since the game goes on
+                    // without them. Anyone else missing one has been cheated of the check.
+                    if !seals.commitments.is_empty() && !left {
+                        seals.check(seat_name.as_deref());
+                    }
+                    return Ok(());
+                }
+                ServerMsg::Greeting { .. } => {} // Already checked
+            },
+            ClientEvent::Typed(text) if watching => {
+                if text == "/quit" {
+                    return Ok(());
+                }
+                outln!("{}", tr("net.watch_only"));
+            }
+            ClientEvent::Typed(text) => match said(&text) {
+                Some("") => outln!("{}", tr("net.say_usage")),
+                Some(message) => {
+                    send(&mut writer, &ClientMsg::Chat(message.to_string())).ok();
+                }
+                None if asked || in_lobby => {
+                    asked = false;
+                    left = !in_lobby && text == "/quit";
+                    send(&mut writer, &ClientMsg::Input(text)).ok();
+                }
+                None => outln!("{}", tr("net.not_your_turn")),
+            },
+        }
+    }
+}
+
+/// Connects to the host at `address` and checks its greeting.
+fn connect(address: &str) -> Result<(BufReader<TcpStream>, TcpStream), String> {
+    let writer = TcpStream::connect(address).map_err(|e| fill("net.unreachable", &[("address", &address), ("error", &e)]))?;
+    writer.set_nodelay(true).ok();
+    let mut reader = BufReader::new(writer.try_clone().map_err(|e| e.to_string())?);
+    expect_greeting(&mut reader)?;
+    Ok((reader, writer))
+}
+
+/// What a joined client waits for: word from the host, or a line the player typed.
+enum ClientEvent {
+    Host(io::Result<ServerMsg>),
+    Typed(String),
+}
+
+/// Passes on the host's messages until the game ends or the connection drops.
+fn listen_to_host(mut reader: BufReader<TcpStream>, events: Sender<ClientEvent>) {
+    thread::spawn(move || {
+        loop {
+            let message = receive(&mut reader);
+            let last = !matches!(message, Ok(ref m) if *m != ServerMsg::End && !matches!(m, ServerMsg::Refuse(_)));
+            if events.send(ClientEvent::Host(message)).is_err() || last {
+                return;
+            }
+        }
+    });
+}
+
+/// Passes on every line the player types, answering `/help` and the like on the spot.
+fn read_typing(events: Sender<ClientEvent>) {
+    thread::spawn(move || {
+        loop {
+            let line = read_line();
+            if !answer_command(&line) && events.send(ClientEvent::Typed(line)).is_err() {
+                return;
+            }
+        }
+    });
+}
+
+// --- Rejoining ---
+// A client that loses its connection mid-game keeps trying to get back in for
+// as long as the host holds its seat. The token the host gave it is also kept
+// in `rejoin.txt` in the data directory until the game ends, so running
+// `join` again (after a crash, say) offers the seat back.
+
+/// How long a client waits between tries at getting back in.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(2);
+
+fn rejoin_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("rejoin.txt"))
+}
+
+fn save_rejoin(address: &str, name: &str, token: &str) {
+    if let Some(path) = rejoin_path() {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).ok();
+        }
+        fs::write(path, format!("{}\t{}\t{}\n", address, clean_field(name), token)).ok();
+    }
+}
+
+/// The name and token of a seat still held at `address`, if there is one.
+fn saved_rejoin(address: &str) -> Option<(String, String)> {
+    let text = fs::read_to_string(rejoin_path()?).ok()?;
+    match text.trim_end().split('\t').collect::<Vec<_>>().as_slice() {
+        [saved, name, token] if *saved == address => Some((name.to_string(), token.to_string())),
+        _ => None,
+    }
+}
+
+fn forget_rejoin() {
+    if let Some(path) = rejoin_path() {
+        fs::remove_file(path).ok();
+    }
+}
+
+/// Tries each of `addresses` in turn for up to `within` (as long as the host
+/// holds a seat, usually) to get back into the game with `comeback`, passing
+/// the host's messages on to `events` again. Returns the connection and the
+/// address it was made to.
+fn reconnect(addresses: &[String], comeback: &ClientMsg, events: &Sender<ClientEvent>, within: Duration) -> Result<(TcpStream, String), String> {
+    let deadline = Instant::now() + within;
+    loop {
+        for address in addresses {
+            if let Ok((reader, mut writer)) = connect(address)
+                && send(&mut writer, comeback).is_ok()
+            {
+                listen_to_host(reader, events.clone());
+                return Ok((writer, address.clone()));
+            }
+        }
+        if Instant::now() + RECONNECT_INTERVAL > deadline {
+            return Err(fill("net.rejoin_failed", &[("address", &addresses[0])]));
+        }
+        thread::sleep(RECONNECT_INTERVAL);
+    }
+}
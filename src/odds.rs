@@ -0,0 +1,48 @@
+use rand::seq::IndexedRandom;
+use rand::Rng;
+
+use crate::code::{calculate_score, Guess};
+use crate::solver::filter_candidates;
+
+// --- Win Chances ---
+// Estimates who is likely to crack their code first by Monte Carlo rollouts.
+// Each player's future is sampled from their own candidate set: a secret is
+// drawn from the codes that still fit their feedback, then played out by
+// guessing random candidates. Players don't affect each other's codes, so
+// whoever's playout is shortest, along the turn order, wins the rollout.
+
+/// How many imagined finishes of the game are played out.
+const ROLLOUTS: usize = 200;
+
+/// How many more guesses one random-candidate playout takes to crack a secret
+/// drawn from `candidates`, including the guess that cracks it.
+fn playout(candidates: &[Guess], rng: &mut impl Rng) -> u32 {
+    let Some(&secret) = candidates.choose(rng) else { return 0 };
+    let mut remaining = candidates.to_vec();
+    let mut guesses = 0;
+    loop {
+        guesses += 1;
+        let guess = *remaining.choose(rng).expect("the secret is always a candidate");
+        if guess == secret {
+            return guesses;
+        }
+        filter_candidates(&mut remaining, &guess, calculate_score(&guess, &secret));
+    }
+}
+
+/// Each player's chance of being the next to crack their code, from 0.0 to 1.0.
+/// `candidates` are listed in turn order, starting with the player about to move.
+pub fn win_chances(candidates: &[&[Guess]], rng: &mut impl Rng) -> Vec<f64> {
+    let seats = candidates.len();
+    if seats == 0 {
+        return Vec::new();
+    }
+    let mut wins = vec![0usize; seats];
+    for _ in 0..ROLLOUTS {
+        // Seats take turns, so a player needing k more guesses finishes on turn (k - 1) * seats + seat
+        let finishing_turn = |seat: usize, rng: &mut _| (playout(candidates[seat], rng) as usize).saturating_sub(1) * seats + seat;
+        let winner = (0..seats).min_by_key(|&seat| finishing_turn(seat, rng)).expect("there is at least one seat");
+        wins[winner] += 1;
+    }
+    wins.into_iter().map(|n| n as f64 / ROLLOUTS as f64).collect()
+}
@@ -0,0 +1,249 @@
+use crate::coach::coach_guess;
+use crate::code::{calculate_score, format_code, generate_secret, is_solved, parse_guess, Guess, Score};
+use crate::console::{clear_screen, prompt, prompt_with_history};
+use crate::feedback::player_notation;
+use crate::rules::{GameSpec, Rules, Variant};
+use crate::session::{Session, Solve};
+use crate::snapshot::{code_field, parse_code_field, parse_score_field, score_field, Snapshot, SnapshotBody, SnapshotError};
+use crate::solver::{all_codes, filter_candidates};
+use crate::strings::{fill, tr};
+use crate::theme::paint_feedback;
+
+// --- Practice Sandbox ---
+// A relaxed solo game for learning: every guess can be undone and redone,
+// and a line abandoned by undoing is kept so it can be compared with the
+// line played instead. The undo history holds engine snapshots, the same
+// format used for saves.
+
+/// The engine state of a practice game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PracticeState {
+    pub secret: Guess,
+    pub turns: Vec<(Guess, Score)>,
+}
+
+impl PracticeState {
+    pub fn new(secret: Guess) -> Self {
+        PracticeState { secret, turns: Vec::new() }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.turns.last().is_some_and(|&(_, score)| is_solved(score))
+    }
+
+    /// Scores a guess and adds it to the game.
+    pub fn play(&mut self, guess: Guess) -> Score {
+        let score = calculate_score(&guess, &self.secret);
+        self.turns.push((guess, score));
+        score
+    }
+
+    /// The codes that still fit every turn's feedback.
+    pub fn candidates(&self) -> Vec<Guess> {
+        let mut candidates = all_codes();
+        for (guess, score) in &self.turns {
+            filter_candidates(&mut candidates, guess, *score);
+        }
+        candidates
+    }
+
+    /// How many codes were still possible after each turn.
+    pub fn remaining_after_each_turn(&self) -> Vec<usize> {
+        let mut candidates = all_codes();
+        self.turns
+            .iter()
+            .map(|(guess, score)| {
+                filter_candidates(&mut candidates, guess, *score);
+                candidates.len()
+            })
+            .collect()
+    }
+}
+
+impl Snapshot for PracticeState {
+    const KIND: &'static str = "practice";
+
+    fn spec(&self) -> GameSpec {
+        GameSpec::new(Variant::IndividualSecrets, Rules::default())
+    }
+
+    fn records(&self) -> Vec<Vec<String>> {
+        let mut records = vec![vec!["secret".to_string(), code_field(&self.secret)]];
+        for (guess, score) in &self.turns {
+            records.push(vec!["turn".to_string(), code_field(guess), score_field(*score)]);
+        }
+        records
+    }
+
+    fn from_body(body: SnapshotBody) -> Result<Self, SnapshotError> {
+        let mut secret = None;
+        let mut turns = Vec::new();
+        for record in &body.records {
+            match record.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+                ["secret", code] => secret = Some(parse_code_field(code)?),
+                ["turn", guess, score] => turns.push((parse_code_field(guess)?, parse_score_field(score)?)),
+                _ => {} // Records from newer builds are skipped
+            }
+        }
+        let secret = secret.ok_or_else(|| SnapshotError::Malformed("missing secret".to_string()))?;
+        Ok(PracticeState { secret, turns })
+    }
+}
+
+/// A practice game with its undo/redo history.
+pub struct PracticeGame {
+    current: PracticeState,
+    undo: Vec<String>, // Snapshots of earlier states, most recent last
+    redo: Vec<String>, // Snapshots of undone states, most recent undo last
+    abandoned: Vec<PracticeState>, // Lines discarded by playing a new guess after undoing
+}
+
+impl PracticeGame {
+    pub fn new(secret: Guess) -> Self {
+        PracticeGame { current: PracticeState::new(secret), undo: Vec::new(), redo: Vec::new(), abandoned: Vec::new() }
+    }
+
+    pub fn state(&self) -> &PracticeState {
+        &self.current
+    }
+
+    pub fn abandoned_lines(&self) -> &[PracticeState] {
+        &self.abandoned
+    }
+
+    fn restore(snapshot: &str) -> PracticeState {
+        PracticeState::from_snapshot(snapshot).expect("snapshots written by this game always restore")
+    }
+
+    /// Plays a guess. If guesses had been undone, the undone line is set aside for comparison.
+    pub fn play(&mut self, guess: Guess) -> Score {
+        if let Some(furthest) = self.redo.first() {
+            self.abandoned.push(Self::restore(furthest));
+            self.redo.clear();
+        }
+        self.undo.push(self.current.to_snapshot());
+        self.current.play(guess)
+    }
+
+    /// Steps back one guess. Returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(previous) = self.undo.pop() else { return false };
+        self.redo.push(self.current.to_snapshot());
+        self.current = Self::restore(&previous);
+        true
+    }
+
+    /// Replays the most recently undone guess. Returns false if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(next) = self.redo.pop() else { return false };
+        self.undo.push(self.current.to_snapshot());
+        self.current = Self::restore(&next);
+        true
+    }
+}
+
+// --- Practice Screen ---
+
+/// Prints a line of play with the candidates left after each guess.
+fn print_line(state: &PracticeState) {
+    if state.turns.is_empty() {
+        outln!("{}", tr("practice.no_guesses"));
+    }
+    for (i, ((guess, score), remaining)) in state.turns.iter().zip(state.remaining_after_each_turn()).enumerate() {
+        outln!("{}", fill("practice.line_row", &[
+            ("number", &format!("{:>2}", i + 1)),
+            ("guess", &format_code(guess)),
+            ("score", &player_notation().render(*score)),
+            ("remaining", &remaining),
+        ]));
+    }
+}
+
+/// Shows each abandoned line next to the current one.
+fn print_comparison(game: &PracticeGame) {
+    if game.abandoned_lines().is_empty() {
+        outln!("{}", tr("practice.no_lines"));
+        return;
+    }
+    for (i, line) in game.abandoned_lines().iter().enumerate() {
+        outln!("{}", fill("practice.earlier_line", &[("number", &(i + 1)), ("status", &line_status(line))]));
+        print_line(line);
+    }
+    outln!("{}", fill("practice.current_line", &[("status", &line_status(game.state()))]));
+    print_line(game.state());
+}
+
+fn line_status(line: &PracticeState) -> &'static str {
+    tr(if line.is_solved() { "practice.solved" } else { "practice.unfinished" })
+}
+
+fn print_help() {
+    outln!("{}", tr("practice.commands"));
+}
+
+/// Runs a practice game with undo/redo.
+pub fn run_practice(session: &mut Session) {
+    clear_screen();
+    outln!("{}", tr("practice.title"));
+    outln!("{}", tr("practice.intro"));
+    print_help();
+
+    let player = prompt(tr("common.name_prompt"));
+    let mut game = PracticeGame::new(generate_secret());
+    let mut revealed = false;
+
+    loop {
+        let input = if game.state().is_solved() {
+            prompt(tr("practice.solved_prompt"))
+        } else {
+            let recall: Vec<String> = game.state().turns.iter().map(|(guess, _)| format_code(guess)).collect();
+            prompt_with_history(&fill("practice.guess_prompt", &[("number", &(game.state().turns.len() + 1))]), &recall)
+        };
+
+        match input.as_str() {
+            "" if game.state().is_solved() => break,
+            "/undo" => {
+                if game.undo() {
+                    outln!("{}", fill("practice.undone", &[("number", &(game.state().turns.len() + 1))]));
+                } else {
+                    outln!("{}", tr("practice.nothing_to_undo"));
+                }
+            }
+            "/redo" => {
+                if game.redo() {
+                    outln!("{}", fill("practice.redone", &[("number", &game.state().turns.len())]));
+                } else {
+                    outln!("{}", tr("practice.nothing_to_redo"));
+                }
+            }
+            "/history" => print_line(game.state()),
+            "/compare" => print_comparison(&game),
+            "/reveal" => {
+                outln!("{}", fill("practice.secret", &[("secret", &format_code(&game.state().secret))]));
+                revealed = true;
+            }
+            "/quit" => break,
+            _ if game.state().is_solved() => print_help(),
+            _ => match parse_guess(&input) {
+                Ok(guess) => {
+                    let candidates = game.state().candidates();
+                    let score = game.play(guess);
+                    outln!("{}", fill("game.feedback", &[("guess", &format_code(&guess)), ("score", &paint_feedback(score, &player_notation().render(score)))]));
+                    coach_guess(&guess, &candidates);
+                    if is_solved(score) {
+                        outln!("{}", fill("practice.cracked", &[("count", &game.state().turns.len())]));
+                    }
+                }
+                Err(message) => {
+                    outln!("{}", message);
+                    print_help();
+                }
+            },
+        }
+    }
+
+    // A peek at the secret means the solve doesn't count towards session trivia
+    let state = game.state();
+    let solve = (state.is_solved() && !revealed).then_some(Solve { player, guesses: state.turns.len() as u32 });
+    session.record_secret("practice", state.secret, solve);
+}
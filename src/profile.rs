@@ -0,0 +1,429 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+use clap::Subcommand;
+
+use crate::achievement::Achievement;
+use crate::config::Config;
+use crate::console::prompt;
+use crate::glyphs::OutputProfile;
+use crate::locale::Locale;
+use crate::rules::SUPPORTED_CODE_LENGTHS;
+use crate::stats::{update_leaderboard, PlayerSummary};
+use crate::store::{clean_field, data_dir, format_game_id, legacy_game_id, parse_game_id, GameResult, StoreSet};
+use crate::strings::{fill, tr};
+use crate::theme::Theme;
+
+// --- Player Profiles ---
+// A player who enters the same name at setup gets the same profile back: the
+// theme, symbols and code length they prefer, and the achievements they've
+// earned, kept in `profiles/<name>.txt` in the data directory as
+// `key = value` lines, like the settings. While it's
+// their turn, their theme and symbols are used. Their stats need no link of
+// their own, as results are already saved under the same name.
+
+/// One player's preferences; None leaves a choice to the settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Profile {
+    pub name: String,
+    pub theme: Option<String>,
+    pub output: Option<OutputProfile>,
+    pub code_length: Option<usize>,
+    pub achievements: Vec<Achievement>, // In the order they were earned
+}
+
+/// The file name for a player's profile: their name in lower case, without
+/// anything that can't go in a file name. Names with nothing left have no profile.
+fn file_stem(name: &str) -> Option<String> {
+    let stem: String = name.chars().filter(|c| c.is_alphanumeric() || matches!(c, '-' | '_')).flat_map(char::to_lowercase).collect();
+    (!stem.is_empty()).then_some(stem)
+}
+
+/// The next of `options` after `current`, with None before the first.
+fn cycle<T: Clone + PartialEq>(options: &[T], current: &Option<T>) -> Option<T> {
+    match current {
+        None => options.first().cloned(),
+        Some(value) => options.iter().position(|o| o == value).and_then(|i| options.get(i + 1)).cloned(),
+    }
+}
+
+impl Profile {
+    /// Where the profile for `name` lives, if there is a data directory.
+    pub fn path(name: &str) -> Option<PathBuf> {
+        Some(data_dir()?.join("profiles").join(format!("{}.txt", file_stem(name)?)))
+    }
+
+    /// Parses a profile file. Values that can't be understood are left to the settings.
+    pub fn parse(name: &str, text: &str) -> Profile {
+        let mut profile = Profile { name: name.to_string(), ..Profile::default() };
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once('=') else { continue };
+            let value = value.trim();
+            match key.trim() {
+                "theme" if !value.is_empty() => profile.theme = Some(value.to_string()),
+                "output" => profile.output = OutputProfile::from_id(value),
+                "code_length" => profile.code_length = value.parse().ok().filter(|n| SUPPORTED_CODE_LENGTHS.contains(n)),
+                "achievements" => profile.achievements = value.split(',').filter_map(|id| Achievement::from_id(id.trim())).collect(),
+                _ => {}
+            }
+        }
+        profile
+    }
+
+    /// Renders the profile in the format read by `parse`.
+    pub fn to_text(&self) -> String {
+        let mut text = format!("# Profile for {}\n", self.name);
+        if let Some(theme) = &self.theme {
+            text.push_str(&format!("theme = {}\n", theme));
+        }
+        if let Some(output) = self.output {
+            text.push_str(&format!("output = {}\n", output.id()));
+        }
+        if let Some(length) = self.code_length {
+            text.push_str(&format!("code_length = {}\n", length));
+        }
+        if !self.achievements.is_empty() {
+            let ids: Vec<&str> = self.achievements.iter().map(|a| a.id()).collect();
+            text.push_str(&format!("achievements = {}\n", ids.join(", ")));
+        }
+        text
+    }
+
+    /// The saved profile for `name`, if there is one.
+    pub fn load(name: &str) -> Option<Profile> {
+        let text = fs::read_to_string(Profile::path(name)?).ok()?;
+        Some(Profile::parse(name, &text))
+    }
+
+    /// Writes the profile, creating the profiles directory if needed.
+    pub fn save(&self) -> io::Result<()> {
+        let path = Profile::path(&self.name).ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory, or a name that can't be a file name"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_text())
+    }
+
+    /// A short list of the preferences, for the welcome message.
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(theme) = &self.theme {
+            parts.push(fill("profile.part_theme", &[("value", theme)]));
+        }
+        if let Some(output) = self.output {
+            parts.push(fill("profile.part_output", &[("value", &tr(output.label_id()))]));
+        }
+        if let Some(length) = self.code_length {
+            parts.push(fill("profile.part_length", &[("count", &length)]));
+        }
+        if parts.is_empty() { tr("profile.no_preferences").to_string() } else { parts.join(", ") }
+    }
+
+    /// Makes the theme and symbols the player's own while it's their turn.
+    pub fn apply(profile: Option<&Profile>) {
+        Theme::set_player(profile.and_then(|p| p.theme.clone()));
+        OutputProfile::set_player(profile.and_then(|p| p.output));
+    }
+}
+
+/// Greets a returning player once their name has been seated, with their
+/// preferences and what the stats say about them.
+pub fn welcome(profile: &Profile, summary: Option<&PlayerSummary>) {
+    outln!("{}", fill("profile.welcome", &[("name", &profile.name), ("preferences", &profile.describe())]));
+    if let Some(summary) = summary {
+        let average = summary.total_guesses as f64 / summary.games.max(1) as f64;
+        outln!("{}", fill("profile.stats", &[("games", &summary.games), ("wins", &summary.wins), ("average", &format!("{:.1}", average))]));
+    }
+}
+
+/// Lets a player set up or change their profile; changes are saved on the way out.
+pub fn edit_profile(name: &str) {
+    let mut profile = Profile::load(name).unwrap_or_else(|| Profile { name: name.to_string(), ..Profile::default() });
+    let lengths: Vec<usize> = SUPPORTED_CODE_LENGTHS.collect();
+    loop {
+        let from_settings = tr("profile.from_settings");
+        outln!("{}", fill("profile.title", &[("name", &profile.name)]));
+        outln!("{}", fill("profile.theme", &[("value", &profile.theme.clone().unwrap_or_else(|| from_settings.to_string()))]));
+        outln!("{}", fill("profile.output", &[("value", &profile.output.map_or(from_settings, |o| tr(o.label_id())))]));
+        outln!("{}", fill("profile.length", &[("value", &profile.code_length.map_or(from_settings.to_string(), |n| n.to_string()))]));
+        if !profile.achievements.is_empty() {
+            let names: Vec<&str> = profile.achievements.iter().map(|a| a.name()).collect();
+            outln!("{}", fill("profile.achievements", &[("list", &names.join(", "))]));
+        }
+        outln!("{}", tr("profile.done"));
+
+        match prompt(tr("profile.prompt")).as_str() {
+            "1" => {
+                let themes: Vec<String> = Theme::all(&Config::current()).into_iter().map(|t| t.name).collect();
+                profile.theme = cycle(&themes, &profile.theme);
+            }
+            "2" => profile.output = cycle(&OutputProfile::ALL, &profile.output),
+            "3" => profile.code_length = cycle(&lengths, &profile.code_length),
+            "4" => {
+                if let Err(e) = profile.save() {
+                    outln!("{}", fill("profile.save_failed", &[("error", &e)]));
+                    prompt(tr("common.continue"));
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+}
+
+// --- Moving Profiles ---
+// `profile export <name>` bundles a player's profile and their saved results
+// (from the personal and club stores) into one text file, and `profile
+// import <file>` adds it to this machine's profile and stores. Like the
+// stats store, the bundle is one record per line with tab-separated fields:
+//
+//   sgg-profile 1
+//   name    Ann
+//   pref    theme = Ocean              A line of the profile file
+//   result  timestamp, mode, rank, field size, guesses, rules
+//
+// Importing merges rather than replaces. Achievements are combined, results
+// already here are skipped, and a result that disagrees with this machine's
+// record of the same game (same time, mode and player) is left out in favour
+// of the local one and listed. If both sides have different preferences,
+// the player picks which to keep.
+
+const BUNDLE_HEADER: &str = "sgg-profile 1";
+
+/// What `profile` does.
+#[derive(Debug, Subcommand)]
+pub enum ProfileCommand {
+    /// Write a player's profile and results to one file
+    Export {
+        /// The player whose profile is written
+        name: String,
+        /// The file written to, `<name>.profile.txt` if not given
+        file: Option<String>,
+    },
+    /// Merge a profile written by `profile export` into this machine's
+    Import {
+        /// The file written by `profile export`
+        file: PathBuf,
+        /// Import the profile under this name instead
+        #[arg(long = "as", value_name = "NAME")]
+        rename: Option<String>,
+    },
+}
+
+/// A profile and its results, as carried between machines.
+#[derive(Debug, Clone)]
+pub struct ProfileBundle {
+    pub profile: Profile,
+    pub results: Vec<GameResult>,
+}
+
+impl ProfileBundle {
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{}\nname\t{}\n", BUNDLE_HEADER, clean_field(&self.profile.name));
+        for line in self.profile.to_text().lines().filter(|line| !line.starts_with('#')) {
+            text.push_str(&format!("pref\t{}\n", line));
+        }
+        for r in &self.results {
+            text.push_str(&format!(
+                "result\t{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+                r.timestamp,
+                clean_field(&r.mode),
+                r.rank,
+                r.field_size,
+                r.guesses,
+                clean_field(&r.rules),
+                format_game_id(r.game)
+            ));
+        }
+        text
+    }
+
+    /// Reads a bundle, explaining what's wrong with it if it can't be used.
+    pub fn parse(text: &str) -> Result<ProfileBundle, String> {
+        let mut lines = text.lines();
+        if lines.next() != Some(BUNDLE_HEADER) {
+            return Err("it isn't a profile file".to_string());
+        }
+        let mut name = None;
+        let mut preferences = Vec::new();
+        let mut results = Vec::new();
+        for (number, line) in lines.enumerate().filter(|(_, l)| !l.is_empty()) {
+            let bad = || format!("line {} is damaged", number + 2);
+            match line.split('\t').collect::<Vec<_>>().as_slice() {
+                ["name", value] => name = Some(value.to_string()),
+                ["pref", value] => preferences.push(*value),
+                // Bundles from before game ids leave the last field off
+                ["result", timestamp, mode, rank, field_size, guesses, rules, game @ ..] if game.len() <= 1 => {
+                    let timestamp = timestamp.parse().map_err(|_| bad())?;
+                    let game = match game.first() {
+                        Some(game) => parse_game_id(game).ok_or_else(bad)?,
+                        None => legacy_game_id(timestamp, mode),
+                    };
+                    results.push(GameResult {
+                        game,
+                        timestamp,
+                        mode: mode.to_string(),
+                        player: String::new(), // Filled in below, once the name is known
+                        rank: rank.parse().map_err(|_| bad())?,
+                        field_size: field_size.parse().map_err(|_| bad())?,
+                        guesses: guesses.parse().map_err(|_| bad())?,
+                        rules: rules.to_string(),
+                    })
+                }
+                _ => {} // Records from newer builds are skipped
+            }
+        }
+        let name = name.filter(|n| !n.is_empty()).ok_or("it doesn't say whose profile it is")?;
+        for r in &mut results {
+            r.player = name.clone();
+        }
+        Ok(ProfileBundle { profile: Profile::parse(&name, &preferences.join("\n")), results })
+    }
+
+    /// The same bundle for a player going by `name` on this machine.
+    fn renamed(mut self, name: &str) -> ProfileBundle {
+        self.profile.name = name.to_string();
+        for r in &mut self.results {
+            r.player = name.to_string();
+        }
+        self
+    }
+}
+
+impl Profile {
+    fn has_preferences(&self) -> bool {
+        self.theme.is_some() || self.output.is_some() || self.code_length.is_some()
+    }
+
+    fn same_preferences(&self, other: &Profile) -> bool {
+        (&self.theme, self.output, self.code_length) == (&other.theme, other.output, other.code_length)
+    }
+}
+
+/// Writes `name`'s profile and results to `file` (by default `<name>.profile.txt` here).
+fn export_profile(name: &str, file: Option<&str>, stores: &StoreSet) -> Result<(), String> {
+    let saved = Profile::load(name);
+    let mut results: Vec<GameResult> = Vec::new();
+    for r in stores.personal.results().iter().chain(stores.club.iter().flat_map(|club| club.results())) {
+        if r.player == name && !results.contains(r) {
+            results.push(r.clone());
+        }
+    }
+    if saved.is_none() && results.is_empty() {
+        return Err(fill("profile.nothing_to_export", &[("name", &name)]));
+    }
+    let profile = saved.unwrap_or_else(|| Profile { name: name.to_string(), ..Profile::default() });
+    let path = PathBuf::from(match file {
+        Some(file) => file.to_string(),
+        None => format!("{}.profile.txt", file_stem(name).ok_or_else(|| fill("profile.bad_file_name", &[("name", &name)]))?),
+    });
+    let bundle = ProfileBundle { profile, results };
+    fs::write(&path, bundle.to_text()).map_err(|e| fill("common.write_failed", &[("path", &path.display()), ("error", &e)]))?;
+    outln!("{}", fill("profile.exported", &[
+        ("name", &name),
+        ("achievements", &bundle.profile.achievements.len()),
+        ("results", &bundle.results.len()),
+        ("path", &path.display()),
+    ]));
+    outln!("{}", fill("profile.import_hint", &[("path", &path.display())]));
+    Ok(())
+}
+
+/// Combines an imported profile with the one already here, if any.
+fn merge_profile(imported: Profile) -> Profile {
+    let Some(mut local) = Profile::load(&imported.name) else { return imported };
+    if imported.has_preferences() && !local.same_preferences(&imported) {
+        let replace = !local.has_preferences() || {
+            outln!("{}", fill("profile.other_preferences", &[("name", &local.name)]));
+            outln!("{}", fill("profile.here", &[("preferences", &local.describe())]));
+            outln!("{}", fill("profile.imported_preferences", &[("preferences", &imported.describe())]));
+            prompt(tr("profile.replace_prompt")).eq_ignore_ascii_case(tr("common.yes"))
+        };
+        if replace {
+            local.theme = imported.theme;
+            local.output = imported.output;
+            local.code_length = imported.code_length;
+        }
+    }
+    for achievement in imported.achievements {
+        if !local.achievements.contains(&achievement) {
+            local.achievements.push(achievement);
+        }
+    }
+    local
+}
+
+/// Adds the results of a bundle to the stores they belong in, leaving out
+/// those already here and those that disagree with this machine's history.
+fn merge_results(results: Vec<GameResult>, stores: &mut StoreSet) -> Result<(), String> {
+    let locale = Locale::current();
+    let (mut duplicates, mut conflicts) = (0, Vec::new());
+    let (mut group, mut solo) = (Vec::new(), Vec::new());
+    for r in results {
+        let existing = stores.store_for(r.field_size).results();
+        if existing.contains(&r) {
+            duplicates += 1;
+        } else if let Some(local) = existing.iter().find(|e| e.timestamp == r.timestamp && e.mode == r.mode && e.player == r.player) {
+            let when = locale.timestamp(UNIX_EPOCH + Duration::from_secs(r.timestamp));
+            conflicts.push(fill("profile.conflict", &[
+                ("when", &when),
+                ("mode", &r.mode),
+                ("here_place", &locale.ordinal(local.rank)),
+                ("here_guesses", &local.guesses),
+                ("place", &locale.ordinal(r.rank)),
+                ("guesses", &r.guesses),
+            ]));
+        } else if r.field_size > 1 {
+            group.push(r);
+        } else {
+            solo.push(r);
+        }
+    }
+
+    let added = group.len() + solo.len();
+    for (field_size, batch) in [(2, group), (1, solo)] {
+        if batch.is_empty() {
+            continue;
+        }
+        let store = stores.store_for(field_size);
+        store.record_game(batch, &[]).map_err(|e| fill("profile.save_results_failed", &[("error", &e)]))?;
+        update_leaderboard(store);
+    }
+
+    outln!("{}", fill("profile.added", &[("added", &added), ("duplicates", &duplicates)]));
+    if !conflicts.is_empty() {
+        outln!("{}", fill("profile.conflicts", &[("count", &conflicts.len())]));
+        for conflict in conflicts {
+            outln!("{}", conflict);
+        }
+    }
+    Ok(())
+}
+
+/// Reads the bundle at `file` and merges it in, under `name` if one is given.
+fn import_profile(file: &Path, name: Option<&str>, stores: &mut StoreSet) -> Result<(), String> {
+    let text = fs::read_to_string(file).map_err(|e| fill("common.read_failed", &[("path", &file.display()), ("error", &e)]))?;
+    let mut bundle = ProfileBundle::parse(&text).map_err(|e| fill("profile.cant_import", &[("path", &file.display()), ("error", &e)]))?;
+    if let Some(name) = name {
+        bundle = bundle.renamed(name);
+    }
+    let name = bundle.profile.name.clone();
+    if Profile::path(&name).is_none() {
+        return Err(fill("profile.bad_name", &[("name", &name)]));
+    }
+
+    let profile = merge_profile(bundle.profile);
+    profile.save().map_err(|e| fill("profile.import_save_failed", &[("name", &name), ("error", &e)]))?;
+    outln!("{}", fill("profile.imported", &[("name", &name), ("preferences", &profile.describe()), ("count", &profile.achievements.len())]));
+    merge_results(bundle.results, stores)
+}
+
+/// Entry point for the `profile` subcommand.
+pub fn run_profile_command(command: &ProfileCommand, stores: &mut StoreSet) -> Result<(), String> {
+    match command {
+        ProfileCommand::Export { name, file } => export_profile(name, file.as_deref(), stores),
+        ProfileCommand::Import { file, rename } => import_profile(file, rename.as_deref(), stores),
+    }
+}
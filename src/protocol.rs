@@ -0,0 +1,334 @@
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+use crate::code::{Guess, Score};
+use crate::rules::GameSpec;
+use crate::snapshot::{code_field, parse_code_field, parse_score_field, score_field};
+use crate::store::clean_field;
+use crate::strings::{fill, tr};
+
+// --- Wire Protocol ---
+// Host and client talk in lines of tab-separated fields, the first naming the
+// message. What a client may send is a `ClientMsg`, what a host may send a
+// `ServerMsg`:
+//
+//   sgg-host  version                Host: greets every connection, before anything else
+//   hello     version, name, room    Client: asks for a seat
+//   rejoin    version, token         Client: asks for its seat back after losing it
+//   watch     version, room          Client: asks to watch instead of play
+//   spec      game spec              Host: the rules of the game on offer
+//   welcome   name, token            Host: seated, under this name; the token claims the seat back
+//   refuse    reason                 Host: no seat, and why
+//   info      text                   Host: a line to print
+//   prompt    text                   Host: ask the player, then wait for `input`
+//   input     text                   Client: what the player typed (a room command before `start`)
+//   chat      text                   Client: a `/say` line for everyone else
+//   start                            Host: the room is closed and the game begins
+//   started                          Client: no more room commands follow
+//   commit    name, hash             Host: a seat's sealed secret, as the game starts
+//   feedback  guess, score           Host: the player's own feedback, for them alone
+//   reveal    name, secret, salt     Host: a seat's secret and what it was sealed with, as the game ends
+//   backup    state                  Host: the game so far, for the client that takes over if the host goes
+//   listening port                   Client: where it takes connections if it has to take over
+//   successor name, address          Host: who takes over if the host goes, and where to find them
+//   end                              Host: the game is over
+//
+// Both sides give their protocol version up front, and each turns the other
+// away with a clear message if they differ. The greeting's shape never changes,
+// so a build of any version can read it. Once the versions match, a line with
+// an unknown name is skipped. A known message that doesn't read properly ends
+// the connection rather than being guessed at, and so does a line longer than
+// its kind of message ever needs to be: a few kilobytes from a client, a few
+// megabytes from a host (whose backups carry the whole game).
+
+/// Bumped whenever the messages change in a way older builds can't follow.
+pub const PROTOCOL_VERSION: u32 = 7;
+
+/// How long a client waits for the host's greeting.
+const GREETING_WAIT: Duration = Duration::from_secs(5);
+
+/// What a client sends the host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientMsg {
+    Hello { version: u32, name: String, room: String },
+    Rejoin { version: u32, token: String },
+    Watch { version: u32, room: String },
+    Input(String),
+    Chat(String),
+    Started,
+    Listening(u16),
+}
+
+/// What the host sends a client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerMsg {
+    Greeting { version: u32 },
+    Spec(GameSpec),
+    Welcome { name: String, token: String },
+    Refuse(String),
+    Info(String),
+    Prompt(String),
+    Start,
+    Commit { name: String, hash: String },
+    Feedback { guess: Guess, score: Score },
+    Reveal { name: String, secret: Guess, salt: String },
+    Backup(String), // A snapshot of the game, in hex
+    Successor { name: String, address: String },
+    End,
+}
+
+/// Why a line couldn't be read as a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WireError {
+    Unknown(String),        // A message this build doesn't know, by name
+    Malformed(&'static str), // A known message whose fields don't read
+}
+
+impl fmt::Display for WireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WireError::Unknown(tag) => write!(f, "{}", fill("wire.unknown", &[("tag", tag)])),
+            WireError::Malformed(tag) => write!(f, "{}", fill("wire.malformed", &[("tag", tag)])),
+        }
+    }
+}
+
+/// A message that travels as one line.
+pub trait Wire: Sized {
+    /// The longest line of this kind a reader takes, line ending included.
+    const MAX_LENGTH: usize;
+
+    fn to_line(&self) -> String;
+    fn from_line(line: &str) -> Result<Self, WireError>;
+}
+
+/// Splits a line into its message name and the rest.
+fn split_tag(line: &str) -> (&str, &str) {
+    line.split_once('\t').unwrap_or((line, ""))
+}
+
+impl Wire for ClientMsg {
+    const MAX_LENGTH: usize = 4 * 1024;
+
+    fn to_line(&self) -> String {
+        match self {
+            ClientMsg::Hello { version, name, room } => format!("hello\t{}\t{}\t{}", version, clean_field(name), clean_field(room)),
+            ClientMsg::Rejoin { version, token } => format!("rejoin\t{}\t{}", version, clean_field(token)),
+            ClientMsg::Watch { version, room } => format!("watch\t{}\t{}", version, clean_field(room)),
+            ClientMsg::Input(text) => format!("input\t{}", clean_field(text)),
+            ClientMsg::Chat(text) => format!("chat\t{}", clean_field(text)),
+            ClientMsg::Started => "started".to_string(),
+            ClientMsg::Listening(port) => format!("listening\t{}", port),
+        }
+    }
+
+    fn from_line(line: &str) -> Result<Self, WireError> {
+        let (tag, rest) = split_tag(line);
+        match tag {
+            "hello" => {
+                // Version 1 clients send no room code, and are turned away for their version
+                let mut fields = rest.splitn(3, '\t');
+                let (Some(version), Some(name)) = (fields.next(), fields.next()) else { return Err(WireError::Malformed("hello")) };
+                let version = version.parse().map_err(|_| WireError::Malformed("hello"))?;
+                Ok(ClientMsg::Hello { version, name: name.to_string(), room: fields.next().unwrap_or_default().to_string() })
+            }
+            "rejoin" => match rest.split_once('\t').map(|(version, token)| (version.parse(), token)) {
+                Some((Ok(version), token)) => Ok(ClientMsg::Rejoin { version, token: token.to_string() }),
+                _ => Err(WireError::Malformed("rejoin")),
+            },
+            "watch" => match rest.split_once('\t').map(|(version, room)| (version.parse(), room)) {
+                Some((Ok(version), room)) => Ok(ClientMsg::Watch { version, room: room.to_string() }),
+                _ => Err(WireError::Malformed("watch")),
+            },
+            "input" => Ok(ClientMsg::Input(rest.to_string())),
+            "chat" => Ok(ClientMsg::Chat(rest.to_string())),
+            "started" => Ok(ClientMsg::Started),
+            "listening" => rest.parse().map(ClientMsg::Listening).map_err(|_| WireError::Malformed("listening")),
+            _ => Err(WireError::Unknown(tag.to_string())),
+        }
+    }
+}
+
+impl Wire for ServerMsg {
+    const MAX_LENGTH: usize = 4 * 1024 * 1024;
+
+    fn to_line(&self) -> String {
+        match self {
+            ServerMsg::Greeting { version } => format!("sgg-host\t{}", version),
+            ServerMsg::Spec(spec) => format!("spec\t{}", spec),
+            ServerMsg::Welcome { name, token } => format!("welcome\t{}\t{}", clean_field(name), clean_field(token)),
+            ServerMsg::Refuse(reason) => format!("refuse\t{}", clean_field(reason)),
+            ServerMsg::Info(text) => format!("info\t{}", clean_field(text)),
+            ServerMsg::Prompt(text) => format!("prompt\t{}", clean_field(text)),
+            ServerMsg::Start => "start".to_string(),
+            ServerMsg::Commit { name, hash } => format!("commit\t{}\t{}", clean_field(name), clean_field(hash)),
+            ServerMsg::Feedback { guess, score } => format!("feedback\t{}\t{}", code_field(guess), score_field(*score)),
+            ServerMsg::Reveal { name, secret, salt } => format!("reveal\t{}\t{}\t{}", clean_field(name), code_field(secret), clean_field(salt)),
+            ServerMsg::Backup(state) => format!("backup\t{}", clean_field(state)),
+            ServerMsg::Successor { name, address } => format!("successor\t{}\t{}", clean_field(name), clean_field(address)),
+            ServerMsg::End => "end".to_string(),
+        }
+    }
+
+    fn from_line(line: &str) -> Result<Self, WireError> {
+        let (tag, rest) = split_tag(line);
+        match tag {
+            "sgg-host" => rest.parse().map(|version| ServerMsg::Greeting { version }).map_err(|_| WireError::Malformed("sgg-host")),
+            "spec" => rest.parse().map(ServerMsg::Spec).map_err(|_| WireError::Malformed("spec")),
+            "welcome" => match rest.split_once('\t') {
+                Some((name, token)) => Ok(ServerMsg::Welcome { name: name.to_string(), token: token.to_string() }),
+                None => Err(WireError::Malformed("welcome")),
+            },
+            "refuse" => Ok(ServerMsg::Refuse(rest.to_string())),
+            "info" => Ok(ServerMsg::Info(rest.to_string())),
+            "prompt" => Ok(ServerMsg::Prompt(rest.to_string())),
+            "start" => Ok(ServerMsg::Start),
+            "commit" => match rest.split_once('\t') {
+                Some((name, hash)) => Ok(ServerMsg::Commit { name: name.to_string(), hash: hash.to_string() }),
+                None => Err(WireError::Malformed("commit")),
+            },
+            "reveal" => {
+                let malformed = || WireError::Malformed("reveal");
+                let mut fields = rest.splitn(3, '\t');
+                let (Some(name), Some(secret), Some(salt)) = (fields.next(), fields.next(), fields.next()) else { return Err(malformed()) };
+                Ok(ServerMsg::Reveal { name: name.to_string(), secret: parse_code_field(secret).map_err(|_| malformed())?, salt: salt.to_string() })
+            }
+            "feedback" => {
+                let malformed = || WireError::Malformed("feedback");
+                let (guess, score) = rest.split_once('\t').ok_or_else(malformed)?;
+                Ok(ServerMsg::Feedback { guess: parse_code_field(guess).map_err(|_| malformed())?, score: parse_score_field(score).map_err(|_| malformed())? })
+            }
+            "backup" => Ok(ServerMsg::Backup(rest.to_string())),
+            "successor" => match rest.split_once('\t') {
+                Some((name, address)) => Ok(ServerMsg::Successor { name: name.to_string(), address: address.to_string() }),
+                None => Err(WireError::Malformed("successor")),
+            },
+            "end" => Ok(ServerMsg::End),
+            _ => Err(WireError::Unknown(tag.to_string())),
+        }
+    }
+}
+
+/// Sends one message down `writer`.
+pub fn send<M: Wire>(writer: &mut TcpStream, message: &M) -> io::Result<()> {
+    writeln!(writer, "{}", message.to_line())?;
+    writer.flush()
+}
+
+/// Reads one line of at most `limit` bytes, without its line ending.
+fn read_wire_line(reader: &mut BufReader<TcpStream>, limit: usize) -> io::Result<String> {
+    let mut line = String::new();
+    let read = reader.by_ref().take(limit as u64).read_line(&mut line)?;
+    if read == 0 {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "the connection was closed"));
+    }
+    if !line.ends_with('\n') && read == limit {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("the other side sent a line longer than {} bytes", limit)));
+    }
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Waits for the next message from `reader`, skipping ones this build doesn't know.
+pub fn receive<M: Wire>(reader: &mut BufReader<TcpStream>) -> io::Result<M> {
+    loop {
+        match M::from_line(&read_wire_line(reader, M::MAX_LENGTH)?) {
+            Ok(message) => return Ok(message),
+            Err(WireError::Unknown(_)) => continue,
+            Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("the other side sent {}", e))),
+        }
+    }
+}
+
+/// Checks the greeting a host opens with, before the client says anything,
+/// so a mismatched host or something that isn't one is caught straight away.
+pub fn expect_greeting(reader: &mut BufReader<TcpStream>) -> Result<(), String> {
+    reader.get_ref().set_read_timeout(Some(GREETING_WAIT)).map_err(|e| e.to_string())?;
+    let line = read_wire_line(reader, ServerMsg::MAX_LENGTH);
+    reader.get_ref().set_read_timeout(None).map_err(|e| e.to_string())?;
+    let line = line.map_err(|e| match e.kind() {
+        // Hosts before protocol version 3 wait for the client to speak first
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => tr("net.no_greeting").to_string(),
+        _ => fill("net.connection_lost", &[("error", &e)]),
+    })?;
+    match ServerMsg::from_line(&line) {
+        Ok(ServerMsg::Greeting { version }) if version == PROTOCOL_VERSION => Ok(()),
+        Ok(ServerMsg::Greeting { version }) => {
+            Err(fill("net.version_mismatch", &[("theirs", &version), ("ours", &PROTOCOL_VERSION)]))
+        }
+        _ => Err(tr("net.not_a_host").to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{Rules, Variant};
+
+    fn round_trip<M: Wire + fmt::Debug + PartialEq>(message: M) {
+        assert_eq!(M::from_line(&message.to_line()), Ok(message));
+    }
+
+    #[test]
+    fn client_messages_read_back_as_written() {
+        round_trip(ClientMsg::Hello { version: PROTOCOL_VERSION, name: "Ann".to_string(), room: "KXRB".to_string() });
+        round_trip(ClientMsg::Rejoin { version: PROTOCOL_VERSION, token: "00c0ffee00c0ffee".to_string() });
+        round_trip(ClientMsg::Watch { version: PROTOCOL_VERSION, room: "KXRB".to_string() });
+        round_trip(ClientMsg::Input("seat 3".to_string()));
+        round_trip(ClientMsg::Input(String::new()));
+        round_trip(ClientMsg::Chat("good luck, everyone!".to_string()));
+        round_trip(ClientMsg::Started);
+        round_trip(ClientMsg::Listening(7411));
+    }
+
+    #[test]
+    fn server_messages_read_back_as_written() {
+        let secret = Guess::from_digits(&[9, 0, 4, 2, 7]);
+        round_trip(ServerMsg::Greeting { version: PROTOCOL_VERSION });
+        round_trip(ServerMsg::Spec(GameSpec::new(Variant::IndividualSecrets, Rules { code_length: 5, allow_repeats: false })));
+        round_trip(ServerMsg::Welcome { name: "Ann (2)".to_string(), token: "00c0ffee00c0ffee".to_string() });
+        round_trip(ServerMsg::Refuse("the room is full".to_string()));
+        round_trip(ServerMsg::Info("🔓 Ann cracked their code in 5 guess(es)!".to_string()));
+        round_trip(ServerMsg::Prompt("Round 2, your guess: ".to_string()));
+        round_trip(ServerMsg::Start);
+        round_trip(ServerMsg::Commit { name: "Ann".to_string(), hash: "ab".repeat(32) });
+        round_trip(ServerMsg::Feedback { guess: Guess::from_digits(&[0, 1, 2, 3]), score: (1, 2) });
+        round_trip(ServerMsg::Reveal { name: "Ann".to_string(), secret, salt: "5a17".to_string() });
+        round_trip(ServerMsg::Backup("73676720".repeat(100)));
+        round_trip(ServerMsg::Successor { name: "Ben".to_string(), address: "192.168.1.20:7412".to_string() });
+        round_trip(ServerMsg::End);
+    }
+
+    #[test]
+    fn tabs_and_line_breaks_in_text_travel_as_spaces() {
+        let line = ServerMsg::Info("one\ttwo\nthree\r".to_string()).to_line();
+        assert_eq!(ServerMsg::from_line(&line), Ok(ServerMsg::Info("one two three ".to_string())));
+        let line = ClientMsg::Hello { version: 7, name: "A\tB".to_string(), room: "KX\nRB".to_string() }.to_line();
+        assert_eq!(ClientMsg::from_line(&line), Ok(ClientMsg::Hello { version: 7, name: "A B".to_string(), room: "KX RB".to_string() }));
+    }
+
+    #[test]
+    fn a_version_1_hello_reads_without_a_room_code() {
+        assert_eq!(ClientMsg::from_line("hello\t1\tAnn"), Ok(ClientMsg::Hello { version: 1, name: "Ann".to_string(), room: String::new() }));
+    }
+
+    #[test]
+    fn unknown_messages_are_told_apart_from_broken_ones() {
+        assert_eq!(ClientMsg::from_line("emote\twave"), Err(WireError::Unknown("emote".to_string())));
+        assert_eq!(ServerMsg::from_line("end"), Ok(ServerMsg::End));
+        assert_eq!(ClientMsg::from_line("end"), Err(WireError::Unknown("end".to_string())));
+
+        assert_eq!(ClientMsg::from_line("hello\tseven\tAnn\tKXRB"), Err(WireError::Malformed("hello")));
+        assert_eq!(ClientMsg::from_line("hello\t7"), Err(WireError::Malformed("hello")));
+        assert_eq!(ClientMsg::from_line("rejoin\t7"), Err(WireError::Malformed("rejoin")));
+        assert_eq!(ClientMsg::from_line("listening\t70000"), Err(WireError::Malformed("listening")));
+        assert_eq!(ServerMsg::from_line("sgg-host\t"), Err(WireError::Malformed("sgg-host")));
+        assert_eq!(ServerMsg::from_line("spec\tlength=4"), Err(WireError::Malformed("spec")));
+        assert_eq!(ServerMsg::from_line("welcome\tAnn"), Err(WireError::Malformed("welcome")));
+        assert_eq!(ServerMsg::from_line("feedback\t0123"), Err(WireError::Malformed("feedback")));
+        assert_eq!(ServerMsg::from_line("feedback\t0123\t5,3"), Err(WireError::Malformed("feedback")));
+        assert_eq!(ServerMsg::from_line("feedback\t0012\t1,1"), Err(WireError::Malformed("feedback")));
+        assert_eq!(ServerMsg::from_line("reveal\tAnn\t01234567\t5a17"), Err(WireError::Malformed("reveal")));
+    }
+}
@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use crate::code::{format_code, Guess};
+use crate::config::Config;
+use crate::glyphs::decorate;
+use crate::locale::Locale;
+use crate::strings::{fill, tr};
+
+// --- Final Rankings ---
+// The table shown when a game ends. Columns are sized to their contents, so
+// long names (bot names are often twenty characters or more) stay aligned.
+
+/// How a player's game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Finish {
+    Placed(usize),              // Cracked their code and took this rank
+    DidNotFinish { round: u32 }, // Left without cracking their code, in this round
+}
+
+/// One row of the final rankings.
+#[derive(Debug, Clone)]
+pub struct Standing {
+    pub name: String,
+    pub finish: Finish,
+    pub guesses: u32,
+    pub time: Duration, // From the start of the game until they finished or dropped
+    pub secret: Guess,
+    pub rating: Option<(i32, i32)>, // Rating after the game and how much it moved, for a rated game
+}
+
+/// Roughly how many terminal columns `text` takes up once printed in the
+/// current output profile: emoji and CJK characters are drawn double width.
+pub fn display_width(text: &str) -> usize {
+    decorate(text)
+        .chars()
+        .map(|c| match c as u32 {
+            0x1100..=0x115F | 0x2E80..=0xA4CF | 0xAC00..=0xD7A3 | 0xF900..=0xFAFF | 0xFF00..=0xFF60 | 0x1F300..=0x1FAFF => 2,
+            _ => 1,
+        })
+        .sum()
+}
+
+/// Left-aligns `text` in a column `width` wide.
+pub fn pad(text: &str, width: usize) -> String {
+    format!("{}{}", text, " ".repeat(width.saturating_sub(display_width(text))))
+}
+
+/// Sorts standings into display order: placed players by rank (and time,
+/// within a tie), then players who didn't finish, latest to drop first.
+pub fn sort_standings(standings: &mut [Standing]) {
+    standings.sort_by_key(|s| match s.finish {
+        Finish::Placed(rank) => (0, rank, 0, s.time),
+        Finish::DidNotFinish { round } => (1, 0, u32::MAX - round, s.time),
+    });
+}
+
+/// Renders the rankings as table lines. Tied players are grouped under one
+/// rank label; DNF rows say which round the player dropped in. For screen
+/// readers each player gets a sentence instead, with ties labelled on every row.
+pub fn render_rankings(standings: &[Standing]) -> Vec<String> {
+    let locale = Locale::current();
+    let screen_reader = Config::current().screen_reader;
+    let mut standings = standings.to_vec();
+    sort_standings(&mut standings);
+
+    let rated = standings.iter().any(|s| s.rating.is_some());
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for (i, s) in standings.iter().enumerate() {
+        let tied_with = |other: Option<&Standing>| other.is_some_and(|o| matches!(o.finish, Finish::Placed(_)) && o.finish == s.finish);
+        let previous = i.checked_sub(1).and_then(|j| standings.get(j));
+        let label = match s.finish {
+            Finish::Placed(_) if tied_with(previous) => String::new(), // Grouped under the row above
+            Finish::Placed(rank) if tied_with(standings.get(i + 1)) => fill("rankings.tie", &[("place", &locale.ordinal(rank))]),
+            Finish::Placed(rank) => locale.ordinal(rank),
+            Finish::DidNotFinish { round } => fill("rankings.dnf", &[("round", &round)]),
+        };
+        let label = match s.finish {
+            Finish::Placed(rank) if screen_reader && label.is_empty() => fill("rankings.tie", &[("place", &locale.ordinal(rank))]),
+            _ => label,
+        };
+        let mut row = vec![label, s.name.clone(), locale.count(s.guesses as u64), locale.duration(s.time), format_code(&s.secret)];
+        if rated {
+            row.push(s.rating.map_or(String::new(), |(rating, change)| format!("{} ({:+})", rating, change)));
+        }
+        rows.push(row);
+    }
+
+    if screen_reader {
+        return rows
+            .into_iter()
+            .map(|row| {
+                let sentence = fill("rankings.sentence", &[("rank", &row[0]), ("name", &row[1]), ("guesses", &row[2]), ("time", &row[3]), ("secret", &row[4])]);
+                match row.get(5).filter(|rating| !rating.is_empty()) {
+                    Some(rating) => format!("{} {}", sentence, fill("rankings.rating_sentence", &[("rating", rating)])),
+                    None => sentence,
+                }
+            })
+            .collect();
+    }
+
+    let mut headers = vec!["rankings.rank", "rankings.player", "rankings.guesses", "rankings.time", "rankings.secret"];
+    if rated {
+        headers.push("rankings.rating");
+    }
+    let headers: Vec<String> = headers.into_iter().map(|id| tr(id).to_string()).collect();
+    let mut widths: Vec<usize> = headers.iter().map(|h| display_width(h)).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(display_width(cell));
+        }
+    }
+
+    let line = |cells: &[String]| -> String {
+        let padded: Vec<String> = cells.iter().zip(&widths).map(|(cell, width)| pad(cell, *width)).collect();
+        format!("| {} |", padded.join(" | "))
+    };
+    let rule = format!("+{}+", widths.iter().map(|w| "-".repeat(w + 2)).collect::<Vec<_>>().join("+"));
+
+    let mut lines = vec![rule.clone(), line(&headers), rule.clone()];
+    lines.extend(rows.iter().map(|row| line(row)));
+    lines.push(rule);
+    lines
+}
+
+/// Prints the final rankings under a heading.
+pub fn print_rankings(standings: &[Standing]) {
+    outln!("{}", tr("rankings.title"));
+    for line in render_rankings(standings) {
+        outln!("{}", line);
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+
+use crate::stats::group_games;
+use crate::store::GameResult;
+
+// --- Ratings ---
+// Every player gets an Elo-style rating from the finishing order of their
+// multi-player games. A game isn't one head-to-head match, so it's scored as
+// one between every pair at the table: finishing ahead of someone is a win,
+// level with them (such as both not finishing) a draw. A player's change is
+// the sum over their pairings, scaled by 1/(n-1) so a big table doesn't swing
+// ratings more than a two-player game. Ratings are replayed from the stored
+// results whenever they're needed, so they live with the stats (encrypted
+// along with them) and a store moved to another machine keeps its ratings.
+
+/// The rating a player starts with.
+pub const START_RATING: f64 = 1500.0;
+
+/// How far one game can move a rating.
+const K_FACTOR: f64 = 32.0;
+
+/// Ratings by player name.
+#[derive(Debug, Clone, Default)]
+pub struct Ratings {
+    ratings: BTreeMap<String, f64>,
+}
+
+/// How likely a player rated `rating` is to finish ahead of one rated `opponent`.
+fn expected_score(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0))
+}
+
+impl Ratings {
+    /// Replays every stored multi-player game, oldest first.
+    pub fn from_results(results: &[GameResult]) -> Ratings {
+        let mut ratings = Ratings::default();
+        for game in group_games(results) {
+            let finishes: Vec<(&str, usize)> = game.iter().map(|r| (r.player.as_str(), r.rank)).collect();
+            ratings.update(&finishes);
+        }
+        ratings
+    }
+
+    /// A player's rating, or the starting one if they haven't played a rated game.
+    pub fn get(&self, name: &str) -> f64 {
+        self.ratings.get(name).copied().unwrap_or(START_RATING)
+    }
+
+    /// A player's rating, rounded for display, if they have one.
+    pub fn rounded(&self, name: &str) -> Option<i32> {
+        self.ratings.get(name).map(|rating| rating.round() as i32)
+    }
+
+    /// Applies one game, given each player's name and place (lower is better).
+    /// A game with fewer than two players changes nothing.
+    pub fn update(&mut self, finishes: &[(&str, usize)]) {
+        if finishes.len() < 2 {
+            return;
+        }
+        let before: Vec<f64> = finishes.iter().map(|(name, _)| self.get(name)).collect();
+        let scale = K_FACTOR / (finishes.len() - 1) as f64;
+        for (i, (name, place)) in finishes.iter().enumerate() {
+            let change: f64 = finishes
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(j, (_, other))| {
+                    let actual = match place.cmp(other) {
+                        std::cmp::Ordering::Less => 1.0,
+                        std::cmp::Ordering::Equal => 0.5,
+                        std::cmp::Ordering::Greater => 0.0,
+                    };
+                    actual - expected_score(before[i], before[j])
+                })
+                .sum();
+            self.ratings.insert(name.to_string(), before[i] + scale * change);
+        }
+    }
+
+    /// Each player's rating after the game in `finishes` and how much it moved,
+    /// both rounded, without changing these ratings.
+    pub fn changes(&self, finishes: &[(&str, usize)]) -> BTreeMap<String, (i32, i32)> {
+        let mut after = self.clone();
+        after.update(finishes);
+        finishes
+            .iter()
+            .map(|(name, _)| {
+                let (old, new) = (self.get(name).round() as i32, after.get(name).round() as i32);
+                (name.to_string(), (new, new - old))
+            })
+            .collect()
+    }
+}
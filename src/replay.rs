@@ -0,0 +1,250 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use clap::Args;
+
+use crate::code::{format_code, is_solved};
+use crate::console::{pause, prompt};
+use crate::feedback::player_notation;
+use crate::locale::{civil_from_days, Locale};
+use crate::rankings::{display_width, pad, print_rankings, Finish, Standing};
+use crate::rules::{GameSpec, Rules, Variant};
+use crate::snapshot::{code_field, parse_code_field, parse_score_field, score_field, Snapshot, SnapshotBody, SnapshotError};
+use crate::store::{data_dir, format_game_id, parse_game_id};
+use crate::strings::{fill, tr};
+use crate::transcript::{clock, Move, Transcript};
+
+// --- Replays ---
+// Every Individual Secrets game that reaches the final rankings is kept in
+// `replays/` in the data directory, named after when it ended, so a good game
+// can be watched again or attached to a bug report. `replay <file>` plays one
+// back a turn at a time, keeping each secret hidden until it's cracked. The
+// file is an engine snapshot of kind "replay":
+//
+//   game    seed, milliseconds played
+//   id      the game id its results were saved under (see store.rs)
+//   player  name, secret, place or dnf:<round>, guesses, milliseconds to finish
+//   move    round, name, guess, score, milliseconds in
+//
+// Players are listed in their final order; moves in the order they were made.
+// Replays saved before game ids have no `id` record.
+
+/// Pause after each move during playback.
+const TURN_DELAY: Duration = Duration::from_millis(1200);
+
+/// The options of `replay`.
+#[derive(Debug, Args)]
+pub struct ReplayArgs {
+    /// The replay to watch, by name or path; without one the replays kept are listed
+    #[arg(value_name = "FILE OR NAME")]
+    pub file: Option<String>,
+    /// Move on with Enter instead of after a short pause
+    #[arg(long)]
+    pub step: bool,
+}
+
+/// A finished game, as kept for playback.
+#[derive(Debug, Clone)]
+pub struct Replay {
+    pub seed: u64,
+    pub game: Option<u64>,
+    pub duration: Duration,
+    pub standings: Vec<Standing>,
+    pub moves: Vec<Move>,
+}
+
+/// The folder replays are kept in, if there is a data directory.
+pub fn replay_dir() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("replays"))
+}
+
+/// Where the replay called `name` is: the file itself if there is one, and
+/// otherwise the replay of that name in the replays folder.
+fn replay_path(name: &str) -> PathBuf {
+    let path = Path::new(name);
+    match replay_dir() {
+        Some(dir) if !path.is_file() && path.components().count() == 1 => dir.join(format!("{}.txt", name.trim_end_matches(".txt"))),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// A name for a replay made at `time`, which also sorts them oldest first.
+fn replay_name(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    format!("{}-{:02}-{:02}-{:02}{:02}{:02}", year, month, day, (secs / 3600) % 24, (secs / 60) % 60, secs % 60)
+}
+
+fn parse_number<T: std::str::FromStr>(field: &str) -> Result<T, SnapshotError> {
+    field.parse().map_err(|_| SnapshotError::Malformed(format!("bad number '{}'", field)))
+}
+
+impl Replay {
+    pub fn new(seed: u64, game: u64, duration: Duration, standings: &[Standing], transcript: &Transcript) -> Replay {
+        Replay { seed, game: Some(game), duration, standings: standings.to_vec(), moves: transcript.moves.clone() }
+    }
+
+    /// Writes the replay into the replays folder and returns where it went.
+    pub fn record(&self) -> io::Result<PathBuf> {
+        let dir = replay_dir().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("{}.txt", replay_name(SystemTime::now())));
+        fs::write(&path, self.to_snapshot())?;
+        Ok(path)
+    }
+
+    /// Reads the replay at `path`, explaining what's wrong with it if it can't be played.
+    pub fn load(path: &Path) -> Result<Replay, String> {
+        let text = fs::read_to_string(path).map_err(|e| fill("replay.read_failed", &[("path", &path.display()), ("error", &e)]))?;
+        Replay::from_snapshot(&text).map_err(|e| fill("replay.unplayable", &[("path", &path.display()), ("error", &e)]))
+    }
+}
+
+impl Snapshot for Replay {
+    const KIND: &'static str = "replay";
+
+    fn spec(&self) -> GameSpec {
+        GameSpec::new(Variant::IndividualSecrets, Rules::default())
+    }
+
+    fn records(&self) -> Vec<Vec<String>> {
+        let mut records = vec![vec!["game".to_string(), self.seed.to_string(), self.duration.as_millis().to_string()]];
+        if let Some(game) = self.game {
+            records.push(vec!["id".to_string(), format_game_id(game)]);
+        }
+        for s in &self.standings {
+            let finish = match s.finish {
+                Finish::Placed(rank) => rank.to_string(),
+                Finish::DidNotFinish { round } => format!("dnf:{}", round),
+            };
+            // Tabs separate the fields, so they can't be part of a name
+            records.push(vec!["player".to_string(), s.name.replace('\t', " "), code_field(&s.secret), finish, s.guesses.to_string(), s.time.as_millis().to_string()]);
+        }
+        for m in &self.moves {
+            records.push(vec![
+                "move".to_string(),
+                m.round.to_string(),
+                m.player.replace('\t', " "),
+                code_field(&m.guess),
+                score_field(m.score),
+                m.at.as_millis().to_string(),
+            ]);
+        }
+        records
+    }
+
+    fn from_body(body: SnapshotBody) -> Result<Self, SnapshotError> {
+        let mut game = None;
+        let mut id = None;
+        let mut standings = Vec::new();
+        let mut moves = Vec::new();
+        for record in &body.records {
+            match record.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+                ["game", seed, duration] => game = Some((parse_number(seed)?, Duration::from_millis(parse_number(duration)?))),
+                ["id", game] => id = Some(parse_game_id(game).ok_or_else(|| SnapshotError::Malformed(format!("bad game id '{}'", game)))?),
+                ["player", name, secret, finish, guesses, time] => {
+                    let finish = match finish.strip_prefix("dnf:") {
+                        Some(round) => Finish::DidNotFinish { round: parse_number(round)? },
+                        None => Finish::Placed(parse_number(finish)?),
+                    };
+                    standings.push(Standing {
+                        name: name.to_string(),
+                        finish,
+                        guesses: parse_number(guesses)?,
+                        time: Duration::from_millis(parse_number(time)?),
+                        secret: parse_code_field(secret)?,
+                        rating: None,
+                    });
+                }
+                ["move", round, name, guess, score, at] => moves.push(Move {
+                    round: parse_number(round)?,
+                    player: name.to_string(),
+                    guess: parse_code_field(guess)?,
+                    score: parse_score_field(score)?,
+                    at: Duration::from_millis(parse_number(at)?),
+                }),
+                _ => {} // Records from newer builds are skipped
+            }
+        }
+        let (seed, duration) = game.ok_or_else(|| SnapshotError::Malformed("missing game record".to_string()))?;
+        Ok(Replay { seed, game: id, duration, standings, moves })
+    }
+}
+
+/// Records a game that has reached its final rankings, saying where it went.
+pub fn record_replay(replay: &Replay) {
+    match replay.record() {
+        Ok(path) => outln!("{}", fill("replay.saved", &[("path", &path.display()), ("name", &path.file_stem().unwrap_or_default().to_string_lossy())])),
+        Err(e) => outln!("{}", fill("replay.failed", &[("error", &e)])),
+    }
+}
+
+/// The names of the replays in `dir`, oldest first.
+pub fn replay_names(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .map(|entries| entries.flatten().filter_map(|e| e.path().file_stem().map(|s| s.to_string_lossy().into_owned())).collect())
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// Lists the replays in the replays folder, oldest first.
+fn list_replays() -> Result<(), String> {
+    let dir = replay_dir().ok_or(tr("replay.no_data_dir"))?;
+    let names = replay_names(&dir);
+    if names.is_empty() {
+        outln!("{}", fill("replay.none", &[("dir", &dir.display())]));
+        return Ok(());
+    }
+    outln!("{}", fill("replay.list", &[("dir", &dir.display())]));
+    for name in names {
+        outln!("  {}", name);
+    }
+    outln!("{}", tr("replay.list_hint"));
+    Ok(())
+}
+
+/// Waits before the next move: for Enter with `--step`, or a moment otherwise.
+fn wait_for_next(step: bool) {
+    if step {
+        prompt(tr("replay.step_prompt"));
+    } else {
+        pause(TURN_DELAY);
+    }
+}
+
+/// Plays a replay back, one move at a time.
+fn play_back(replay: &Replay, step: bool) {
+    let names: Vec<&str> = replay.standings.iter().map(|s| s.name.as_str()).collect();
+    let width = names.iter().map(|name| display_width(name)).max().unwrap_or(0);
+
+    outln!("{}", fill("replay.title", &[("seed", &replay.seed)]));
+    outln!("{}", fill("replay.players", &[("names", &names.join(", "))]));
+
+    let mut round = 0;
+    for m in &replay.moves {
+        if m.round != round {
+            round = m.round;
+            outln!("{}", fill("replay.round", &[("round", &round)]));
+        }
+        wait_for_next(step);
+        outln!("  {}  {}  {}  {}", clock(m.at), pad(&m.player, width), format_code(&m.guess), player_notation().render(m.score));
+        if is_solved(m.score) {
+            outln!("{}", fill("replay.cracked", &[("name", &m.player), ("secret", &format_code(&m.guess))]));
+        }
+    }
+
+    wait_for_next(step);
+    print_rankings(&replay.standings);
+    outln!("{}", fill("common.game_length", &[("time", &Locale::current().duration(replay.duration))]));
+}
+
+/// Entry point for the `replay` subcommand.
+pub fn run_replay(args: &ReplayArgs) -> Result<(), String> {
+    let Some(file) = &args.file else { return list_replays() };
+    let replay = Replay::load(&replay_path(file))?;
+    play_back(&replay, args.step);
+    Ok(())
+}
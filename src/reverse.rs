@@ -0,0 +1,113 @@
+use crate::code::{calculate_score, code_length, format_code, is_solved, parse_feedback, parse_guess, Guess, Score};
+use crate::console::{clear_screen, hidden_prompt, prompt};
+use crate::feedback::player_notation;
+use crate::session::{Session, Solve};
+use crate::solver::{all_codes, filter_candidates, is_consistent, suggest_guess};
+use crate::strings::{fill, tr};
+
+// --- Reverse Mode ---
+// The player is the codemaker and the computer is the codebreaker. The
+// secret can stay in the player's head, in which case the engine can only
+// notice a mistake once no code fits all the feedback; if it was typed in
+// (hidden), every piece of feedback is checked on the spot.
+
+/// Asks for the secret, which may be left out. The screen is cleared afterwards.
+fn get_optional_secret(player: &str) -> Option<Guess> {
+    loop {
+        let input = hidden_prompt(&fill("reverse.secret_prompt", &[("name", &player)]));
+        if input.is_empty() {
+            return None;
+        }
+        match parse_guess(&input) {
+            Ok(secret) => {
+                clear_screen();
+                return Some(secret);
+            }
+            Err(message) => outln!("{}", message),
+        }
+    }
+}
+
+/// Asks the player to score the computer's guess.
+fn get_feedback(player: &str, guess: &Guess) -> Score {
+    loop {
+        match parse_feedback(&prompt(&fill("common.score_prompt", &[("name", &player), ("guess", &format_code(guess))]))) {
+            Ok(score) => return score,
+            Err(message) => outln!("{}", message),
+        }
+    }
+}
+
+/// Finds an earlier clue that can't be true at the same time as `latest`.
+fn conflicting_clue(clues: &[(Guess, Score)], latest: (Guess, Score)) -> Option<usize> {
+    let codes = all_codes();
+    clues.iter().position(|&(guess, score)| {
+        !codes.iter().any(|code| is_consistent(code, &guess, score) && is_consistent(code, &latest.0, latest.1))
+    })
+}
+
+/// Runs a game where the computer cracks the player's secret.
+pub fn run_reverse(session: &mut Session) {
+    clear_screen();
+    outln!("{}", tr("reverse.title"));
+    outln!("{}", fill("reverse.intro", &[("length", &code_length())]));
+
+    let player = prompt(tr("common.name_prompt"));
+    let secret = get_optional_secret(&player);
+
+    let mut candidates = all_codes();
+    let mut clues: Vec<(Guess, Score)> = Vec::new();
+
+    loop {
+        let guess = suggest_guess(&candidates);
+        outln!("\n======================================");
+        outln!("{}", fill("reverse.guess_header", &[("number", &(clues.len() + 1)), ("guess", &format_code(&guess))]));
+        outln!("======================================");
+
+        let mut score = get_feedback(&player, &guess);
+        if let Some(secret) = secret {
+            let actual = calculate_score(&guess, &secret);
+            if score != actual {
+                outln!("{}", fill("reverse.corrected", &[("guess", &format_code(&guess)), ("score", &player_notation().render(actual))]));
+                score = actual;
+            }
+        }
+
+        if is_solved(score) {
+            outln!("{}", fill("reverse.cracked", &[("secret", &format_code(&guess)), ("count", &(clues.len() + 1))]));
+            session.record_secret("reverse", guess, Some(Solve { player: "Computer".to_string(), guesses: clues.len() as u32 + 1 }));
+            return;
+        }
+
+        let mut narrowed = candidates.clone();
+        filter_candidates(&mut narrowed, &guess, score);
+        if narrowed.is_empty() {
+            // Only possible when the secret wasn't typed in
+            outln!("{}", tr("reverse.no_fit"));
+            match conflicting_clue(&clues, (guess, score)) {
+                Some(i) => outln!("{}", fill("reverse.conflict", &[
+                    ("guess", &format_code(&guess)),
+                    ("score", &player_notation().render(score)),
+                    ("number", &(i + 1)),
+                    ("other", &format_code(&clues[i].0)),
+                    ("other_score", &player_notation().render(clues[i].1)),
+                ])),
+                None => outln!("{}", fill("reverse.contradiction", &[("guess", &format_code(&guess)), ("score", &player_notation().render(score))])),
+            }
+            for (i, (clue, clue_score)) in clues.iter().enumerate() {
+                outln!("{}", fill("reverse.clue", &[("number", &(i + 1)), ("guess", &format_code(clue)), ("score", &player_notation().render(*clue_score))]));
+            }
+            if prompt(tr("reverse.restart_prompt")).eq_ignore_ascii_case(tr("common.yes")) {
+                candidates = all_codes();
+                clues.clear();
+                continue;
+            }
+            outln!("{}", tr("reverse.abandoned"));
+            return;
+        }
+
+        candidates = narrowed;
+        clues.push((guess, score));
+        outln!("{}", fill("reverse.remaining", &[("count", &candidates.len())]));
+    }
+}
@@ -0,0 +1,621 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::BufReader;
+use std::net::{IpAddr, Shutdown, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rand::seq::IndexedRandom;
+use rand::Rng;
+
+use crate::console::prompt;
+use crate::network::chat_line;
+use crate::protocol::{receive, send, ClientMsg, ServerMsg, PROTOCOL_VERSION};
+use crate::rules::{describe_record_key, GameSpec, Rules, Variant};
+use crate::strings::{fill, tr};
+
+// --- Rooms ---
+// A hosted game gathers its players in a room before anything is dealt. The
+// host is given a short room code to pass on, and a client is only seated if
+// its `hello` carries it. Seats are the turn order: the host has seat 1, a
+// newcomer takes the first open one, and `seat <n>` moves to another. `ready`
+// says a player is set to play, and typing it again takes that back. Once
+// everyone seated is ready the host is asked whether to start; a full room
+// starts straight away. Every change shows the room again, rules and all, to
+// everyone in it.
+//
+// Each client gets a thread that reads its messages into one channel, so the
+// host deals with them in the order they come, in the room and through the
+// game. At the start the host sends `start`, and the client answers `started`
+// once the last of its room commands is on its way. `/say <message>` sends
+// the others a chat line, here and during the game.
+//
+// No one client can hold the host up. A connection has a couple of minutes
+// to say what it wants (time to type the room code) and is closed if it
+// doesn't, and a write to a client that has stopped reading gives up after a
+// few seconds, counting the client as gone. Past a short burst, a client's
+// messages are taken no faster than a couple a second; the rest wait their
+// turn on its own connection, not in the host's channel. The host keeps at
+// most 64 connections open.
+//
+// A client can also `watch` instead of taking a seat, with the same room
+// code, up to the number of spectators the host allows. Spectators see what
+// everyone in the room sees, and during the game every guess with its score
+// as it's made. Secrets are never sent to them, and they can't chat, so a
+// spectator can't pass the feedback they see on to a player.
+
+/// Room codes are made of these, leaving out I and O, which are easily taken for 1 and 0.
+const CODE_LETTERS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+
+const CODE_LENGTH: usize = 4;
+
+/// How long the host waits for every client to answer `start`.
+const START_WAIT: Duration = Duration::from_secs(5);
+
+/// How long a new connection has to send its `hello`, `rejoin` or `watch`.
+const HELLO_WAIT: Duration = Duration::from_secs(120);
+
+/// How long a message to a client may wait to be taken before the client counts as gone.
+const WRITE_WAIT: Duration = Duration::from_secs(10);
+
+/// The most connections a host keeps open at once.
+const MAX_CONNECTIONS: usize = 64;
+
+/// How many messages a client may send in a row before it's slowed down,
+/// and how many a second it's slowed down to.
+const MESSAGE_BURST: f64 = 10.0;
+const MESSAGE_RATE: f64 = 2.0;
+
+/// A fresh room code, such as "KXRB".
+pub fn room_code() -> String {
+    let mut rng = rand::rng();
+    (0..CODE_LENGTH).map(|_| *CODE_LETTERS.choose(&mut rng).expect("there are code letters") as char).collect()
+}
+
+/// Whether `typed` is `code`, ignoring case and spaces.
+fn same_code(typed: &str, code: &str) -> bool {
+    typed.split_whitespace().collect::<String>().eq_ignore_ascii_case(code)
+}
+
+/// Why a client speaking protocol `version` is turned away.
+pub fn version_mismatch(version: u32) -> String {
+    fill("room.version_mismatch", &[("host", &PROTOCOL_VERSION), ("yours", &version)])
+}
+
+/// A name nobody in `taken` has yet, based on the one asked for.
+pub fn unique_name(wanted: &str, taken: &[&str]) -> String {
+    let base = if wanted.trim().is_empty() { format!("Player {}", taken.len() + 1) } else { wanted.trim().to_string() };
+    let is_taken = |name: &str| taken.iter().any(|t| t.eq_ignore_ascii_case(name));
+    let mut name = base.clone();
+    let mut n = 2;
+    while is_taken(&name) {
+        name = format!("{} ({})", base, n);
+        n += 1;
+    }
+    name
+}
+
+/// Something that happened to one of the room's connections.
+pub enum Event {
+    Arrived { id: usize, writer: TcpStream },
+    Said { id: usize, message: ClientMsg },
+    Gone { id: usize },
+    Typed(String), // A line typed at the host's own keyboard, once the game is under way
+}
+
+/// A player's way to their client, once the game starts: the messages from
+/// it arrive as events carrying `id`, and `token` claims the seat back after
+/// a lost connection.
+pub struct Client {
+    pub id: usize,
+    pub writer: TcpStream,
+    pub token: String,
+}
+
+/// The players of a game about to start, in seat order, each with their
+/// client but the host.
+pub type Players = Vec<(String, Option<Client>)>;
+
+/// A fresh session token: 16 hex digits, hard to guess.
+pub fn session_token() -> String {
+    format!("{:016x}", rand::rng().random::<u64>())
+}
+
+/// Turns a client away, saying why.
+pub fn refuse(mut writer: TcpStream, reason: &str) {
+    send(&mut writer, &ServerMsg::Refuse(reason.to_string())).ok();
+    writer.shutdown(Shutdown::Both).ok();
+}
+
+/// The connections watching the game rather than playing in it.
+pub struct Watchers {
+    code: String,
+    cap: usize,
+    streams: BTreeMap<usize, TcpStream>, // By connection
+    here: bool, // Whether what spectators see is shown at this machine too
+}
+
+impl Watchers {
+    pub fn new(code: &str, cap: usize) -> Watchers {
+        Watchers { code: code.to_string(), cap, streams: BTreeMap::new(), here: false }
+    }
+
+    /// Shows what spectators see at this machine too, for a host with no seat of its own.
+    pub fn show_here(&mut self) {
+        self.here = true;
+    }
+
+    pub fn count(&self) -> usize {
+        self.streams.len()
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn cap(&self) -> usize {
+        self.cap
+    }
+
+    /// Lets connection `id` watch if it has the room code and there's room
+    /// for another spectator, or turns it away. Returns whether it got in.
+    pub fn admit(&mut self, id: usize, mut writer: TcpStream, version: u32, code: &str) -> bool {
+        let refusal = if version != PROTOCOL_VERSION {
+            Some(version_mismatch(version))
+        } else if !same_code(code, &self.code) {
+            Some(tr("room.wrong_code").to_string())
+        } else if self.cap == 0 {
+            Some(tr("room.no_spectators").to_string())
+        } else if self.count() >= self.cap {
+            Some(fill("room.spectators_full", &[("cap", &self.cap)]))
+        } else {
+            None
+        };
+        if let Some(reason) = refusal {
+            refuse(writer, &reason);
+            return false;
+        }
+        let address = writer.peer_addr().map(|a| a.ip().to_string()).unwrap_or_else(|_| tr("room.somewhere").to_string());
+        send(&mut writer, &ServerMsg::Spec(GameSpec::new(Variant::IndividualSecrets, Rules::default()))).ok();
+        send(&mut writer, &ServerMsg::Info(tr("room.watch_help").to_string())).ok();
+        self.streams.insert(id, writer);
+        outln!("{}", fill("room.spectator_arrived", &[("address", &address), ("count", &self.count()), ("cap", &self.cap)]));
+        true
+    }
+
+    /// The spectator who has been watching longest.
+    pub fn first(&self) -> Option<usize> {
+        self.streams.keys().next().copied()
+    }
+
+    pub fn contains(&self, id: usize) -> bool {
+        self.streams.contains_key(&id)
+    }
+
+    /// The address spectator `id` is watching from.
+    pub fn address(&self, id: usize) -> Option<IpAddr> {
+        self.streams.get(&id)?.peer_addr().ok().map(|a| a.ip())
+    }
+
+    /// Forgets connection `id` if it was watching, returning whether it was.
+    pub fn remove(&mut self, id: usize) -> bool {
+        self.streams.remove(&id).is_some()
+    }
+
+    /// Sends `message` to every spectator, forgetting any that are gone.
+    pub fn send(&mut self, message: &ServerMsg) {
+        self.streams.retain(|_, writer| send(writer, message).is_ok());
+    }
+
+    /// Sends `message` to spectator `id` alone. Returns false, forgetting
+    /// them, if they're gone.
+    pub fn send_to(&mut self, id: usize, message: &ServerMsg) -> bool {
+        let Some(writer) = self.streams.get_mut(&id) else { return false };
+        let sent = send(writer, message).is_ok();
+        if !sent {
+            self.streams.remove(&id);
+        }
+        sent
+    }
+
+    /// Shows a line to every spectator.
+    pub fn tell(&mut self, text: &str) {
+        if self.here {
+            outln!("{}", text);
+        }
+        self.send(&ServerMsg::Info(text.to_string()));
+    }
+}
+
+/// A connection to the room, with a seat once it has said hello.
+struct Guest {
+    writer: TcpStream,
+    seat: Option<(usize, String)>, // Seat number and name
+    token: String,
+    ready: bool,
+}
+
+impl Guest {
+    fn send(&mut self, message: &ServerMsg) {
+        // A client that's gone is noticed by its reading thread
+        send(&mut self.writer, message).ok();
+    }
+
+    fn tell(&mut self, text: &str) {
+        self.send(&ServerMsg::Info(text.to_string()));
+    }
+
+    fn refuse(self, reason: &str) {
+        refuse(self.writer, reason);
+    }
+}
+
+/// Spaces out one client's messages once it has sent a burst of them.
+struct Throttle {
+    allowance: f64, // Messages it may send straight away
+    counted_at: Instant,
+}
+
+impl Throttle {
+    fn new() -> Throttle {
+        Throttle { allowance: MESSAGE_BURST, counted_at: Instant::now() }
+    }
+
+    /// Waits, if need be, until the client may be heard again.
+    fn pace(&mut self) {
+        let now = Instant::now();
+        self.allowance = (self.allowance + now.duration_since(self.counted_at).as_secs_f64() * MESSAGE_RATE).min(MESSAGE_BURST);
+        self.counted_at = now;
+        if self.allowance < 1.0 {
+            thread::sleep(Duration::from_secs_f64((1.0 - self.allowance) / MESSAGE_RATE));
+            self.allowance = 1.0;
+            self.counted_at = Instant::now();
+        }
+        self.allowance -= 1.0;
+    }
+}
+
+/// Accepts connections on `listener` for as long as the program runs, giving
+/// each a reading thread. Once the game is under way only players coming
+/// back are let in.
+pub fn accept_clients(listener: TcpListener, events: Sender<Event>) {
+    let open = Arc::new(AtomicUsize::new(0));
+    thread::spawn(move || {
+        for (id, stream) in listener.incoming().flatten().enumerate() {
+            let Ok(mut writer) = stream.try_clone() else { continue };
+            stream.set_write_timeout(Some(WRITE_WAIT)).ok();
+            if send(&mut writer, &ServerMsg::Greeting { version: PROTOCOL_VERSION }).is_err() {
+                continue;
+            }
+            // The client reads the refusal as the answer to whatever it asks first
+            if open.load(Ordering::SeqCst) >= MAX_CONNECTIONS {
+                refuse(writer, tr("room.too_many"));
+                continue;
+            }
+            stream.set_nodelay(true).ok();
+            stream.set_read_timeout(Some(HELLO_WAIT)).ok();
+            if events.send(Event::Arrived { id, writer }).is_err() {
+                return;
+            }
+            open.fetch_add(1, Ordering::SeqCst);
+            let (events, open) = (events.clone(), Arc::clone(&open));
+            thread::spawn(move || {
+                let mut reader = BufReader::new(stream);
+                let mut throttle = Throttle::new();
+                loop {
+                    match receive(&mut reader) {
+                        Ok(message) => {
+                            // Once it has said who it is, a client may go quiet for as long as it likes
+                            reader.get_ref().set_read_timeout(None).ok();
+                            throttle.pace();
+                            if events.send(Event::Said { id, message }).is_err() {
+                                break;
+                            }
+                        }
+                        Err(_) => {
+                            reader.get_ref().shutdown(Shutdown::Both).ok();
+                            events.send(Event::Gone { id }).ok();
+                            break;
+                        }
+                    }
+                }
+                open.fetch_sub(1, Ordering::SeqCst);
+            });
+        }
+    });
+}
+
+/// The room as the host keeps it.
+struct Room {
+    code: String,
+    host: String,
+    seat_count: usize,
+    guests: BTreeMap<usize, Guest>, // By connection
+    watchers: Watchers,
+}
+
+impl Room {
+    fn seated(&self) -> impl Iterator<Item = &Guest> {
+        self.guests.values().filter(|g| g.seat.is_some())
+    }
+
+    fn open_seats(&self) -> usize {
+        self.seat_count - 1 - self.seated().count()
+    }
+
+    fn seat_is_open(&self, seat: usize) -> bool {
+        (2..=self.seat_count).contains(&seat) && !self.seated().any(|g| g.seat.as_ref().is_some_and(|(n, _)| *n == seat))
+    }
+
+    /// Whether there's someone to play and everyone seated is ready.
+    fn all_ready(&self) -> bool {
+        self.seated().count() > 0 && self.seated().all(|g| g.ready)
+    }
+
+    /// Prints a line for the host and sends it to everyone seated.
+    fn announce(&mut self, text: &str) {
+        self.announce_except(text, None);
+    }
+
+    /// Like `announce`, leaving out one connection.
+    fn announce_except(&mut self, text: &str, except: Option<usize>) {
+        outln!("{}", text);
+        for (_, guest) in self.guests.iter_mut().filter(|(id, g)| g.seat.is_some() && Some(**id) != except) {
+            guest.tell(text);
+        }
+        self.watchers.tell(text);
+    }
+
+    /// Shows the room: its code, the rules and every seat.
+    fn show(&mut self) {
+        let spec = GameSpec::new(Variant::IndividualSecrets, Rules::default());
+        let mut lines = vec![
+            fill("room.title", &[("code", &self.code)]),
+            fill("room.game", &[("rules", &describe_record_key(&spec.rules.record_key()))]),
+            fill("room.host_seat", &[("name", &self.host)]),
+        ];
+        for seat in 2..=self.seat_count {
+            let guest = self.seated().find(|g| g.seat.as_ref().is_some_and(|(n, _)| *n == seat));
+            lines.push(match guest {
+                Some(Guest { seat: Some((_, name)), ready, .. }) => fill(if *ready { "room.seat_ready" } else { "room.seat_taken" }, &[("seat", &seat), ("name", name)]),
+                _ => fill("room.seat_open", &[("seat", &seat)]),
+            });
+        }
+        if self.watchers.count() > 0 {
+            lines.push(fill("room.watching", &[("count", &self.watchers.count()), ("cap", &self.watchers.cap)]));
+        }
+        for line in lines {
+            self.announce(&line);
+        }
+    }
+
+    /// Deals with one event, returning whether the room changed.
+    fn handle(&mut self, event: Event) -> bool {
+        match event {
+            Event::Arrived { id, writer } => {
+                self.guests.insert(id, Guest { writer, seat: None, token: String::new(), ready: false });
+                false
+            }
+            Event::Said { id, message: ClientMsg::Hello { version, name, room } } => self.seat(id, version, &name, &room),
+            Event::Said { id, message: ClientMsg::Rejoin { .. } } => {
+                if let Some(guest) = self.guests.remove(&id) {
+                    guest.refuse(tr("room.not_started"));
+                }
+                false
+            }
+            Event::Said { id, message: ClientMsg::Watch { version, room } } => match self.guests.remove(&id) {
+                Some(Guest { writer, seat: None, .. }) => self.watchers.admit(id, writer, version, &room),
+                Some(guest) => {
+                    self.guests.insert(id, guest);
+                    false
+                }
+                None => false,
+            },
+            Event::Said { id, message: ClientMsg::Input(text) } => self.command(id, text.trim()),
+            Event::Said { id, message: ClientMsg::Chat(text) } => {
+                if let Some((_, name)) = self.guests.get(&id).and_then(|g| g.seat.clone()) {
+                    self.announce_except(&chat_line(&name, &text), Some(id));
+                }
+                false
+            }
+            Event::Said { .. } => false, // Nothing else is expected in the room
+            Event::Typed(_) => false,
+            Event::Gone { id } => match self.guests.remove(&id) {
+                Some(Guest { seat: Some((_, name)), .. }) => {
+                    self.announce(&fill("room.left", &[("name", &name)]));
+                    true
+                }
+                // A spectator leaving doesn't change what anyone sees but the count
+                _ => self.watchers.remove(id),
+            },
+        }
+    }
+
+    /// Seats a client that has said hello, or turns it away.
+    fn seat(&mut self, id: usize, version: u32, wanted: &str, code: &str) -> bool {
+        if self.guests.get(&id).is_none_or(|g| g.seat.is_some()) {
+            return false;
+        }
+        let open = (2..=self.seat_count).find(|&n| self.seat_is_open(n));
+        let refusal = if version != PROTOCOL_VERSION {
+            Some(version_mismatch(version))
+        } else if !same_code(code, &self.code) {
+            Some(tr("room.wrong_code").to_string())
+        } else if open.is_none() {
+            Some(tr("room.full").to_string())
+        } else {
+            None
+        };
+        if let Some(reason) = refusal {
+            if let Some(guest) = self.guests.remove(&id) {
+                guest.refuse(&reason);
+            }
+            return false;
+        }
+        let Some(seat) = open else { return false };
+
+        let mut taken: Vec<&str> = vec![&self.host];
+        taken.extend(self.seated().filter_map(|g| g.seat.as_ref().map(|(_, name)| name.as_str())));
+        let name = unique_name(wanted, &taken);
+        let Some(guest) = self.guests.get_mut(&id) else { return false };
+        let address = guest.writer.peer_addr().map(|a| a.ip().to_string()).unwrap_or_else(|_| tr("room.somewhere").to_string());
+        guest.send(&ServerMsg::Spec(GameSpec::new(Variant::IndividualSecrets, Rules::default())));
+        guest.token = session_token();
+        let token = guest.token.clone();
+        guest.send(&ServerMsg::Welcome { name: name.clone(), token });
+        guest.tell(tr("room.help"));
+        guest.seat = Some((seat, name.clone()));
+        self.announce(&fill("room.joined", &[("name", &name), ("address", &address), ("seat", &seat)]));
+        true
+    }
+
+    /// Carries out a seated player's room command.
+    fn command(&mut self, id: usize, text: &str) -> bool {
+        let Some((seat, name)) = self.guests.get(&id).and_then(|g| g.seat.clone()) else { return false };
+        let words: Vec<&str> = text.split_whitespace().collect();
+        match words.as_slice() {
+            ["ready"] => {
+                let Some(guest) = self.guests.get_mut(&id) else { return false };
+                guest.ready = !guest.ready;
+                let text = fill(if guest.ready { "room.ready" } else { "room.unready" }, &[("name", &name)]);
+                self.announce(&text);
+                true
+            }
+            ["seat", wanted] => match wanted.parse() {
+                Ok(wanted) if wanted == seat => false,
+                Ok(wanted) if self.seat_is_open(wanted) => {
+                    if let Some(guest) = self.guests.get_mut(&id) {
+                        guest.seat = Some((wanted, name.clone()));
+                    }
+                    self.announce(&fill("room.moved", &[("name", &name), ("seat", &wanted)]));
+                    true
+                }
+                _ => {
+                    let open: Vec<String> = (2..=self.seat_count).filter(|&n| self.seat_is_open(n)).map(|n| n.to_string()).collect();
+                    let text = if open.is_empty() { tr("room.no_open_seats").to_string() } else { fill("room.open_seats", &[("seats", &open.join(", "))]) };
+                    if let Some(guest) = self.guests.get_mut(&id) {
+                        guest.tell(&text);
+                    }
+                    false
+                }
+            },
+            ["/quit"] => {
+                if let Some(mut guest) = self.guests.remove(&id) {
+                    guest.tell(tr("room.left_you"));
+                    guest.send(&ServerMsg::End);
+                }
+                self.announce(&fill("room.left", &[("name", &name)]));
+                true
+            }
+            _ => {
+                if let Some(guest) = self.guests.get_mut(&id) {
+                    guest.tell(tr("room.help"));
+                }
+                false
+            }
+        }
+    }
+
+    /// Deals with everything that has happened so far, returning whether the room changed.
+    fn catch_up(&mut self, events: &Receiver<Event>) -> bool {
+        let mut changed = false;
+        while let Ok(event) = events.try_recv() {
+            changed |= self.handle(event);
+        }
+        changed
+    }
+
+    /// Tells everyone seated the game is starting and waits for them to
+    /// answer. Returns the players in seat order, the host's first, and the
+    /// spectators.
+    fn start(mut self, events: &Receiver<Event>) -> (Players, Watchers) {
+        let unseated: Vec<usize> = self.guests.iter().filter(|(_, g)| g.seat.is_none()).map(|(id, _)| *id).collect();
+        for id in unseated {
+            if let Some(guest) = self.guests.remove(&id) {
+                guest.refuse(tr("net.started"));
+            }
+        }
+        for guest in self.guests.values_mut() {
+            guest.send(&ServerMsg::Start);
+        }
+
+        let mut waiting: BTreeSet<usize> = self.guests.keys().copied().collect();
+        let deadline = Instant::now() + START_WAIT;
+        while !waiting.is_empty() {
+            let Ok(event) = events.recv_timeout(deadline.saturating_duration_since(Instant::now())) else { break };
+            match event {
+                Event::Said { id, message: ClientMsg::Started } => {
+                    waiting.remove(&id);
+                }
+                Event::Gone { id } => {
+                    waiting.remove(&id);
+                    self.guests.remove(&id);
+                    self.watchers.remove(id);
+                }
+                Event::Arrived { writer, .. } => refuse(writer, tr("net.started")),
+                Event::Said { .. } | Event::Typed(_) => {} // Room commands and chat sent just before the start
+            }
+        }
+
+        let mut players: Vec<(usize, String, Option<Client>)> = vec![(1, self.host.clone(), None)];
+        for (id, guest) in self.guests {
+            let Some((seat, name)) = guest.seat else { continue };
+            if waiting.contains(&id) {
+                outln!("{}", fill("room.no_answer", &[("name", &name)]));
+                guest.writer.shutdown(Shutdown::Both).ok();
+            } else {
+                players.push((seat, name, Some(Client { id, writer: guest.writer, token: guest.token })));
+            }
+        }
+        players.sort_by_key(|(seat, _, _)| *seat);
+        (players.into_iter().map(|(_, name, client)| (name, client)).collect(), self.watchers)
+    }
+}
+
+/// Runs the room for the game hosted on `listener` until the host starts
+/// it, keeping `open_seats` up to date for LAN searches. Allows up to
+/// `spectator_cap` spectators. Returns the players in seat order, each with
+/// their client but the host, the spectators, and both ends of the channel
+/// the clients' messages keep arriving on.
+pub fn run_room(listener: TcpListener, host: &str, seat_count: usize, spectator_cap: usize, open_seats: &AtomicUsize) -> (Players, Watchers, Sender<Event>, Receiver<Event>) {
+    let (sender, events) = mpsc::channel();
+    accept_clients(listener, sender.clone());
+
+    let code = room_code();
+    let watchers = Watchers::new(&code, spectator_cap);
+    let mut room = Room { code, host: host.to_string(), seat_count, guests: BTreeMap::new(), watchers };
+    room.show();
+    outln!("{}", fill("room.pass_code", &[("code", &room.code)]));
+    while let Ok(event) = events.recv() {
+        let changed = room.handle(event) | room.catch_up(&events);
+        if !changed {
+            continue;
+        }
+        open_seats.store(room.open_seats(), Ordering::Relaxed);
+        room.show();
+        if !room.all_ready() {
+            continue;
+        }
+        if room.open_seats() > 0 {
+            let answer = prompt(tr("room.start_prompt"));
+            // Anything that happened while the host was deciding counts first
+            if room.catch_up(&events) {
+                open_seats.store(room.open_seats(), Ordering::Relaxed);
+                room.show();
+            }
+            if !answer.eq_ignore_ascii_case(tr("common.yes")) {
+                continue;
+            }
+            if !room.all_ready() {
+                outln!("{}", tr("room.not_all_ready"));
+                continue;
+            }
+        }
+        break;
+    }
+    open_seats.store(0, Ordering::Relaxed);
+    let (players, watchers) = room.start(&events);
+    (players, watchers, sender, events)
+}
@@ -0,0 +1,293 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::code::{calculate_score, code_length, format_code, solved_score, Guess, MAX_CODE_LENGTH};
+use crate::config::Config;
+use crate::feedback::player_notation;
+use crate::strings::{fill, tr};
+
+// --- Rule Set Versioning ---
+// Saves and network games describe the game they contain with a `GameSpec`
+// before any of the actual game state. Loaders check the spec first so an
+// older build can say exactly what it is missing instead of failing halfway
+// through reading a file it doesn't understand.
+
+/// Version of the scoring/turn rules implemented by this build.
+/// Bump this whenever a change would alter the outcome of an existing game.
+pub const RULES_VERSION: u32 = 1;
+
+/// Code lengths this build can play.
+pub const SUPPORTED_CODE_LENGTHS: std::ops::RangeInclusive<usize> = 3..=MAX_CODE_LENGTH;
+
+/// Whether this build can generate and score codes with repeated digits.
+pub const SUPPORTS_REPEATED_DIGITS: bool = false;
+
+/// The digits codes are made from.
+pub const DIGIT_RANGE: std::ops::RangeInclusive<u8> = 0..=9;
+
+/// The game variants this build knows how to run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    IndividualSecrets, // Every player races to crack their own code
+    DoubleBlind,       // A human codemaker scores guesses, the engine referees
+}
+
+impl Variant {
+    /// Every variant this build supports.
+    pub const ALL: [Variant; 2] = [Variant::IndividualSecrets, Variant::DoubleBlind];
+
+    /// Stable identifier used in saves and network handshakes.
+    pub fn id(self) -> &'static str {
+        match self {
+            Variant::IndividualSecrets => "individual",
+            Variant::DoubleBlind => "double-blind",
+        }
+    }
+
+    /// Latest version of this variant's rules implemented by this build.
+    pub fn version(self) -> u32 {
+        match self {
+            Variant::IndividualSecrets => 1,
+            Variant::DoubleBlind => 1,
+        }
+    }
+
+    /// Looks up a variant by its stable identifier.
+    pub fn from_id(id: &str) -> Option<Variant> {
+        Variant::ALL.into_iter().find(|v| v.id() == id)
+    }
+}
+
+/// The rule configuration a game is played with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    pub code_length: usize,
+    pub allow_repeats: bool,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules { code_length: code_length(), allow_repeats: false }
+    }
+}
+
+impl Rules {
+    /// The rules every game was played with before lengths other than 4
+    /// could be chosen, which results from then were kept under.
+    pub const STANDARD: Rules = Rules { code_length: 4, allow_repeats: false };
+
+    /// The key results are kept under, so records set under one rule set
+    /// aren't compared with another's: `length=4;repeats=false;digits=0-9`.
+    pub fn record_key(self) -> String {
+        format!("length={};repeats={};digits={}-{}", self.code_length, self.allow_repeats, DIGIT_RANGE.start(), DIGIT_RANGE.end())
+    }
+}
+
+/// How a record key reads to players, e.g. "4 digits, no repeats, 0-9".
+/// Keys this build can't read (from a newer one) are shown as they are.
+pub fn describe_record_key(key: &str) -> String {
+    let fields: Vec<(&str, &str)> = key.split(';').filter_map(|pair| pair.split_once('=')).collect();
+    match fields.as_slice() {
+        [("length", length), ("repeats", repeats), ("digits", digits)] => {
+            let repeats = tr(if *repeats == "true" { "rules.repeats" } else { "rules.no_repeats" });
+            fill("rules.record_key", &[("length", length), ("repeats", &repeats), ("digits", digits)])
+        }
+        _ => key.to_string(),
+    }
+}
+
+/// Describes the game stored in a save or offered by a network host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameSpec {
+    pub variant: String, // Kept as the raw id so unknown variants can be reported by name
+    pub variant_version: u32,
+    pub rules_version: u32,
+    pub rules: Rules,
+    pub unknown_options: Vec<String>, // Options written by a newer build that we don't recognise
+}
+
+impl GameSpec {
+    /// Builds the spec for a game started by this build.
+    pub fn new(variant: Variant, rules: Rules) -> Self {
+        GameSpec {
+            variant: variant.id().to_string(),
+            variant_version: variant.version(),
+            rules_version: RULES_VERSION,
+            rules,
+            unknown_options: Vec::new(),
+        }
+    }
+
+    /// Checks that this build supports everything the spec requires.
+    /// Returns the resolved variant, or every missing capability at once.
+    pub fn check_supported(&self) -> Result<Variant, Incompatibility> {
+        let mut missing = Vec::new();
+
+        let variant = Variant::from_id(&self.variant);
+        match variant {
+            None => missing.push(Capability::Variant(self.variant.clone())),
+            Some(v) if self.variant_version > v.version() => missing.push(Capability::VariantVersion {
+                variant: self.variant.clone(),
+                required: self.variant_version,
+                supported: v.version(),
+            }),
+            Some(_) => {}
+        }
+
+        if self.rules_version > RULES_VERSION {
+            missing.push(Capability::RulesVersion { required: self.rules_version, supported: RULES_VERSION });
+        }
+
+        if !SUPPORTED_CODE_LENGTHS.contains(&self.rules.code_length) {
+            missing.push(Capability::CodeLength(self.rules.code_length));
+        }
+
+        if self.rules.allow_repeats && !SUPPORTS_REPEATED_DIGITS {
+            missing.push(Capability::RepeatedDigits);
+        }
+
+        for option in &self.unknown_options {
+            missing.push(Capability::RuleOption(option.clone()));
+        }
+
+        match variant {
+            Some(v) if missing.is_empty() => Ok(v),
+            _ => Err(Incompatibility { missing }),
+        }
+    }
+}
+
+// The spec travels as a single `key=value;key=value` line, e.g.
+// `variant=individual;variant_version=1;rules_version=1;length=4;repeats=false`.
+impl fmt::Display for GameSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "variant={};variant_version={};rules_version={};length={};repeats={}",
+               self.variant, self.variant_version, self.rules_version,
+               self.rules.code_length, self.rules.allow_repeats)
+    }
+}
+
+impl FromStr for GameSpec {
+    type Err = SpecError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut variant = None;
+        let mut variant_version = 1;
+        let mut rules_version = 1;
+        let mut rules = Rules::default();
+        let mut unknown_options = Vec::new();
+
+        for pair in s.trim().split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = pair.split_once('=').ok_or_else(|| SpecError(format!("expected key=value, found '{}'", pair)))?;
+            let (key, value) = (key.trim(), value.trim());
+
+            match key {
+                "variant" => variant = Some(value.to_string()),
+                "variant_version" => variant_version = parse_number(key, value)?,
+                "rules_version" => rules_version = parse_number(key, value)?,
+                "length" => rules.code_length = parse_number(key, value)?,
+                "repeats" => rules.allow_repeats = value.parse().map_err(|_| SpecError(format!("'{}' is not true/false for repeats", value)))?,
+                // Unknown keys are rule options from a newer build; keep them so they can be reported
+                _ => unknown_options.push(key.to_string()),
+            }
+        }
+
+        let variant = variant.ok_or_else(|| SpecError("missing the 'variant' field".to_string()))?;
+        Ok(GameSpec { variant, variant_version, rules_version, rules, unknown_options })
+    }
+}
+
+/// Parses a numeric spec field, naming the field on failure.
+fn parse_number<T: FromStr>(key: &str, value: &str) -> Result<T, SpecError> {
+    value.parse().map_err(|_| SpecError(format!("'{}' is not a valid number for {}", value, key)))
+}
+
+// --- Compatibility Errors ---
+
+/// A single capability a game needs that this build does not have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Capability {
+    Variant(String),
+    VariantVersion { variant: String, required: u32, supported: u32 },
+    RulesVersion { required: u32, supported: u32 },
+    CodeLength(usize),
+    RepeatedDigits,
+    RuleOption(String),
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Capability::Variant(id) => write!(f, "{}", fill("rules.needs_variant", &[("id", id)])),
+            Capability::VariantVersion { variant, required, supported } => {
+                write!(f, "{}", fill("rules.needs_variant_version", &[("required", required), ("variant", variant), ("supported", supported)]))
+            }
+            Capability::RulesVersion { required, supported } => {
+                write!(f, "{}", fill("rules.needs_rules_version", &[("required", required), ("supported", supported)]))
+            }
+            Capability::CodeLength(length) => write!(f, "{}", fill("rules.needs_code_length", &[
+                ("length", length),
+                ("min", SUPPORTED_CODE_LENGTHS.start()),
+                ("max", SUPPORTED_CODE_LENGTHS.end()),
+            ])),
+            Capability::RepeatedDigits => write!(f, "{}", tr("rules.needs_repeats")),
+            Capability::RuleOption(option) => write!(f, "{}", fill("rules.needs_option", &[("option", option)])),
+        }
+    }
+}
+
+/// Every capability a game requires that this build is missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Incompatibility {
+    pub missing: Vec<Capability>,
+}
+
+impl fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", tr("rules.incompatible"))?;
+        for capability in &self.missing {
+            write!(f, "\n  - {}", capability)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Incompatibility {}
+
+/// A spec line that couldn't be read at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecError(pub String);
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", fill("rules.invalid_spec", &[("reason", &self.0)]))
+    }
+}
+
+impl std::error::Error for SpecError {}
+
+// --- Rule Summary ---
+
+/// Prints the rules in play and how feedback works, for `/rules`. Settings
+/// that change the game (hard mode, the turn timer) are included when on.
+pub fn print_rules() {
+    let rules = Rules::default();
+    let config = Config::current();
+    outln!("{}", tr("rules.title"));
+    outln!("{}", fill("rules.code", &[("length", &rules.code_length)]));
+    outln!("{}", tr("rules.feedback"));
+    let length = rules.code_length;
+    let (secret, guess) = (Guess::from_digits(&[1, 2, 3, 4, 5, 6][..length]), Guess::from_digits(&[1, 3, 5, 0, 7, 8][..length]));
+    outln!("{}", fill("rules.example", &[
+        ("secret", &format_code(&secret)),
+        ("guess", &format_code(&guess)),
+        ("score", &player_notation().render(calculate_score(&guess, &secret))),
+    ]));
+    outln!("{}", fill("rules.win", &[("score", &player_notation().render(solved_score()))]));
+    if config.hard_mode {
+        outln!("{}", tr("rules.hard_mode"));
+    }
+    if let Some(limit) = config.turn_limit() {
+        outln!("{}", fill("rules.timer", &[("count", &limit.as_secs())]));
+    }
+}
@@ -0,0 +1,286 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::bot::{Bot, Difficulty, Personality};
+use crate::engine::Engine;
+use crate::game::Player;
+use crate::profile::Profile;
+use crate::rules::{GameSpec, Rules, Variant};
+use crate::snapshot::{code_field, parse_code_field, parse_number_field, parse_score_field, score_field, Snapshot, SnapshotBody, SnapshotError};
+use crate::solver::{all_codes, filter_candidates};
+use crate::store::data_dir;
+use crate::strings::fill;
+use crate::transcript::{Move, Transcript};
+
+// --- Saved Games ---
+// `/save <name>` writes an Individual Secrets game as it stands at the start
+// of the turn (or, from the post-game menu, once the winner has left play),
+// and `play --resume <name>` carries on from there. A bare name is kept in
+// `saves/` in the data directory; anything with a directory or extension is
+// a path. The file is an engine snapshot of kind "individual":
+//
+//   game    seed, round, guesses, skipped turns, next rank, last ranked round, whose turn, milliseconds played
+//   player  playing|done, name, secret, seat, rank, cracked, guesses, hints, undos, turns to sit out, finish
+//   clue    guess, score        A free clue for the player above
+//   guess   guess, score        One of their guesses, oldest first
+//   note    text                One of their notes
+//   move    round, name, guess, score, milliseconds in   A line of the transcript
+//
+// A seat is `human`, a bot's difficulty and personality (`Hard/Gambler`), or
+// `engine:` and the command of an external bot program (`engine:./mybot`).
+// Candidates and bots' knowledge aren't stored; they follow from the clues
+// and guesses. Randomness after the resume is fresh, so the game's seed only
+// replays it from the start.
+
+/// An Individual Secrets game between turns.
+#[derive(Debug)]
+pub struct SavedGame {
+    pub seed: u64,
+    pub round: u32,
+    pub total_guesses: u32,
+    pub skipped_turns: u32,
+    pub rank_to_assign: usize,
+    pub last_assigned_round: u32,
+    pub current: usize, // Index into `players` of whoever is up
+    pub elapsed: Duration,
+    pub players: Vec<Player>,   // Still playing, in turn order
+    pub completed: Vec<Player>, // Finished or out, in the order they left
+    pub transcript: Transcript,
+}
+
+static RESUME: Mutex<Option<SavedGame>> = Mutex::new(None);
+
+/// Makes the next Individual Secrets game carry on from `game` instead of dealing a new one.
+pub fn resume_game(game: SavedGame) {
+    *RESUME.lock().unwrap() = Some(game);
+}
+
+/// The game given with `resume_game`, if no game has used it yet.
+pub fn take_resumed_game() -> Option<SavedGame> {
+    RESUME.lock().unwrap().take()
+}
+
+/// Tabs separate the fields, so they can't be part of one.
+fn text_field(text: &str) -> String {
+    text.replace('\t', " ")
+}
+
+fn seat_field(player: &Player) -> String {
+    match (&player.bot, &player.engine) {
+        (Some(bot), _) => format!("{}/{}", bot.difficulty, bot.personality),
+        (None, Some(engine)) => format!("engine:{}", text_field(&engine.command)),
+        (None, None) => "human".to_string(),
+    }
+}
+
+fn parse_seat_field(field: &str) -> Result<Option<(Difficulty, Personality)>, SnapshotError> {
+    if field == "human" {
+        return Ok(None);
+    }
+    let bad = || SnapshotError::Malformed(format!("unknown seat '{}'", field));
+    let (difficulty, personality) = field.split_once('/').ok_or_else(bad)?;
+    let difficulty = Difficulty::ALL.into_iter().find(|d| d.to_string() == difficulty).ok_or_else(bad)?;
+    let personality = Personality::ALL.into_iter().find(|p| p.to_string() == personality).ok_or_else(bad)?;
+    Ok(Some((difficulty, personality)))
+}
+
+/// A player's record, without the clues, guesses and notes that follow it.
+fn player_record(player: &Player, state: &str) -> Vec<String> {
+    vec![
+        "player".to_string(),
+        state.to_string(),
+        text_field(&player.name),
+        code_field(&player.secret_code),
+        seat_field(player),
+        player.rank.map_or("-".to_string(), |rank| rank.to_string()),
+        (player.cracked as u8).to_string(),
+        player.guesses.to_string(),
+        player.hints.to_string(),
+        player.undos.to_string(),
+        player.sits_out.to_string(),
+        player.finished.map_or("-".to_string(), |(round, time)| format!("{},{}", round, time.as_millis())),
+    ]
+}
+
+/// Reads a player record, leaving the clues and guesses to be added.
+fn parse_player(fields: &[&str]) -> Result<(bool, Player), SnapshotError> {
+    let [state, name, secret, seat, rank, cracked, guesses, hints, undos, sits_out, finished] = fields else {
+        return Err(SnapshotError::Malformed("a player record has the wrong number of fields".to_string()));
+    };
+    let playing = match *state {
+        "playing" => true,
+        "done" => false,
+        other => return Err(SnapshotError::Malformed(format!("unknown player state '{}'", other))),
+    };
+    let finished = match *finished {
+        "-" => None,
+        field => {
+            let (round, millis) = field.split_once(',').ok_or_else(|| SnapshotError::Malformed(format!("bad finish '{}'", field)))?;
+            Some((parse_number_field(round)?, Duration::from_millis(parse_number_field(millis)?)))
+        }
+    };
+    // A seat played by an external program starts it afresh on its next turn
+    let engine = seat.strip_prefix("engine:").map(Engine::new);
+    let bot = if engine.is_some() { None } else { parse_seat_field(seat)? };
+    let player = Player {
+        name: name.to_string(),
+        secret_code: parse_code_field(secret)?,
+        rank: if *rank == "-" { None } else { Some(parse_number_field(rank)?) },
+        guesses: parse_number_field(guesses)?,
+        cracked: *cracked == "1",
+        candidates: all_codes(),
+        hints: parse_number_field(hints)?,
+        notes: Vec::new(),
+        undos: parse_number_field(undos)?,
+        head_start: Vec::new(),
+        history: Vec::new(),
+        sits_out: parse_number_field(sits_out)?,
+        finished,
+        bot: bot.map(|(difficulty, personality)| Bot::with_personality(difficulty, personality)),
+        profile: if bot.is_none() && engine.is_none() { Profile::load(name) } else { None },
+        engine,
+    };
+    Ok((playing, player))
+}
+
+/// Where the save called `name` goes.
+pub fn save_path(name: &str) -> PathBuf {
+    let path = Path::new(name);
+    let bare = path.components().count() == 1 && path.extension().is_none();
+    match data_dir() {
+        Some(dir) if bare => dir.join("saves").join(format!("{}.txt", name)),
+        _ => path.to_path_buf(),
+    }
+}
+
+impl SavedGame {
+    /// Reads the save called `name` (or a file by that name, if there is
+    /// one), explaining what's wrong with it if it can't be used.
+    pub fn load(name: &str) -> Result<SavedGame, String> {
+        let path = if Path::new(name).is_file() { PathBuf::from(name) } else { save_path(name) };
+        let text = fs::read_to_string(&path).map_err(|e| fill("save.read_failed", &[("path", &path.display()), ("error", &e)]))?;
+        SavedGame::from_snapshot(&text).map_err(|e| fill("save.cant_resume", &[("path", &path.display()), ("error", &e)]))
+    }
+
+    /// Writes the game to `path`, replacing any file there.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_snapshot())
+    }
+
+    /// Like `write`, telling the table how it went.
+    pub fn save_to(&self, path: &Path) {
+        match self.write(path) {
+            Ok(()) => outln!("{}", fill("save.done", &[("path", &path.display())])),
+            Err(e) => outln!("{}", fill("save.failed", &[("path", &path.display()), ("error", &e)])),
+        }
+    }
+}
+
+impl Snapshot for SavedGame {
+    const KIND: &'static str = "individual";
+
+    fn spec(&self) -> GameSpec {
+        GameSpec::new(Variant::IndividualSecrets, Rules::default())
+    }
+
+    fn records(&self) -> Vec<Vec<String>> {
+        let mut records = vec![vec![
+            "game".to_string(),
+            self.seed.to_string(),
+            self.round.to_string(),
+            self.total_guesses.to_string(),
+            self.skipped_turns.to_string(),
+            self.rank_to_assign.to_string(),
+            self.last_assigned_round.to_string(),
+            self.current.to_string(),
+            self.elapsed.as_millis().to_string(),
+        ]];
+        let players = self.players.iter().map(|p| (p, "playing")).chain(self.completed.iter().map(|p| (p, "done")));
+        for (player, state) in players {
+            records.push(player_record(player, state));
+            for (clue, score) in &player.head_start {
+                records.push(vec!["clue".to_string(), code_field(clue), score_field(*score)]);
+            }
+            for (guess, score) in &player.history {
+                records.push(vec!["guess".to_string(), code_field(guess), score_field(*score)]);
+            }
+            for note in &player.notes {
+                records.push(vec!["note".to_string(), text_field(note)]);
+            }
+        }
+        for m in &self.transcript.moves {
+            records.push(vec![
+                "move".to_string(),
+                m.round.to_string(),
+                text_field(&m.player),
+                code_field(&m.guess),
+                score_field(m.score),
+                m.at.as_millis().to_string(),
+            ]);
+        }
+        records
+    }
+
+    fn from_body(body: SnapshotBody) -> Result<Self, SnapshotError> {
+        if body.variant != Variant::IndividualSecrets {
+            return Err(SnapshotError::Malformed(format!("a {} game can't be resumed", body.variant.id())));
+        }
+        let mut game: Option<SavedGame> = None;
+        let mut seated: Vec<(bool, Player)> = Vec::new();
+        let mut moves = Vec::new();
+        let no_player = || SnapshotError::Malformed("a clue, guess or note comes before any player".to_string());
+        for record in &body.records {
+            match record.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+                ["game", seed, round, total, skipped, rank, last_round, current, elapsed] => {
+                    game = Some(SavedGame {
+                        seed: parse_number_field(seed)?,
+                        round: parse_number_field(round)?,
+                        total_guesses: parse_number_field(total)?,
+                        skipped_turns: parse_number_field(skipped)?,
+                        rank_to_assign: parse_number_field(rank)?,
+                        last_assigned_round: parse_number_field(last_round)?,
+                        current: parse_number_field(current)?,
+                        elapsed: Duration::from_millis(parse_number_field(elapsed)?),
+                        players: Vec::new(),
+                        completed: Vec::new(),
+                        transcript: Transcript::default(),
+                    });
+                }
+                ["player", fields @ ..] => seated.push(parse_player(fields)?),
+                ["clue", guess, score] => seated.last_mut().ok_or_else(no_player)?.1.head_start.push((parse_code_field(guess)?, parse_score_field(score)?)),
+                ["guess", guess, score] => seated.last_mut().ok_or_else(no_player)?.1.history.push((parse_code_field(guess)?, parse_score_field(score)?)),
+                ["note", text] => seated.last_mut().ok_or_else(no_player)?.1.notes.push(text.to_string()),
+                ["move", round, name, guess, score, at] => moves.push(Move {
+                    round: parse_number_field(round)?,
+                    player: name.to_string(),
+                    guess: parse_code_field(guess)?,
+                    score: parse_score_field(score)?,
+                    at: Duration::from_millis(parse_number_field(at)?),
+                }),
+                _ => {} // Records from newer builds are skipped
+            }
+        }
+
+        let mut game = game.ok_or_else(|| SnapshotError::Malformed("missing game record".to_string()))?;
+        game.transcript.moves = moves;
+        for (playing, mut player) in seated {
+            for (guess, score) in player.head_start.iter().chain(&player.history) {
+                filter_candidates(&mut player.candidates, guess, *score);
+                if let Some(bot) = &mut player.bot {
+                    bot.observe(guess, *score);
+                }
+            }
+            if playing { game.players.push(player) } else { game.completed.push(player) }
+        }
+        if game.current >= game.players.len() {
+            return Err(SnapshotError::Malformed("nobody is left to take the next turn".to_string()));
+        }
+        Ok(game)
+    }
+}
@@ -0,0 +1,241 @@
+use std::time::Duration;
+
+use crate::code::{format_code, Guess};
+use crate::console::clear_screen;
+use crate::locale::Locale;
+use crate::store::StoreSet;
+use crate::strings::{fill, tr};
+
+// --- Session Accumulator ---
+// Collects every secret played while the program runs, across all modes, so
+// the end-of-session trivia screen has something to talk about, and every
+// Individual Secrets game for the session summary after it. Nothing here is
+// saved; long-term results live in the stats store.
+
+/// Who cracked a secret, and how quickly.
+#[derive(Debug, Clone)]
+pub struct Solve {
+    pub player: String,
+    pub guesses: u32,
+}
+
+/// One secret that came up during the session.
+#[derive(Debug, Clone)]
+pub struct SecretRecord {
+    pub mode: &'static str,
+    pub secret: Guess,
+    pub solve: Option<Solve>, // None if nobody cracked it
+}
+
+/// One player's result in a game of the session.
+#[derive(Debug, Clone)]
+pub struct Placement {
+    pub player: String,
+    pub place: Option<usize>, // None if they didn't finish
+    pub guesses: u32,
+}
+
+/// One Individual Secrets game played during the session.
+#[derive(Debug, Clone)]
+pub struct GameRecord {
+    pub duration: Duration,
+    pub total_guesses: u32,
+    pub placements: Vec<Placement>, // In finishing order
+}
+
+#[derive(Debug, Default)]
+pub struct Session {
+    secrets: Vec<SecretRecord>,
+    games: Vec<GameRecord>,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Session::default()
+    }
+
+    /// Notes a secret from a finished (or abandoned) game.
+    pub fn record_secret(&mut self, mode: &'static str, secret: Guess, solve: Option<Solve>) {
+        self.secrets.push(SecretRecord { mode, secret, solve });
+    }
+
+    pub fn secrets(&self) -> &[SecretRecord] {
+        &self.secrets
+    }
+
+    /// Notes an Individual Secrets game once it's over, finished or not.
+    pub fn record_game(&mut self, game: GameRecord) {
+        self.games.push(game);
+    }
+
+    pub fn games(&self) -> &[GameRecord] {
+        &self.games
+    }
+
+    /// How often each digit appeared in a secret.
+    pub fn digit_counts(&self) -> [usize; 10] {
+        let mut counts = [0; 10];
+        for record in &self.secrets {
+            for &digit in &record.secret {
+                counts[digit as usize] += 1;
+            }
+        }
+        counts
+    }
+
+    /// How often each digit opened a secret.
+    pub fn leading_digit_counts(&self) -> [usize; 10] {
+        let mut counts = [0; 10];
+        for record in &self.secrets {
+            counts[record.secret[0] as usize] += 1;
+        }
+        counts
+    }
+
+    /// The quickest solve of the session; on a tie, the earliest one.
+    pub fn fastest_solve(&self) -> Option<(&SecretRecord, &Solve)> {
+        self.secrets
+            .iter()
+            .filter_map(|record| record.solve.as_ref().map(|solve| (record, solve)))
+            .min_by_key(|(_, solve)| solve.guesses)
+    }
+
+    /// The solve that took the most guesses; on a tie, the earliest one.
+    pub fn longest_solve(&self) -> Option<(&SecretRecord, &Solve)> {
+        self.secrets
+            .iter()
+            .filter_map(|record| record.solve.as_ref().map(|solve| (record, solve)))
+            .fold(None, |longest: Option<(&SecretRecord, &Solve)>, entry| match longest {
+                Some(current) if current.1.guesses >= entry.1.guesses => Some(current),
+                _ => Some(entry),
+            })
+    }
+}
+
+/// Digits sharing the highest count, joined for display, or None if all are zero.
+fn hottest(counts: &[usize; 10]) -> Option<(String, usize)> {
+    let max = *counts.iter().max()?;
+    if max == 0 {
+        return None;
+    }
+    let digits: Vec<String> = (0..10).filter(|&d| counts[d] == max).map(|d| d.to_string()).collect();
+    Some((digits.join(", "), max))
+}
+
+/// Shows fun facts about the secrets played this session, if there were any.
+pub fn show_trivia(session: &Session, stores: &StoreSet) {
+    if session.secrets().is_empty() {
+        return;
+    }
+
+    let locale = Locale::current();
+    clear_screen();
+    outln!("======================================");
+    outln!("{}", tr("session.trivia"));
+    outln!("======================================");
+    outln!("{}", fill("session.secrets", &[("count", &locale.count(session.secrets().len() as u64))]));
+
+    let counts = session.digit_counts();
+    if let Some((digits, count)) = hottest(&counts) {
+        outln!("{}", fill("session.hottest", &[("digits", &digits), ("count", &locale.count(count as u64))]));
+    }
+
+    let never: Vec<String> = (0..10).filter(|&d| counts[d] == 0).map(|d| d.to_string()).collect();
+    if never.is_empty() {
+        outln!("{}", tr("session.every_digit"));
+    } else {
+        outln!("{}", fill("session.never", &[("digits", &never.join(", "))]));
+    }
+
+    if let Some((digits, count)) = hottest(&session.leading_digit_counts()) {
+        outln!("{}", fill("session.first_digit", &[("digits", &digits), ("count", &locale.count(count as u64))]));
+    }
+
+    match session.fastest_solve() {
+        Some((record, solve)) => {
+            outln!("{}", fill("session.fastest", &[
+                ("name", &solve.player),
+                ("secret", &format_code(&record.secret)),
+                ("count", &solve.guesses),
+                ("mode", &record.mode),
+            ]));
+
+            // Put the session's best next to the player's saved history
+            let all_time_best = stores
+                .personal
+                .results()
+                .iter()
+                .chain(stores.club.iter().flat_map(|club| club.results()))
+                .filter(|result| result.player == solve.player && result.rank == 1 && result.guesses > 0)
+                .map(|result| result.guesses)
+                .min();
+            if let Some(best) = all_time_best {
+                outln!("{}", fill("session.all_time_best", &[("name", &solve.player), ("count", &best)]));
+            }
+        }
+        None => outln!("{}", tr("session.nobody")),
+    }
+
+    if let Some((record, solve)) = session.longest_solve()
+        && session.secrets().iter().filter(|r| r.solve.is_some()).count() > 1
+    {
+        outln!("{}", fill("session.longest", &[
+            ("name", &solve.player),
+            ("count", &solve.guesses),
+            ("secret", &format_code(&record.secret)),
+            ("mode", &record.mode),
+        ]));
+    }
+    outln!("======================================");
+}
+
+/// Sums up the session's Individual Secrets games when there was more than
+/// one: each player's placements, the guesses made, the fastest solve and
+/// the longest game.
+pub fn show_summary(session: &Session) {
+    let games = session.games();
+    if games.len() < 2 {
+        return;
+    }
+
+    let locale = Locale::current();
+    outln!("\n======================================");
+    outln!("{}", tr("session.summary"));
+    outln!("======================================");
+    outln!("{}", fill("session.games", &[("count", &locale.count(games.len() as u64))]));
+
+    // Players in the order they first sat down
+    let mut players: Vec<&str> = Vec::new();
+    for placement in games.iter().flat_map(|g| &g.placements) {
+        if !players.contains(&placement.player.as_str()) {
+            players.push(&placement.player);
+        }
+    }
+    outln!("{}", tr("session.placements"));
+    for player in players {
+        let places: Vec<String> = games
+            .iter()
+            .flat_map(|g| &g.placements)
+            .filter(|p| p.player == player)
+            .map(|p| p.place.map_or(tr("session.did_not_finish").to_string(), |place| locale.ordinal(place)))
+            .collect();
+        outln!("{}", fill("session.placement_row", &[("name", &player), ("places", &places.join(", "))]));
+    }
+
+    let total: u64 = games.iter().map(|g| g.total_guesses as u64).sum();
+    outln!("{}", fill("session.total_guesses", &[("count", &locale.count(total))]));
+
+    let fastest = games
+        .iter()
+        .enumerate()
+        .flat_map(|(i, g)| g.placements.iter().filter(|p| p.place.is_some()).map(move |p| (i, p)))
+        .min_by_key(|(_, p)| p.guesses);
+    if let Some((game, placement)) = fastest {
+        outln!("{}", fill("session.fastest_game", &[("name", &placement.player), ("count", &placement.guesses), ("game", &(game + 1))]));
+    }
+
+    if let Some((game, record)) = games.iter().enumerate().max_by_key(|(i, g)| (g.duration, std::cmp::Reverse(*i))) {
+        outln!("{}", fill("session.longest_game", &[("game", &(game + 1)), ("time", &locale.duration(record.duration))]));
+    }
+    outln!("======================================");
+}
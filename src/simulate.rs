@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use clap::Args;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::analysis::StrategyReport;
+use crate::config::Config;
+use crate::code::{calculate_score, is_solved, Guess, Score};
+use crate::solver::{all_codes, filter_candidates, Strategy};
+use crate::strings::fill;
+
+// --- Strategy Benchmarks ---
+// Plays a strategy against many random secrets, non-interactively. The
+// strategies are deterministic, so the guess chosen after a given sequence of
+// feedback is cached; after the first few games most turns are lookups.
+
+/// Plays one game of `strategy` against `secret`, returning the number of guesses.
+fn play_one(strategy: Strategy, secret: &Guess, codes: &[Guess], cache: &mut HashMap<Vec<Score>, Guess>) -> usize {
+    let mut candidates = codes.to_vec();
+    let mut path: Vec<Score> = Vec::new();
+
+    loop {
+        let guess = *cache
+            .entry(path.clone())
+            .or_insert_with(|| strategy.choose(&candidates, codes).expect("the secret is always a candidate"));
+        let score = calculate_score(&guess, secret);
+        path.push(score);
+
+        if is_solved(score) {
+            return path.len();
+        }
+        filter_candidates(&mut candidates, &guess, score);
+    }
+}
+
+/// Plays `games` games of `strategy` against secrets drawn from `rng`.
+pub fn simulate_strategy(strategy: Strategy, games: usize, rng: &mut impl Rng) -> StrategyReport {
+    let codes = all_codes();
+    let mut cache = HashMap::new();
+    let mut counts: Vec<usize> = Vec::new();
+
+    for _ in 0..games {
+        let secret = codes[rng.random_range(0..codes.len())];
+        let guesses = play_one(strategy, &secret, &codes, &mut cache);
+        if counts.len() < guesses {
+            counts.resize(guesses, 0);
+        }
+        counts[guesses - 1] += 1;
+    }
+    StrategyReport { strategy, counts }
+}
+
+/// Prints one strategy's results with a small bar chart of the distribution.
+fn print_report(report: &StrategyReport, seconds: f64) {
+    outln!("{}", fill("simulate.title", &[("strategy", &report.strategy.id()), ("count", &report.secrets()), ("seconds", &format!("{:.1}", seconds))]));
+    let most = report.counts.iter().copied().max().unwrap_or(0).max(1);
+    for (i, &n) in report.counts.iter().enumerate() {
+        let bar = "█".repeat((n * 40).div_ceil(most));
+        outln!("{}", fill("simulate.depth", &[("count", &format!("{:>2}", i + 1)), ("games", &format!("{:>6}", n)), ("bar", &bar)]));
+    }
+    outln!("{}", fill("analyze.summary", &[("average", &format!("{:.3}", report.average())), ("worst", &report.worst_case())]));
+}
+
+/// The options of `simulate`.
+#[derive(Debug, Args)]
+pub struct SimulateArgs {
+    /// Random secrets each strategy plays against
+    #[arg(long, value_name = "N", default_value_t = 1000, value_parser = clap::value_parser!(u32).range(1..))]
+    pub games: u32,
+    /// Run only this strategy, instead of all of them
+    #[arg(long, value_enum)]
+    pub strategy: Option<Strategy>,
+    /// Deal the same secrets as an earlier run
+    #[arg(long, value_name = "N")]
+    pub seed: Option<u64>,
+    /// Threads the solver searches with (0 for one per core)
+    #[arg(long, value_name = "N")]
+    pub threads: Option<usize>,
+}
+
+/// Entry point for the `simulate` subcommand.
+pub fn run_simulation(args: &SimulateArgs) -> Result<(), String> {
+    let games = args.games as usize;
+    let strategies = args.strategy.map_or(Strategy::ALL.to_vec(), |strategy| vec![strategy]);
+    if let Some(threads) = args.threads {
+        Config::set_current(Config { solver_threads: threads, ..Config::current() });
+    }
+
+    // Every strategy faces the same secrets, so the comparison is fair
+    let seed = args.seed.unwrap_or_else(|| rand::rng().random());
+    outln!("{}", fill("simulate.start", &[("count", &games), ("seed", &seed)]));
+
+    for strategy in strategies {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let started_at = Instant::now();
+        let report = simulate_strategy(strategy, games, &mut rng);
+        print_report(&report, started_at.elapsed().as_secs_f64());
+    }
+    Ok(())
+}
@@ -0,0 +1,243 @@
+use std::fmt;
+
+use crate::code::{format_code, set_code_length, Guess, Score, MAX_CODE_LENGTH};
+use crate::rules::{GameSpec, Incompatibility, SpecError, Variant, SUPPORTED_CODE_LENGTHS};
+use crate::strings::fill;
+
+// --- Engine Snapshots ---
+// A snapshot is a plain-text copy of a game's engine state, used for saves
+// and for the practice mode's undo history. Like the stats store, it is one
+// record per line with tab-separated fields:
+//
+//   sgg-snapshot 1
+//   spec    variant=individual;variant_version=1;...
+//   kind    practice
+//   ...     records specific to the kind of game
+//
+// The spec line is checked against this build before anything else is read,
+// so an incompatible save is reported by capability rather than as garbage.
+
+const SNAPSHOT_HEADER: &str = "sgg-snapshot 1";
+
+/// Why a snapshot couldn't be restored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotError {
+    Malformed(String),             // Not a snapshot, or damaged
+    Incompatible(Incompatibility), // Written for rules this build doesn't support
+    WrongKind { expected: String, found: String },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Malformed(reason) => write!(f, "{}", fill("snapshot.damaged", &[("reason", reason)])),
+            SnapshotError::Incompatible(incompatibility) => write!(f, "{}", incompatibility),
+            SnapshotError::WrongKind { expected, found } => {
+                write!(f, "{}", fill("snapshot.wrong_kind", &[("expected", expected), ("found", found)]))
+            }
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<Incompatibility> for SnapshotError {
+    fn from(e: Incompatibility) -> Self {
+        SnapshotError::Incompatible(e)
+    }
+}
+
+impl From<SpecError> for SnapshotError {
+    fn from(e: SpecError) -> Self {
+        SnapshotError::Malformed(e.to_string())
+    }
+}
+
+/// The records of a snapshot that passed the header and compatibility checks.
+#[derive(Debug, Clone)]
+pub struct SnapshotBody {
+    pub variant: Variant,
+    pub spec: GameSpec,
+    pub records: Vec<Vec<String>>, // Each record's tab-separated fields, tag first
+}
+
+/// Game states that can be written to and restored from a snapshot.
+pub trait Snapshot: Sized {
+    /// Identifies the kind of game in the snapshot (e.g. "practice").
+    const KIND: &'static str;
+
+    /// The rules the state was played under.
+    fn spec(&self) -> GameSpec;
+
+    /// The kind-specific records, as tab-separated fields (tag first).
+    fn records(&self) -> Vec<Vec<String>>;
+
+    /// Rebuilds the state from a checked snapshot body.
+    fn from_body(body: SnapshotBody) -> Result<Self, SnapshotError>;
+
+    /// Writes the full snapshot text.
+    fn to_snapshot(&self) -> String {
+        let mut text = format!("{}\nspec\t{}\nkind\t{}\n", SNAPSHOT_HEADER, self.spec(), Self::KIND);
+        for record in self.records() {
+            text.push_str(&record.join("\t"));
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Restores a state from snapshot text, checking compatibility first.
+    fn from_snapshot(text: &str) -> Result<Self, SnapshotError> {
+        let mut lines = text.lines();
+        if lines.next() != Some(SNAPSHOT_HEADER) {
+            return Err(SnapshotError::Malformed("missing snapshot header".to_string()));
+        }
+
+        let spec: GameSpec = match lines.next().and_then(|line| line.strip_prefix("spec\t")) {
+            Some(spec) => spec.parse()?,
+            None => return Err(SnapshotError::Malformed("missing game description".to_string())),
+        };
+        let variant = spec.check_supported()?;
+        // The game carries on at the length it was started with
+        set_code_length(spec.rules.code_length);
+
+        let kind = lines.next().and_then(|line| line.strip_prefix("kind\t")).unwrap_or("unknown");
+        if kind != Self::KIND {
+            return Err(SnapshotError::WrongKind { expected: Self::KIND.to_string(), found: kind.to_string() });
+        }
+
+        let records = lines
+            .filter(|line| !line.is_empty())
+            .map(|line| line.split('\t').map(str::to_string).collect())
+            .collect();
+        Self::from_body(SnapshotBody { variant, spec, records })
+    }
+}
+
+// --- Field Helpers ---
+
+/// Writes a code as a snapshot field.
+pub fn code_field(code: &Guess) -> String {
+    format_code(code)
+}
+
+/// Reads a code field, at whatever supported length it was written.
+pub fn parse_code_field(field: &str) -> Result<Guess, SnapshotError> {
+    let bad = || SnapshotError::Malformed(format!("bad code '{}'", field));
+    let digits = field.chars().map(|c| c.to_digit(10).map(|d| d as u8)).collect::<Option<Vec<u8>>>().ok_or_else(bad)?;
+    let repeats = digits.iter().enumerate().any(|(i, digit)| digits[..i].contains(digit));
+    if repeats || !SUPPORTED_CODE_LENGTHS.contains(&digits.len()) {
+        return Err(bad());
+    }
+    Ok(Guess::from_digits(&digits))
+}
+
+/// Reads a number field.
+pub fn parse_number_field<T: std::str::FromStr>(field: &str) -> Result<T, SnapshotError> {
+    field.parse().map_err(|_| SnapshotError::Malformed(format!("bad number '{}'", field)))
+}
+
+/// Writes a score as a snapshot field, in the internal (position, wrong position) order.
+pub fn score_field(score: Score) -> String {
+    format!("{},{}", score.0, score.1)
+}
+
+/// Reads a score field.
+pub fn parse_score_field(field: &str) -> Result<Score, SnapshotError> {
+    let bad = || SnapshotError::Malformed(format!("bad score '{}'", field));
+    let (position, wrong) = field.split_once(',').ok_or_else(bad)?;
+    let score = (position.parse().map_err(|_| bad())?, wrong.parse().map_err(|_| bad())?);
+    if score.0 as u32 + score.1 as u32 > MAX_CODE_LENGTH as u32 {
+        return Err(bad());
+    }
+    Ok(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{Capability, Rules};
+
+    /// A stand-in game whose state is a list of notes.
+    #[derive(Debug, PartialEq)]
+    struct Notes {
+        spec: GameSpec,
+        notes: Vec<(Guess, Score)>,
+    }
+
+    impl Snapshot for Notes {
+        const KIND: &'static str = "notes";
+
+        fn spec(&self) -> GameSpec {
+            self.spec.clone()
+        }
+
+        fn records(&self) -> Vec<Vec<String>> {
+            self.notes.iter().map(|(guess, score)| vec!["note".to_string(), code_field(guess), score_field(*score)]).collect()
+        }
+
+        fn from_body(body: SnapshotBody) -> Result<Self, SnapshotError> {
+            let mut notes = Vec::new();
+            for record in &body.records {
+                match record.as_slice() {
+                    [tag, guess, score] if tag == "note" => notes.push((parse_code_field(guess)?, parse_score_field(score)?)),
+                    _ => return Err(SnapshotError::Malformed("bad note".to_string())),
+                }
+            }
+            Ok(Notes { spec: body.spec, notes })
+        }
+    }
+
+    fn notes(code_length: usize) -> Notes {
+        let digits: Vec<u8> = (0..code_length as u8).collect();
+        let reversed: Vec<u8> = digits.iter().rev().copied().collect();
+        Notes {
+            spec: GameSpec::new(Variant::IndividualSecrets, Rules { code_length, allow_repeats: false }),
+            notes: vec![(Guess::from_digits(&digits), (1, 2)), (Guess::from_digits(&reversed), (0, 0))],
+        }
+    }
+
+    #[test]
+    fn a_snapshot_reads_back_as_written() {
+        for length in SUPPORTED_CODE_LENGTHS {
+            let game = notes(length);
+            assert_eq!(Notes::from_snapshot(&game.to_snapshot()), Ok(game));
+        }
+    }
+
+    #[test]
+    fn a_damaged_snapshot_is_turned_down() {
+        let text = notes(4).to_snapshot();
+        assert!(matches!(Notes::from_snapshot(&text.replacen("sgg-snapshot 1", "sgg-snapshot", 1)), Err(SnapshotError::Malformed(_))));
+        assert!(matches!(Notes::from_snapshot("sgg-snapshot 1\nkind\tnotes\n"), Err(SnapshotError::Malformed(_))));
+        assert!(matches!(Notes::from_snapshot(&text.replace("1,2", "1;2")), Err(SnapshotError::Malformed(_))));
+        assert!(matches!(Notes::from_snapshot(&text.replace("0123", "0113")), Err(SnapshotError::Malformed(_))));
+        assert_eq!(
+            Notes::from_snapshot(&text.replace("kind\tnotes", "kind\tpractice")),
+            Err(SnapshotError::WrongKind { expected: "notes".to_string(), found: "practice".to_string() })
+        );
+    }
+
+    #[test]
+    fn a_snapshot_for_rules_this_build_lacks_says_what_is_missing() {
+        let text = notes(4).to_snapshot().replace("length=4", "length=9").replace("variant_version=1", "variant_version=99");
+        let Err(SnapshotError::Incompatible(incompatibility)) = Notes::from_snapshot(&text) else { panic!("the snapshot should be incompatible") };
+        assert!(matches!(incompatibility.missing.as_slice(), [Capability::VariantVersion { required: 99, .. }, Capability::CodeLength(9)]));
+    }
+
+    #[test]
+    fn fields_take_every_supported_length() {
+        for length in SUPPORTED_CODE_LENGTHS {
+            let digits: Vec<u8> = (10 - length as u8..10).collect();
+            let code = Guess::from_digits(&digits);
+            assert_eq!(parse_code_field(&code_field(&code)), Ok(code));
+        }
+        assert!(parse_code_field("12").is_err());
+        assert!(parse_code_field("0123456").is_err());
+        assert!(parse_code_field("01a3").is_err());
+
+        assert_eq!(parse_score_field(&score_field((2, 3))), Ok((2, 3)));
+        assert_eq!(parse_score_field("6,0"), Ok((6, 0)));
+        assert!(parse_score_field("4,3").is_err());
+        assert!(parse_score_field("4").is_err());
+    }
+}
@@ -0,0 +1,322 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::thread;
+
+use clap::builder::PossibleValue;
+use clap::ValueEnum;
+use rand::seq::IndexedRandom;
+
+use crate::code::{calculate_score, code_length, format_code, game_rng, solved_score, Guess, Score, MAX_CODE_LENGTH};
+use crate::config::Config;
+use crate::feedback::{DpNotation, FeedbackRenderer};
+use crate::strings::fill;
+
+// --- Candidate Sets ---
+// A candidate set is every code that is still consistent with the feedback
+// seen so far. All the AI strategies are built on narrowing this set down.
+
+/// Every valid code: `code_length()` non-repeating digits, leading zero
+/// allowed (5040 of them for 4 digits), in increasing order.
+pub fn all_codes() -> Vec<Guess> {
+    let length = code_length();
+    let mut codes = Vec::with_capacity((0..length).map(|i| 10 - i).product());
+    let mut digits = Vec::with_capacity(length);
+    push_codes(&mut digits, length, &mut codes);
+    codes
+}
+
+/// Adds every code that starts with `digits` and goes on to `length` digits.
+fn push_codes(digits: &mut Vec<u8>, length: usize, codes: &mut Vec<Guess>) {
+    if digits.len() == length {
+        codes.push(Guess::from_digits(digits));
+        return;
+    }
+    for digit in 0..10u8 {
+        if !digits.contains(&digit) {
+            digits.push(digit);
+            push_codes(digits, length, codes);
+            digits.pop();
+        }
+    }
+}
+
+/// True if `candidate` could be the secret, given that `guess` scored `score`.
+pub fn is_consistent(candidate: &Guess, guess: &Guess, score: Score) -> bool {
+    calculate_score(guess, candidate) == score
+}
+
+/// Drops every candidate that contradicts the feedback for `guess`.
+pub fn filter_candidates(candidates: &mut Vec<Guess>, guess: &Guess, score: Score) {
+    candidates.retain(|candidate| is_consistent(candidate, guess, score));
+}
+
+/// An earlier clue that a guess contradicts: if the guess were the secret,
+/// the clue's guess would have scored differently.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClueViolation {
+    pub clue: usize, // 1-based turn number of the clue
+    pub guess: Guess,
+    pub score: Score,
+    pub would_score: Score,
+}
+
+impl fmt::Display for ClueViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Explained in D,P terms, the way players see feedback
+        let code = format_code(&self.guess);
+        let (digits, would_digits) = (self.score.0 + self.score.1, self.would_score.0 + self.would_score.1);
+        write!(f, "{}", fill("clue.violated", &[("clue", &self.clue), ("code", &code), ("score", &DpNotation.render(self.score))]))?;
+        if digits != would_digits {
+            write!(f, "{}", fill("clue.digits", &[("digits", &digits), ("code", &code), ("would", &would_digits)]))
+        } else {
+            write!(f, "{}", fill("clue.places", &[("places", &self.score.0), ("code", &code), ("would", &self.would_score.0)]))
+        }
+    }
+}
+
+/// Finds the first clue in `history` that `guess` contradicts, if any.
+pub fn find_violated_clue(guess: &Guess, history: &[(Guess, Score)]) -> Option<ClueViolation> {
+    history.iter().enumerate().find_map(|(i, (clue, score))| {
+        let would_score = calculate_score(clue, guess);
+        (would_score != *score).then_some(ClueViolation { clue: i + 1, guess: *clue, score: *score, would_score })
+    })
+}
+
+/// The slots in a table of feedback outcomes, enough for the longest code.
+pub const SCORE_SLOTS: usize = (MAX_CODE_LENGTH + 1) * (MAX_CODE_LENGTH + 1);
+
+/// Maps a score to a slot in a table of feedback outcomes.
+pub fn score_index(score: Score) -> usize {
+    score.0 as usize * (MAX_CODE_LENGTH + 1) + score.1 as usize
+}
+
+/// Counts how the candidates would split up by feedback if `guess` were played.
+pub fn partition_sizes(guess: &Guess, candidates: &[Guess]) -> [usize; SCORE_SLOTS] {
+    let mut sizes = [0usize; SCORE_SLOTS];
+    for candidate in candidates {
+        sizes[score_index(calculate_score(guess, candidate))] += 1;
+    }
+    sizes
+}
+
+/// The number of candidates left in the worst case after playing `guess`.
+pub fn worst_case(guess: &Guess, candidates: &[Guess]) -> usize {
+    partition_sizes(guess, candidates).into_iter().max().unwrap_or(0)
+}
+
+/// Picks the candidate with the smallest worst-case remainder.
+/// A cheap approximation of minimax that only considers possible secrets.
+pub fn best_candidate_guess(candidates: &[Guess]) -> Option<Guess> {
+    candidates.iter().copied().min_by_key(|guess| worst_case(guess, candidates))
+}
+
+/// What every remaining candidate agrees on, digit by digit.
+/// Comparing these before and after a guess shows what the feedback taught.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deductions {
+    pub present: [bool; 10],   // Digits found in every candidate
+    pub absent: [bool; 10],    // Digits found in no candidate
+    pub fixed: Vec<Option<u8>>, // Positions where every candidate has the same digit
+}
+
+impl Deductions {
+    pub fn from_candidates(candidates: &[Guess]) -> Self {
+        let mut present = [!candidates.is_empty(); 10];
+        let mut absent = [true; 10];
+        let mut fixed: Vec<Option<u8>> = match candidates.first() {
+            Some(first) => first.iter().copied().map(Some).collect(),
+            None => vec![None; code_length()],
+        };
+
+        for candidate in candidates {
+            for digit in 0..10u8 {
+                let contains = candidate.contains(&digit);
+                present[digit as usize] &= contains;
+                absent[digit as usize] &= !contains;
+            }
+            for (slot, &digit) in fixed.iter_mut().zip(candidate) {
+                if *slot != Some(digit) {
+                    *slot = None;
+                }
+            }
+        }
+        Deductions { present, absent, fixed }
+    }
+}
+
+// --- Whole-Space Strategies ---
+// These strategies consider every code as a possible guess, not only the
+// remaining candidates. A non-candidate can split the set better, at the cost
+// of not possibly being the secret itself. Longer codes have far more of
+// both (151200 of each for 6 digits), so where weighing every code against
+// every candidate would take more scorings than a 4-digit opening does,
+// only the candidates are weighed, or an even spread of them.
+
+/// The most scorings one choice of guess may take: a 4-digit game's
+/// opening, every code against every code.
+const SCORING_BUDGET: usize = 5040 * 5040;
+
+/// The guesses worth weighing against `candidates` within the budget.
+fn guess_pool<'a>(candidates: &'a [Guess], codes: &'a [Guess]) -> Cow<'a, [Guess]> {
+    if codes.len().saturating_mul(candidates.len()) <= SCORING_BUDGET {
+        return Cow::Borrowed(codes);
+    }
+    let room = (SCORING_BUDGET / candidates.len().max(1)).max(1);
+    if candidates.len() <= room {
+        return Cow::Borrowed(candidates);
+    }
+    let step = candidates.len().div_ceil(room);
+    Cow::Owned(candidates.iter().step_by(step).copied().collect())
+}
+
+/// Measures `partition_sizes` for every code in `codes` against `candidates`,
+/// spread across threads (one per core unless the settings say otherwise)
+/// since early turns need millions of scorings.
+fn evaluate_all<T: Send>(codes: &[Guess], candidates: &[Guess], measure: fn(&[usize; SCORE_SLOTS]) -> T) -> Vec<T> {
+    let threads = Config::current().solver_threads();
+    let chunk_size = codes.len().div_ceil(threads).max(1);
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = codes
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(|guess| measure(&partition_sizes(guess, candidates))).collect::<Vec<_>>()))
+            .collect();
+        workers.into_iter().flat_map(|worker| worker.join().expect("scoring thread panicked")).collect()
+    })
+}
+
+/// Knuth's minimax: picks the guess from `codes` with the smallest worst-case remainder.
+/// Ties go to guesses that could still be the secret, then to the lowest code,
+/// so the choice is deterministic.
+pub fn minimax_guess(candidates: &[Guess], codes: &[Guess]) -> Option<Guess> {
+    // With one or two candidates left, guessing one of them is never worse
+    if candidates.len() <= 2 {
+        return candidates.first().copied();
+    }
+
+    let codes = &*guess_pool(candidates, codes);
+    let scores = evaluate_all(codes, candidates, |sizes| sizes.iter().copied().max().unwrap_or(0));
+    codes
+        .iter()
+        .zip(scores)
+        .min_by_key(|&(guess, worst)| (worst, !candidates.contains(guess), *guess))
+        .map(|(guess, _)| *guess)
+}
+
+/// The Shannon entropy, in bits, of how a guess splits the candidates:
+/// the expected information its feedback reveals.
+pub fn partition_entropy(sizes: &[usize; SCORE_SLOTS]) -> f64 {
+    let total: usize = sizes.iter().sum();
+    if total == 0 {
+        return 0.0;
+    }
+    sizes
+        .iter()
+        .filter(|&&n| n > 0)
+        .map(|&n| {
+            let p = n as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Picks the guess from `codes` with the highest expected information gain.
+/// Ties are broken the same way as `minimax_guess`.
+pub fn entropy_guess(candidates: &[Guess], codes: &[Guess]) -> Option<Guess> {
+    if candidates.len() <= 2 {
+        return candidates.first().copied();
+    }
+
+    let codes = &*guess_pool(candidates, codes);
+    let scores = evaluate_all(codes, candidates, partition_entropy);
+    codes
+        .iter()
+        .zip(scores)
+        .min_by(|&(a, a_bits), &(b, b_bits)| {
+            b_bits
+                .total_cmp(&a_bits)
+                .then_with(|| candidates.contains(b).cmp(&candidates.contains(a)))
+                .then_with(|| a.cmp(b))
+        })
+        .map(|(guess, _)| *guess)
+}
+
+/// How many candidates a guess leaves on average, given how it splits them.
+/// The partition where the guess is the secret counts as leaving none.
+pub fn expected_remaining(sizes: &[usize; SCORE_SLOTS]) -> f64 {
+    let total: usize = sizes.iter().sum();
+    if total == 0 {
+        return 0.0;
+    }
+    let solved = score_index(solved_score());
+    let left: usize = sizes.iter().enumerate().filter(|&(i, _)| i != solved).map(|(_, &n)| n * n).sum();
+    left as f64 / total as f64
+}
+
+/// Picks the guess from `codes` that leaves the fewest candidates on average,
+/// returning it with that average. Ties are broken the same way as `minimax_guess`.
+pub fn fewest_remaining_guess(candidates: &[Guess], codes: &[Guess]) -> Option<(Guess, f64)> {
+    let codes = &*guess_pool(candidates, codes);
+    let scores = evaluate_all(codes, candidates, expected_remaining);
+    codes
+        .iter()
+        .zip(scores)
+        .min_by(|&(a, a_left), &(b, b_left)| {
+            a_left
+                .total_cmp(&b_left)
+                .then_with(|| candidates.contains(b).cmp(&candidates.contains(a)))
+                .then_with(|| a.cmp(b))
+        })
+        .map(|(guess, left)| (*guess, left))
+}
+
+/// A solver strategy that can be plugged into bots and the analysis tools.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    Minimax, // Minimise the worst-case number of candidates left
+    Entropy, // Maximise the expected information from the feedback
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 2] = [Strategy::Minimax, Strategy::Entropy];
+
+    /// The name used on the command line.
+    pub fn id(self) -> &'static str {
+        match self {
+            Strategy::Minimax => "minimax",
+            Strategy::Entropy => "entropy",
+        }
+    }
+
+    /// Chooses the next guess given the remaining candidates and the full code space.
+    pub fn choose(self, candidates: &[Guess], codes: &[Guess]) -> Option<Guess> {
+        match self {
+            Strategy::Minimax => minimax_guess(candidates, codes),
+            Strategy::Entropy => entropy_guess(candidates, codes),
+        }
+    }
+}
+
+// `--strategy` takes a strategy by its id
+impl ValueEnum for Strategy {
+    fn value_variants<'a>() -> &'a [Self] {
+        &Strategy::ALL
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(self.id()))
+    }
+}
+
+// --- Hints ---
+
+/// Suggests a next guess for a player, given the codes that still fit their feedback.
+pub fn suggest_guess(candidates: &[Guess]) -> Guess {
+    let codes = all_codes();
+
+    // Before any feedback every opening is equally good, so skip the search
+    if candidates.len() == codes.len() {
+        return *candidates.choose(&mut game_rng()).expect("the code space is never empty");
+    }
+    minimax_guess(candidates, &codes).expect("the secret is always a candidate")
+}
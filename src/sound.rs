@@ -0,0 +1,185 @@
+use std::io::{self, Write};
+
+use crate::config::Config;
+use crate::console::plain_output;
+
+// --- Sound Cues ---
+// In a noisy room it's easy to miss that the keyboard is yours. With cues
+// switched on, the terminal bell rings when a human's turn starts, when a
+// code is cracked and when the game ends. Builds with the `audio` feature
+// can play a short tone for each cue instead, through the system's own
+// sound player; where none is found the bell rings as before.
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SoundCues {
+    #[default]
+    Off,
+    Bell,  // The terminal bell
+    Tones, // A tone per cue (with the `audio` feature), or else the bell
+}
+
+impl SoundCues {
+    pub const ALL: [SoundCues; 3] = [SoundCues::Off, SoundCues::Bell, SoundCues::Tones];
+
+    /// The value used in the settings file.
+    pub fn id(self) -> &'static str {
+        match self {
+            SoundCues::Off => "off",
+            SoundCues::Bell => "bell",
+            SoundCues::Tones => "tones",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<SoundCues> {
+        SoundCues::ALL.into_iter().find(|s| s.id() == id.to_ascii_lowercase())
+    }
+
+    /// The string id of the option's description in the settings menu.
+    pub fn label_id(self) -> &'static str {
+        match self {
+            SoundCues::Off => "sound.off",
+            SoundCues::Bell => "sound.bell",
+            SoundCues::Tones => "sound.tones",
+        }
+    }
+
+    /// The next option, for cycling through them in the menu.
+    pub fn next(self) -> SoundCues {
+        let index = SoundCues::ALL.iter().position(|&s| s == self).unwrap_or(0);
+        SoundCues::ALL[(index + 1) % SoundCues::ALL.len()]
+    }
+}
+
+/// The moments worth hearing about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cue {
+    TurnStart,
+    Cracked,
+    GameEnd,
+}
+
+impl Cue {
+    /// The notes of the cue's tone as (frequency in Hz, length in ms).
+    #[cfg(feature = "audio")]
+    fn notes(self) -> &'static [(f32, u32)] {
+        match self {
+            Cue::TurnStart => &[(880.0, 120)],
+            Cue::Cracked => &[(660.0, 100), (990.0, 180)],
+            Cue::GameEnd => &[(523.0, 140), (659.0, 140), (784.0, 260)],
+        }
+    }
+}
+
+/// Plays `cue` as the settings ask.
+pub fn play(cue: Cue) {
+    match Config::current().sound {
+        SoundCues::Off => {}
+        SoundCues::Bell => ring_bell(),
+        SoundCues::Tones => {
+            #[cfg(feature = "audio")]
+            if tones::play(cue.notes()) {
+                return;
+            }
+            #[cfg(not(feature = "audio"))]
+            let _ = cue;
+            ring_bell();
+        }
+    }
+}
+
+/// Rings the terminal bell. Plain output (logs and pipes) is left alone.
+fn ring_bell() {
+    if plain_output() {
+        return;
+    }
+    out!("\x07");
+    io::stdout().flush().unwrap();
+}
+
+#[cfg(feature = "audio")]
+mod tones {
+    use std::f32::consts::TAU;
+    use std::fs;
+    use std::path::Path;
+    use std::process::{Command, Stdio};
+    use std::thread;
+
+    const SAMPLE_RATE: u32 = 22_050;
+
+    /// The system's sound players to try, in order, for the WAV file at `path`.
+    #[cfg(target_os = "macos")]
+    fn players(path: &Path) -> Vec<Command> {
+        let mut afplay = Command::new("afplay");
+        afplay.arg(path);
+        vec![afplay]
+    }
+
+    #[cfg(windows)]
+    fn players(path: &Path) -> Vec<Command> {
+        let mut powershell = Command::new("powershell");
+        let script = format!("(New-Object Media.SoundPlayer '{}').PlaySync()", path.display());
+        powershell.args(["-NoProfile", "-Command", &script]);
+        vec![powershell]
+    }
+
+    #[cfg(not(any(target_os = "macos", windows)))]
+    fn players(path: &Path) -> Vec<Command> {
+        [("paplay", None), ("aplay", Some("-q")), ("pw-play", None)]
+            .into_iter()
+            .map(|(program, flag)| {
+                let mut command = Command::new(program);
+                command.args(flag).arg(path);
+                command
+            })
+            .collect()
+    }
+
+    /// A 16-bit mono WAV file of the notes, each faded in and out so it doesn't click.
+    fn wav(notes: &[(f32, u32)]) -> Vec<u8> {
+        let mut samples = Vec::new();
+        for &(frequency, millis) in notes {
+            let count = SAMPLE_RATE * millis / 1000;
+            let fade = (count / 10).max(1);
+            for i in 0..count {
+                let envelope = (i.min(count - i).min(fade) as f32) / fade as f32;
+                let value = (TAU * frequency * i as f32 / SAMPLE_RATE as f32).sin() * envelope * 0.4;
+                samples.extend_from_slice(&((value * i16::MAX as f32) as i16).to_le_bytes());
+            }
+        }
+
+        let mut out = Vec::with_capacity(44 + samples.len());
+        out.extend_from_slice(b"RIFF");
+        out.extend_from_slice(&(36 + samples.len() as u32).to_le_bytes());
+        out.extend_from_slice(b"WAVEfmt ");
+        out.extend_from_slice(&16u32.to_le_bytes()); // Format chunk size
+        out.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        out.extend_from_slice(&1u16.to_le_bytes()); // Mono
+        out.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+        out.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes()); // Bytes per second
+        out.extend_from_slice(&2u16.to_le_bytes()); // Bytes per sample
+        out.extend_from_slice(&16u16.to_le_bytes()); // Bits per sample
+        out.extend_from_slice(b"data");
+        out.extend_from_slice(&(samples.len() as u32).to_le_bytes());
+        out.extend_from_slice(&samples);
+        out
+    }
+
+    /// Starts playing the notes in the background; false if nothing could play them.
+    pub fn play(notes: &[(f32, u32)]) -> bool {
+        let key: u32 = notes.iter().map(|&(frequency, millis)| frequency as u32 * 31 + millis).sum();
+        let path = std::env::temp_dir().join(format!("secret-guessing-game-{}.wav", key));
+        if !path.exists() && fs::write(&path, wav(notes)).is_err() {
+            return false;
+        }
+        players(&path).into_iter().any(|mut command| {
+            match command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).spawn() {
+                // Reaped in the background so the game doesn't wait for the tone
+                Ok(mut child) => {
+                    thread::spawn(move || child.wait());
+                    true
+                }
+                Err(_) => false,
+            }
+        })
+    }
+}
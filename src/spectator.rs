@@ -0,0 +1,281 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::{thread, time::Duration};
+
+use clap::Args;
+
+use crate::code::{code_length, feedback_pair, Score};
+use crate::console::draw_frame;
+use crate::feedback::{DpNotation, FeedbackRenderer};
+use crate::locale::Locale;
+use crate::rankings::{display_width, pad};
+use crate::snapshot::{parse_score_field, score_field};
+use crate::strings::{fill, tr};
+
+// --- Spectator Feed ---
+// An Individual Secrets game can publish its public progress as a stream of
+// events, one per line with tab-separated fields, to a feed file given with
+// `--feed <path>`. `spectate <path>` follows that file from a second terminal
+// (or a projector) and redraws a read-only view after every event:
+//
+//   start   Alice   Bob        A game began with these players, in seat order
+//   turn    2       Alice   3   2,1    Round, player, guesses so far, best score
+//   sitout  1       Bob                A late-start handicap passed the turn
+//   timeout 3       Bob                The turn timer ran out
+//   out     5       Bob                The guess limit ran out
+//   finish  Alice   1       5          Player, place, guesses
+//   end                                The game is over
+//
+// Only what the table's scoreboard already shows is published: no secrets,
+// guesses, or feedback beyond each player's best score.
+
+/// How often the spectator looks for new lines in the feed.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// How many recent events the spectator view lists.
+const RECENT_EVENTS: usize = 6;
+
+/// Something that happened in a game, as far as onlookers may know.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameEvent {
+    Started { players: Vec<String> },
+    Turn { round: u32, player: String, guesses: u32, best: Option<Score> },
+    SatOut { round: u32, player: String },
+    TimedOut { round: u32, player: String },
+    OutOfGuesses { round: u32, player: String },
+    Finished { player: String, place: usize, guesses: u32 },
+    Ended,
+}
+
+/// Keeps a name from breaking the line format.
+fn clean(name: &str) -> String {
+    name.replace(['\t', '\n', '\r'], " ")
+}
+
+impl GameEvent {
+    pub fn to_line(&self) -> String {
+        let fields: Vec<String> = match self {
+            GameEvent::Started { players } => std::iter::once("start".to_string()).chain(players.iter().map(|p| clean(p))).collect(),
+            GameEvent::Turn { round, player, guesses, best } => {
+                let best = best.map_or("-".to_string(), score_field);
+                vec!["turn".to_string(), round.to_string(), clean(player), guesses.to_string(), best]
+            }
+            GameEvent::SatOut { round, player } => vec!["sitout".to_string(), round.to_string(), clean(player)],
+            GameEvent::TimedOut { round, player } => vec!["timeout".to_string(), round.to_string(), clean(player)],
+            GameEvent::OutOfGuesses { round, player } => vec!["out".to_string(), round.to_string(), clean(player)],
+            GameEvent::Finished { player, place, guesses } => vec!["finish".to_string(), clean(player), place.to_string(), guesses.to_string()],
+            GameEvent::Ended => vec!["end".to_string()],
+        };
+        fields.join("\t")
+    }
+
+    /// Parses a feed line; lines from other versions that can't be read give None.
+    pub fn parse_line(line: &str) -> Option<GameEvent> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let event = match fields.as_slice() {
+            ["start", players @ ..] => GameEvent::Started { players: players.iter().map(|p| p.to_string()).collect() },
+            ["turn", round, player, guesses, best] => GameEvent::Turn {
+                round: round.parse().ok()?,
+                player: player.to_string(),
+                guesses: guesses.parse().ok()?,
+                best: if *best == "-" { None } else { Some(parse_score_field(best).ok()?) },
+            },
+            ["sitout", round, player] => GameEvent::SatOut { round: round.parse().ok()?, player: player.to_string() },
+            ["timeout", round, player] => GameEvent::TimedOut { round: round.parse().ok()?, player: player.to_string() },
+            ["out", round, player] => GameEvent::OutOfGuesses { round: round.parse().ok()?, player: player.to_string() },
+            ["finish", player, place, guesses] => GameEvent::Finished {
+                player: player.to_string(),
+                place: place.parse().ok()?,
+                guesses: guesses.parse().ok()?,
+            },
+            ["end"] => GameEvent::Ended,
+            _ => return None,
+        };
+        Some(event)
+    }
+}
+
+// --- Publishing ---
+
+static FEED: Mutex<Option<File>> = Mutex::new(None);
+
+/// Starts publishing events to `path`, replacing anything already in it.
+pub fn open_feed(path: &Path) -> io::Result<()> {
+    let file = OpenOptions::new().create(true).write(true).truncate(true).open(path)?;
+    *FEED.lock().unwrap() = Some(file);
+    Ok(())
+}
+
+/// Publishes an event, if there is a feed. A feed that can't be written to
+/// is reported once and then dropped, so the game itself carries on.
+pub fn emit(event: GameEvent) {
+    let mut feed = FEED.lock().unwrap();
+    let Some(file) = feed.as_mut() else { return };
+    if let Err(e) = writeln!(file, "{}", event.to_line()).and_then(|()| file.flush()) {
+        outln!("{}", fill("spectate.write_failed", &[("error", &e)]));
+        *feed = None;
+    }
+}
+
+// --- Spectator View ---
+
+#[derive(Debug, Clone, Default)]
+struct SeatView {
+    name: String,
+    guesses: u32,
+    best: Option<Score>,
+    place: Option<usize>,
+    out: bool, // Used up the guess limit
+}
+
+/// Everything the spectator view shows, rebuilt from the events seen so far.
+#[derive(Debug, Clone, Default)]
+struct View {
+    seats: Vec<SeatView>,
+    round: u32,
+    recent: Vec<String>,
+    over: bool,
+}
+
+impl View {
+    fn seat(&mut self, name: &str) -> Option<&mut SeatView> {
+        self.seats.iter_mut().find(|s| s.name == name)
+    }
+
+    fn log(&mut self, line: String) {
+        self.recent.push(line);
+        let excess = self.recent.len().saturating_sub(RECENT_EVENTS);
+        self.recent.drain(..excess);
+    }
+
+    fn apply(&mut self, event: GameEvent) {
+        let locale = Locale::current();
+        match event {
+            GameEvent::Started { players } => {
+                *self = View {
+                    seats: players.into_iter().map(|name| SeatView { name, ..SeatView::default() }).collect(),
+                    round: 1,
+                    ..View::default()
+                };
+                self.log(tr("spectate.started").to_string());
+            }
+            GameEvent::Turn { round, player, guesses, best } => {
+                self.round = round;
+                if let Some(seat) = self.seat(&player) {
+                    seat.guesses = guesses;
+                    seat.best = best;
+                }
+                self.log(fill("spectate.took_guess", &[("name", &player), ("number", &guesses)]));
+            }
+            GameEvent::SatOut { round, player } => {
+                self.round = round;
+                self.log(fill("spectate.sat_out", &[("name", &player)]));
+            }
+            GameEvent::TimedOut { round, player } => {
+                self.round = round;
+                self.log(fill("spectate.timed_out", &[("name", &player)]));
+            }
+            GameEvent::OutOfGuesses { round, player } => {
+                self.round = round;
+                if let Some(seat) = self.seat(&player) {
+                    seat.out = true;
+                }
+                self.log(fill("spectate.out", &[("name", &player)]));
+            }
+            GameEvent::Finished { player, place, guesses } => {
+                if let Some(seat) = self.seat(&player) {
+                    seat.place = Some(place);
+                    seat.guesses = guesses;
+                }
+                self.log(fill("spectate.finished", &[("name", &player), ("count", &guesses), ("place", &locale.ordinal(place))]));
+            }
+            GameEvent::Ended => {
+                self.over = true;
+                self.log(tr("spectate.over").to_string());
+            }
+        }
+    }
+
+    fn draw(&self) {
+        let locale = Locale::current();
+        let mut lines = vec![tr("spectate.title").to_string()];
+        if self.seats.is_empty() {
+            lines.extend([String::new(), tr("spectate.waiting").to_string()]);
+            return draw_frame(&lines);
+        }
+        lines.push(if self.over { tr("spectate.final").to_string() } else { fill("spectate.round", &[("round", &self.round)]) });
+        lines.push(String::new());
+
+        let width = self.seats.iter().map(|s| display_width(&s.name)).max().unwrap_or(0);
+        for seat in &self.seats {
+            let status = match seat.place {
+                Some(place) => fill("spectate.place", &[("place", &locale.ordinal(place))]),
+                None if seat.out => tr("spectate.out_status").to_string(),
+                None => match seat.best {
+                    Some(best) => fill("spectate.best", &[("score", &DpNotation.render(best))]),
+                    None => tr("spectate.no_guesses").to_string(),
+                },
+            };
+            // A bar of digits placed so far gives the room a feel for who's close. The
+            // feed doesn't say how long the codes are, so the bar is as long as this
+            // machine's codes, and a longer best score stretches it
+            let in_place = seat.best.map_or(0, |best| feedback_pair(best).1) as usize;
+            let bar = "█".repeat(in_place) + &"·".repeat(code_length().saturating_sub(in_place));
+            lines.push(fill("spectate.row", &[("name", &pad(&seat.name, width)), ("count", &format!("{:>3}", seat.guesses)), ("bar", &bar), ("status", &status)]));
+        }
+
+        lines.extend([String::new(), tr("spectate.recent").to_string()]);
+        lines.extend(self.recent.iter().map(|line| format!("  {}", line)));
+        draw_frame(&lines);
+    }
+}
+
+/// Reads the next complete line of a growing file, waiting for one to be
+/// written. If the file is truncated (a new session started), starts over.
+fn next_line(reader: &mut BufReader<File>, partial: &mut String) -> io::Result<String> {
+    loop {
+        let position = reader.stream_position()?;
+        if reader.get_ref().metadata()?.len() < position {
+            reader.seek(SeekFrom::Start(0))?;
+            partial.clear();
+        }
+        if reader.read_line(partial)? == 0 {
+            thread::sleep(POLL_INTERVAL);
+            continue;
+        }
+        // A line still being written is kept until its newline arrives
+        if partial.ends_with('\n') {
+            let line = partial.trim_end().to_string();
+            partial.clear();
+            return Ok(line);
+        }
+    }
+}
+
+/// The options of `spectate`.
+#[derive(Debug, Args)]
+pub struct SpectateArgs {
+    /// The feed file the game was started with (`--feed`)
+    #[arg(value_name = "FEED FILE")]
+    pub feed: PathBuf,
+}
+
+/// Follows a spectator feed until interrupted (Ctrl+C).
+pub fn run_spectator(args: &SpectateArgs) -> Result<(), String> {
+    let path = &args.feed;
+    let file = File::open(path).map_err(|e| fill("spectate.open_failed", &[("path", &path.display()), ("error", &e)]))?;
+    let mut reader = BufReader::new(file);
+    let mut partial = String::new();
+    let mut view = View::default();
+    view.draw();
+
+    loop {
+        let line = next_line(&mut reader, &mut partial).map_err(|e| fill("spectate.read_failed", &[("error", &e)]))?;
+        if let Some(event) = GameEvent::parse_line(&line) {
+            view.apply(event);
+            view.draw();
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use std::path::{Path, PathBuf};
+
+use rusqlite::{params, Connection};
+
+use crate::code::format_code;
+use crate::store::{clean_field, format_game_id, legacy_game_id, parse_game_id, GameResult, StatsBackend, StoreError};
+use crate::transcript::Move;
+
+// --- SQLite Store ---
+// With the `sqlite` feature, a store whose path ends in `.db`, `.sqlite` or
+// `.sqlite3` is kept in an SQLite database, so a club that has played for
+// years can ask its history questions in SQL rather than parsing the flat
+// file. The database is driven through rusqlite, with SQLite built in, so
+// nothing needs to be installed. Tables:
+//
+//   games    id, timestamp, mode, field_size, rules, uid    uid: the game's id
+//   results  game_id, player, rank, guesses             One row per player
+//   turns    game_id, round, player, guess, right_place, wrong_place, at_ms
+//
+// and a `player_stats` view with each player's games, wins, average guesses
+// and last game. Turns are kept for modes that keep a transcript. A database
+// store can't be encrypted; use the flat file for that. `uid` is the game id
+// the rest of the program knows the game by, as 16 hex digits; it is the same
+// in every store and export, where `id` belongs to this database alone.
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS games (
+    id INTEGER PRIMARY KEY,
+    timestamp INTEGER NOT NULL,
+    mode TEXT NOT NULL,
+    field_size INTEGER NOT NULL,
+    rules TEXT NOT NULL,
+    uid TEXT
+);
+CREATE TABLE IF NOT EXISTS results (
+    game_id INTEGER NOT NULL REFERENCES games(id),
+    player TEXT NOT NULL,
+    rank INTEGER NOT NULL,
+    guesses INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS turns (
+    game_id INTEGER NOT NULL REFERENCES games(id),
+    round INTEGER NOT NULL,
+    player TEXT NOT NULL,
+    guess TEXT NOT NULL,
+    right_place INTEGER NOT NULL,
+    wrong_place INTEGER NOT NULL,
+    at_ms INTEGER NOT NULL
+);
+CREATE VIEW IF NOT EXISTS player_stats AS
+    SELECT player,
+           count(*) AS games,
+           sum(rank = 1) AS wins,
+           round(avg(guesses), 2) AS average_guesses,
+           max(timestamp) AS last_played
+    FROM results JOIN games ON games.id = results.game_id
+    GROUP BY player;
+";
+
+const LOAD_RESULTS: &str = "
+SELECT games.uid, games.timestamp, games.mode, results.player, results.rank, games.field_size, results.guesses, games.rules
+FROM results JOIN games ON games.id = results.game_id
+ORDER BY results.rowid;
+";
+
+/// Game results kept in an SQLite database.
+#[derive(Debug)]
+pub struct SqliteStore {
+    path: PathBuf,
+    connection: Connection,
+    results: Vec<GameResult>,
+}
+
+impl From<rusqlite::Error> for StoreError {
+    fn from(e: rusqlite::Error) -> Self {
+        StoreError::Database(e.to_string())
+    }
+}
+
+impl SqliteStore {
+    /// Opens the database at `path`, creating it (and its tables) if needed.
+    pub fn open(path: PathBuf) -> Result<SqliteStore, StoreError> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let connection = Connection::open(&path)?;
+        connection.execute_batch(SCHEMA)?;
+        // Databases made before game ids get the column; their games keep the
+        // ids `legacy_game_id` gives them
+        let has_uid = connection.prepare("SELECT 1 FROM pragma_table_info('games') WHERE name = 'uid'")?.exists([])?;
+        if !has_uid {
+            connection.execute("ALTER TABLE games ADD COLUMN uid TEXT", [])?;
+        }
+        let results = connection
+            .prepare(LOAD_RESULTS)?
+            .query_map([], |row| {
+                let timestamp = row.get(1)?;
+                let mode: String = row.get(2)?;
+                let uid: Option<String> = row.get(0)?;
+                Ok(GameResult {
+                    game: uid.as_deref().and_then(parse_game_id).unwrap_or_else(|| legacy_game_id(timestamp, &mode)),
+                    timestamp,
+                    mode,
+                    player: row.get(3)?,
+                    rank: row.get(4)?,
+                    field_size: row.get(5)?,
+                    guesses: row.get(6)?,
+                    rules: row.get(7)?,
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+        Ok(SqliteStore { path, connection, results })
+    }
+}
+
+impl StatsBackend for SqliteStore {
+    fn path(&self) -> Option<&Path> {
+        Some(&self.path)
+    }
+
+    fn results(&self) -> &[GameResult] {
+        &self.results
+    }
+
+    /// Writes the game, its results and its turns in one transaction.
+    fn record_game(&mut self, results: Vec<GameResult>, turns: &[Move]) -> Result<(), StoreError> {
+        let Some(first) = results.first() else { return Ok(()) };
+        let transaction = self.connection.transaction()?;
+        transaction.execute(
+            "INSERT INTO games (timestamp, mode, field_size, rules, uid) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![first.timestamp, first.mode, first.field_size, first.rules, format_game_id(first.game)],
+        )?;
+        let game = transaction.last_insert_rowid();
+        for r in &results {
+            transaction.execute(
+                "INSERT INTO results (game_id, player, rank, guesses) VALUES (?1, ?2, ?3, ?4)",
+                params![game, clean_field(&r.player), r.rank, r.guesses],
+            )?;
+        }
+        for m in turns {
+            transaction.execute(
+                "INSERT INTO turns (game_id, round, player, guess, right_place, wrong_place, at_ms) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![game, m.round, clean_field(&m.player), format_code(&m.guess), m.score.0, m.score.1, m.at.as_millis() as u64],
+            )?;
+        }
+        transaction.commit()?;
+        self.results.extend(results.into_iter().map(|r| GameResult { player: clean_field(&r.player), ..r }));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::code::Guess;
+
+    #[test]
+    fn a_database_store_reads_back_as_written() {
+        let path = std::env::temp_dir().join(format!("sgg-sqlite-test-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let result = |player: &str, rank| GameResult {
+            game: 0x0123_4567_89ab_cdef,
+            timestamp: 1_760_000_000,
+            mode: "individual".to_string(),
+            player: player.to_string(),
+            rank,
+            field_size: 2,
+            guesses: 6,
+            rules: "length=4;repeats=false;digits=0-9".to_string(),
+        };
+        // Names go in as parameters, so quotes and SQL in them are just text
+        let results = vec![result("O'Brien", 1), result("x'); DROP TABLE games; --", 2)];
+        let turns = [Move { round: 1, player: "O'Brien".to_string(), guess: Guess::from_digits(&[0, 1, 2, 3]), score: (1, 2), at: Duration::from_millis(1500) }];
+
+        let mut store = SqliteStore::open(path.clone()).unwrap();
+        store.record_game(results.clone(), &turns).unwrap();
+        store.record_game(results.clone(), &[]).unwrap();
+        let store = SqliteStore::open(path.clone()).unwrap();
+        assert_eq!(store.results(), [results.clone(), results].concat().as_slice());
+        let turn_count: u32 = store.connection.query_row("SELECT count(*) FROM turns WHERE guess = '0123'", [], |row| row.get(0)).unwrap();
+        assert_eq!(turn_count, 1);
+        drop(store);
+        let _ = std::fs::remove_file(&path);
+    }
+}
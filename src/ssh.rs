@@ -0,0 +1,484 @@
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::os::fd::{AsRawFd, FromRawFd};
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::process::{CommandExt, ExitStatusExt};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use russh::keys::ssh_key::private::Ed25519Keypair;
+use russh::keys::{HashAlg, PrivateKey};
+use russh::server::{self, Auth, Msg, Session};
+use russh::{Channel, ChannelId, ChannelWriteHalf, Disconnect, MethodKind, MethodSet, Pty, SshId};
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::runtime::Runtime;
+use tokio::task::JoinHandle;
+
+use crate::crypto::{from_hex, random_bytes, to_hex};
+use crate::store::data_dir;
+use crate::strings::{fill, tr};
+
+// --- SSH Server ---
+// `serve --ssh` lets players in with nothing but an SSH client: `ssh -p 2222
+// play@<server>` gets them the game, menus and all, as if it were installed
+// on their own machine. Each session runs this program in a pseudo-terminal
+// of its own, so the line editor and the full-screen frontend work too; a
+// session without one (`ssh -T`, or input piped in) gets the plain output a
+// pipe does. Sessions share the server's data directory, and so its stats and
+// profiles.
+//
+// A command other than play, daily or tui is turned away, and none of them
+// takes options. Anyone who can reach the port gets in, under any user name
+// and without a password, so every session runs as a guest (`--guest`): the
+// settings, stats encryption, `/save` and every prompt for a file path are
+// shut off, and it writes nothing but the game's own records in the data
+// directory. Hosting or joining a networked game would open a port on the
+// server or reach one from it, so sessions can't. A session in which nothing
+// is typed for ten minutes is disconnected, and so is one that types a line
+// longer than 4 KiB or stops taking the game's output for ten seconds.
+//
+// The protocol itself is russh's: key exchange, the Ed25519 host key (made
+// on the first run and kept in the data directory), the ciphers and
+// rekeying. This file only decides who gets in and what their session runs.
+
+/// The port `serve --ssh` listens on unless told otherwise.
+pub const DEFAULT_SSH_PORT: u16 = 2222;
+
+/// What the server calls itself in the version exchange.
+const SERVER_VERSION: &str = "SSH-2.0-SecretGuessingGame";
+
+/// The host key's file in the data directory, holding the private key in hex.
+const HOST_KEY_FILE: &str = "ssh_host_key";
+
+/// How long a client has to finish the key exchange and log in.
+const HANDSHAKE_WAIT: Duration = Duration::from_secs(30);
+
+/// The most sessions the server runs at once.
+const MAX_SESSIONS: usize = 32;
+
+/// How long a session may go without anything being typed before it's disconnected.
+const IDLE_LIMIT: Duration = Duration::from_secs(10 * 60);
+
+/// How long the game's output may wait on a client that isn't taking it.
+const WRITE_WAIT: Duration = Duration::from_secs(10);
+
+/// The longest line a client may type.
+const MAX_LINE: usize = 4 * 1024;
+
+/// What a session may run. None of them takes options.
+const SESSION_COMMANDS: [&str; 3] = ["play", "daily", "tui"];
+
+/// The terminal a client asked for.
+struct Terminal {
+    name: String,
+    columns: u32,
+    rows: u32,
+}
+
+/// One client's connection: its session channel, and the program it runs.
+struct Connection {
+    address: String,
+    user: Option<String>,          // Set once the client has logged in
+    logged_in: Arc<AtomicBool>,
+    typed_at: Arc<Mutex<Instant>>, // Only typing counts against the idle limit
+    idle_watch: Option<JoinHandle<()>>,
+    channel: Option<ChannelId>,
+    output: Option<ChannelWriteHalf<Msg>>, // Taken by the thread sending the program's output
+    line: usize,                   // Data typed since the last Enter
+    terminal: Option<Terminal>,
+    pty: Option<File>,             // The pseudo-terminal's controlling side
+    input: Option<Box<dyn Write + Send>>,
+    child: Option<Arc<Mutex<Child>>>,
+}
+
+/// Opens a pseudo-terminal of the given size, giving back its controlling
+/// side and the terminal side the program runs on.
+fn open_pty(columns: u32, rows: u32) -> io::Result<(File, File)> {
+    // SAFETY: each call is checked, and each descriptor is owned by a File as soon as it's open
+    unsafe {
+        let master = libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY | libc::O_CLOEXEC);
+        if master < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let master = File::from_raw_fd(master);
+        if libc::grantpt(master.as_raw_fd()) != 0 || libc::unlockpt(master.as_raw_fd()) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut name = [0 as libc::c_char; 128];
+        if libc::ptsname_r(master.as_raw_fd(), name.as_mut_ptr(), name.len()) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let terminal = libc::open(name.as_ptr(), libc::O_RDWR | libc::O_NOCTTY | libc::O_CLOEXEC);
+        if terminal < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let terminal = File::from_raw_fd(terminal);
+        resize(&master, columns, rows);
+        Ok((master, terminal))
+    }
+}
+
+fn resize(pty: &File, columns: u32, rows: u32) {
+    let size = libc::winsize { ws_row: rows.min(u16::MAX as u32) as u16, ws_col: columns.min(u16::MAX as u32) as u16, ws_xpixel: 0, ws_ypixel: 0 };
+    // SAFETY: TIOCSWINSZ only reads the winsize struct passed in
+    unsafe {
+        libc::ioctl(pty.as_raw_fd(), libc::TIOCSWINSZ, &size);
+    }
+}
+
+/// The exit status to report for a finished program; a signal counts as
+/// 128 plus its number, as shells have it.
+fn exit_code(status: ExitStatus) -> u32 {
+    status.code().map(|code| code as u32).or_else(|| status.signal().map(|signal| 128 + signal as u32)).unwrap_or(1)
+}
+
+/// The game's arguments for what a client asked to run, or why it can't.
+fn allowed_command(command: &str) -> Result<Vec<String>, String> {
+    let words: Vec<String> = command.split_whitespace().map(str::to_string).collect();
+    let Some(name) = words.first() else { return Ok(Vec::new()) };
+    if !SESSION_COMMANDS.contains(&name.as_str()) {
+        return Err(fill("ssh.unknown_command", &[("name", name), ("names", &SESSION_COMMANDS.join(", "))]));
+    }
+    if let Some(option) = words.get(1) {
+        return Err(fill("ssh.bad_option", &[("name", name), ("option", option)]));
+    }
+    Ok(words)
+}
+
+impl Connection {
+    fn new(address: String, logged_in: Arc<AtomicBool>) -> Connection {
+        Connection {
+            address,
+            user: None,
+            logged_in,
+            typed_at: Arc::new(Mutex::new(Instant::now())),
+            idle_watch: None,
+            channel: None,
+            output: None,
+            line: 0,
+            terminal: None,
+            pty: None,
+            input: None,
+            child: None,
+        }
+    }
+
+    /// Lets any user straight in, with or without credentials.
+    fn log_in(&mut self, user: &str) -> Auth {
+        if self.user.is_none() {
+            outln!("{}", fill("ssh.connected", &[("name", &user), ("address", &self.address)]));
+            self.user = Some(user.to_string());
+            self.logged_in.store(true, Ordering::SeqCst);
+        }
+        Auth::Accept
+    }
+
+    /// Runs the game with `args` on the channel, sending its output back
+    /// until it finishes and then closing the channel.
+    fn start(&mut self, args: &[String], session: &Session) -> io::Result<()> {
+        let Some(output) = self.output.take() else { return Ok(()) };
+        let mut command = Command::new(env::current_exe()?);
+        command.arg("--guest").args(args);
+        let mut program_output: Box<dyn Read + Send> = match &self.terminal {
+            Some(terminal) => {
+                let (pty, program_side) = open_pty(terminal.columns, terminal.rows)?;
+                command.env("TERM", &terminal.name).stdin(program_side.try_clone()?).stdout(program_side.try_clone()?).stderr(program_side);
+                // SAFETY: setsid and ioctl are async-signal-safe, as pre_exec needs
+                unsafe {
+                    command.pre_exec(|| {
+                        // A session of its own, with the terminal as its controlling one,
+                        // so Ctrl+C and window size changes reach the program
+                        if libc::setsid() < 0 || libc::ioctl(0, libc::TIOCSCTTY, 0) < 0 {
+                            return Err(io::Error::last_os_error());
+                        }
+                        Ok(())
+                    });
+                }
+                self.input = Some(Box::new(pty.try_clone()?));
+                self.pty = Some(pty.try_clone()?);
+                Box::new(pty)
+            }
+            None => {
+                let (reader, writer) = io::pipe()?;
+                command.env_remove("TERM").stdin(Stdio::piped()).stdout(writer.try_clone()?).stderr(writer);
+                Box::new(reader)
+            }
+        };
+        let mut child = command.spawn()?;
+        // The program's ends of the terminal or pipe go with `command`, so
+        // the output reaches its end once the program has finished
+        drop(command);
+        if let Some(stdin) = child.stdin.take() {
+            self.input = Some(Box::new(stdin));
+        }
+        let child = Arc::new(Mutex::new(child));
+        self.child = Some(Arc::clone(&child));
+
+        // russh holds the output back while the client's window is full, and
+        // the thread waits with it, up to `WRITE_WAIT`
+        let (runtime, handle) = (tokio::runtime::Handle::current(), session.handle());
+        thread::spawn(move || {
+            let mut buffer = vec![0; 16 * 1024];
+            loop {
+                let read = match program_output.read(&mut buffer) {
+                    Ok(0) => break,
+                    Ok(read) => read,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(_) => break, // A pseudo-terminal reports an error once the program has gone
+                };
+                match runtime.block_on(async { tokio::time::timeout(WRITE_WAIT, output.data_bytes(buffer[..read].to_vec())).await }) {
+                    Ok(Ok(())) => {}
+                    Ok(Err(_)) => break,
+                    Err(_) => {
+                        let reason = "the game's output wasn't being taken".to_string();
+                        let _ = runtime.block_on(handle.disconnect(Disconnect::ByApplication, reason, String::new()));
+                        break;
+                    }
+                }
+            }
+            let status = child.lock().unwrap().wait().map(exit_code).unwrap_or(1);
+            runtime.block_on(async {
+                let _ = output.exit_status(status).await;
+                let _ = output.eof().await;
+                let _ = output.close().await;
+            });
+        });
+        Ok(())
+    }
+
+    /// Turns down what a client asked to run, closing the channel.
+    fn refuse(&self, channel: ChannelId, reason: &str, session: &mut Session) -> Result<(), russh::Error> {
+        session.extended_data(channel, 1, format!("{}\r\n", reason).into_bytes())?;
+        session.exit_status_request(channel, 2)?;
+        session.eof(channel)?;
+        session.close(channel)
+    }
+
+    /// Starts the program for a shell or exec request, or turns it down.
+    fn run(&mut self, channel: ChannelId, command: &str, session: &mut Session) -> Result<(), russh::Error> {
+        if self.channel != Some(channel) || self.child.is_some() {
+            return session.channel_failure(channel);
+        }
+        session.channel_success(channel)?;
+        match allowed_command(command) {
+            Ok(args) => Ok(self.start(&args, session)?),
+            Err(reason) => self.refuse(channel, &reason, session),
+        }
+    }
+
+    /// Hangs the terminal up on the program, if it's still running.
+    fn hang_up(&self) {
+        let Some(child) = &self.child else { return };
+        let mut child = child.lock().unwrap();
+        if let Ok(None) = child.try_wait() {
+            // SAFETY: the child hasn't been waited for, so its process id is still its own
+            unsafe {
+                libc::kill(child.id() as libc::pid_t, libc::SIGHUP);
+            }
+        }
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.hang_up();
+        if let Some(watch) = &self.idle_watch {
+            watch.abort();
+        }
+        if let Some(user) = &self.user {
+            outln!("{}", fill("ssh.disconnected", &[("name", user), ("address", &self.address)]));
+        }
+    }
+}
+
+impl server::Handler for Connection {
+    type Error = russh::Error;
+
+    async fn auth_none(&mut self, user: &str) -> Result<Auth, Self::Error> {
+        Ok(self.log_in(user))
+    }
+
+    async fn auth_password(&mut self, user: &str, _password: &str) -> Result<Auth, Self::Error> {
+        Ok(self.log_in(user))
+    }
+
+    async fn auth_publickey(&mut self, user: &str, _key: &russh::keys::PublicKey) -> Result<Auth, Self::Error> {
+        Ok(self.log_in(user))
+    }
+
+    async fn channel_open_session(&mut self, channel: Channel<Msg>, reply: server::ChannelOpenHandle, session: &mut Session) -> Result<(), Self::Error> {
+        if self.channel.is_some() {
+            reply.reject(russh::ChannelOpenFailure::AdministrativelyProhibited).await;
+            return Ok(());
+        }
+        self.channel = Some(channel.id());
+        // Requests and typing come to the handler, so only the sending half is kept
+        let (_, output) = channel.split();
+        self.output = Some(output);
+        reply.accept().await;
+
+        let (handle, typed_at) = (session.handle(), Arc::clone(&self.typed_at));
+        self.idle_watch = Some(tokio::spawn(async move {
+            loop {
+                let idle_for = typed_at.lock().unwrap().elapsed();
+                if idle_for >= IDLE_LIMIT {
+                    let _ = handle.disconnect(Disconnect::ByApplication, "nothing was typed for ten minutes".to_string(), String::new()).await;
+                    return;
+                }
+                tokio::time::sleep(IDLE_LIMIT - idle_for).await;
+            }
+        }));
+        Ok(())
+    }
+
+    async fn pty_request(
+        &mut self,
+        channel: ChannelId,
+        term: &str,
+        col_width: u32,
+        row_height: u32,
+        _pix_width: u32,
+        _pix_height: u32,
+        _modes: &[(Pty, u32)],
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        if self.channel != Some(channel) || self.child.is_some() {
+            return session.channel_failure(channel);
+        }
+        self.terminal = Some(Terminal { name: term.to_string(), columns: col_width, rows: row_height });
+        session.channel_success(channel)
+    }
+
+    async fn window_change_request(&mut self, channel: ChannelId, col_width: u32, row_height: u32, _pix_width: u32, _pix_height: u32, session: &mut Session) -> Result<(), Self::Error> {
+        if let Some(pty) = &self.pty {
+            resize(pty, col_width, row_height);
+        }
+        session.channel_success(channel)
+    }
+
+    async fn shell_request(&mut self, channel: ChannelId, session: &mut Session) -> Result<(), Self::Error> {
+        self.run(channel, "", session)
+    }
+
+    async fn exec_request(&mut self, channel: ChannelId, data: &[u8], session: &mut Session) -> Result<(), Self::Error> {
+        self.run(channel, &String::from_utf8_lossy(data), session)
+    }
+
+    // Environment variables, signals, agent and X11 forwarding aren't offered,
+    // which is russh's answer unless a handler says otherwise
+
+    async fn data(&mut self, _channel: ChannelId, data: &[u8], session: &mut Session) -> Result<(), Self::Error> {
+        *self.typed_at.lock().unwrap() = Instant::now();
+        // A pseudo-terminal sends Enter as CR, a pipe as LF
+        match data.iter().rposition(|&byte| byte == b'\r' || byte == b'\n') {
+            Some(end) => self.line = data.len() - end - 1,
+            None => self.line += data.len(),
+        }
+        if self.line > MAX_LINE {
+            session.disconnect(Disconnect::ByApplication, "that line is too long", "")?;
+            return Ok(());
+        }
+        if let Some(input) = &mut self.input
+            && input.write_all(data).and_then(|_| input.flush()).is_err()
+        {
+            self.input = None;
+        }
+        Ok(())
+    }
+
+    async fn channel_eof(&mut self, _channel: ChannelId, _session: &mut Session) -> Result<(), Self::Error> {
+        self.input = None;
+        Ok(())
+    }
+
+    async fn channel_close(&mut self, _channel: ChannelId, _session: &mut Session) -> Result<(), Self::Error> {
+        self.hang_up();
+        Ok(())
+    }
+}
+
+// --- Host Key ---
+
+/// Loads the host key from the data directory, making one on the first run.
+/// With no data directory the key lasts only as long as the server. The file
+/// holds the key's 32-byte Ed25519 seed.
+fn load_host_key() -> Result<PrivateKey, String> {
+    let host_key = |seed: [u8; 32]| PrivateKey::from(Ed25519Keypair::from_seed(&seed));
+    let Some(dir) = data_dir() else {
+        outln!("{}", tr("ssh.no_data_dir"));
+        return Ok(host_key(random_bytes()));
+    };
+    let path = dir.join(HOST_KEY_FILE);
+    match fs::read_to_string(&path) {
+        Ok(text) => from_hex(text.trim())
+            .and_then(|seed| seed.try_into().ok())
+            .map(host_key)
+            .ok_or_else(|| fill("ssh.bad_host_key", &[("path", &path.display())])),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let seed = random_bytes();
+            fs::create_dir_all(&dir).map_err(|e| fill("common.create_failed", &[("path", &dir.display()), ("error", &e)]))?;
+            let mut file = OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .mode(0o600)
+                .open(&path)
+                .map_err(|e| fill("common.create_failed", &[("path", &path.display()), ("error", &e)]))?;
+            writeln!(file, "{}", to_hex(&seed)).map_err(|e| fill("common.write_failed", &[("path", &path.display()), ("error", &e)]))?;
+            Ok(host_key(seed))
+        }
+        Err(e) => Err(fill("common.read_failed", &[("path", &path.display()), ("error", &e)])),
+    }
+}
+
+/// Runs `serve --ssh [--port <port>]`, letting players in until stopped.
+pub fn run_ssh_serve(port: u16) -> Result<(), String> {
+    let host_key = load_host_key()?;
+    let fingerprint = host_key.public_key().fingerprint(HashAlg::Sha256);
+    let config = Arc::new(server::Config {
+        server_id: SshId::Standard(SERVER_VERSION.into()),
+        methods: MethodSet::from(&[MethodKind::None, MethodKind::Password, MethodKind::PublicKey][..]),
+        auth_rejection_time: Duration::ZERO,
+        keys: vec![host_key],
+        nodelay: true,
+        ..Default::default()
+    });
+    let runtime = Runtime::new().map_err(|e| e.to_string())?;
+    runtime.block_on(async {
+        let listener = TcpListener::bind(("0.0.0.0", port)).await.map_err(|e| fill("net.listen_failed", &[("port", &port), ("error", &e)]))?;
+        outln!("{}", tr("ssh.serving"));
+        outln!("{}", fill("ssh.connect_hint", &[("port", &port)]));
+        outln!("{}", fill("ssh.fingerprint", &[("fingerprint", &fingerprint)]));
+        let sessions = Arc::new(AtomicUsize::new(0));
+        loop {
+            let Ok((mut stream, peer)) = listener.accept().await else { continue };
+            if sessions.load(Ordering::SeqCst) >= MAX_SESSIONS {
+                let _ = stream.write_all(format!("{}\r\n", tr("server.full")).as_bytes()).await;
+                continue;
+            }
+            sessions.fetch_add(1, Ordering::SeqCst);
+            let (sessions, config) = (Arc::clone(&sessions), Arc::clone(&config));
+            tokio::spawn(async move {
+                let logged_in = Arc::new(AtomicBool::new(false));
+                let connection = Connection::new(peer.ip().to_string(), Arc::clone(&logged_in));
+                // A connection that fails before logging in isn't worth reporting
+                if let Ok(Ok(running)) = tokio::time::timeout(HANDSHAKE_WAIT, server::run_stream(config, stream, connection)).await {
+                    let handle = running.handle();
+                    let deadline = tokio::spawn(async move {
+                        tokio::time::sleep(HANDSHAKE_WAIT).await;
+                        if !logged_in.load(Ordering::SeqCst) {
+                            let _ = handle.disconnect(Disconnect::ByApplication, "the client didn't log in".to_string(), String::new()).await;
+                        }
+                    });
+                    let _ = running.await;
+                    deadline.abort();
+                }
+                sessions.fetch_sub(1, Ordering::SeqCst);
+            });
+        }
+    })
+}
@@ -0,0 +1,638 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::config::Config;
+use crate::console::{clear_screen, prompt};
+use crate::locale::Locale;
+use crate::rankings::{display_width, pad};
+use crate::rating::Ratings;
+use crate::rules::describe_record_key;
+use crate::store::{is_database_path, now_timestamp, os_user, GameResult, OpenedStore, StatsBackend, Store, StoreSet};
+use crate::transcript::Move;
+use crate::strings::{fill, tr};
+
+// --- Opening the Store ---
+
+/// Opens the personal store (and the club store, if a path was given) at startup.
+pub fn open_stores(club_path: Option<PathBuf>) -> StoreSet {
+    let personal = match Store::personal_path() {
+        Some(path) => {
+            migrate_legacy_store(&path);
+            open_store(path, tr("stats.personal"))
+        }
+        None => {
+            outln!("{}", tr("stats.no_home"));
+            Box::new(Store::session_only())
+        }
+    };
+
+    let club = club_path.map(|path| open_store(path, tr("stats.club")));
+    if club.is_some() {
+        let user = os_user().unwrap_or_else(|| tr("stats.this_account").to_string());
+        outln!("{}", fill("stats.split_stores", &[("user", &user)]));
+    }
+
+    StoreSet { personal, club }
+}
+
+/// Moves a store from the old shared location into the per-user directory,
+/// unless the user already has a store of their own.
+fn migrate_legacy_store(personal: &Path) {
+    let Some(legacy) = Store::legacy_path() else { return };
+    if legacy == personal || !legacy.exists() || personal.exists() {
+        return;
+    }
+    if let Some(dir) = personal.parent()
+        && fs::create_dir_all(dir).and_then(|_| fs::rename(&legacy, personal)).is_ok()
+    {
+        outln!("{}", fill("stats.migrated", &[("path", &personal.display())]));
+    }
+}
+
+/// Opens one store: a database for a path like `club.db`, and otherwise the
+/// flat file. Falls back to a session-only store when it can't be opened.
+pub fn open_store(path: PathBuf, label: &str) -> Box<dyn StatsBackend> {
+    if is_database_path(&path) {
+        return open_database(path, label);
+    }
+    Box::new(open_flat_store(path, label))
+}
+
+#[cfg(feature = "sqlite")]
+fn open_database(path: PathBuf, label: &str) -> Box<dyn StatsBackend> {
+    match crate::sqlite::SqliteStore::open(path) {
+        Ok(store) => Box::new(store),
+        Err(e) => {
+            outln!("{}", fill("stats.open_failed", &[("error", &e), ("store", &label)]));
+            Box::new(Store::session_only())
+        }
+    }
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn open_database(path: PathBuf, label: &str) -> Box<dyn StatsBackend> {
+    outln!("{}", fill("stats.needs_sqlite", &[("path", &path.display()), ("store", &label)]));
+    Box::new(Store::session_only())
+}
+
+/// Opens a flat-file store, asking for the passphrase if it is encrypted.
+/// Falls back to a session-only store when it can't (or shouldn't) be unlocked.
+fn open_flat_store(path: PathBuf, label: &str) -> Store {
+    let locked = match Store::open(path.clone()) {
+        Ok(OpenedStore::Ready(store)) => return store,
+        Ok(OpenedStore::Locked(locked)) => locked,
+        Err(e) => {
+            outln!("{}", fill("stats.open_failed", &[("error", &e), ("store", &label)]));
+            return Store::session_only();
+        }
+    };
+
+    outln!("{}", fill("stats.locked_title", &[("store", &label)]));
+    outln!("{}", fill("stats.locked_intro", &[("path", &path.display())]));
+
+    loop {
+        outln!("{}", tr("stats.unlock_passphrase"));
+        outln!("{}", tr("stats.unlock_recovery"));
+        outln!("{}", tr("stats.unlock_skip"));
+        outln!("{}", tr("stats.unlock_fresh"));
+
+        match prompt(tr("stats.unlock_prompt")).as_str() {
+            "1" => {
+                let passphrase = prompt(tr("stats.passphrase"));
+                clear_screen(); // Don't leave the passphrase on screen
+                match locked.unlock(&passphrase) {
+                    Ok(store) => {
+                        outln!("{}", tr("stats.unlocked"));
+                        return store;
+                    }
+                    Err(e) => outln!("{}", fill("stats.failed", &[("error", &e)])),
+                }
+            }
+            "2" => {
+                let code = prompt(tr("stats.recovery_prompt"));
+                clear_screen();
+                match locked.unlock_with_recovery_code(&code) {
+                    Ok(mut store) => {
+                        outln!("{}", tr("stats.unlocked_recovery"));
+                        let passphrase = get_new_passphrase();
+                        match store.change_passphrase(&passphrase) {
+                            Ok(()) => outln!("{}", tr("stats.passphrase_saved")),
+                            Err(e) => outln!("{}", fill("stats.passphrase_save_failed", &[("error", &e)])),
+                        }
+                        return store;
+                    }
+                    Err(e) => outln!("{}", fill("stats.failed", &[("error", &e)])),
+                }
+            }
+            "3" => return Store::session_only(),
+            "4" => {
+                return match locked.archive() {
+                    Ok(archived) => {
+                        outln!("{}", fill("stats.archived", &[("path", &archived.display())]));
+                        match Store::open(path) {
+                            Ok(OpenedStore::Ready(store)) => store,
+                            _ => Store::session_only(),
+                        }
+                    }
+                    Err(e) => {
+                        outln!("{}", fill("stats.session_only", &[("error", &e)]));
+                        Store::session_only()
+                    }
+                };
+            }
+            _ => outln!("{}", tr("stats.unlock_invalid")),
+        }
+    }
+}
+
+/// Asks for a new passphrase twice until both entries match.
+fn get_new_passphrase() -> String {
+    loop {
+        let first = prompt(tr("stats.new_passphrase"));
+        let second = prompt(tr("stats.passphrase_again"));
+        clear_screen();
+
+        if first.is_empty() {
+            outln!("{}", tr("stats.passphrase_empty"));
+        } else if first != second {
+            outln!("{}", tr("stats.passphrase_mismatch"));
+        } else {
+            return first;
+        }
+    }
+}
+
+/// Saves a finished game's results (and its `turns`, where the store keeps
+/// them) to the right store, warning (but carrying on) if that fails.
+pub fn record_results(stores: &mut StoreSet, results: Vec<GameResult>, turns: &[Move]) {
+    let field_size = results.first().map_or(1, |r| r.field_size);
+    let store = stores.store_for(field_size);
+    match store.record_game(results, turns) {
+        Ok(()) => update_leaderboard(store),
+        Err(e) => outln!("{}", fill("stats.save_failed", &[("error", &e)])),
+    }
+}
+
+// --- Celebrations ---
+
+/// A meaningful moment in a player's history, worth a bigger celebration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Milestone {
+    FirstWin,                                       // Never won a multi-player game before
+    PersonalBest { previous: u32, rules: String },  // Fewer guesses than any earlier win in this mode and rule set
+    StreakEnded { rival: String, streak: usize },   // Beat someone on a winning run
+}
+
+/// Groups stored multi-player results into games (results of one game share
+/// its id), oldest first.
+pub fn group_games(results: &[GameResult]) -> Vec<Vec<&GameResult>> {
+    let mut index: HashMap<u64, usize> = HashMap::new();
+    let mut games: Vec<Vec<&GameResult>> = Vec::new();
+    for r in results.iter().filter(|r| r.field_size > 1) {
+        let slot = *index.entry(r.game).or_insert_with(|| {
+            games.push(Vec::new());
+            games.len() - 1
+        });
+        games[slot].push(r);
+    }
+    games.sort_by_key(|game| game[0].timestamp);
+    games
+}
+
+/// How many of their multi-player games in a row `player` has won, counting back from their latest.
+pub fn win_streak(results: &[GameResult], player: &str) -> usize {
+    streaks(results, player).wins
+}
+
+// --- Streaks ---
+// Like the ratings, a player's streaks are worked out from the results saved
+// under their name rather than kept in their profile, so they always agree
+// with the stats. A win streak counts the multi-player games the player took
+// part in; games they sat out don't break it. Days are UTC days (as for the
+// daily challenge) on which they finished any game, solo ones included.
+
+/// A player's current and best runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Streaks {
+    pub wins: usize,      // Multi-player wins in a row, up to their latest game
+    pub best_wins: usize,
+    pub days: usize,      // Days played in a row, up to today or yesterday
+    pub best_days: usize,
+}
+
+/// The latest and longest runs of `true` in `flags`.
+fn runs(flags: impl Iterator<Item = bool>) -> (usize, usize) {
+    let (mut current, mut best) = (0, 0);
+    for flag in flags {
+        current = if flag { current + 1 } else { 0 };
+        best = best.max(current);
+    }
+    (current, best)
+}
+
+/// `player`'s streaks in the stored results.
+pub fn streaks(results: &[GameResult], player: &str) -> Streaks {
+    let won = group_games(results)
+        .into_iter()
+        .filter(|game| game.iter().any(|r| r.player == player))
+        .map(|game| game.iter().any(|r| r.player == player && r.rank == 1));
+    let (wins, best_wins) = runs(won);
+
+    let mut days: Vec<u64> = results.iter().filter(|r| r.player == player).map(|r| r.timestamp / 86_400).collect();
+    days.sort_unstable();
+    days.dedup();
+    let (mut days_in_a_row, mut best_days) = (0, 0);
+    for (i, day) in days.iter().enumerate() {
+        days_in_a_row = if i > 0 && days[i - 1] + 1 == *day { days_in_a_row + 1 } else { 1 };
+        best_days = best_days.max(days_in_a_row);
+    }
+    // The run is only current while it reaches today (or yesterday, with today still to play)
+    if days.last().is_some_and(|last| last + 1 < now_timestamp() / 86_400) {
+        days_in_a_row = 0;
+    }
+    Streaks { wins, best_wins, days: days_in_a_row, best_days }
+}
+
+/// `player`'s fewest guesses in a win, by mode and rule set (`Rules::record_key`).
+pub fn personal_bests(results: &[GameResult], player: &str) -> BTreeMap<(String, String), u32> {
+    let mut bests: BTreeMap<(String, String), u32> = BTreeMap::new();
+    for r in results.iter().filter(|r| r.player == player && r.rank == 1 && r.guesses > 0) {
+        let best = bests.entry((r.mode.clone(), r.rules.clone())).or_insert(r.guesses);
+        *best = (*best).min(r.guesses);
+    }
+    bests
+}
+
+/// Works out what is special about `winner` cracking their code in `guesses`
+/// guesses, judged against the games already in `results` (so call this
+/// before the current game is recorded). `rules` is the game's
+/// `Rules::record_key`, as records only count against the same rule set.
+/// `opponents` are the other players.
+pub fn milestones(results: &[GameResult], mode: &str, rules: &str, winner: &str, guesses: u32, opponents: &[&str]) -> Vec<Milestone> {
+    let mut found = Vec::new();
+    let is_multiplayer = !opponents.is_empty();
+
+    let earlier_wins: Vec<&GameResult> = results
+        .iter()
+        .filter(|r| r.player == winner && r.rank == 1 && r.guesses > 0)
+        .collect();
+
+    if is_multiplayer && !earlier_wins.iter().any(|r| r.field_size > 1) {
+        found.push(Milestone::FirstWin);
+    }
+
+    if let Some(previous) = personal_bests(results, winner).get(&(mode.to_string(), rules.to_string())).copied()
+        && guesses < previous
+    {
+        found.push(Milestone::PersonalBest { previous, rules: rules.to_string() });
+    }
+
+    for &rival in opponents {
+        let streak = win_streak(results, rival);
+        if streak >= 2 {
+            found.push(Milestone::StreakEnded { rival: rival.to_string(), streak });
+        }
+    }
+    found
+}
+
+/// Prints the win banner, scaled up for meaningful moments.
+pub fn celebrate(winner: &str, milestones: &[Milestone]) {
+    if milestones.is_empty() {
+        outln!("\n{}", tr("stats.code_guessed"));
+        return;
+    }
+
+    outln!("\n🎆🎆🎆🎆🎆🎆🎆🎆🎆🎆🎆🎆🎆🎆🎆🎆");
+    outln!("{}", tr("stats.code_guessed"));
+    for milestone in milestones {
+        match milestone {
+            Milestone::FirstWin => outln!("{}", fill("stats.first_win", &[("name", &winner)])),
+            Milestone::PersonalBest { previous, rules } => {
+                outln!("{}", fill("stats.personal_best", &[("name", &winner), ("previous", previous), ("rules", &describe_record_key(rules))]))
+            }
+            Milestone::StreakEnded { rival, streak } => outln!("{}", fill("stats.streak_ended", &[("name", &winner), ("rival", rival), ("streak", streak)])),
+        }
+    }
+    outln!("🎆🎆🎆🎆🎆🎆🎆🎆🎆🎆🎆🎆🎆🎆🎆🎆");
+}
+
+// --- Comeback Weighting ---
+
+/// How many of a player's latest multi-player games count as "recent".
+const RECENT_GAMES: usize = 5;
+
+/// Weights for picking a starting player, leaning towards players who have
+/// finished low lately. Each weight is 1 plus up to 2 more depending on the
+/// player's average place over their recent games (0 for always winning, 2
+/// for always last); players with no history sit in the middle.
+pub fn comeback_weights(results: &[GameResult], players: &[&str]) -> Vec<f64> {
+    players
+        .iter()
+        .map(|&player| {
+            let mut recent: Vec<&GameResult> = results.iter().filter(|r| r.player == player && r.field_size > 1).collect();
+            recent.sort_by_key(|r| std::cmp::Reverse(r.timestamp));
+            recent.truncate(RECENT_GAMES);
+
+            let placing = if recent.is_empty() {
+                0.5
+            } else {
+                // 0.0 for first place, 1.0 for last, whatever the field size
+                let total: f64 = recent.iter().map(|r| (r.rank.saturating_sub(1)) as f64 / (r.field_size - 1) as f64).sum();
+                (total / recent.len() as f64).min(1.0)
+            };
+            1.0 + 2.0 * placing
+        })
+        .collect()
+}
+
+// --- Leaderboard ---
+// Players ranked by multi-player wins, then by the fewest guesses on average,
+// with their rating alongside.
+// Each store keeps its leaderboard next to it as plain text, rewritten after
+// every game (`stats.leaderboard.txt` beside `stats.sgg`), so it can be put up
+// on a classroom screen. An encrypted store gets none, as it would give the
+// stats away.
+
+/// One player's line on the leaderboard.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    pub games: u64,
+    pub wins: u64,
+    pub average: f64, // Guesses per game
+    pub rating: i32,
+}
+
+/// Ranks everyone with a multi-player game in `results`. Solo games are
+/// left out, as every one of them is a win.
+pub fn leaderboard(results: &[GameResult]) -> Vec<LeaderboardEntry> {
+    let multiplayer: Vec<GameResult> = results.iter().filter(|r| r.field_size > 1).cloned().collect();
+    let ratings = Ratings::from_results(results);
+    let mut entries: Vec<LeaderboardEntry> = summarize(&multiplayer)
+        .into_iter()
+                .map(|(name, s)| {
+            let rating = ratings.get(&name).round() as i32;
+            LeaderboardEntry { name, games: s.games, wins: s.wins, average: s.total_guesses as f64 / s.games.max(1) as f64, rating }
+        })
+        .collect();
+    entries.sort_by(|a, b| b.wins.cmp(&a.wins).then(a.average.total_cmp(&b.average)).then_with(|| a.name.cmp(&b.name)));
+    entries
+}
+
+/// Renders the leaderboard as table lines, or as sentences for screen
+/// readers. Players level on wins and average share a place.
+pub fn render_leaderboard(entries: &[LeaderboardEntry]) -> Vec<String> {
+    let locale = Locale::current();
+    let mut rows: Vec<[String; 6]> = Vec::new();
+    let mut place = 0;
+    for (i, e) in entries.iter().enumerate() {
+        if i == 0 || entries[i - 1].wins != e.wins || entries[i - 1].average.total_cmp(&e.average).is_ne() {
+            place = i + 1;
+        }
+        rows.push([locale.ordinal(place), e.name.clone(), locale.count(e.wins), locale.count(e.games), locale.decimal(e.average, 1), e.rating.to_string()]);
+    }
+
+    if Config::current().screen_reader {
+        return rows
+            .into_iter()
+            .map(|[place, name, wins, games, average, rating]| {
+                fill("leaderboard.sentence", &[("place", &place), ("name", &name), ("wins", &wins), ("games", &games), ("average", &average), ("rating", &rating)])
+            })
+            .collect();
+    }
+
+    let headers = ["leaderboard.place", "rankings.player", "leaderboard.wins", "leaderboard.games", "leaderboard.average", "rankings.rating"].map(tr);
+    let mut widths = headers.map(display_width);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(display_width(cell));
+        }
+    }
+    let line = |cells: &[String]| cells.iter().zip(widths).map(|(cell, width)| pad(cell, width)).collect::<Vec<_>>().join("  ").trim_end().to_string();
+    let mut lines = vec![line(&headers.map(str::to_string))];
+    lines.extend(rows.iter().map(|row| line(row)));
+    lines
+}
+
+/// Where the leaderboard for the store at `store_path` is kept.
+pub fn leaderboard_path(store_path: &Path) -> PathBuf {
+    store_path.with_extension("leaderboard.txt")
+}
+
+/// Rewrites the leaderboard file of `store`, or removes it once the store is
+/// encrypted. A session-only store has no file to keep one next to.
+pub fn update_leaderboard(store: &dyn StatsBackend) {
+    let Some(path) = store.path().map(leaderboard_path) else { return };
+    let updated = if store.is_encrypted() {
+        match fs::remove_file(&path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    } else {
+        let mut text = vec![tr("leaderboard.file_title").to_string()];
+        text.extend(render_leaderboard(&leaderboard(store.results())));
+        text.push(String::new());
+        fs::write(&path, text.join("\n"))
+    };
+    if let Err(e) = updated {
+        outln!("{}", fill("stats.leaderboard_failed", &[("path", &path.display()), ("error", &e)]));
+    }
+}
+
+/// Prints the leaderboard of one store under a heading.
+pub fn print_leaderboard(store: &dyn StatsBackend) {
+    outln!("{}", tr("leaderboard.title"));
+    let entries = leaderboard(store.results());
+    if entries.is_empty() {
+        outln!("{}", tr("leaderboard.empty"));
+    }
+    for line in render_leaderboard(&entries) {
+        outln!("{}", line);
+    }
+    if let Some(path) = store.path().filter(|_| !store.is_encrypted()) {
+        outln!("{}", fill("leaderboard.kept", &[("path", &leaderboard_path(path).display())]));
+    }
+}
+
+/// Prints the leaderboards for the personal store and the club store, if
+/// there is one, for `stats leaderboard`.
+pub fn print_leaderboards(stores: &StoreSet) {
+    print_leaderboard(stores.personal.as_ref());
+    if let Some(club) = &stores.club {
+        print_leaderboard(club.as_ref());
+    }
+}
+
+// --- Stats Screen ---
+
+/// Per-player totals built from the stored results.
+#[derive(Debug, Default, Clone)]
+pub struct PlayerSummary {
+    pub games: u64,
+    pub wins: u64,
+    pub total_guesses: u64,
+    pub last_played: u64,
+}
+
+/// Totals every stored result by player name.
+pub fn summarize(results: &[GameResult]) -> BTreeMap<String, PlayerSummary> {
+    let mut summaries: BTreeMap<String, PlayerSummary> = BTreeMap::new();
+    for r in results {
+        let summary = summaries.entry(r.player.clone()).or_default();
+        summary.games += 1;
+        summary.total_guesses += r.guesses as u64;
+        summary.last_played = summary.last_played.max(r.timestamp);
+        if r.rank == 1 {
+            summary.wins += 1;
+        }
+    }
+    summaries
+}
+
+/// Prints the per-player stats table for one store.
+fn show_stats(store: &dyn StatsBackend, title: &str) {
+    let locale = Locale::current();
+    let summaries = summarize(store.results());
+
+    outln!("\n======================================");
+    outln!("|   {:^28} |", title);
+    outln!("======================================");
+    if summaries.is_empty() {
+        outln!("{}", tr("stats.no_games"));
+    }
+    for (name, s) in &summaries {
+        let average = s.total_guesses as f64 / s.games.max(1) as f64;
+        let last_played = UNIX_EPOCH + Duration::from_secs(s.last_played);
+        let streak = streaks(store.results(), name);
+        outln!("{}", fill("stats.row", &[
+            ("name", &format!("{:<15}", name)),
+            ("games", &format!("{:>5}", locale.count(s.games))),
+            ("wins", &format!("{:>5}", locale.count(s.wins))),
+            ("average", &format!("{:>6}", locale.decimal(average, 1))),
+            ("last", &locale.timestamp(last_played)),
+            ("streak", &streak.wins),
+            ("best_streak", &streak.best_wins),
+            ("days", &streak.days),
+            ("best_days", &streak.best_days),
+        ]));
+    }
+    outln!("======================================");
+
+    let bests: Vec<String> = summaries
+        .keys()
+        .flat_map(|name| {
+            personal_bests(store.results(), name)
+                .into_iter()
+                .map(move |((mode, rules), guesses)| fill("stats.best_row", &[("name", name), ("guesses", &guesses), ("mode", &mode), ("rules", &describe_record_key(&rules))]))
+        })
+        .collect();
+    if !bests.is_empty() {
+        outln!("{}", tr("stats.bests"));
+        for line in bests {
+            outln!("{}", line);
+        }
+    }
+
+    match store.path() {
+        Some(path) => {
+            let id = if store.is_encrypted() { "stats.stored_in_encrypted" } else { "stats.stored_in" };
+            outln!("{}", fill(id, &[("path", &path.display())]));
+        }
+        None => outln!("{}", tr("stats.session_store")),
+    }
+}
+
+/// Prints the stats tables for the personal store and the club store, if there is one.
+pub fn print_stats(stores: &StoreSet) {
+    let personal_title = match os_user() {
+        Some(user) => fill("stats.title_user", &[("user", &user.to_uppercase())]),
+        None => tr("stats.title_personal").to_string(),
+    };
+    show_stats(stores.personal.as_ref(), &personal_title);
+    if let Some(club) = &stores.club {
+        show_stats(club.as_ref(), tr("stats.title_club"));
+    }
+}
+
+/// Shows saved stats and lets the players manage store encryption.
+pub fn stats_menu(stores: &mut StoreSet) {
+    loop {
+        clear_screen();
+        print_stats(stores);
+
+        outln!("{}", tr("stats.menu_title"));
+        outln!("{}", tr("stats.menu_personal"));
+        if stores.club.is_some() {
+            outln!("{}", tr("stats.menu_club"));
+        }
+        outln!("{}", tr("stats.menu_back"));
+
+        match prompt(tr("stats.choice_prompt")).as_str() {
+            "0" => return,
+            "1" => encryption_menu(stores.personal.as_mut()),
+            "2" => {
+                if let Some(club) = &mut stores.club {
+                    encryption_menu(club.as_mut());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Turns encryption on or off for one store, or changes its passphrase.
+fn encryption_menu(store: &mut dyn StatsBackend) {
+    outln!("{}", tr("stats.encryption_title"));
+    if !store.can_encrypt() {
+        outln!("{}", tr("stats.database"));
+        prompt(tr("common.continue"));
+        return;
+    }
+    if store.is_encrypted() {
+        outln!("{}", tr("stats.change_passphrase"));
+        outln!("{}", tr("stats.turn_off"));
+    } else {
+        outln!("{}", tr("stats.turn_on"));
+    }
+    outln!("{}", tr("stats.menu_back"));
+
+    match (prompt(tr("stats.choice_prompt")).as_str(), store.is_encrypted()) {
+        ("1", false) => {
+            let passphrase = get_new_passphrase();
+            match store.enable_encryption(&passphrase) {
+                Ok(recovery_code) => {
+                    update_leaderboard(store);
+                    outln!("{}", tr("stats.encrypted"));
+                    outln!("{}", fill("stats.recovery_code", &[("code", &recovery_code)]));
+                    outln!("{}", tr("stats.recovery_advice"));
+                    prompt(tr("stats.recovery_written"));
+                }
+                Err(e) => {
+                    outln!("{}", fill("stats.failed", &[("error", &e)]));
+                    prompt(tr("common.continue"));
+                }
+            }
+        }
+        ("1", true) => {
+            let passphrase = get_new_passphrase();
+            if let Err(e) = store.change_passphrase(&passphrase) {
+                outln!("{}", fill("stats.failed", &[("error", &e)]));
+                prompt(tr("common.continue"));
+            }
+        }
+        ("2", true) if prompt(tr("stats.decrypt_prompt")).eq_ignore_ascii_case(tr("common.yes")) => {
+            match store.disable_encryption() {
+                Ok(()) => update_leaderboard(store),
+                Err(e) => {
+                    outln!("{}", fill("stats.failed", &[("error", &e)]));
+                    prompt(tr("common.continue"));
+                }
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,728 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand::Rng;
+
+use crate::crypto::{open, pbkdf2_sha256, random_bytes, seal, to_hex, SEAL_OVERHEAD};
+use crate::rules::Rules;
+use crate::strings::{fill, tr};
+use crate::transcript::Move;
+
+// --- Stats Store ---
+// Finished games are kept as one line per player result in a small text file.
+// The file can optionally be encrypted with a passphrase. Encryption wraps a
+// random data key twice: once under the passphrase and once under a recovery
+// code shown when encryption is turned on, so a forgotten passphrase (a common
+// event on shared lab machines) doesn't have to mean lost stats.
+
+/// File name of the store inside the data directory.
+pub const STORE_FILE: &str = "stats.sgg";
+
+const PLAIN_HEADER: &str = "sgg-store 1";
+const ENCRYPTED_MAGIC: &[u8; 8] = b"SGGENC2\n";
+// Unit tests stretch far less, so a debug build runs them in seconds rather than minutes
+const KDF_ITERATIONS: u32 = if cfg!(test) { 1_000 } else { 100_000 };
+
+/// One player's result in one finished game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameResult {
+    pub game: u64,      // Shared by every result of the same game (see `new_game_id`)
+    pub timestamp: u64, // Seconds since the Unix epoch
+    pub mode: String,   // e.g. "individual", "daily"
+    pub player: String,
+    pub rank: usize,
+    pub field_size: usize, // Number of players in that game
+    pub guesses: u32,
+    pub rules: String, // The rule set's `Rules::record_key`
+}
+
+/// A fresh id for a finished game, shared by all of its results. Ids are
+/// random, so games that end in the same second (on the HTTP server, say)
+/// are still told apart. Like stats encryption, they never draw on `--seed`.
+pub fn new_game_id() -> u64 {
+    rand::rng().random()
+}
+
+/// The id of a game saved before results had ids. Its results were told
+/// apart from other games' by when the game ended and its mode, so they get
+/// an id made from those (FNV-1a).
+pub fn legacy_game_id(timestamp: u64, mode: &str) -> u64 {
+    let mut hash: u64 = 0xCBF2_9CE4_8422_2325;
+    for byte in timestamp.to_be_bytes().iter().chain(mode.as_bytes()) {
+        hash = (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01B3);
+    }
+    hash
+}
+
+/// A game id as written in stores and exports: 16 hex digits.
+pub fn format_game_id(game: u64) -> String {
+    format!("{:016x}", game)
+}
+
+/// Reads a game id written by `format_game_id`.
+pub fn parse_game_id(text: &str) -> Option<u64> {
+    u64::from_str_radix(text, 16).ok().filter(|_| text.len() == 16)
+}
+
+/// The current time in seconds since the Unix epoch.
+pub fn now_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+/// The name of the logged-in OS account, reduced to characters safe for a directory name.
+pub fn os_user() -> Option<String> {
+    ["USER", "USERNAME", "LOGNAME"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .map(|name| name.chars().filter(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.')).collect::<String>())
+        .find(|name| !name.is_empty() && !name.starts_with('.'))
+}
+
+/// The per-platform directory where the game keeps its data.
+pub fn data_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("XDG_DATA_HOME").filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir).join("secret-guessing-game"));
+    }
+    if cfg!(windows) && let Some(dir) = std::env::var_os("APPDATA") {
+        return Some(PathBuf::from(dir).join("secret-guessing-game"));
+    }
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share/secret-guessing-game"))
+}
+
+// --- Errors ---
+
+#[derive(Debug)]
+pub enum StoreError {
+    Io(io::Error),
+    Corrupt(String),  // The file exists but can't be read as a store
+    WrongPassphrase,  // No key slot matched the passphrase or recovery code
+    SessionOnly,      // The operation needs a store file, but this store isn't saved anywhere
+    Unsupported(&'static str), // A feature this kind of store doesn't have, such as encryption
+    Database(String), // The database behind the store reported a problem
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::Io(e) => write!(f, "{}", fill("store.io", &[("error", e)])),
+            StoreError::Corrupt(reason) => write!(f, "{}", fill("store.corrupt", &[("reason", reason)])),
+            StoreError::WrongPassphrase => write!(f, "{}", tr("store.wrong_passphrase")),
+            StoreError::SessionOnly => write!(f, "{}", tr("store.session_only")),
+            StoreError::Unsupported(feature) => write!(f, "{}", fill("store.unsupported", &[("feature", feature)])),
+            StoreError::Database(reason) => write!(f, "{}", fill("store.database", &[("reason", reason)])),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<io::Error> for StoreError {
+    fn from(e: io::Error) -> Self {
+        StoreError::Io(e)
+    }
+}
+
+// --- Encryption Keys ---
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SlotKind {
+    Passphrase = 1,
+    Recovery = 2,
+}
+
+/// The data key, wrapped under a key derived from a passphrase or recovery code.
+#[derive(Clone)]
+struct KeySlot {
+    kind: SlotKind,
+    salt: [u8; 16],
+    wrapped: [u8; WRAPPED_LEN], // The sealed data key
+}
+
+const WRAPPED_LEN: usize = 32 + SEAL_OVERHEAD;
+const SLOT_LEN: usize = 1 + 16 + WRAPPED_LEN;
+
+impl KeySlot {
+    /// Wraps `data_key` under a freshly salted key derived from `secret`.
+    fn seal(kind: SlotKind, secret: &str, data_key: &[u8; 32]) -> KeySlot {
+        let salt = random_bytes::<16>();
+        let kek = derive_key_encryption_key(secret, &salt);
+        let wrapped = seal(&kek, &slot_header(kind, &salt), data_key).try_into().expect("a sealed key is WRAPPED_LEN bytes");
+        KeySlot { kind, salt, wrapped }
+    }
+
+    /// Recovers the data key if `secret` is the one this slot was sealed with.
+    fn open(&self, secret: &str) -> Option<[u8; 32]> {
+        let kek = derive_key_encryption_key(secret, &self.salt);
+        open(&kek, &slot_header(self.kind, &self.salt), &self.wrapped)?.try_into().ok()
+    }
+
+    fn write_to(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&slot_header(self.kind, &self.salt));
+        out.extend_from_slice(&self.wrapped);
+    }
+
+    fn read_from(bytes: &[u8]) -> Result<KeySlot, StoreError> {
+        let kind = match bytes[0] {
+            1 => SlotKind::Passphrase,
+            2 => SlotKind::Recovery,
+            other => return Err(StoreError::Corrupt(format!("unknown key slot type {}", other))),
+        };
+        Ok(KeySlot { kind, salt: bytes[1..17].try_into().unwrap(), wrapped: bytes[17..SLOT_LEN].try_into().unwrap() })
+    }
+}
+
+/// The start of a slot, which its sealed key is bound to: a key can't be
+/// moved to a slot of another kind, or given another salt.
+fn slot_header(kind: SlotKind, salt: &[u8; 16]) -> Vec<u8> {
+    let mut header = vec![kind as u8];
+    header.extend_from_slice(salt);
+    header
+}
+
+/// Stretches a passphrase into a key-encryption key.
+fn derive_key_encryption_key(secret: &str, salt: &[u8; 16]) -> [u8; 32] {
+    let mut kek = [0u8; 32];
+    pbkdf2_sha256(secret.as_bytes(), salt, KDF_ITERATIONS, &mut kek);
+    kek
+}
+
+/// Recovery codes are compared without dashes, spaces, or case.
+fn normalize_recovery_code(code: &str) -> String {
+    code.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_ascii_uppercase()
+}
+
+/// Formats a fresh recovery code as eight dash-separated groups of four.
+fn new_recovery_code() -> String {
+    let hex = to_hex(&random_bytes::<16>()).to_ascii_uppercase();
+    hex.as_bytes().chunks(4).map(|c| String::from_utf8_lossy(c).into_owned()).collect::<Vec<_>>().join("-")
+}
+
+#[derive(Clone)]
+struct StoreKeys {
+    data_key: [u8; 32], // Seals the results with XChaCha20-Poly1305
+    slots: Vec<KeySlot>,
+}
+
+// --- Storage Backends ---
+// Where a store keeps its results between runs. The flat file below is the
+// default, and the only kind that can be encrypted. Builds with the `sqlite`
+// feature can keep a store in an SQLite database instead (see `sqlite.rs`),
+// chosen by a path ending in `.db`, `.sqlite` or `.sqlite3`.
+
+/// Path extensions that pick the SQLite store.
+pub const DATABASE_EXTENSIONS: [&str; 3] = ["db", "sqlite", "sqlite3"];
+
+/// Whether the store at `path` is meant to be an SQLite database.
+pub fn is_database_path(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()).is_some_and(|e| DATABASE_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+}
+
+/// What the rest of the game needs from a store, whatever keeps it.
+pub trait StatsBackend {
+    /// Where the results are kept; None for a store that lasts only this session.
+    fn path(&self) -> Option<&Path>;
+
+    /// Every result kept so far, oldest first.
+    fn results(&self) -> &[GameResult];
+
+    /// Adds the results of a finished game and saves them. `turns` are its
+    /// guesses, where the mode keeps a record of them; backends that only
+    /// keep results ignore them.
+    fn record_game(&mut self, results: Vec<GameResult>, turns: &[Move]) -> Result<(), StoreError>;
+
+    fn is_persistent(&self) -> bool {
+        self.path().is_some()
+    }
+
+    /// Whether the store can be protected with a passphrase.
+    fn can_encrypt(&self) -> bool {
+        false
+    }
+
+    fn is_encrypted(&self) -> bool {
+        false
+    }
+
+    /// Turns on encryption and returns the recovery code, which is shown only once.
+    fn enable_encryption(&mut self, _passphrase: &str) -> Result<String, StoreError> {
+        Err(StoreError::Unsupported(tr("store.encryption")))
+    }
+
+    /// Replaces the passphrase. The recovery code keeps working.
+    fn change_passphrase(&mut self, _passphrase: &str) -> Result<(), StoreError> {
+        Err(StoreError::Unsupported(tr("store.encryption")))
+    }
+
+    /// Stores the results unencrypted again.
+    fn disable_encryption(&mut self) -> Result<(), StoreError> {
+        Err(StoreError::Unsupported(tr("store.encryption")))
+    }
+}
+
+// --- The Store ---
+
+/// Saved game results in a flat file, optionally encrypted at rest.
+pub struct Store {
+    path: Option<PathBuf>, // None means results only last for this session
+    results: Vec<GameResult>,
+    extra_lines: Vec<String>, // Records written by newer builds, kept as-is
+    keys: Option<StoreKeys>,
+}
+
+/// What was found when opening a store file.
+pub enum OpenedStore {
+    Ready(Store),
+    Locked(LockedStore),
+}
+
+/// An encrypted store waiting for its passphrase or recovery code.
+pub struct LockedStore {
+    path: PathBuf,
+    slots: Vec<KeySlot>,
+    header: Vec<u8>, // Everything up to the end of the key slots
+    sealed: Vec<u8>, // The sealed results, bound to the header
+}
+
+impl Store {
+    /// The current OS user's own store. Each account gets its own directory, so
+    /// people sharing a data directory (or a machine) don't mix their stats.
+    pub fn personal_path() -> Option<PathBuf> {
+        let dir = data_dir()?;
+        Some(match os_user() {
+            Some(user) => dir.join("users").join(user).join(STORE_FILE),
+            None => dir.join(STORE_FILE),
+        })
+    }
+
+    /// Where stores were kept before they became per-user.
+    pub fn legacy_path() -> Option<PathBuf> {
+        data_dir().map(|dir| dir.join(STORE_FILE))
+    }
+
+    /// A store that keeps results in memory only.
+    pub fn session_only() -> Store {
+        Store { path: None, results: Vec::new(), extra_lines: Vec::new(), keys: None }
+    }
+
+    /// Opens the store at `path`. A missing file is an empty store.
+    pub fn open(path: PathBuf) -> Result<OpenedStore, StoreError> {
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return Ok(OpenedStore::Ready(Store { path: Some(path), ..Store::session_only() }));
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        if bytes.starts_with(ENCRYPTED_MAGIC) {
+            return LockedStore::parse(path, &bytes).map(OpenedStore::Locked);
+        }
+
+        let text = String::from_utf8(bytes).map_err(|_| StoreError::Corrupt("not valid text".to_string()))?;
+        let mut store = Store { path: Some(path), ..Store::session_only() };
+        store.load_text(&text)?;
+        Ok(OpenedStore::Ready(store))
+    }
+
+    /// Writes the store to disk (atomically, via a temporary file).
+    pub fn save(&self) -> Result<(), StoreError> {
+        let Some(path) = &self.path else { return Ok(()) };
+
+        let text = self.to_text();
+        let bytes = match &self.keys {
+            None => text.into_bytes(),
+            Some(keys) => seal_file(keys, text.as_bytes()),
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let temp = path.with_extension("tmp");
+        fs::write(&temp, bytes)?;
+        fs::rename(&temp, path)?;
+        Ok(())
+    }
+
+    // --- Text Format ---
+    // sgg-store 1
+    // result<TAB>timestamp<TAB>mode<TAB>rank<TAB>field_size<TAB>guesses<TAB>player[<TAB>rules[<TAB>game]]
+    // The rule set is left off for the standard rules, which every game used
+    // before it was recorded, so builds that don't know the field still read
+    // those results. The game id is left off for results saved before there
+    // were ids, which keep the one `legacy_game_id` gives them.
+
+    fn to_text(&self) -> String {
+        let standard = Rules::STANDARD.record_key();
+        let mut text = format!("{}\n", PLAIN_HEADER);
+        for r in &self.results {
+            text.push_str(&format!("result\t{}\t{}\t{}\t{}\t{}\t{}",
+                                   r.timestamp, clean_field(&r.mode), r.rank, r.field_size, r.guesses, clean_field(&r.player)));
+            let legacy = r.game == legacy_game_id(r.timestamp, &r.mode);
+            if r.rules != standard || !legacy {
+                text.push_str(&format!("\t{}", clean_field(&r.rules)));
+            }
+            if !legacy {
+                text.push_str(&format!("\t{}", format_game_id(r.game)));
+            }
+            text.push('\n');
+        }
+        for line in &self.extra_lines {
+            text.push_str(line);
+            text.push('\n');
+        }
+        text
+    }
+
+    fn load_text(&mut self, text: &str) -> Result<(), StoreError> {
+        let mut lines = text.lines();
+        if lines.next() != Some(PLAIN_HEADER) {
+            return Err(StoreError::Corrupt("unrecognised header".to_string()));
+        }
+
+        for (number, line) in lines.enumerate().filter(|(_, l)| !l.is_empty()) {
+            let fields: Vec<&str> = line.split('\t').collect();
+            match fields[0] {
+                "result" if (7..=9).contains(&fields.len()) => {
+                    let bad = |_| StoreError::Corrupt(format!("bad number on line {}", number + 2));
+                    let timestamp = fields[1].parse().map_err(bad)?;
+                    let game = match fields.get(8) {
+                        Some(game) => parse_game_id(game).ok_or_else(|| StoreError::Corrupt(format!("bad game id on line {}", number + 2)))?,
+                        None => legacy_game_id(timestamp, fields[2]),
+                    };
+                    self.results.push(GameResult {
+                        game,
+                        timestamp,
+                        mode: fields[2].to_string(),
+                        rank: fields[3].parse().map_err(bad)?,
+                        field_size: fields[4].parse().map_err(bad)?,
+                        guesses: fields[5].parse().map_err(bad)?,
+                        player: fields[6].to_string(),
+                        rules: fields.get(7).map_or_else(|| Rules::STANDARD.record_key(), |rules| rules.to_string()),
+                    });
+                }
+                _ => self.extra_lines.push(line.to_string()),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl StatsBackend for Store {
+    fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    fn results(&self) -> &[GameResult] {
+        &self.results
+    }
+
+    /// The flat file has nowhere to keep turns, so only the results are saved.
+    fn record_game(&mut self, results: Vec<GameResult>, _turns: &[Move]) -> Result<(), StoreError> {
+        self.results.extend(results);
+        self.save()
+    }
+
+    fn can_encrypt(&self) -> bool {
+        true
+    }
+
+    fn is_encrypted(&self) -> bool {
+        self.keys.is_some()
+    }
+
+    fn enable_encryption(&mut self, passphrase: &str) -> Result<String, StoreError> {
+        if self.path.is_none() {
+            return Err(StoreError::SessionOnly);
+        }
+        let recovery_code = new_recovery_code();
+        let data_key = random_bytes::<32>();
+        self.keys = Some(StoreKeys {
+            data_key,
+            slots: vec![
+                KeySlot::seal(SlotKind::Passphrase, passphrase, &data_key),
+                KeySlot::seal(SlotKind::Recovery, &normalize_recovery_code(&recovery_code), &data_key),
+            ],
+        });
+        self.save()?;
+        Ok(recovery_code)
+    }
+
+    fn change_passphrase(&mut self, passphrase: &str) -> Result<(), StoreError> {
+        if let Some(keys) = &mut self.keys {
+            let data_key = keys.data_key;
+            keys.slots.retain(|slot| slot.kind != SlotKind::Passphrase);
+            keys.slots.insert(0, KeySlot::seal(SlotKind::Passphrase, passphrase, &data_key));
+        }
+        self.save()
+    }
+
+    fn disable_encryption(&mut self) -> Result<(), StoreError> {
+        self.keys = None;
+        self.save()
+    }
+}
+
+/// Tabs and newlines would break the line format, so they become spaces.
+pub(crate) fn clean_field(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+// --- Encrypted File Format ---
+// magic | iterations (u32) | slot count (u8) | slots | sealed results
+// The results are sealed with the data key and every byte before them as
+// associated data, so a change to the header or a slot is caught as well.
+
+fn seal_file(keys: &StoreKeys, plaintext: &[u8]) -> Vec<u8> {
+    let mut out = ENCRYPTED_MAGIC.to_vec();
+    out.extend_from_slice(&KDF_ITERATIONS.to_be_bytes());
+    out.push(keys.slots.len() as u8);
+    for slot in &keys.slots {
+        slot.write_to(&mut out);
+    }
+    let sealed = seal(&keys.data_key, &out, plaintext);
+    out.extend_from_slice(&sealed);
+    out
+}
+
+impl LockedStore {
+    fn parse(path: PathBuf, bytes: &[u8]) -> Result<LockedStore, StoreError> {
+        let truncated = || StoreError::Corrupt("file is truncated".to_string());
+        let header_len = ENCRYPTED_MAGIC.len() + 4 + 1;
+        if bytes.len() < header_len {
+            return Err(truncated());
+        }
+
+        let iterations = u32::from_be_bytes(bytes[8..12].try_into().unwrap());
+        if iterations != KDF_ITERATIONS {
+            return Err(StoreError::Corrupt(format!("unsupported key derivation setting ({})", iterations)));
+        }
+
+        let slot_count = bytes[12] as usize;
+        let slots_end = header_len + slot_count * SLOT_LEN;
+        if bytes.len() < slots_end + SEAL_OVERHEAD {
+            return Err(truncated());
+        }
+
+        let slots = bytes[header_len..slots_end]
+            .chunks(SLOT_LEN)
+            .map(KeySlot::read_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(LockedStore { path, slots, header: bytes[..slots_end].to_vec(), sealed: bytes[slots_end..].to_vec() })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Unlocks the store with its passphrase.
+    pub fn unlock(&self, passphrase: &str) -> Result<Store, StoreError> {
+        self.unlock_slot(SlotKind::Passphrase, passphrase)
+    }
+
+    /// Unlocks the store with the recovery code shown when encryption was enabled.
+    pub fn unlock_with_recovery_code(&self, code: &str) -> Result<Store, StoreError> {
+        self.unlock_slot(SlotKind::Recovery, &normalize_recovery_code(code))
+    }
+
+    /// Moves the locked file aside so a fresh store can be started.
+    /// The old file is kept in case the passphrase turns up later.
+    pub fn archive(self) -> Result<PathBuf, StoreError> {
+        let archived = self.path.with_extension(format!("sgg.locked-{}", now_timestamp()));
+        fs::rename(&self.path, &archived)?;
+        Ok(archived)
+    }
+
+    fn unlock_slot(&self, kind: SlotKind, secret: &str) -> Result<Store, StoreError> {
+        let data_key = self
+            .slots
+            .iter()
+            .filter(|slot| slot.kind == kind)
+            .find_map(|slot| slot.open(secret))
+            .ok_or(StoreError::WrongPassphrase)?;
+
+        let plaintext = open(&data_key, &self.header, &self.sealed)
+            .ok_or_else(|| StoreError::Corrupt("contents failed the integrity check".to_string()))?;
+        let text = String::from_utf8(plaintext).map_err(|_| StoreError::Corrupt("not valid text".to_string()))?;
+        let mut store = Store {
+            path: Some(self.path.clone()),
+            keys: Some(StoreKeys { data_key, slots: self.slots.clone() }),
+            ..Store::session_only()
+        };
+        store.load_text(&text)?;
+        Ok(store)
+    }
+}
+
+// --- Personal and Club Stores ---
+
+/// The current user's personal store, plus an optional shared "club" store
+/// (e.g. a family or classroom leaderboard) configured explicitly by path.
+pub struct StoreSet {
+    pub personal: Box<dyn StatsBackend>,
+    pub club: Option<Box<dyn StatsBackend>>,
+}
+
+impl StoreSet {
+    pub fn session_only() -> Self {
+        StoreSet { personal: Box::new(Store::session_only()), club: None }
+    }
+
+    /// Picks where a finished game is recorded. Solo games always stay in the
+    /// personal store; games with several players go to the club store when one
+    /// is configured, so practice runs never leak onto the shared leaderboard.
+    pub fn store_for(&mut self, field_size: usize) -> &mut dyn StatsBackend {
+        match &mut self.club {
+            Some(club) if field_size > 1 => club.as_mut(),
+            _ => self.personal.as_mut(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A store file of its own for each test, removed when the test is done.
+    struct TempStore(PathBuf);
+
+    impl TempStore {
+        fn new(name: &str) -> TempStore {
+            let path = std::env::temp_dir().join(format!("sgg-store-test-{}-{}.sgg", std::process::id(), name));
+            let _ = fs::remove_file(&path);
+            TempStore(path)
+        }
+
+        fn open(&self) -> OpenedStore {
+            Store::open(self.0.clone()).expect("the test store opens")
+        }
+
+        fn open_ready(&self) -> Store {
+            match self.open() {
+                OpenedStore::Ready(store) => store,
+                OpenedStore::Locked(_) => panic!("the store should not be encrypted"),
+            }
+        }
+
+        fn open_locked(&self) -> LockedStore {
+            match self.open() {
+                OpenedStore::Locked(store) => store,
+                OpenedStore::Ready(_) => panic!("the store should be encrypted"),
+            }
+        }
+    }
+
+    impl Drop for TempStore {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    fn results() -> Vec<GameResult> {
+        let result = |player: &str, rank, rules: String| GameResult {
+            game: 0x0123_4567_89ab_cdef,
+            timestamp: 1_760_000_000,
+            mode: "individual".to_string(),
+            player: player.to_string(),
+            rank,
+            field_size: 2,
+            guesses: 6,
+            rules,
+        };
+        vec![
+            result("Ann", 1, Rules::STANDARD.record_key()),
+            result("Ben\tthe\nsecond", 2, Rules { code_length: 5, allow_repeats: false }.record_key()),
+        ]
+    }
+
+    fn cleaned(mut results: Vec<GameResult>) -> Vec<GameResult> {
+        for r in &mut results {
+            r.player = clean_field(&r.player);
+        }
+        results
+    }
+
+    #[test]
+    fn a_plain_store_reads_back_as_written() {
+        let file = TempStore::new("plain");
+        let mut store = file.open_ready();
+        assert!(store.results().is_empty());
+        store.record_game(results(), &[]).unwrap();
+        fs::write(&file.0, fs::read_to_string(&file.0).unwrap() + "medal\tAnn\tgold\n").unwrap();
+
+        let mut store = file.open_ready();
+        assert_eq!(store.results(), cleaned(results()).as_slice());
+        // Records from newer builds survive the next save
+        store.record_game(Vec::new(), &[]).unwrap();
+        assert!(fs::read_to_string(&file.0).unwrap().ends_with("medal\tAnn\tgold\n"));
+    }
+
+    #[test]
+    fn results_saved_before_game_ids_keep_one_id_per_game() {
+        let file = TempStore::new("legacy");
+        let line = |player, rank| format!("result\t1760000000\tindividual\t{}\t2\t6\t{}\n", rank, player);
+        fs::write(&file.0, format!("{}\n{}{}", PLAIN_HEADER, line("Ann", 1), line("Ben", 2))).unwrap();
+
+        let mut store = file.open_ready();
+        let legacy = legacy_game_id(1_760_000_000, "individual");
+        assert!(store.results().iter().all(|r| r.game == legacy));
+        // Nor do they gain an id when saved again
+        store.record_game(Vec::new(), &[]).unwrap();
+        assert_eq!(fs::read_to_string(&file.0).unwrap(), format!("{}\n{}{}", PLAIN_HEADER, line("Ann", 1), line("Ben", 2)));
+    }
+
+    #[test]
+    fn an_encrypted_store_opens_only_with_its_passphrase_or_recovery_code() {
+        let file = TempStore::new("encrypted");
+        let mut store = file.open_ready();
+        store.record_game(results(), &[]).unwrap();
+        let recovery_code = store.enable_encryption("correct horse").unwrap();
+        assert!(!String::from_utf8_lossy(&fs::read(&file.0).unwrap()).contains("individual"));
+
+        let locked = file.open_locked();
+        assert!(matches!(locked.unlock("correct hose"), Err(StoreError::WrongPassphrase)));
+        assert!(matches!(locked.unlock(""), Err(StoreError::WrongPassphrase)));
+        assert!(matches!(locked.unlock(&recovery_code), Err(StoreError::WrongPassphrase)));
+        assert!(matches!(locked.unlock_with_recovery_code("correct horse"), Err(StoreError::WrongPassphrase)));
+
+        assert_eq!(locked.unlock("correct horse").unwrap().results(), cleaned(results()).as_slice());
+        let typed = recovery_code.replace('-', " ").to_ascii_lowercase();
+        assert_eq!(locked.unlock_with_recovery_code(&typed).unwrap().results(), cleaned(results()).as_slice());
+    }
+
+    #[test]
+    fn a_changed_passphrase_replaces_the_old_one() {
+        let file = TempStore::new("changed");
+        let mut store = file.open_ready();
+        let recovery_code = store.enable_encryption("first").unwrap();
+        store.change_passphrase("second").unwrap();
+
+        let locked = file.open_locked();
+        assert!(matches!(locked.unlock("first"), Err(StoreError::WrongPassphrase)));
+        let mut store = locked.unlock("second").unwrap();
+        assert!(file.open_locked().unlock_with_recovery_code(&recovery_code).is_ok());
+
+        store.disable_encryption().unwrap();
+        assert!(file.open_ready().results().is_empty());
+    }
+
+    #[test]
+    fn a_tampered_encrypted_store_fails_its_integrity_check() {
+        let file = TempStore::new("tampered");
+        let mut store = file.open_ready();
+        store.record_game(results(), &[]).unwrap();
+        store.enable_encryption("correct horse").unwrap();
+
+        let mut bytes = fs::read(&file.0).unwrap();
+        let last = bytes.len() - 16 - 1; // The last byte before the tag
+        bytes[last] ^= 1;
+        fs::write(&file.0, &bytes).unwrap();
+        assert!(matches!(file.open_locked().unlock("correct horse"), Err(StoreError::Corrupt(_))));
+
+        fs::write(&file.0, &bytes[..20]).unwrap();
+        assert!(matches!(Store::open(file.0.clone()), Err(StoreError::Corrupt(_))));
+    }
+}
@@ -0,0 +1,1922 @@
+use std::fmt::Display;
+use std::sync::RwLock;
+
+use crate::config::Config;
+use crate::locale::Locale;
+
+// --- Message Strings ---
+// Prompts and messages are looked up by id in a per-language table, so the
+// game can be played in the language spoken around the table. Placeholders
+// are written `{name}` and filled in by `fill`. An id missing from a table
+// falls back to the English text.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    En,
+    De,
+}
+
+/// Set by `--lang`, which wins over the settings file.
+static OVERRIDE: RwLock<Option<Language>> = RwLock::new(None);
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::En, Language::De];
+
+    /// Parses a language tag such as "de", "de_DE.UTF-8", or "en-GB".
+    pub fn from_tag(tag: &str) -> Option<Language> {
+        let lang = tag.split(['_', '-', '.', '@']).next().unwrap_or("").to_ascii_lowercase();
+        match lang.as_str() {
+            "en" | "c" | "posix" => Some(Language::En),
+            "de" => Some(Language::De),
+            _ => None,
+        }
+    }
+
+    /// Picks a language from the usual POSIX environment variables, defaulting to English.
+    pub fn from_env() -> Language {
+        ["LANGUAGE", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Language::from_tag(&value))
+            .unwrap_or(Language::En)
+    }
+
+    /// The language for prompts and messages: `--lang`, then the settings, then the environment.
+    pub fn current() -> Language {
+        if let Some(language) = *OVERRIDE.read().unwrap() {
+            return language;
+        }
+        Config::current().language.unwrap_or_else(Language::from_env)
+    }
+
+    /// Overrides the language for the rest of the run.
+    pub fn set_current(language: Language) {
+        *OVERRIDE.write().unwrap() = Some(language);
+    }
+
+    /// Whether `tag` names a language the game can be played in: one with a
+    /// translation, or just with its own number and date formats.
+    pub fn is_known(tag: &str) -> bool {
+        Language::from_tag(tag).is_some() || Locale::from_tag(tag).is_some()
+    }
+
+    /// Switches messages to the language `tag` names, and numbers and dates
+    /// to its formats, for the rest of the run. A language without a
+    /// translation yet keeps its formats with the messages in English.
+    /// Returns false, changing nothing, if the tag isn't known.
+    pub fn select(tag: &str) -> bool {
+        if !Language::is_known(tag) {
+            return false;
+        }
+        Language::set_current(Language::from_tag(tag).unwrap_or(Language::En));
+        if let Some(locale) = Locale::from_tag(tag) {
+            Locale::set_current(locale);
+        }
+        true
+    }
+
+    /// The tag used in the settings file.
+    pub fn id(self) -> &'static str {
+        match self {
+            Language::En => "en",
+            Language::De => "de",
+        }
+    }
+
+    /// The language's name, in that language.
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::En => "English",
+            Language::De => "Deutsch",
+        }
+    }
+
+    fn table(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Language::En => EN,
+            Language::De => DE,
+        }
+    }
+}
+
+/// The text for `id` in the current language.
+pub fn tr(id: &'static str) -> &'static str {
+    let lookup = |table: &'static [(&'static str, &'static str)]| table.iter().find(|(key, _)| *key == id).map(|&(_, text)| text);
+    lookup(Language::current().table()).or_else(|| lookup(EN)).unwrap_or(id)
+}
+
+/// The text for `id` with each `{key}` placeholder replaced by its value.
+pub fn fill(id: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    let mut text = tr(id).to_string();
+    for (key, value) in args {
+        text = text.replace(&format!("{{{}}}", key), &value.to_string());
+    }
+    text
+}
+
+const EN: &[(&str, &str)] = &[
+    // Main menu
+    ("menu.title", "--- 🎲 Multiplayer Code Guessing Game ---"),
+    ("menu.individual", "[1] Individual Secrets: Every player races to crack their own code."),
+    ("menu.double_blind", "[2] Double-Blind: A human codemaker scores guesses, the engine referees."),
+    ("menu.chat", "[3] Chat Plays: A stream audience votes on guesses against one secret."),
+    ("menu.tournament", "[4] Tournament: Knockout bracket of head-to-head games."),
+    ("menu.daily", "[5] Daily Challenge: Everyone cracks the same code today."),
+    ("menu.time_attack", "[6] Time Attack: Crack as many codes as you can against the clock."),
+    ("menu.practice", "[7] Practice: A solo sandbox with undo, redo, and line comparison."),
+    ("menu.watch", "[8] Watch: Bots play each other with full narration."),
+    ("menu.reverse", "[9] Reverse: You make the code, the computer breaks it."),
+    ("menu.stats", "[10] Stats: View saved results and manage stats encryption."),
+    ("menu.settings", "[11] Settings: Turn optional features on or off."),
+    ("menu.stats_view", "[10] Stats: View saved results."),
+    ("menu.prompt", "Select a mode (1-11): "),
+    ("menu.prompt_guest", "Select a mode (1-10): "),
+    ("guest.unavailable", "That isn't available when playing on someone else's server."),
+    ("menu.invalid", "Invalid input. Please enter a number from 1 to 11."),
+    ("over.title", "\n--- Game Over ---"),
+    ("over.new", "[1] Start a New Game"),
+    ("over.quit", "[2] Quit Program"),
+    ("over.prompt", "Enter choice (1 or 2): "),
+    ("over.goodbye", "Thank you for playing! Goodbye."),
+    ("over.invalid", "Invalid input. Restarting the menu..."),
+    // Shared prompts
+    ("common.continue", "Press Enter to continue..."),
+    ("common.name_prompt", "Enter your name: "),
+    ("common.score_prompt", "{name}, score the guess {guess} (D,P): "),
+    ("pause.paused", "⏸️ The game is paused, and so is any clock. Press Enter to carry on..."),
+    ("pause.resumed", "▶️ Carrying on where you left off."),
+    ("input.ended", "The input has run out, so the game stops here."),
+    ("help.commands", "--- ❔ Commands ---\n  /help         This list. Works at any prompt.\n  /rules        The rules and how feedback works. Works at any prompt.\n  /pause        Blanks the screen and stops the clock until Enter is pressed. Works at any prompt.\n  /hint         A suggested guess, on your turn.\n  /note <text>  Saves a private note, on your turn.\n  /notes        Shows your notes, on your turn.\n  /history      Shows your guesses so far, on your turn.\n  /score        Shows the scoreboard, on your turn.\n  /skip         Passes your turn without guessing.\n  /save <name>  Saves the game to carry on later, on your turn.\n  /quit         Ends the game for everyone, once you confirm.\n  /undo         Takes back the guess you just made, before the keyboard is passed on.\nOnly /skip uses up your turn."),
+    ("rules.title", "--- 📜 Rules ---"),
+    ("rules.code", "Every secret is {length} different digits, and it may start with 0."),
+    ("rules.feedback", "Each guess is scored D,P: D is how many of its digits are in the secret at all, P how many of those are in the right place."),
+    ("rules.example", "For example, if the secret were {secret}, the guess {guess} would get {score}."),
+    ("rules.win", "A code is cracked with {score}."),
+    ("rules.hard_mode", "Hard mode is on: every guess must fit all the feedback you've had so far."),
+    ("rules.timer", "The turn timer is on: each turn lasts {count} seconds."),
+    ("rules.record_key", "{length} digits, {repeats}, {digits}"),
+    ("rules.repeats", "repeats allowed"),
+    ("rules.no_repeats", "no repeats"),
+    ("common.yes", "y"),
+    ("common.no", "n"),
+    ("common.hand_over", "\n🔒 Pass the keyboard to {name}. {name}, press Enter when you're ready..."),
+    ("common.hide_turn", "\n🔒 Press Enter to hide your feedback..."),
+    // Input errors
+    ("error.guess_length", "Guess must be exactly {length} digits."),
+    ("error.guess_repeat", "Digits must not be repeated."),
+    ("error.guess_digits", "Input contains non-digit characters."),
+    ("error.feedback_form", "Feedback must be two numbers in the form D,P (e.g. 2,1)."),
+    ("error.feedback_numbers", "Feedback must only contain numbers."),
+    ("error.feedback_range", "D must be at most {length}, and P can never be larger than D."),
+    ("error.feedback_impossible", "{total},{position} is not a possible feedback."),
+    // Seats and lobby
+    ("seat.type", "Player {number}: [1] Human  [2] Easy bot  [3] Medium bot  [4] Hard bot"),
+    ("seat.type_prompt", "Enter seat type (Enter for Human): "),
+    ("seat.type_invalid", "Please enter a number from 1 to 4."),
+    ("seat.personality_prompt", "Enter personality (Enter for Standard): "),
+    ("seat.personality_invalid", "Please enter a number from 1 to {max}."),
+    ("seat.human", "Human"),
+    ("seat.engine", "Program: {command}"),
+    ("seat.default_name", "Player {number}"),
+    ("seat.name_prompt", "Enter name for seat {number} (Enter for \"{default}\"): "),
+    ("seat.count_prompt", "{message} (0 to 3, Enter keeps {current}): "),
+    ("seat.count_invalid", "Please enter a number from 0 to 3."),
+    ("handicap.none", "none"),
+    ("handicap.free_clues", "{count} free clue(s)"),
+    ("handicap.late_start", "sits out {count} turn(s)"),
+    ("lobby.title", "\n--- 🪑 Lobby ---"),
+    ("lobby.empty", "  (no seats yet)"),
+    ("lobby.rated", "{name} ({rating})"),
+    ("lobby.handicap", "Handicap: {handicap}"),
+    ("lobby.commands", "\n[A] Add seat  [E n] Edit seat n  [R n] Remove seat n  [P n] Profile for seat n  [S] Start game"),
+    ("lobby.bot_profile", "Only people have profiles. Press Enter to continue..."),
+    ("lobby.prompt", "Enter command: "),
+    ("lobby.full", "The table is full ({max} seats)."),
+    ("lobby.no_seats", "Add at least one seat first. Press Enter to continue..."),
+    ("lobby.unknown", "Unknown command. Use A, E <seat>, R <seat>, P <seat>, or S. Press Enter to continue..."),
+    ("profile.title", "\n--- 👤 Profile: {name} ---"),
+    ("profile.theme", "[1] Colour theme: {value}"),
+    ("profile.output", "[2] Symbols: {value}"),
+    ("profile.length", "[3] Code length: {value}"),
+    ("profile.done", "[4] Save and go back"),
+    ("profile.prompt", "Enter choice (1-4): "),
+    ("profile.from_settings", "as in the settings"),
+    ("profile.save_failed", "⚠️ Couldn't save the profile: {error}"),
+    ("profile.welcome", "👤 Welcome back, {name}! Your profile is loaded: {preferences}."),
+    ("profile.stats", "   Your stats: {games} game(s), {wins} win(s), {average} guesses on average."),
+    ("profile.part_theme", "{value} theme"),
+    ("profile.part_output", "{value}"),
+    ("profile.part_length", "{count}-digit codes"),
+    ("profile.uses_length", "🔢 {name} prefers {length}-digit codes, so this game's codes are {length} digits long."),
+    ("profile.no_preferences", "everything as in the settings"),
+    ("profile.achievements", "    Achievements: {list}"),
+    ("achievement.banner", "ACHIEVEMENT UNLOCKED"),
+    ("achievement.unlocked", "🏅 {name}: {achievement} ({about})"),
+    ("achievement.quick_solve", "Quick Thinker"),
+    ("achievement.quick_solve_about", "cracked a code in 3 guesses or fewer"),
+    ("achievement.hat_trick", "Hat Trick"),
+    ("achievement.hat_trick_about", "won three multi-player games in a row"),
+    ("achievement.no_hints", "Unaided"),
+    ("achievement.no_hints_about", "won a multi-player game without a hint"),
+    ("achievement.long_code", "Long Haul"),
+    ("achievement.long_code_about", "cracked a code of 6 digits or more"),
+    ("edit.title", "\n--- Seat {number}: {name} ---"),
+    ("edit.player", "[1] Player: {player}"),
+    ("edit.name", "[2] Name: {name}"),
+    ("edit.free_clues", "[3] Free clues before the start: {count}"),
+    ("edit.late_start", "[4] Turns to sit out at the start: {count}"),
+    ("edit.done", "[5] Done"),
+    ("edit.prompt", "Enter choice (1-5): "),
+    ("edit.free_clues_prompt", "Free clues"),
+    ("edit.late_start_prompt", "Turns to sit out"),
+    // Individual Secrets
+    ("game.title", "--- 🎲 Multiplayer Code Guessing Game (Individual Secrets) ---"),
+    ("game.intro_code", "Each player has a unique, hidden {length}-digit code (non-repeating digits, can start with 0)."),
+    ("game.intro_goal", "Players take turns guessing their own secret. First to guess wins!"),
+    ("game.secrets_ready", "\nAll secret codes have been generated. Let the guessing begin!"),
+    ("game.all_finished", "\nAll players have finished the game. Thanks for playing!"),
+    ("game.ended_early", "\n🚪 The game was ended early. Thanks for playing!"),
+    ("debug.enabled", "🔬 Debug mode: secrets are shown on each turn, and this game won't be saved to the stats."),
+    ("debug.secret", "🔬 Debug: {name}'s secret is {secret}."),
+    ("debug.refused", "⚠️ --debug-secrets only works when one person is playing (or with --i-know-what-im-doing), so the secrets stay hidden."),
+    ("game.last_player_title", "\n--- Final Player Ranked ---"),
+    ("game.last_player", "{name} is automatically assigned {place} place (did not finish)."),
+    ("game.sits_out", "\n⏸️ {name} sits out this turn (handicap)."),
+    ("game.turn_header", "ROUND {round} | {name}'s Guess"),
+    ("game.bot_thinking", "🤖 {name} is thinking..."),
+    ("game.bot_guesses", "🤖 {name} guesses {guess}"),
+    ("engine.replaced", "⚠️ {name}'s program {reason}, so a Medium bot plays the seat from here."),
+    ("engine.lost_turn", "⏰ {name}'s program {reason}, so it loses this turn."),
+    ("game.feedback", "Guess {guess}: Feedback (D,P) -> {score}"),
+    ("game.cracked", "{name} correctly guessed their secret code: {guess}. They finished in {place} place!"),
+    ("game.length", "Game length: {time} | Total guesses: {count}"),
+    ("game.seed", "🎲 Seed: {seed} (play with --seed {seed} to deal this game again)"),
+    ("game.hints_used", "💡 Hints used: {players}"),
+    ("game.undos_used", "↩️ Guesses taken back: {players}"),
+    ("undo.offer", "↩️ Typo? Type /undo to take {guess} back and guess again."),
+    ("undo.confirm", "{name}, may {player} take back {guess}? (y/N): "),
+    ("undo.refused", "The guess stands."),
+    ("undo.done", "↩️ {player} took back {guess}."),
+    ("game.rematch_prompt", "\nTurn this game into a tournament bracket, seeded by these results? (y/N): "),
+    ("guess.prompt", "{name}, enter your {length}-digit guess: "),
+    ("guess.prompt_hint", "{name}, enter your {length}-digit guess (or a command like /hint): "),
+    ("guess.prompt_budget", "{name}, guess {number} of {limit} (or a command like /hint): "),
+    ("guess.last_guess", "🚨🚨 LAST GUESS, {name}! Miss this one and you're out. 🚨🚨"),
+    ("game.out_of_guesses", "🛑 {name} has used all {limit} guesses and is out. Their code was {secret}."),
+    ("guess.hint", "💡 Hint: try {guess} ({count} code(s) still fit your feedback)."),
+    ("guess.hard_mode", "🚫 Hard mode: {guess} can't be the secret. {reason}."),
+    ("guess.remaining", "🔎 {count} code(s) remain consistent with your feedback."),
+    ("guess.time_up", "⏰ Time's up! {name} loses this turn."),
+    ("guess.digits", "🔢 Digits: {digits}  (· tried, ✗ not in your code)"),
+    ("guess.digits_sentence", "Digits tried so far: {tried}. Digits not in your code: {absent}."),
+    ("guess.digits_none", "none"),
+    ("guess.note_added", "📝 Note saved. Only you will see it, with /notes on your turns."),
+    ("guess.note_usage", "Type /note followed by what you want to remember, for example: /note 7 isn't first"),
+    ("guess.notes_header", "📝 Your notes:"),
+    ("guess.notes_empty", "📝 No notes yet. Type /note followed by something to remember."),
+    ("guess.notes_reminder", "📝 You have {count} note(s). Type /notes to read them."),
+    ("guess.unknown_command", "❔ {command} isn't a command here. Instead of a guess you can type:"),
+    ("guess.skipped", "⏭️ {name} passes this turn."),
+    ("guess.quit_confirm", "🚪 End the game for everyone now? Nobody still guessing will be ranked. (y/N): "),
+    ("guess.quit_cancelled", "The game goes on."),
+    ("guess.save_usage", "Type /save followed by a name for the save, such as /save friday."),
+    ("save.done", "💾 Saved the game to {path}. Carry on, or /quit and resume later with play --resume {path}."),
+    ("save.failed", "⚠️ Couldn't save the game to {path}: {error}"),
+    ("save.unavailable", "Saving isn't available in the full-screen frontend."),
+    ("save.guest", "Saving isn't available when playing on someone else's server."),
+    ("save.resumed", "💾 Resuming the saved game in round {round}, with {name} up next."),
+    ("commands.hint", "A suggested guess based on your feedback so far."),
+    ("commands.note", "Saves a private note."),
+    ("commands.notes", "Shows your notes."),
+    ("commands.history", "Shows your guesses so far."),
+    ("commands.score", "Shows the scoreboard."),
+    ("commands.skip", "Passes your turn without guessing."),
+    ("commands.save", "Saves the game so far, to carry on later with play --resume."),
+    ("commands.quit", "Ends the game for everyone."),
+    ("history.guess", "Guess"),
+    ("history.pegs", "Feedback"),
+    ("history.empty", "No guesses yet."),
+    ("history.legend", "  ● digit in the right place   ○ right digit, wrong place   · not in the code"),
+    ("history.clue_sentence", "Free clue: {guess}, {score}."),
+    ("history.guess_sentence", "Guess {number}: {guess}, {score}."),
+    ("scoreboard.title", "\n--- 📋 Scoreboard ---"),
+    ("scoreboard.playing", "{count} guess(es), best {best}"),
+    ("scoreboard.finished", "🏁 {place} in {count} guess(es)"),
+    ("announce.guessed", "📢 {name} has guessed."),
+    ("announce.feedback", "📢 {name} got {score}."),
+    ("announce.full", "📢 {name} guessed {guess} and got {score}."),
+    ("chances.title", "📊 Chances of finishing {place} (estimated):"),
+    ("start.title", "\n--- Select Starting Player ---"),
+    ("start.random", "  [0] Random selection"),
+    ("start.comeback", "  [C] Comeback draw (random, favouring players with worse recent results)"),
+    ("start.prompt", "Enter selection (0, 1, 2, ... or C): "),
+    ("start.chance", "{percent}% chance"),
+    ("start.comeback_selected", "Comeback draw selected {name} to start!"),
+    ("start.random_selected", "Randomly selected {name} to start!"),
+    ("start.selected", "Starting player is {name}."),
+    ("start.invalid", "Invalid selection. Please enter 0 for random, C for a comeback draw, or a number corresponding to a player."),
+    ("post.title", "\n--- Post-Game Menu ---"),
+    ("post.finish", "[1] Finish Game: Assign {name} rank and view final menu."),
+    ("post.continue", "[1] Continue: Remove {name} and play for next place."),
+    ("post.restart", "[2] Restart: Start a new game with current players."),
+    ("post.quit", "[3] Quit: Exit the program."),
+    ("post.review", "[4] Review: See how {name}'s guesses compared with the best available."),
+    ("post.save", "Or type /save <name> to save the game and carry on another time."),
+    ("post.prompt", "Enter your choice (1, 2, 3, or 4): "),
+    ("post.removed", "Removed {name} ({place} place) from active play."),
+    ("post.goodbye", "Thank you for playing! Goodbye."),
+    ("post.back", "\nPress Enter to return to the menu..."),
+    ("post.invalid", "Invalid input. Please enter 1, 2, 3, or 4."),
+    // Final rankings
+    ("rankings.title", "\n--- 🏁 FINAL RANKINGS ---"),
+    ("transcript.prompt", "📜 To keep a transcript, enter a file name (ending in .md for Markdown), or press Enter to skip: "),
+    ("transcript.saved", "📜 Wrote the transcript to {path}."),
+    ("transcript.failed", "⚠️ Couldn't write the transcript to {path}: {error}"),
+    ("replay.saved", "🎬 Replay kept in {path}. Watch it again with `replay {name}`."),
+    ("replay.failed", "⚠️ Couldn't keep a replay of this game: {error}"),
+    ("transcript.title", "Secret Guessing Game: Individual Secrets"),
+    ("transcript.played", "Played {time}, lasting {length}. Seed {seed}."),
+    ("transcript.guesses", "Guesses"),
+    ("transcript.rankings", "Final rankings"),
+    ("transcript.round", "Round"),
+    ("transcript.time", "Time"),
+    ("transcript.guess", "Guess"),
+    ("transcript.feedback", "Feedback"),
+    ("rankings.rank", "Rank"),
+    ("rankings.player", "Player"),
+    ("rankings.guesses", "Guesses"),
+    ("rankings.time", "Time"),
+    ("rankings.secret", "Secret"),
+    ("rankings.rating", "Rating"),
+    ("rankings.rating_sentence", "Rating {rating}."),
+    ("rankings.tie", "{place} (tie)"),
+    ("rankings.dnf", "DNF (round {round})"),
+    ("rankings.sentence", "{rank}: {name}, {guesses} guess(es), {time}, secret {secret}."),
+    // Leaderboard
+    ("leaderboard.title", "\n--- 🏆 LEADERBOARD ---"),
+    ("leaderboard.file_title", "Leaderboard"),
+    ("leaderboard.empty", "No multi-player games recorded yet."),
+    ("leaderboard.place", "Place"),
+    ("leaderboard.wins", "Wins"),
+    ("leaderboard.games", "Games"),
+    ("leaderboard.average", "Avg guesses"),
+    ("leaderboard.sentence", "{place}: {name}, {wins} win(s) in {games} game(s), {average} guesses on average, rated {rating}."),
+    ("leaderboard.kept", "Kept up to date in {path}"),
+    // Settings
+    ("settings.title", "--- ⚙️ Settings ---"),
+    ("settings.on", "ON"),
+    ("settings.off", "OFF"),
+    ("settings.remaining", "[1] Show remaining codes after each guess: {value}"),
+    ("settings.announcement", "[2] After each turn, announce to everyone: {value}"),
+    ("settings.hard_mode", "[3] Hard mode (guesses must fit all earlier feedback): {value}"),
+    ("settings.win_chances", "[4] Show win chances between turns: {value}"),
+    ("settings.coach", "[5] Coach rates each guess: {value}"),
+    ("settings.threads_auto", "[6] Solver threads: automatic ({count})"),
+    ("settings.threads", "[6] Solver threads: {count}"),
+    ("settings.language", "[7] Language: {value}"),
+    ("settings.language_auto", "automatic ({value})"),
+    ("settings.output", "[8] Symbols: {value}"),
+    ("settings.timer_off", "[9] Turn timer: off"),
+    ("settings.timer", "[9] Turn timer: {count} s"),
+    ("settings.timer_prompt", "Seconds per turn (0 for no limit): "),
+    ("settings.sound", "[10] Sound cues: {value}"),
+    ("settings.screen_reader", "[11] Screen reader friendly output: {value}"),
+    ("settings.confirm_undo", "[12] /undo needs another player's OK: {value}"),
+    ("settings.guess_limit_off", "[13] Guess limit: none"),
+    ("settings.guess_limit", "[13] Guess limit: {count} per player"),
+    ("settings.guess_limit_prompt", "Guesses per player (0 for no limit): "),
+    ("settings.theme", "[14] Colour theme: {value}"),
+    ("settings.leaderboard", "[15] Leaderboard after the final rankings: {value}"),
+    ("settings.back", "[16] Back"),
+    ("settings.prompt", "Enter choice (1-16): "),
+    ("settings.threads_prompt", "Threads for the solver (0 for one per core): "),
+    ("settings.save_failed", "⚠️ Couldn't save settings: {error}. They'll apply for this session only."),
+    ("announcement.nothing", "nothing"),
+    ("announcement.guessed", "that they guessed"),
+    ("announcement.feedback", "their feedback"),
+    ("announcement.full", "their guess and feedback"),
+    ("output.emoji", "emoji"),
+    ("output.unicode", "Unicode symbols, no emoji"),
+    ("output.ascii", "plain ASCII"),
+    ("sentence.feedback", "{digits}, {placed}"),
+    ("sentence.digits_0", "no digits correct"),
+    ("sentence.digits_1", "one digit correct"),
+    ("sentence.digits_2", "two digits correct"),
+    ("sentence.digits_3", "three digits correct"),
+    ("sentence.digits_4", "four digits correct"),
+    ("sentence.digits_5", "five digits correct"),
+    ("sentence.digits_all", "all digits correct"),
+    ("sentence.placed_0", "none in the right position"),
+    ("sentence.placed_1", "one in the right position"),
+    ("sentence.placed_2", "two in the right position"),
+    ("sentence.placed_3", "three in the right position"),
+    ("sentence.placed_4", "four in the right position"),
+    ("sentence.placed_5", "five in the right position"),
+    ("sentence.placed_all", "all in the right position"),
+    ("sound.off", "off"),
+    ("sound.bell", "terminal bell"),
+    ("sound.tones", "tones (bell if unavailable)"),
+    // Reverse mode
+    ("reverse.title", "--- 🔁 Reverse Mode (You Make the Code, the Computer Breaks It) ---"),
+    ("reverse.intro", "Think of a {length}-digit code with no repeated digits. The computer guesses; you give the usual D,P feedback."),
+    ("reverse.secret_prompt", "{name}, type your secret (it won't be shown) so the engine can check your scoring, or press Enter to keep it in your head: "),
+    ("reverse.guess_header", "GUESS {number} | 🤖 The computer guesses {guess}"),
+    ("reverse.corrected", "🚩 That's not right: {guess} against your secret scores {score}. Using the correct feedback."),
+    ("reverse.cracked", "\n🤖 Got it! Your secret is {secret}, cracked in {count} guesses."),
+    ("reverse.no_fit", "\n🚩 No code fits all of your feedback, so one of your answers must be wrong."),
+    ("reverse.conflict", "   {guess} -> {score} can't be true together with guess #{number}: {other} -> {other_score}."),
+    ("reverse.contradiction", "   {guess} -> {score} contradicts your earlier answers taken together."),
+    ("reverse.clue", "   Guess #{number}: {guess} -> {score}"),
+    ("reverse.restart_prompt", "Start over with the same secret? (y/N): "),
+    ("reverse.abandoned", "Game abandoned. Double-check your scoring next time!"),
+    ("reverse.remaining", "🔎 {count} code(s) still fit your feedback."),
+    // Time Attack
+    ("time_attack.limit_title", "\n--- Select Time Limit ---"),
+    ("time_attack.limit_2", "  [1] 2 minutes"),
+    ("time_attack.limit_5", "  [2] 5 minutes"),
+    ("time_attack.limit_10", "  [3] 10 minutes"),
+    ("time_attack.limit_prompt", "Enter selection (1, 2, or 3): "),
+    ("time_attack.limit_invalid", "Invalid selection. Please enter 1, 2, or 3."),
+    ("time_attack.title", "--- ⏱️ Solo Time Attack ---"),
+    ("time_attack.intro", "Crack as many codes in a row as you can before time runs out!"),
+    ("time_attack.start_prompt", "\nYou have {time}. Press Enter to start the clock..."),
+    ("time_attack.status", "\n⏱️ {time} left | Codes cracked: {cracked} | Guesses on this code: {guesses}"),
+    ("time_attack.too_late", "\n⏰ Time's up! That last guess came in too late."),
+    ("time_attack.cracked", "🎉 Cracked code #{number} in {guesses} guesses! Here comes the next one..."),
+    ("time_attack.results", "|       TIME ATTACK RESULTS      |"),
+    ("time_attack.summary", "{name} cracked {count} code(s) in {time}."),
+    ("time_attack.code_row", "  Code #{number} {guesses} guesses"),
+    ("time_attack.average", "Average: {average} guesses per code"),
+    ("time_attack.unfinished", "The unfinished code was {secret} ({guesses} guesses in)."),
+    // Practice
+    ("practice.no_guesses", "  (no guesses yet)"),
+    ("practice.line_row", "  {number}. {guess} -> {score}   {remaining} code(s) left"),
+    ("practice.no_lines", "No other lines yet. Undo a few guesses and try something different first."),
+    ("practice.earlier_line", "\n--- Earlier line #{number} ({status}) ---"),
+    ("practice.current_line", "\n--- Current line ({status}) ---"),
+    ("practice.solved", "solved"),
+    ("practice.unfinished", "unfinished"),
+    ("practice.commands", "Commands: /undo, /redo, /history, /compare, /reveal, /quit"),
+    ("practice.title", "--- 🧪 Practice Sandbox ---"),
+    ("practice.intro", "Take your time: rewind guesses, try another line, and compare how they went."),
+    ("practice.solved_prompt", "\nSolved! /undo to try another line, /compare to see your lines, or Enter to finish: "),
+    ("practice.guess_prompt", "\nGuess #{number} (or a command): "),
+    ("practice.undone", "↩️ Rewound to before guess #{number}."),
+    ("practice.nothing_to_undo", "Nothing to undo."),
+    ("practice.redone", "↪️ Replayed guess #{number}."),
+    ("practice.nothing_to_redo", "Nothing to redo."),
+    ("practice.secret", "The secret is {secret}."),
+    ("practice.cracked", "🎉 Cracked in {count} guesses!"),
+    // Daily challenge
+    ("daily.share_title", "Secret Guessing Game — Daily #{number} ({date})"),
+    ("daily.share_cracked", "Cracked in {count} guesses"),
+    ("daily.title", "--- 📅 Daily Challenge #{number} ---"),
+    ("daily.intro", "Everyone in the world is cracking the same code today. Good luck!"),
+    ("daily.feedback", "Guess {guess}: Feedback (D,P) -> {score}   {emoji}"),
+    ("daily.cracked", "{name} cracked today's code in {time}."),
+    ("daily.streak_one", "🔥 {days} day in a row (best {best_days}). Win streak in group games: {wins} (best {best_wins})."),
+    ("daily.streak", "🔥 {days} days in a row (best {best_days}). Win streak in group games: {wins} (best {best_wins})."),
+    ("daily.share", "\n--- Share your result (no spoilers!) ---\n"),
+    // Double-Blind
+    ("double_blind.secret_prompt", "{name}, enter your secret {length}-digit code (it won't be shown as you type): "),
+    ("double_blind.title", "--- 🕵️ Double-Blind Mode (Human Codemaker, Engine Referee) ---"),
+    ("double_blind.intro", "The codemaker picks a secret and scores every guess by hand.\nThe engine checks each piece of feedback and flags any mistakes immediately."),
+    ("double_blind.codemaker_prompt", "Enter name for the Codemaker: "),
+    ("double_blind.codebreaker_prompt", "Enter name for the Codebreaker: "),
+    ("double_blind.look_away", "\n{codebreaker}, look away while {codemaker} enters the secret!"),
+    ("double_blind.guess_header", "GUESS {number} | {name} is guessing"),
+    ("double_blind.mistake", "\n🚩 REFEREE: {name} scored this {claimed} but the correct feedback is {actual}."),
+    ("double_blind.guessed", "\n🎉🎉🎉 CODE GUESSED! 🎉🎉🎉"),
+    ("double_blind.cracked", "{codebreaker} cracked {codemaker}'s secret {secret} in {count} guesses."),
+    ("double_blind.report", "|         REFEREE REPORT         |"),
+    ("double_blind.no_mistakes", "{name} scored every guess correctly. Well refereed!"),
+    ("double_blind.mistakes", "{name} made {count} scoring mistake(s) out of {guesses} guesses."),
+    ("double_blind.length", "Game length: {time}"),
+    // Demo and Watch mode
+    ("demo.cracked", "Cracked it! 🎉"),
+    ("demo.no_digits", "No matching digits at all — that rules out {length} digits in one go!"),
+    ("demo.all_digits", "Every digit found; now it's just a matter of order."),
+    ("demo.one_left", "Only one code fits now. Watch this..."),
+    ("demo.in_place", "{count} in exactly the right spot. Nice!"),
+    ("demo.ruled_out", "That ruled out {eliminated} codes; {remaining} still possible."),
+    ("demo.bot", "  🤖 {name} ({difficulty} bot)"),
+    ("demo.bot_personality", "  🤖 {name} ({difficulty} bot, {personality})"),
+    ("demo.round", "\n========== ROUND {round} =========="),
+    ("demo.guesses", "{name} guesses {guess} -> {score}"),
+    ("demo.wins", "\n🏆 {name} wins in {count} guesses! The secret was {secret}."),
+    ("demo.title", "--- 🎲 DEMO: Watch the bots crack their codes! (press any key to play) ---"),
+    ("demo.play_prompt", "\nPress any key to start your own game!"),
+    ("watch.count_prompt", "How many bots should play (2 to 6)? "),
+    ("watch.count_invalid", "Please enter a number between 2 and 6."),
+    ("watch.difficulty_prompt", "Bot {number}: [1] Easy  [2] Medium  [3] Hard: "),
+    ("watch.choice_invalid", "Please enter 1, 2, or 3."),
+    ("watch.speed_prompt", "Speed: [1] Slow  [2] Normal  [3] Fast: "),
+    ("watch.title", "--- 📺 Watch Mode (Bots vs. Bots) ---"),
+    ("watch.intro", "Pick a line-up and sit back: every bot explains what it learns from each guess."),
+    ("watch.running", "--- 📺 Watch Mode (press any key to stop) ---"),
+    ("watch.rules", "Rules: {length}-digit codes, {repeats} ({spec})"),
+    ("watch.repeats", "repeats allowed"),
+    ("watch.no_repeats", "no repeated digits"),
+    ("watch.menu_prompt", "\nPress any key to return to the menu..."),
+    ("watch.stopped", "\nStopped watching."),
+    // Chat Plays
+    ("chat.seconds_prompt", "{label} in seconds [{default}]: "),
+    ("chat.seconds_invalid", "Please enter a whole number of seconds."),
+    ("chat.settings_title", "\n--- Vote Settings (press Enter to keep a default) ---"),
+    ("chat.window", "Vote window"),
+    ("chat.min_votes_prompt", "Minimum distinct voters per guess [{default}]: "),
+    ("chat.min_votes_invalid", "Please enter a number of at least 1."),
+    ("chat.user_cooldown", "Per-user cooldown"),
+    ("chat.round_cooldown", "Cooldown after each guess"),
+    ("chat.title", "--- 💬 Chat Plays Mode (Crowd vs. One Secret) ---"),
+    ("chat.intro", "Chat lines look like `user: !guess 1234` (or just `user: 1234`).\nEach voting window picks the most popular guess once enough people have voted."),
+    ("chat.feed_prompt", "Chat feed file or FIFO (Enter to read chat from this terminal): "),
+    ("chat.feed_failed", "Couldn't open the chat feed: {error}"),
+    ("chat.open", "\nA secret code has been generated. Voting is open!"),
+    ("chat.voted", "🗳️ {name} voted {guess}"),
+    ("chat.feed_closed", "\nThe chat feed closed before the code was cracked.\nThe secret was {secret}."),
+    ("chat.waiting", "Waiting for more voters ({votes} of {needed} needed)..."),
+    ("chat.crowd_guess", "CROWD GUESS {number} | {guess} ({votes} of {total} votes)"),
+    ("chat.feedback", "Feedback (D,P) -> {score}"),
+    ("chat.cracked", "\n🎉🎉🎉 CHAT CRACKED THE CODE! 🎉🎉🎉"),
+    ("chat.solved", "Solved in {count} guesses and {time}."),
+    ("chat.winners", "Winning voters: {names}"),
+    ("chat.next_vote", "Next vote opens in {seconds}s."),
+    ("vote.user_cooldown", "slow down, you can vote again in {seconds}s"),
+    ("vote.round_cooldown", "voting reopens in {seconds}s"),
+    // Correspondence games
+    ("turn.tampered_secret", "the secret dealt to {name}"),
+    ("turn.tampered_guess", "guess #{number}"),
+    ("turn.tampered_score", "the score of {name}'s guess #{number}"),
+    ("turn.read_failed", "Couldn't read the game {path}: {error}"),
+    ("turn.unplayable", "Can't play {path}: {error}."),
+    ("turn.first_turn", "{name}, this is your first turn. Choose a passphrase for this game: it seals the secret you deal {other}, and you'll need it on every turn. Nobody else should know it."),
+    ("turn.passphrase", "Passphrase: "),
+    ("turn.passphrase_empty", "The passphrase can't be empty."),
+    ("turn.passphrase_again", "Type it again: "),
+    ("turn.passphrase_mismatch", "Those didn't match. Let's try again."),
+    ("turn.passphrase_prompt", "{name}, your passphrase: "),
+    ("turn.passphrase_wrong", "That isn't the passphrase for this seat."),
+    ("turn.passphrase_locked", "Too many wrong passphrases for {name}. Nothing was changed."),
+    ("turn.cracked_status", "🔓 cracked it in {count} guess(es)"),
+    ("turn.guesses_status", "{count} guess(es)"),
+    ("turn.over", "The game is over. Final places:"),
+    ("turn.place", "  {place}  {name}  {count} guess(es), secret {secret}"),
+    ("turn.title", "--- 📜 Correspondence game: {names} ---"),
+    ("turn.whose_turn", "It's {name}'s turn."),
+    ("turn.tampered", "{path} has been changed outside the game: {what} isn't what {name} wrote. Play can't go on from this copy, so find one that hasn't been touched."),
+    ("turn.unreadable_secret", "The secret dealt to {name} can't be read."),
+    ("turn.scored", "You scored {name}'s guess #{number}."),
+    ("turn.cracked_other", "🔓 That cracked {name}'s code!"),
+    ("turn.cracked_own", "🔓 You cracked your code in {count} guess(es), so this turn only scores {name}'s."),
+    ("turn.your_guesses", "Your guesses so far:"),
+    ("turn.guess_prompt", "Your guess: "),
+    ("turn.write_back_failed", "Couldn't write the game back to {path}: {error}"),
+    ("turn.saved", "Saved. Pass {path} on to {name}; their turn is next."),
+    ("turn.no_game", "There's no game at {path} yet."),
+    ("turn.start_prompt", "Start a new correspondence game there? (y/N): "),
+    ("turn.players_prompt", "Players, in turn order, separated by commas: "),
+    ("turn.player_count", "A correspondence game has 2 to 8 players."),
+    ("turn.write_failed", "Couldn't write the game to {path}: {error}"),
+    ("turn.first_player", "{name} plays first."),
+    // Tournament
+    ("tournament.final", "Final"),
+    ("tournament.semi_finals", "Semi-finals"),
+    ("tournament.quarter_finals", "Quarter-finals"),
+    ("tournament.round", "Round {number}"),
+    ("tournament.bracket", "|        TOURNAMENT BRACKET        |"),
+    ("tournament.bye", "  {mark} {name} (bye)"),
+    ("tournament.pairing", "  {mark} {home} vs {away_mark} {away}"),
+    ("tournament.match_start", "⚔️ {home} vs {away} — {first} moves first."),
+    ("tournament.turn_header", "ROUND {round} | {name}'s Guess"),
+    ("tournament.cracked", "\n🎉 {name} cracked their code!"),
+    ("tournament.draw", "Both players cracked their codes in round {round}! Press Enter to replay with new secrets..."),
+    ("tournament.count_prompt", "Enter the number of entrants (2 to 16): "),
+    ("tournament.count_invalid", "Please enter a number between 2 and 16."),
+    ("tournament.champion", "\n🏆🏆🏆 {name} is the tournament champion! 🏆🏆🏆"),
+    ("tournament.match_prompt", "\n{round}: {home} vs {away}. Press Enter to start the match..."),
+    ("tournament.advances", "\n{name} advances from the {round_lower}!"),
+    ("tournament.rematch_title", "--- 🏆 Rematch Tournament ---"),
+    ("tournament.seeded_by", "Seeded by tonight's finishing order:"),
+    ("tournament.seed", "  Seed {number}: {name}"),
+    ("tournament.bots_out", "(Bots sit this one out: {names})"),
+    ("tournament.byes", "{count} bye(s) in the opening round for the top seeds."),
+    ("tournament.title", "--- 🏆 Knockout Tournament ---"),
+    ("tournament.intro", "Entrants are paired into head-to-head games. Winners advance until one champion remains."),
+    ("tournament.name_prompt", "Enter name for Entrant {number} (seed {number}): "),
+    ("tournament.shuffle_prompt", "Shuffle the seeding? (y/N): "),
+    ("tournament.entrant_byes", "{count} entrants means {byes} bye(s) in the opening round for the top seeds."),
+    // Stats
+    ("stats.no_home", "⚠️ No home directory found; stats will only be kept for this session."),
+    ("stats.this_account", "this account"),
+    ("stats.split_stores", "📁 Solo games are saved for '{user}'; group games go to the club store."),
+    ("stats.migrated", "📁 Moved your saved stats to {path}."),
+    ("stats.personal", "personal"),
+    ("stats.club", "club"),
+    ("stats.open_failed", "⚠️ {error} ({store} store). Those stats will only be kept for this session."),
+    ("stats.needs_sqlite", "⚠️ {path} is an SQLite database, which needs a build with `--features sqlite` ({store} store). Those stats will only be kept for this session."),
+    ("stats.locked_title", "\n--- 🔒 Encrypted Stats Store ({store}) ---"),
+    ("stats.locked_intro", "The stats file at {path} is protected with a passphrase."),
+    ("stats.unlock_passphrase", "[1] Enter the passphrase"),
+    ("stats.unlock_recovery", "[2] Use a recovery code (then choose a new passphrase)"),
+    ("stats.unlock_skip", "[3] Play without saving stats this session"),
+    ("stats.unlock_fresh", "[4] Start a fresh store (the locked file is kept, renamed)"),
+    ("stats.unlock_prompt", "Enter your choice (1-4): "),
+    ("stats.passphrase", "Passphrase: "),
+    ("stats.unlocked", "🔓 Stats unlocked."),
+    ("stats.failed", "❌ {error}."),
+    ("stats.recovery_prompt", "Recovery code: "),
+    ("stats.unlocked_recovery", "🔓 Stats unlocked with the recovery code."),
+    ("stats.passphrase_saved", "New passphrase saved. Your recovery code still works."),
+    ("stats.passphrase_save_failed", "⚠️ Couldn't save the new passphrase: {error}."),
+    ("stats.archived", "The locked file was moved to {path}."),
+    ("stats.session_only", "❌ {error}. Stats will only be kept for this session."),
+    ("stats.unlock_invalid", "Invalid input. Please enter a number from 1 to 4."),
+    ("stats.new_passphrase", "Choose a passphrase: "),
+    ("stats.passphrase_again", "Type it again: "),
+    ("stats.passphrase_empty", "The passphrase can't be empty."),
+    ("stats.passphrase_mismatch", "Those didn't match. Please try again."),
+    ("stats.save_failed", "⚠️ Couldn't save stats: {error}."),
+    ("stats.code_guessed", "🎉🎉🎉 CODE GUESSED! 🎉🎉🎉"),
+    ("stats.first_win", "🥇 {name}'s FIRST EVER WIN! Welcome to the winners' club!"),
+    ("stats.personal_best", "📈 NEW PERSONAL BEST for {name}! (previous best: {previous} guesses with {rules})"),
+    ("stats.streak_ended", "🛑 {name} ends {rival}'s {streak}-game winning streak!"),
+    ("stats.no_games", "No games recorded yet."),
+    ("stats.row", "| {name} | Games: {games} | Wins: {wins} | Avg guesses: {average} | Last: {last} | Win streak: {streak} (best {best_streak}) | Days in a row: {days} (best {best_days})"),
+    ("stats.best_row", "  {name}: {guesses} guesses in {mode} ({rules})"),
+    ("stats.bests", "Personal bests:"),
+    ("stats.stored_in", "Stored in {path}"),
+    ("stats.stored_in_encrypted", "Stored in {path} (encrypted)"),
+    ("stats.session_store", "Stats are only being kept for this session."),
+    ("stats.title_user", "STATS FOR {user}"),
+    ("stats.title_personal", "PERSONAL STATS"),
+    ("stats.title_club", "CLUB STATS"),
+    ("stats.menu_title", "\n--- Stats Menu ---"),
+    ("stats.menu_personal", "[1] Personal store encryption"),
+    ("stats.menu_club", "[2] Club store encryption"),
+    ("stats.menu_back", "[0] Back"),
+    ("stats.choice_prompt", "Enter your choice: "),
+    ("stats.encryption_title", "\n--- Encryption ---"),
+    ("stats.database", "This store is a database, which can't be encrypted. Keep stats in the flat file to protect them with a passphrase."),
+    ("stats.change_passphrase", "[1] Change the passphrase"),
+    ("stats.turn_off", "[2] Turn off encryption"),
+    ("stats.turn_on", "[1] Protect these stats with a passphrase"),
+    ("stats.encrypted", "🔒 Stats are now encrypted."),
+    ("stats.recovery_code", "\nRECOVERY CODE: {code}"),
+    ("stats.recovery_advice", "Write this down and keep it somewhere safe (e.g. with the teacher).\nIt unlocks the stats if the passphrase is forgotten, and it won't be shown again."),
+    ("stats.recovery_written", "\nPress Enter once you've written it down..."),
+    ("stats.decrypt_prompt", "Store these stats unencrypted again? (y/N): "),
+    ("stats.leaderboard_failed", "⚠️ Couldn't update the leaderboard at {path}: {error}."),
+    // Networked games
+    ("net.lost_held", "📡 {name} lost their connection. Their seat is held for {seconds} s."),
+    ("net.lost", "📡 {name} lost their connection."),
+    ("net.no_seat", "there's no seat waiting for you in this game"),
+    ("net.you_left", "you left this game"),
+    ("net.welcome_back", "Welcome back! Your guesses so far:"),
+    ("net.back", "🔌 {name} is back."),
+    ("net.under_way", "The game is under way, so you'll see it from here on."),
+    ("net.started", "the game has already started"),
+    ("net.not_your_turn_host", "It isn't your turn. Type /host for what you can do as the host."),
+    ("net.timed_out_you", "⏳ You didn't answer in time, so your turn is skipped."),
+    ("net.timed_out", "⏳ {name} didn't answer in {minutes} minutes, so their turn is skipped."),
+    ("net.waiting_rejoin", "⏳ Waiting up to {seconds} s for {name} to come back..."),
+    ("net.not_back", "⏭ {name} didn't come back in time, so their turns are skipped unless they do."),
+    ("net.no_discovery", "(This game won't show up in LAN searches: {error}.)"),
+    ("net.left_you", "You've left the game."),
+    ("net.left", "👋 {name} has left the game."),
+    ("net.guess_prompt", "Round {round}, your guess (/say to chat, /quit to leave): "),
+    ("net.listen_failed", "Couldn't listen on port {port}: {error}"),
+    ("net.hosting", "--- 🌐 Hosting Individual Secrets ---"),
+    ("net.join_hint", "Players can find this game with `join`, or join with: join <this machine's address>:{port}"),
+    ("net.begins", "The game begins! Seats: {names}. Everyone has their own secret to crack."),
+    ("net.sealed", "🔒 Every secret is sealed, and the seals are checked against the secrets when the game ends."),
+    ("net.handover", "🌐 If the host drops out, a spectator takes the game over. Their machine is sent a copy of the game, every secret included, so it's trusted as much as the host's. Players are never sent it."),
+    ("net.handover_needs_spectators", "--handover hands the game to a spectator, so it needs --spectators of at least 1."),
+    ("net.no_successor", "🌐 Nobody is watching, so nobody can take the game over if you drop out. Handover starts once a spectator joins."),
+    ("net.spectator_at", "the spectator at {address}"),
+    ("net.hosting_unseated", "You're hosting this game without a seat, so only the host commands work here. Type /host to see them."),
+    ("net.turn", "Round {round}: {name}'s turn."),
+    ("net.watch_guess", "{name} guessed {guess}: {score}"),
+    ("net.cracked", "🔓 {name} cracked their code in {count} guess(es)!"),
+    ("net.guessed", "{name} made guess #{number}."),
+    ("net.seed", "Seed: {seed}"),
+    ("net.host_commands", "As the host you can type these at any time: /kick <name> removes a player, /delay <seconds> waits before each turn, /hold and /resume stop and restart the game, /end reveals the secrets and ends it, and /host lists these again."),
+    ("net.held", "⏸ The game is on hold until the host resumes it."),
+    ("net.delay_off", "⏱ The host has done away with the wait before each turn."),
+    ("net.delay_on", "⏱ The host has set a {seconds} s wait before each turn."),
+    ("net.delay_usage", "/delay takes a number of seconds from 0 to {max}."),
+    ("net.already_held", "The game is already on hold. Type /resume to carry on."),
+    ("net.hold", "⏸ The host has put the game on hold."),
+    ("net.not_held", "The game isn't on hold."),
+    ("net.resumed", "▶ The host has resumed the game."),
+    ("net.ended", "🏁 The host has ended the game here, so every secret is revealed now."),
+    ("net.kick_unknown", "There's nobody called '{name}' at the table. Type /kick <name>."),
+    ("net.kick_self", "That's your own seat. Type /quit on your turn to leave the game."),
+    ("net.kick_gone", "{name} has already left the game."),
+    ("net.kicked_you", "🚪 The host has removed you from the game."),
+    ("net.kicked", "🚪 The host has removed {name} from the game."),
+    ("net.backup_unreadable", "The host's backup of the game doesn't read, so the game can't go on."),
+    ("net.take_over_failed", "Couldn't take the game over: {error}."),
+    ("net.taken_over_you", "🌐 {name} has gone, so you're hosting the game from here. Everyone else's seat is held for {seconds} s while they come back."),
+    ("net.searching", "Looking for games on the local network..."),
+    ("net.search_failed", "Couldn't search the local network: {error}."),
+    ("net.none_found", "No games found. Ask the host for their address and use: join <host>[:<port>]"),
+    ("net.games", "Games waiting for players:"),
+    ("net.needs_newer", " (needs a newer build)"),
+    ("net.host_needs_newer", " (the host needs a newer build)"),
+    ("net.game_row", "  {number}. {host}'s game at {address} - {rules} - {seats} open seat(s){note}"),
+    ("net.pick_prompt", "Pick a game (Enter to cancel): "),
+    ("net.pick_invalid", "Please enter a number from 1 to {max}."),
+    ("net.seal_changed", "⚠️ The host has sent a different seal on {name}'s secret than at the start. The secret may have been changed."),
+    ("net.no_reveals", "⚠️ The game ended without the host revealing any secrets, so none of them can be checked against their seals."),
+    ("net.not_revealed", "{name}'s secret wasn't revealed"),
+    ("net.wrong_secret", "{name}'s secret, {secret}, isn't the one that was sealed at the start"),
+    ("net.never_sealed", "{name}'s secret was never sealed"),
+    ("net.wrong_feedback", "the feedback on your guess {guess} doesn't match your secret"),
+    ("net.seals_ok", "✅ Every secret matches its seal from the start of the game."),
+    ("net.seals_ok_feedback", "✅ Every secret matches its seal from the start of the game, and all your feedback was scored right."),
+    ("net.seals_bad", "⚠️ The host's secrets don't check out:"),
+    ("net.watching", "--- 🌐 Watching {address} ---"),
+    ("net.joining", "--- 🌐 Joining {address} ---"),
+    ("net.connection_lost", "Lost the connection to the host ({error})."),
+    ("net.room_prompt", "Room code (the host has it): "),
+    ("net.rejoin_prompt", "You still have a seat in a game here as {name}. Take it back? (Y/n): "),
+    ("net.closed", "The connection to the host closed."),
+    ("net.checking_host", "📡 Lost the connection to the host. Seeing whether it's really gone..."),
+    ("net.reconnecting", "📡 Lost the connection to the host. Trying to get back in..."),
+    ("net.taken_over", "🌐 {name} has taken the game over."),
+    ("net.cant_join", "Can't join this game: {error}"),
+    ("net.seated", "Seated as {name}."),
+    ("net.refused", "The host turned us away: {reason}."),
+    ("net.successor", "🌐 If the host goes, {name} takes the game over."),
+    ("net.successor_you", "🌐 If the host goes, you take the game over."),
+    ("net.watch_only", "You're only watching, so there's nothing to type. Type /quit to stop watching."),
+    ("net.say_usage", "Type your message after /say, as in: /say good luck!"),
+    ("net.not_your_turn", "It isn't your turn yet. Type /say <message> to chat."),
+    ("net.unreachable", "Couldn't reach a host at {address}: {error}"),
+    ("net.rejoin_failed", "Couldn't get back into the game. Once the host is reachable, `join {address}` offers the seat back if the game is still on."),
+    // Rooms
+    ("room.version_mismatch", "the host speaks version {host} of the protocol and you speak version {yours}; update whichever is older"),
+    ("room.wrong_code", "that isn't this room's code"),
+    ("room.no_spectators", "this game doesn't take spectators"),
+    ("room.spectators_full", "the game already has its {cap} spectator(s)"),
+    ("room.somewhere", "somewhere"),
+    ("room.spectator_arrived", "👀 A spectator is watching from {address} ({count} of {cap})."),
+    ("room.too_many", "the host has too many connections open; try again in a while"),
+    ("room.title", "--- 🚪 Room {code} ---"),
+    ("room.game", "Game: Individual Secrets, {rules}"),
+    ("room.host_seat", "  Seat 1: {name} (host)"),
+    ("room.seat_taken", "  Seat {seat}: {name}"),
+    ("room.seat_ready", "  Seat {seat}: {name}, ready"),
+    ("room.seat_open", "  Seat {seat}: open"),
+    ("room.watching", "  Watching: {count} of {cap}"),
+    ("room.not_started", "the game hasn't started yet, so join with the room code"),
+    ("room.left", "👋 {name} left the room."),
+    ("room.full", "the room is full"),
+    ("room.joined", "🪑 {name} joined from {address} and took seat {seat}."),
+    ("room.ready", "✅ {name} is ready."),
+    ("room.unready", "{name} isn't ready after all."),
+    ("room.moved", "🪑 {name} moved to seat {seat}."),
+    ("room.no_open_seats", "There are no open seats to move to."),
+    ("room.open_seats", "Open seats: {seats}."),
+    ("room.left_you", "You've left the room."),
+    ("room.no_answer", "{name} didn't answer in time and is left out."),
+    ("room.pass_code", "Pass the room code {code} on to the players you're expecting."),
+    ("room.start_prompt", "Everyone here is ready. Start the game? (y/N, no waits for more players): "),
+    ("room.not_all_ready", "Not everyone is ready any more."),
+    ("room.watch_help", "You're watching. Every guess shows up here with its score as it's made, but nobody's secret does. Type /quit to stop watching."),
+    ("room.help", "While you wait, type `seat <n>` to move to an open seat, `ready` when you're set to play (again to take it back), /say <message> to chat, or /quit to leave."),
+    // Session
+    ("session.trivia", "|        SESSION TRIVIA        |"),
+    ("session.secrets", "Secrets played this session: {count}"),
+    ("session.hottest", "🔥 Hottest digit: {digits} (in {count} secret(s))"),
+    ("session.every_digit", "🧊 Every digit showed up at least once."),
+    ("session.never", "🧊 Never appeared: {digits}"),
+    ("session.first_digit", "🚪 Most common first digit: {digits} ({count} time(s))"),
+    ("session.fastest", "⚡ Fastest solve: {name} cracked {secret} in {count} guess(es) ({mode})"),
+    ("session.all_time_best", "   ({name}'s best winning game in the stats: {count} guess(es))"),
+    ("session.nobody", "⚡ Nobody cracked a code this session. Next time!"),
+    ("session.longest", "🐢 Longest solve: {name} needed {count} guesses for {secret} ({mode})"),
+    ("session.summary", "|       SESSION SUMMARY        |"),
+    ("session.games", "Games played: {count}"),
+    ("session.placements", "Placements:"),
+    ("session.did_not_finish", "did not finish"),
+    ("session.placement_row", "  {name}: {places}"),
+    ("session.total_guesses", "Total guesses: {count}"),
+    ("session.fastest_game", "⚡ Fastest solve: {name} in {count} guess(es), game {game}"),
+    ("session.longest_game", "🐢 Longest game: game {game}, {time}"),
+    // Coach
+    ("coach.excellent", "Excellent probe: about as informative as a guess can be here."),
+    ("coach.good", "Good guess: the feedback splits the possibilities well."),
+    ("coach.decent", "Decent, but a different guess could have told you more."),
+    ("coach.weak", "Weak probe: many of the possible codes would all give the same feedback."),
+    ("coach.poor", "This guess could barely tell the remaining codes apart."),
+    ("coach.rating", "🎓 Coach: {rating}/100. {comment}"),
+    // Spectator view
+    ("spectate.write_failed", "⚠️ Couldn't write to the spectator feed ({error}). Spectators won't see the rest of this session."),
+    ("spectate.started", "🎲 A new game has started!"),
+    ("spectate.took_guess", "{name} took guess #{number}."),
+    ("spectate.sat_out", "⏸️ {name} sat out a turn (handicap)."),
+    ("spectate.timed_out", "⏰ {name} ran out of time."),
+    ("spectate.out", "🛑 {name} is out of guesses."),
+    ("spectate.finished", "🏁 {name} cracked their code in {count} guesses and took {place} place!"),
+    ("spectate.over", "The game is over. Waiting for the next one..."),
+    ("spectate.title", "--- 📺 Spectator View ---"),
+    ("spectate.waiting", "Waiting for a game to start..."),
+    ("spectate.final", "Final standings"),
+    ("spectate.round", "Round {round}"),
+    ("spectate.place", "🏁 {place}"),
+    ("spectate.out_status", "🛑 out of guesses"),
+    ("spectate.best", "best {score}"),
+    ("spectate.no_guesses", "no guesses yet"),
+    ("spectate.row", "  {name}  {count} guess(es)  {bar}  {status}"),
+    ("spectate.recent", "Recent events:"),
+    ("spectate.open_failed", "Couldn't open the spectator feed {path}: {error}"),
+    ("spectate.read_failed", "Couldn't read the spectator feed: {error}"),
+    // Full-screen frontend
+    ("tui.bot_guesses", "{name}'s guesses"),
+    ("tui.your_guesses", "Your guesses"),
+    ("tui.no_guesses", "(no guesses yet)"),
+    ("tui.scoreboard", "Scoreboard"),
+    ("tui.place", "🏁 {place}"),
+    ("tui.dnf", "DNF"),
+    ("tui.guesses", "{count} guess(es)"),
+    ("tui.title", "─ Round {round} · {name}'s turn "),
+    ("tui.pass", "\n\n   🔒 Pass the keyboard to {name}."),
+    ("tui.pass_prompt", "   {name}, press Enter when nobody else is looking..."),
+    ("tui.screen_reader", "The full-screen frontend isn't suited to screen readers, so here's the classic game instead."),
+    ("tui.header", "--- 🖥️ Individual Secrets (Full Screen) ---"),
+    ("tui.intro", "Each player races to crack their own hidden code. Set up the table below."),
+    ("tui.begin", "Let the guessing begin!"),
+    ("tui.sits_out", "⏸️ {name} sits out this turn (handicap)."),
+    ("tui.bot_guessed", "🤖 {name} guessed {guess} and got {score}."),
+    ("tui.cracked", "🎉 Cracked in {count} guesses! {place} place."),
+    ("tui.cracked_public", "🏁 {name} cracked their code and took {place} place!"),
+    ("tui.remaining", "{count} code(s) still fit your feedback."),
+    ("tui.end_turn", "Press Enter to end your turn..."),
+    ("common.game_length", "Game length: {time}"),
+    // Replays
+    ("replay.no_data_dir", "There's no data directory, so no replays are kept."),
+    ("replay.none", "No replays yet. Every Individual Secrets game that reaches the final rankings is kept in {dir}."),
+    ("replay.list", "Replays in {dir}:"),
+    ("replay.list_hint", "Watch one with `replay <name>`, adding `--step` to move on with Enter."),
+    ("replay.step_prompt", "(Enter for the next move) "),
+    ("replay.read_failed", "Couldn't read the replay {path}: {error}"),
+    ("replay.unplayable", "Can't play {path}: {error}."),
+    ("replay.title", "--- 🎬 Replay (seed {seed}) ---"),
+    ("replay.players", "Players: {names}. Each secret stays hidden until it's cracked."),
+    ("replay.round", "\nRound {round}"),
+    ("replay.cracked", "  🔓 {name} cracked their secret, {secret}."),
+    // Solver assistant
+    ("solve.one_left", "🔎 Only one code fits: {code}."),
+    ("solve.listed", "🔎 {count} codes fit: {codes}"),
+    ("solve.count", "🔎 {count} codes fit."),
+    ("solve.title", "--- 🧠 Solver Assistant ({strategy}) ---"),
+    ("solve.intro", "Type each guess you play with the D,P feedback it got, such as \"0123 2,1\"."),
+    ("solve.intro_more", "Feedback alone means you played the suggestion. /undo takes back the last line and /quit leaves."),
+    ("solve.suggestion", "\n💡 Suggested guess #{number}: {guess}"),
+    ("solve.prompt", "Guess and feedback: "),
+    ("solve.undone", "↩️ Took back {guess} -> {score}."),
+    ("solve.nothing_to_undo", "There's nothing to take back yet."),
+    ("solve.cracked", "🎉 Cracked with {guess} in {count} guesses."),
+    ("solve.no_fit", "🚩 No code fits {guess} -> {score} together with the earlier feedback, so it was left out."),
+    // Profile export and import
+    ("profile.nothing_to_export", "There's no profile or saved result for '{name}' here."),
+    ("profile.bad_file_name", "'{name}' can't be used as a file name; give one after the name."),
+    ("common.write_failed", "Couldn't write {path}: {error}"),
+    ("profile.exported", "Exported {name}'s profile, {achievements} achievement(s) and {results} result(s) to {path}."),
+    ("profile.import_hint", "On the other machine, run: profile import {path}"),
+    ("profile.other_preferences", "{name} already has a profile here, with other preferences."),
+    ("profile.here", "  Here:     {preferences}"),
+    ("profile.imported_preferences", "  Imported: {preferences}"),
+    ("profile.replace_prompt", "Use the imported preferences instead? (y/N): "),
+    ("profile.conflict", "  {when} ({mode}): here {here_place} in {here_guesses} guesses, imported {place} in {guesses} guesses"),
+    ("profile.save_results_failed", "Couldn't save the imported results: {error}."),
+    ("profile.added", "Added {added} result(s), skipping {duplicates} already here."),
+    ("profile.conflicts", "{count} result(s) disagree with this machine's record of the same game, so this machine's were kept:"),
+    ("common.read_failed", "Couldn't read {path}: {error}"),
+    ("profile.cant_import", "Can't import {path}: {error}."),
+    ("profile.bad_name", "'{name}' can't be kept as a profile here; import it under another name with --as."),
+    ("profile.import_save_failed", "Couldn't save {name}'s profile: {error}."),
+    ("profile.imported", "Imported {name}'s profile ({preferences}); {count} achievement(s) in all."),
+    // Bot remarks
+    ("bot.digit_list", "{rest} and {last}"),
+    ("bot.solved", "Every digit in place. Got it!"),
+    ("bot.none_match", "None of {digits} were matches, so I'm dropping all of them."),
+    ("bot.drop_one", "{digits} can't be in my code given what I've seen, so I'm dropping it."),
+    ("bot.drop_many", "{digits} can't be in my code given what I've seen, so I'm dropping them."),
+    ("bot.all_matched", "Every digit matched, so every other digit is out."),
+    ("bot.rules_out", "That also rules out {digits}."),
+    ("bot.confirmed", "{digits} must be in my code somewhere."),
+    ("bot.fixed", "{digit} has to be in position {position}."),
+    ("bot.one_left", "Only one code fits everything I've seen now!"),
+    ("bot.nothing_certain", "Nothing certain about single digits yet, but only {count} codes still fit."),
+    ("bot.remark", "💬 {name}: {remark}"),
+    // Analysis
+    ("analyze.trace", "--- {strategy} solve for secret {secret} ---"),
+    ("analyze.step", "Guess {number}: {guess} -> {score} | {count} candidate(s) left"),
+    ("analyze.overview", "--- {strategy} solver over all {count} secrets ---"),
+    ("analyze.building", "(Building the full decision tree, this may take a moment...)"),
+    ("analyze.depth", "  {count} guess(es): {secrets} secrets"),
+    ("analyze.summary", "Average: {average} guesses | Worst case: {worst} guesses"),
+    ("analyze.comparison", "--- Strategy comparison over all {count} secrets ---"),
+    ("analyze.building_all", "(Building every decision tree, this may take a moment...)"),
+    ("analyze.guesses", "Guesses"),
+    ("analyze.average", "Average"),
+    ("analyze.worst", "Worst"),
+    ("analyze.solver_grade", "Solver-grade play"),
+    ("analyze.sharp", "Sharp"),
+    ("analyze.solid", "Solid"),
+    ("analyze.getting_there", "Getting there"),
+    ("analyze.room_to_improve", "Room to improve"),
+    ("analyze.review", "\n--- 🔬 Guess Review: {name} ---"),
+    ("analyze.reviewing", "(Comparing each guess with the best available, this may take a moment...)"),
+    ("analyze.col_turn", "Turn"),
+    ("analyze.col_guess", "Guess"),
+    ("analyze.col_feedback", "Feedback"),
+    ("analyze.col_before", "Before"),
+    ("analyze.col_after", "After"),
+    ("analyze.col_avg_left", "Avg left"),
+    ("analyze.col_best", "Best"),
+    ("analyze.col_rating", "Rating"),
+    ("analyze.same", "(same)"),
+    ("analyze.efficiency", "\nOverall efficiency: {percent}% ({rating})"),
+    ("analyze.avg_left_note", "\"Avg left\" is how many codes a guess leaves on average; 100% means it was as good as the best guess."),
+    ("analyze.too_many", "There are {count} secrets of this length, too many to build every decision tree. Give a code to trace instead, or play with --length 4 or shorter."),
+    ("simulate.title", "\n--- {strategy} ({count} games, {seconds}s) ---"),
+    ("simulate.depth", "  {count} guess(es): {games}  {bar}"),
+    ("simulate.start", "Simulating {count} game(s) per strategy (seed {seed})."),
+    // Servers and tools
+    ("common.create_failed", "Couldn't create {path}: {error}"),
+    ("server.unknown_address", "an unknown address"),
+    ("server.full", "The server is full. Try again later."),
+    ("telnet.serving", "--- 🌐 Serving Individual Secrets as plain text ---"),
+    ("telnet.connect_hint", "Players connect with `nc <this machine's address> {port}` or telnet. Press Ctrl+C to stop."),
+    ("telnet.connected", "🔌 A player connected from {address}."),
+    ("telnet.disconnected", "👋 The player from {address} disconnected."),
+    ("telnet.line_too_long", "That line is too long, so the game has been closed."),
+    ("telnet.idle", "Nothing was typed for ten minutes, so the game has been closed."),
+    ("ssh.no_data_dir", "⚠ There's no data directory, so the host key is new each time the server starts."),
+    ("ssh.bad_host_key", "{path} isn't a host key this server made. Delete it to make a new one."),
+    ("ssh.unknown_command", "'{name}' isn't something this server runs. Try one of: {names}."),
+    ("ssh.bad_option", "{name} doesn't take '{option}' here."),
+    ("ssh.serving", "--- 🔒 Serving Individual Secrets over SSH ---"),
+    ("ssh.connect_hint", "Players connect with `ssh -p {port} play@<this machine's address>`. Press Ctrl+C to stop."),
+    ("ssh.fingerprint", "The host key's fingerprint is {fingerprint}."),
+    ("ssh.connected", "🔌 {name} connected from {address}."),
+    ("ssh.disconnected", "👋 {name} ({address}) disconnected."),
+    ("api.begins", "🎲 Game {id} begins: {names}."),
+    ("api.over", "🏁 Game {id} is over after {count} round(s)."),
+    ("api.open", "🌐 Game {id} is open for {seats} players."),
+    ("api.abandoned", "⏳ Game {id} was abandoned and has been cleared away."),
+    ("api.seated", "👋 {name} took a seat in game {id} ({count} of {seats})."),
+    ("api.left", "🚪 {name} left game {id}."),
+    ("api.cracked", "🔓 {name} cracked their code in game {id}."),
+    ("api.serving", "--- 🌐 Serving Individual Secrets over HTTP ---"),
+    ("api.hint", "The API is at http://<this machine's address>:{port}/games. Press Ctrl+C to stop."),
+    ("export.games", "Wrote {count} result rows to {path}."),
+    ("export.skipped", "⚠️ Skipped a replay. {error}"),
+    ("export.turns", "Wrote {count} turn rows to {path}."),
+    ("json.log_failed", "⚠️ Couldn't write to the event log ({error}). The rest of this session won't be logged."),
+    ("config.write_failed", "Couldn't write the defaults file: {error}"),
+    ("config.written", "Wrote the defaults file to {path}. Remove the # in front of a setting to use it."),
+    ("config.unknown_theme", "Unknown theme '{name}'. Available themes: {names}"),
+    ("config.default_theme_missing", "⚠️ Ignoring the default theme (there's no theme called {name})."),
+    ("config.event_log_failed", "Couldn't open the event log {path}: {error}"),
+    // Error messages
+    ("net.no_greeting", "The host didn't greet us. It isn't a game host, or it's an older build that needs updating."),
+    ("net.version_mismatch", "The host speaks version {theirs} of the game protocol and this build speaks version {ours}. Update whichever is older."),
+    ("net.not_a_host", "That isn't a game host, or it's an older build that needs updating."),
+    ("wire.unknown", "unknown message '{tag}'"),
+    ("wire.malformed", "a '{tag}' message that doesn't read properly"),
+    ("save.read_failed", "Couldn't read the saved game {path}: {error}"),
+    ("save.cant_resume", "Can't resume from {path}: {error}."),
+    ("snapshot.damaged", "the snapshot is damaged ({reason})"),
+    ("snapshot.wrong_kind", "expected a {expected} snapshot but found a {found} one"),
+    ("store.io", "couldn't access the stats file: {error}"),
+    ("store.corrupt", "the stats file is damaged ({reason})"),
+    ("store.wrong_passphrase", "that passphrase or recovery code is not correct"),
+    ("store.session_only", "stats are not being saved this session"),
+    ("store.unsupported", "this kind of stats store doesn't support {feature}"),
+    ("store.encryption", "encryption"),
+    ("store.database", "the stats database reported a problem ({reason})"),
+    ("rules.incompatible", "this game needs features this build doesn't support:"),
+    ("rules.needs_variant", "the '{id}' game variant"),
+    ("rules.needs_variant_version", "version {required} of the '{variant}' variant (this build has version {supported})"),
+    ("rules.needs_rules_version", "rules version {required} (this build has version {supported})"),
+    ("rules.needs_code_length", "{length}-digit codes (this build supports {min} to {max})"),
+    ("rules.needs_repeats", "codes with repeated digits"),
+    ("rules.needs_option", "the '{option}' rule option"),
+    ("rules.invalid_spec", "invalid game description: {reason}"),
+    ("engine.launch_failed", "couldn't be started ({reason})"),
+    ("engine.gone", "stopped"),
+    ("engine.timed_out", "didn't answer within {seconds} seconds"),
+    ("engine.invalid", "answered with {reason}"),
+    ("clue.violated", "Clue #{clue} was {code} -> {score} (D,P): "),
+    ("clue.digits", "the secret shares {digits} digit(s) with {code}, but your guess shares {would}"),
+    ("clue.places", "{places} digit(s) of {code} are in the right spot, but your guess matches {would} of them"),
+];
+
+const DE: &[(&str, &str)] = &[
+    // Main menu
+    ("menu.title", "--- 🎲 Zahlenraten für mehrere Spieler ---"),
+    ("menu.individual", "[1] Eigene Geheimzahlen: Alle knacken um die Wette ihren eigenen Code."),
+    ("menu.double_blind", "[2] Doppelblind: Ein Mensch bewertet die Tipps, die Engine passt auf."),
+    ("menu.chat", "[3] Chat spielt: Ein Stream-Publikum stimmt über Tipps gegen einen Code ab."),
+    ("menu.tournament", "[4] Turnier: K.-o.-System mit Duellen."),
+    ("menu.daily", "[5] Tagesrätsel: Alle knacken heute denselben Code."),
+    ("menu.time_attack", "[6] Zeitjagd: So viele Codes wie möglich gegen die Uhr."),
+    ("menu.practice", "[7] Training: Allein üben, mit Rückgängig, Wiederholen und Vergleich."),
+    ("menu.watch", "[8] Zuschauen: Bots spielen gegeneinander, mit Kommentar."),
+    ("menu.reverse", "[9] Umgekehrt: Du denkst dir den Code aus, der Computer knackt ihn."),
+    ("menu.stats", "[10] Statistik: Gespeicherte Ergebnisse ansehen und verschlüsseln."),
+    ("menu.settings", "[11] Einstellungen: Zusatzfunktionen ein- oder ausschalten."),
+    ("menu.stats_view", "[10] Statistik: Gespeicherte Ergebnisse ansehen."),
+    ("menu.prompt", "Modus wählen (1-11): "),
+    ("menu.prompt_guest", "Modus wählen (1-10): "),
+    ("guest.unavailable", "Das geht nicht, wenn auf dem Server eines anderen gespielt wird."),
+    ("menu.invalid", "Ungültige Eingabe. Bitte eine Zahl von 1 bis 11 eingeben."),
+    ("over.title", "\n--- Spiel vorbei ---"),
+    ("over.new", "[1] Neues Spiel"),
+    ("over.quit", "[2] Beenden"),
+    ("over.prompt", "Auswahl (1 oder 2): "),
+    ("over.goodbye", "Danke fürs Spielen! Auf Wiedersehen."),
+    ("over.invalid", "Ungültige Eingabe. Das Menü startet neu..."),
+    // Shared prompts
+    ("common.continue", "Weiter mit Enter..."),
+    ("common.name_prompt", "Dein Name: "),
+    ("common.score_prompt", "{name}, bewerte den Tipp {guess} (D,P): "),
+    ("pause.paused", "⏸️ Das Spiel ist angehalten, die Uhr auch. Weiter mit Enter..."),
+    ("pause.resumed", "▶️ Es geht weiter, wo ihr aufgehört habt."),
+    ("input.ended", "Die Eingabe ist zu Ende, deshalb hört das Spiel hier auf."),
+    ("help.commands", "--- ❔ Befehle ---\n  /help         Diese Liste. Geht bei jeder Eingabe.\n  /rules        Die Regeln und wie Bewertungen funktionieren. Geht bei jeder Eingabe.\n  /pause        Macht den Bildschirm leer und hält die Uhr an, bis Enter gedrückt wird. Geht bei jeder Eingabe.\n  /hint         Ein Tippvorschlag, wenn du dran bist.\n  /note <Text>  Speichert eine private Notiz, wenn du dran bist.\n  /notes        Zeigt deine Notizen, wenn du dran bist.\n  /history      Zeigt deine bisherigen Tipps, wenn du dran bist.\n  /score        Zeigt den Spielstand, wenn du dran bist.\n  /skip         Lässt deinen Zug ohne Tipp verstreichen.\n  /save <Name>  Speichert das Spiel zum späteren Weiterspielen, wenn du dran bist.\n  /quit         Beendet das Spiel für alle, nach einer Rückfrage.\n  /undo         Nimmt deinen letzten Tipp zurück, bevor die Tastatur weitergeht.\nNur /skip kostet dich deinen Zug."),
+    ("rules.title", "--- 📜 Regeln ---"),
+    ("rules.code", "Jeder Geheimcode besteht aus {length} verschiedenen Ziffern und darf mit 0 beginnen."),
+    ("rules.feedback", "Jeder Tipp wird mit D,P bewertet: D ist die Zahl seiner Ziffern, die überhaupt im Code vorkommen, P die Zahl davon an der richtigen Stelle."),
+    ("rules.example", "Wäre der Code zum Beispiel {secret}, bekäme der Tipp {guess} die Bewertung {score}."),
+    ("rules.win", "Geknackt ist ein Code mit {score}."),
+    ("rules.hard_mode", "Der schwere Modus ist an: Jeder Tipp muss zu allen bisherigen Bewertungen passen."),
+    ("rules.timer", "Die Zugzeit ist an: Jeder Zug dauert {count} Sekunden."),
+    ("rules.record_key", "{length} Ziffern, {repeats}, {digits}"),
+    ("rules.repeats", "Wiederholungen erlaubt"),
+    ("rules.no_repeats", "keine Wiederholungen"),
+    ("common.yes", "j"),
+    ("common.no", "n"),
+    ("common.hand_over", "\n🔒 Gib die Tastatur an {name} weiter. {name}, drück Enter, wenn du bereit bist..."),
+    ("common.hide_turn", "\n🔒 Mit Enter deine Bewertung verbergen..."),
+    // Input errors
+    ("error.guess_length", "Der Tipp muss genau {length} Ziffern haben."),
+    ("error.guess_repeat", "Ziffern dürfen sich nicht wiederholen."),
+    ("error.guess_digits", "Die Eingabe enthält Zeichen, die keine Ziffern sind."),
+    ("error.feedback_form", "Die Bewertung besteht aus zwei Zahlen in der Form D,P (z. B. 2,1)."),
+    ("error.feedback_numbers", "Die Bewertung darf nur Zahlen enthalten."),
+    ("error.feedback_range", "D ist höchstens {length}, und P kann nie größer als D sein."),
+    ("error.feedback_impossible", "{total},{position} ist keine mögliche Bewertung."),
+    // Seats and lobby
+    ("seat.type", "Platz {number}: [1] Mensch  [2] Leichter Bot  [3] Mittlerer Bot  [4] Schwerer Bot"),
+    ("seat.type_prompt", "Wer spielt hier? (Enter für Mensch): "),
+    ("seat.type_invalid", "Bitte eine Zahl von 1 bis 4 eingeben."),
+    ("seat.personality_prompt", "Spielweise wählen (Enter für Standard): "),
+    ("seat.personality_invalid", "Bitte eine Zahl von 1 bis {max} eingeben."),
+    ("seat.human", "Mensch"),
+    ("seat.engine", "Programm: {command}"),
+    ("seat.default_name", "Spieler {number}"),
+    ("seat.name_prompt", "Name für Platz {number} (Enter für \"{default}\"): "),
+    ("seat.count_prompt", "{message} (0 bis 3, Enter behält {current}): "),
+    ("seat.count_invalid", "Bitte eine Zahl von 0 bis 3 eingeben."),
+    ("handicap.none", "keins"),
+    ("handicap.free_clues", "{count} Gratis-Hinweis(e)"),
+    ("handicap.late_start", "setzt {count} Zug/Züge aus"),
+    ("lobby.title", "\n--- 🪑 Lobby ---"),
+    ("lobby.empty", "  (noch keine Plätze)"),
+    ("lobby.rated", "{name} ({rating})"),
+    ("lobby.handicap", "Handicap: {handicap}"),
+    ("lobby.commands", "\n[A] Platz hinzufügen  [E n] Platz n bearbeiten  [R n] Platz n entfernen  [P n] Profil für Platz n  [S] Spiel starten"),
+    ("lobby.bot_profile", "Nur Menschen haben Profile. Weiter mit Enter..."),
+    ("lobby.prompt", "Befehl eingeben: "),
+    ("lobby.full", "Der Tisch ist voll ({max} Plätze)."),
+    ("lobby.no_seats", "Zuerst mindestens einen Platz hinzufügen. Weiter mit Enter..."),
+    ("lobby.unknown", "Unbekannter Befehl. Möglich sind A, E <Platz>, R <Platz>, P <Platz> oder S. Weiter mit Enter..."),
+    ("profile.title", "\n--- 👤 Profil: {name} ---"),
+    ("profile.theme", "[1] Farbschema: {value}"),
+    ("profile.output", "[2] Symbole: {value}"),
+    ("profile.length", "[3] Code-Länge: {value}"),
+    ("profile.done", "[4] Speichern und zurück"),
+    ("profile.prompt", "Auswahl eingeben (1-4): "),
+    ("profile.from_settings", "wie in den Einstellungen"),
+    ("profile.save_failed", "⚠️ Das Profil konnte nicht gespeichert werden: {error}"),
+    ("profile.welcome", "👤 Willkommen zurück, {name}! Dein Profil ist geladen: {preferences}."),
+    ("profile.stats", "   Deine Statistik: {games} Spiel(e), {wins} Sieg(e), im Schnitt {average} Tipps."),
+    ("profile.part_theme", "Farbschema {value}"),
+    ("profile.part_output", "{value}"),
+    ("profile.part_length", "{count}-stellige Codes"),
+    ("profile.uses_length", "🔢 {name} spielt am liebsten mit {length}-stelligen Codes, also haben die Codes dieses Spiels {length} Stellen."),
+    ("profile.no_preferences", "alles wie in den Einstellungen"),
+    ("profile.achievements", "    Erfolge: {list}"),
+    ("achievement.banner", "ERFOLG FREIGESCHALTET"),
+    ("achievement.unlocked", "🏅 {name}: {achievement} ({about})"),
+    ("achievement.quick_solve", "Schnelldenker"),
+    ("achievement.quick_solve_about", "einen Code mit höchstens 3 Tipps geknackt"),
+    ("achievement.hat_trick", "Hattrick"),
+    ("achievement.hat_trick_about", "drei Spiele mit mehreren Personen in Folge gewonnen"),
+    ("achievement.no_hints", "Ohne Hilfe"),
+    ("achievement.no_hints_about", "ein Spiel mit mehreren Personen ohne Hinweis gewonnen"),
+    ("achievement.long_code", "Langstrecke"),
+    ("achievement.long_code_about", "einen Code mit mindestens 6 Stellen geknackt"),
+    ("edit.title", "\n--- Platz {number}: {name} ---"),
+    ("edit.player", "[1] Spieler: {player}"),
+    ("edit.name", "[2] Name: {name}"),
+    ("edit.free_clues", "[3] Gratis-Hinweise vor dem Start: {count}"),
+    ("edit.late_start", "[4] Auszusetzende Züge zu Beginn: {count}"),
+    ("edit.done", "[5] Fertig"),
+    ("edit.prompt", "Auswahl (1-5): "),
+    ("edit.free_clues_prompt", "Gratis-Hinweise"),
+    ("edit.late_start_prompt", "Auszusetzende Züge"),
+    // Individual Secrets
+    ("game.title", "--- 🎲 Zahlenraten für mehrere Spieler (Eigene Geheimzahlen) ---"),
+    ("game.intro_code", "Alle haben einen eigenen, geheimen {length}-stelligen Code (keine doppelten Ziffern, 0 am Anfang erlaubt)."),
+    ("game.intro_goal", "Reihum rät jede Person ihren eigenen Code. Wer ihn zuerst knackt, gewinnt!"),
+    ("game.secrets_ready", "\nAlle Geheimcodes stehen fest. Viel Spaß beim Raten!"),
+    ("game.all_finished", "\nAlle sind fertig. Danke fürs Spielen!"),
+    ("game.ended_early", "\n🚪 Das Spiel wurde vorzeitig beendet. Danke fürs Spielen!"),
+    ("debug.enabled", "🔬 Debug-Modus: Die Codes werden in jedem Zug gezeigt, und dieses Spiel kommt nicht in die Statistik."),
+    ("debug.secret", "🔬 Debug: Der Code von {name} ist {secret}."),
+    ("debug.refused", "⚠️ --debug-secrets geht nur, wenn eine Person spielt (oder mit --i-know-what-im-doing), deshalb bleiben die Codes verborgen."),
+    ("game.last_player_title", "\n--- Letzter Platz vergeben ---"),
+    ("game.last_player", "{name} bekommt automatisch Platz {place} (nicht geknackt)."),
+    ("game.sits_out", "\n⏸️ {name} setzt diesen Zug aus (Handicap)."),
+    ("game.turn_header", "RUNDE {round} | {name} ist dran"),
+    ("game.bot_thinking", "🤖 {name} überlegt..."),
+    ("game.bot_guesses", "🤖 {name} tippt {guess}"),
+    ("engine.replaced", "⚠️ Das Programm von {name}: {reason}. Ab hier spielt ein mittlerer Bot den Platz."),
+    ("engine.lost_turn", "⏰ Das Programm von {name}: {reason}. Es verliert diesen Zug."),
+    ("game.feedback", "Tipp {guess}: Bewertung (D,P) -> {score}"),
+    ("game.cracked", "{name} hat den eigenen Code geknackt: {guess}. Das ist Platz {place}!"),
+    ("game.length", "Spieldauer: {time} | Tipps insgesamt: {count}"),
+    ("game.seed", "🎲 Startwert: {seed} (mit --seed {seed} wird dieses Spiel noch einmal so gemischt)"),
+    ("game.hints_used", "💡 Genutzte Hinweise: {players}"),
+    ("game.undos_used", "↩️ Zurückgenommene Tipps: {players}"),
+    ("undo.offer", "↩️ Vertippt? Mit /undo nimmst du {guess} zurück und tippst neu."),
+    ("undo.confirm", "{name}, darf {player} den Tipp {guess} zurücknehmen? (j/N): "),
+    ("undo.refused", "Der Tipp bleibt."),
+    ("undo.done", "↩️ {player} hat {guess} zurückgenommen."),
+    ("game.rematch_prompt", "\nAus diesem Spiel ein Turnier machen, gesetzt nach diesen Ergebnissen? (j/N): "),
+    ("guess.prompt", "{name}, gib deinen {length}-stelligen Tipp ein: "),
+    ("guess.prompt_hint", "{name}, gib deinen {length}-stelligen Tipp ein (oder einen Befehl wie /hint): "),
+    ("guess.prompt_budget", "{name}, Tipp {number} von {limit} (oder einen Befehl wie /hint): "),
+    ("guess.last_guess", "🚨🚨 LETZTER TIPP, {name}! Daneben, und du bist raus. 🚨🚨"),
+    ("game.out_of_guesses", "🛑 {name} hat alle {limit} Tipps verbraucht und ist raus. Der Code war {secret}."),
+    ("guess.hint", "💡 Hinweis: Versuch es mit {guess} ({count} Code(s) passen noch zu deinen Bewertungen)."),
+    ("guess.hard_mode", "🚫 Schwerer Modus: {guess} kann nicht der Code sein. {reason}."),
+    ("guess.remaining", "🔎 {count} Code(s) passen noch zu deinen Bewertungen."),
+    ("guess.time_up", "⏰ Die Zeit ist um! {name} verliert diesen Zug."),
+    ("guess.digits", "🔢 Ziffern: {digits}  (· getippt, ✗ nicht in deinem Code)"),
+    ("guess.digits_sentence", "Bisher getippte Ziffern: {tried}. Nicht in deinem Code: {absent}."),
+    ("guess.digits_none", "keine"),
+    ("guess.note_added", "📝 Notiz gespeichert. Nur du siehst sie, mit /notes in deinen Zügen."),
+    ("guess.note_usage", "Schreib /note und dahinter, was du dir merken willst, zum Beispiel: /note 7 ist nicht vorne"),
+    ("guess.notes_header", "📝 Deine Notizen:"),
+    ("guess.notes_empty", "📝 Noch keine Notizen. Schreib /note und dahinter, was du dir merken willst."),
+    ("guess.notes_reminder", "📝 Du hast {count} Notiz(en). Mit /notes kannst du sie lesen."),
+    ("guess.unknown_command", "❔ {command} gibt es hier nicht. Statt eines Tipps kannst du eingeben:"),
+    ("guess.skipped", "⏭️ {name} lässt diesen Zug aus."),
+    ("guess.quit_confirm", "🚪 Das Spiel jetzt für alle beenden? Wer noch rät, bekommt keinen Platz. (j/N): "),
+    ("guess.quit_cancelled", "Das Spiel geht weiter."),
+    ("guess.save_usage", "Gib /save und dahinter einen Namen für den Spielstand ein, etwa /save freitag."),
+    ("save.done", "💾 Spiel in {path} gespeichert. Spielt weiter, oder /quit und später mit play --resume {path} fortsetzen."),
+    ("save.failed", "⚠️ Das Spiel konnte nicht in {path} gespeichert werden: {error}"),
+    ("save.unavailable", "Im Vollbild-Modus kann nicht gespeichert werden."),
+    ("save.guest", "Auf dem Server eines anderen kann nicht gespeichert werden."),
+    ("save.resumed", "💾 Das gespeicherte Spiel geht in Runde {round} weiter, {name} ist am Zug."),
+    ("commands.hint", "Ein Tippvorschlag anhand deiner bisherigen Bewertungen."),
+    ("commands.note", "Speichert eine private Notiz."),
+    ("commands.notes", "Zeigt deine Notizen."),
+    ("commands.history", "Zeigt deine bisherigen Tipps."),
+    ("commands.score", "Zeigt den Spielstand."),
+    ("commands.skip", "Lässt deinen Zug ohne Tipp verstreichen."),
+    ("commands.save", "Speichert das Spiel, um es später mit play --resume fortzusetzen."),
+    ("commands.quit", "Beendet das Spiel für alle."),
+    ("history.guess", "Tipp"),
+    ("history.pegs", "Bewertung"),
+    ("history.empty", "Noch keine Tipps."),
+    ("history.legend", "  ● Ziffer am richtigen Platz   ○ richtige Ziffer, falscher Platz   · nicht im Code"),
+    ("history.clue_sentence", "Geschenkter Hinweis: {guess}, {score}."),
+    ("history.guess_sentence", "Tipp {number}: {guess}, {score}."),
+    ("scoreboard.title", "\n--- 📋 Spielstand ---"),
+    ("scoreboard.playing", "{count} Tipp(s), bestes {best}"),
+    ("scoreboard.finished", "🏁 Platz {place} mit {count} Tipp(s)"),
+    ("announce.guessed", "📢 {name} hat getippt."),
+    ("announce.feedback", "📢 {name} bekam {score}."),
+    ("announce.full", "📢 {name} tippte {guess} und bekam {score}."),
+    ("chances.title", "📊 Chancen auf Platz {place} (geschätzt):"),
+    ("start.title", "\n--- Wer fängt an? ---"),
+    ("start.random", "  [0] Zufällig"),
+    ("start.comeback", "  [C] Aufholziehung (zufällig, bevorzugt wer zuletzt schlechter abgeschnitten hat)"),
+    ("start.prompt", "Auswahl (0, 1, 2, ... oder C): "),
+    ("start.chance", "{percent}% Chance"),
+    ("start.comeback_selected", "Die Aufholziehung hat {name} ausgewählt!"),
+    ("start.random_selected", "Zufällig ausgewählt: {name} fängt an!"),
+    ("start.selected", "{name} fängt an."),
+    ("start.invalid", "Ungültige Auswahl. Bitte 0 für zufällig, C für die Aufholziehung oder die Nummer einer Person eingeben."),
+    ("post.title", "\n--- Menü nach dem Knacken ---"),
+    ("post.finish", "[1] Spiel beenden: {name} einstufen und zum Schlussmenü."),
+    ("post.continue", "[1] Weiter: {name} scheidet aus, gespielt wird um den nächsten Platz."),
+    ("post.restart", "[2] Neustart: Neues Spiel mit denselben Leuten."),
+    ("post.quit", "[3] Beenden: Das Programm verlassen."),
+    ("post.review", "[4] Auswertung: So schlugen sich die Tipps von {name} gegen die besten möglichen."),
+    ("post.save", "Oder /save <Name> eingeben, um das Spiel zu speichern und ein andermal weiterzuspielen."),
+    ("post.prompt", "Auswahl (1, 2, 3 oder 4): "),
+    ("post.removed", "{name} (Platz {place}) spielt nicht mehr mit."),
+    ("post.goodbye", "Danke fürs Spielen! Auf Wiedersehen."),
+    ("post.back", "\nZurück zum Menü mit Enter..."),
+    ("post.invalid", "Ungültige Eingabe. Bitte 1, 2, 3 oder 4 eingeben."),
+    // Final rankings
+    ("rankings.title", "\n--- 🏁 ENDSTAND ---"),
+    ("transcript.prompt", "📜 Für ein Protokoll einen Dateinamen eingeben (mit .md am Ende für Markdown), oder Enter zum Überspringen: "),
+    ("transcript.saved", "📜 Protokoll in {path} geschrieben."),
+    ("transcript.failed", "⚠️ Das Protokoll konnte nicht in {path} geschrieben werden: {error}"),
+    ("replay.saved", "🎬 Aufzeichnung in {path} gespeichert. Noch einmal ansehen mit `replay {name}`."),
+    ("replay.failed", "⚠️ Das Spiel konnte nicht aufgezeichnet werden: {error}"),
+    ("transcript.title", "Secret Guessing Game: Jeder gegen seinen Code"),
+    ("transcript.played", "Gespielt am {time}, Dauer {length}. Startwert {seed}."),
+    ("transcript.guesses", "Tipps"),
+    ("transcript.rankings", "Endstand"),
+    ("transcript.round", "Runde"),
+    ("transcript.time", "Zeit"),
+    ("transcript.guess", "Tipp"),
+    ("transcript.feedback", "Rückmeldung"),
+    ("rankings.rank", "Platz"),
+    ("rankings.player", "Spieler"),
+    ("rankings.guesses", "Tipps"),
+    ("rankings.time", "Zeit"),
+    ("rankings.secret", "Code"),
+    ("rankings.rating", "Wertung"),
+    ("rankings.rating_sentence", "Wertung {rating}."),
+    ("rankings.tie", "{place} (geteilt)"),
+    ("rankings.dnf", "Nicht geknackt (Runde {round})"),
+    ("rankings.sentence", "{rank}: {name}, {guesses} Tipp(s), {time}, Geheimcode {secret}."),
+    // Leaderboard
+    ("leaderboard.title", "\n--- 🏆 BESTENLISTE ---"),
+    ("leaderboard.file_title", "Bestenliste"),
+    ("leaderboard.empty", "Noch keine Spiele mit mehreren Personen gespeichert."),
+    ("leaderboard.place", "Platz"),
+    ("leaderboard.wins", "Siege"),
+    ("leaderboard.games", "Spiele"),
+    ("leaderboard.average", "Tipps im Schnitt"),
+    ("leaderboard.sentence", "{place}: {name}, {wins} Sieg(e) in {games} Spiel(en), im Schnitt {average} Tipps, Wertung {rating}."),
+    ("leaderboard.kept", "Wird laufend in {path} gespeichert"),
+    // Settings
+    ("settings.title", "--- ⚙️ Einstellungen ---"),
+    ("settings.on", "AN"),
+    ("settings.off", "AUS"),
+    ("settings.remaining", "[1] Verbleibende Codes nach jedem Tipp zeigen: {value}"),
+    ("settings.announcement", "[2] Nach jedem Zug allen verraten: {value}"),
+    ("settings.hard_mode", "[3] Schwerer Modus (Tipps müssen zu allen Bewertungen passen): {value}"),
+    ("settings.win_chances", "[4] Gewinnchancen zwischen den Zügen zeigen: {value}"),
+    ("settings.coach", "[5] Coach bewertet jeden Tipp: {value}"),
+    ("settings.threads_auto", "[6] Solver-Threads: automatisch ({count})"),
+    ("settings.threads", "[6] Solver-Threads: {count}"),
+    ("settings.language", "[7] Sprache: {value}"),
+    ("settings.language_auto", "automatisch ({value})"),
+    ("settings.output", "[8] Symbole: {value}"),
+    ("settings.timer_off", "[9] Zugzeit: unbegrenzt"),
+    ("settings.timer", "[9] Zugzeit: {count} s"),
+    ("settings.timer_prompt", "Sekunden pro Zug (0 für unbegrenzt): "),
+    ("settings.sound", "[10] Signaltöne: {value}"),
+    ("settings.screen_reader", "[11] Ausgabe für Screenreader: {value}"),
+    ("settings.confirm_undo", "[12] /undo braucht das OK einer anderen Person: {value}"),
+    ("settings.guess_limit_off", "[13] Tipp-Limit: keins"),
+    ("settings.guess_limit", "[13] Tipp-Limit: {count} pro Person"),
+    ("settings.guess_limit_prompt", "Tipps pro Person (0 für unbegrenzt): "),
+    ("settings.theme", "[14] Farbschema: {value}"),
+    ("settings.leaderboard", "[15] Bestenliste nach dem Endstand: {value}"),
+    ("settings.back", "[16] Zurück"),
+    ("settings.prompt", "Auswahl (1-16): "),
+    ("settings.threads_prompt", "Threads für den Solver (0 für einen pro Kern): "),
+    ("settings.save_failed", "⚠️ Einstellungen konnten nicht gespeichert werden: {error}. Sie gelten nur für diese Sitzung."),
+    ("announcement.nothing", "nichts"),
+    ("announcement.guessed", "dass getippt wurde"),
+    ("announcement.feedback", "die Bewertung"),
+    ("announcement.full", "Tipp und Bewertung"),
+    ("output.emoji", "Emoji"),
+    ("output.unicode", "Unicode-Symbole, keine Emoji"),
+    ("output.ascii", "nur ASCII"),
+    ("sentence.feedback", "{digits}, {placed}"),
+    ("sentence.digits_0", "keine Ziffer richtig"),
+    ("sentence.digits_1", "eine Ziffer richtig"),
+    ("sentence.digits_2", "zwei Ziffern richtig"),
+    ("sentence.digits_3", "drei Ziffern richtig"),
+    ("sentence.digits_4", "vier Ziffern richtig"),
+    ("sentence.digits_5", "fünf Ziffern richtig"),
+    ("sentence.digits_all", "alle Ziffern richtig"),
+    ("sentence.placed_0", "keine an der richtigen Stelle"),
+    ("sentence.placed_1", "eine an der richtigen Stelle"),
+    ("sentence.placed_2", "zwei an der richtigen Stelle"),
+    ("sentence.placed_3", "drei an der richtigen Stelle"),
+    ("sentence.placed_4", "vier an der richtigen Stelle"),
+    ("sentence.placed_5", "fünf an der richtigen Stelle"),
+    ("sentence.placed_all", "alle an der richtigen Stelle"),
+    ("sound.off", "aus"),
+    ("sound.bell", "Terminalglocke"),
+    ("sound.tones", "Töne (sonst Glocke)"),
+    // Reverse mode
+    ("reverse.title", "--- 🔁 Umgekehrt (Du machst den Code, der Computer knackt ihn) ---"),
+    ("reverse.intro", "Denk dir einen {length}-stelligen Code ohne doppelte Ziffern aus. Der Computer rät, du bewertest wie gewohnt mit D,P."),
+    ("reverse.secret_prompt", "{name}, tippe deinen Code ein (er wird nicht angezeigt), damit die Engine deine Bewertungen prüfen kann, oder drück Enter, um ihn im Kopf zu behalten: "),
+    ("reverse.guess_header", "TIPP {number} | 🤖 Der Computer tippt {guess}"),
+    ("reverse.corrected", "🚩 Das stimmt nicht: {guess} ergibt gegen deinen Code {score}. Es geht mit der richtigen Bewertung weiter."),
+    ("reverse.cracked", "\n🤖 Geschafft! Dein Code ist {secret}, geknackt mit {count} Tipps."),
+    ("reverse.no_fit", "\n🚩 Kein Code passt zu all deinen Bewertungen, also muss eine deiner Antworten falsch sein."),
+    ("reverse.conflict", "   {guess} -> {score} kann nicht zusammen mit Tipp #{number} stimmen: {other} -> {other_score}."),
+    ("reverse.contradiction", "   {guess} -> {score} widerspricht deinen bisherigen Antworten zusammengenommen."),
+    ("reverse.clue", "   Tipp #{number}: {guess} -> {score}"),
+    ("reverse.restart_prompt", "Mit demselben Code neu anfangen? (j/N): "),
+    ("reverse.abandoned", "Spiel abgebrochen. Prüf deine Bewertungen nächstes Mal genauer!"),
+    ("reverse.remaining", "🔎 {count} Code(s) passen noch zu deinen Bewertungen."),
+    // Time Attack
+    ("time_attack.limit_title", "\n--- Zeitlimit wählen ---"),
+    ("time_attack.limit_2", "  [1] 2 Minuten"),
+    ("time_attack.limit_5", "  [2] 5 Minuten"),
+    ("time_attack.limit_10", "  [3] 10 Minuten"),
+    ("time_attack.limit_prompt", "Auswahl (1, 2 oder 3): "),
+    ("time_attack.limit_invalid", "Ungültige Auswahl. Bitte 1, 2 oder 3 eingeben."),
+    ("time_attack.title", "--- ⏱️ Zeitrennen ---"),
+    ("time_attack.intro", "Knack so viele Codes hintereinander wie möglich, bevor die Zeit abläuft!"),
+    ("time_attack.start_prompt", "\nDu hast {time}. Mit Enter startet die Uhr..."),
+    ("time_attack.status", "\n⏱️ Noch {time} | Geknackt: {cracked} | Tipps für diesen Code: {guesses}"),
+    ("time_attack.too_late", "\n⏰ Die Zeit ist um! Der letzte Tipp kam zu spät."),
+    ("time_attack.cracked", "🎉 Code #{number} mit {guesses} Tipps geknackt! Hier kommt der nächste..."),
+    ("time_attack.results", "|    ERGEBNIS DES ZEITRENNENS    |"),
+    ("time_attack.summary", "{name} hat in {time} {count} Code(s) geknackt."),
+    ("time_attack.code_row", "  Code #{number} {guesses} Tipps"),
+    ("time_attack.average", "Schnitt: {average} Tipps pro Code"),
+    ("time_attack.unfinished", "Der offene Code war {secret} (nach {guesses} Tipps)."),
+    // Practice
+    ("practice.no_guesses", "  (noch keine Tipps)"),
+    ("practice.line_row", "  {number}. {guess} -> {score}   noch {remaining} Code(s)"),
+    ("practice.no_lines", "Noch keine anderen Linien. Nimm erst ein paar Tipps zurück und probier etwas anderes."),
+    ("practice.earlier_line", "\n--- Frühere Linie #{number} ({status}) ---"),
+    ("practice.current_line", "\n--- Aktuelle Linie ({status}) ---"),
+    ("practice.solved", "gelöst"),
+    ("practice.unfinished", "offen"),
+    ("practice.commands", "Befehle: /undo, /redo, /history, /compare, /reveal, /quit"),
+    ("practice.title", "--- 🧪 Übungsplatz ---"),
+    ("practice.intro", "Lass dir Zeit: Nimm Tipps zurück, probier eine andere Linie und vergleich, wie sie gelaufen sind."),
+    ("practice.solved_prompt", "\nGelöst! /undo für eine andere Linie, /compare zum Vergleichen oder Enter zum Beenden: "),
+    ("practice.guess_prompt", "\nTipp #{number} (oder ein Befehl): "),
+    ("practice.undone", "↩️ Zurück auf die Zeit vor Tipp #{number}."),
+    ("practice.nothing_to_undo", "Nichts zum Zurücknehmen."),
+    ("practice.redone", "↪️ Tipp #{number} wiederhergestellt."),
+    ("practice.nothing_to_redo", "Nichts zum Wiederherstellen."),
+    ("practice.secret", "Der Code ist {secret}."),
+    ("practice.cracked", "🎉 Geknackt mit {count} Tipps!"),
+    // Daily challenge
+    ("daily.share_title", "Secret Guessing Game — Tagesrätsel #{number} ({date})"),
+    ("daily.share_cracked", "Geknackt mit {count} Tipps"),
+    ("daily.title", "--- 📅 Tagesrätsel #{number} ---"),
+    ("daily.intro", "Heute knackt die ganze Welt denselben Code. Viel Glück!"),
+    ("daily.feedback", "Tipp {guess}: Bewertung (D,P) -> {score}   {emoji}"),
+    ("daily.cracked", "{name} hat den heutigen Code in {time} geknackt."),
+    ("daily.streak_one", "🔥 {days} Tag in Folge (Bestwert {best_days}). Siegesserie in Gruppenspielen: {wins} (Bestwert {best_wins})."),
+    ("daily.streak", "🔥 {days} Tage in Folge (Bestwert {best_days}). Siegesserie in Gruppenspielen: {wins} (Bestwert {best_wins})."),
+    ("daily.share", "\n--- Teil dein Ergebnis (ohne zu spoilern!) ---\n"),
+    // Double-Blind
+    ("double_blind.secret_prompt", "{name}, gib deinen geheimen {length}-stelligen Code ein (er wird beim Tippen nicht angezeigt): "),
+    ("double_blind.title", "--- 🕵️ Doppelblind (Ein Mensch macht den Code, die Engine ist Schiedsrichter) ---"),
+    ("double_blind.intro", "Wer den Code macht, wählt ihn aus und bewertet jeden Tipp von Hand.\nDie Engine prüft jede Bewertung und meldet Fehler sofort."),
+    ("double_blind.codemaker_prompt", "Name für den Codemacher: "),
+    ("double_blind.codebreaker_prompt", "Name für den Codeknacker: "),
+    ("double_blind.look_away", "\n{codebreaker}, schau weg, während {codemaker} den Code eingibt!"),
+    ("double_blind.guess_header", "TIPP {number} | {name} rät"),
+    ("double_blind.mistake", "\n🚩 SCHIEDSRICHTER: {name} hat das mit {claimed} bewertet, richtig ist {actual}."),
+    ("double_blind.guessed", "\n🎉🎉🎉 CODE GEKNACKT! 🎉🎉🎉"),
+    ("double_blind.cracked", "{codebreaker} hat den Code {secret} von {codemaker} mit {count} Tipps geknackt."),
+    ("double_blind.report", "|  BERICHT DES SCHIEDSRICHTERS   |"),
+    ("double_blind.no_mistakes", "{name} hat jeden Tipp richtig bewertet. Gut gemacht!"),
+    ("double_blind.mistakes", "{name} hat sich bei {guesses} Tipps {count}-mal verbewertet."),
+    ("double_blind.length", "Spieldauer: {time}"),
+    // Demo and Watch mode
+    ("demo.cracked", "Geknackt! 🎉"),
+    ("demo.no_digits", "Keine einzige passende Ziffer — das schließt {length} Ziffern auf einmal aus!"),
+    ("demo.all_digits", "Alle Ziffern gefunden; jetzt geht es nur noch um die Reihenfolge."),
+    ("demo.one_left", "Jetzt passt nur noch ein Code. Aufgepasst..."),
+    ("demo.in_place", "{count} genau an der richtigen Stelle. Schön!"),
+    ("demo.ruled_out", "Das schließt {eliminated} Codes aus; {remaining} sind noch möglich."),
+    ("demo.bot", "  🤖 {name} ({difficulty}-Bot)"),
+    ("demo.bot_personality", "  🤖 {name} ({difficulty}-Bot, {personality})"),
+    ("demo.round", "\n========== RUNDE {round} =========="),
+    ("demo.guesses", "{name} tippt {guess} -> {score}"),
+    ("demo.wins", "\n🏆 {name} gewinnt mit {count} Tipps! Der Code war {secret}."),
+    ("demo.title", "--- 🎲 DEMO: Sieh zu, wie die Bots ihre Codes knacken! (beliebige Taste zum Spielen) ---"),
+    ("demo.play_prompt", "\nDrück eine beliebige Taste für dein eigenes Spiel!"),
+    ("watch.count_prompt", "Wie viele Bots sollen spielen (2 bis 6)? "),
+    ("watch.count_invalid", "Bitte eine Zahl von 2 bis 6 eingeben."),
+    ("watch.difficulty_prompt", "Bot {number}: [1] Leicht  [2] Mittel  [3] Schwer: "),
+    ("watch.choice_invalid", "Bitte 1, 2 oder 3 eingeben."),
+    ("watch.speed_prompt", "Tempo: [1] Langsam  [2] Normal  [3] Schnell: "),
+    ("watch.title", "--- 📺 Zuschauen (Bots gegen Bots) ---"),
+    ("watch.intro", "Stell die Runde zusammen und lehn dich zurück: Jeder Bot erklärt, was er aus jedem Tipp lernt."),
+    ("watch.running", "--- 📺 Zuschauen (beliebige Taste zum Beenden) ---"),
+    ("watch.rules", "Regeln: {length}-stellige Codes, {repeats} ({spec})"),
+    ("watch.repeats", "Wiederholungen erlaubt"),
+    ("watch.no_repeats", "keine doppelten Ziffern"),
+    ("watch.menu_prompt", "\nZurück zum Menü mit einer beliebigen Taste..."),
+    ("watch.stopped", "\nZuschauen beendet."),
+    // Chat Plays
+    ("chat.seconds_prompt", "{label} in Sekunden [{default}]: "),
+    ("chat.seconds_invalid", "Bitte eine ganze Zahl von Sekunden eingeben."),
+    ("chat.settings_title", "\n--- Abstimmung einstellen (Enter behält den Standardwert) ---"),
+    ("chat.window", "Abstimmungsfenster"),
+    ("chat.min_votes_prompt", "Mindestzahl verschiedener Abstimmender pro Tipp [{default}]: "),
+    ("chat.min_votes_invalid", "Bitte eine Zahl ab 1 eingeben."),
+    ("chat.user_cooldown", "Wartezeit pro Person"),
+    ("chat.round_cooldown", "Pause nach jedem Tipp"),
+    ("chat.title", "--- 💬 Chat spielt (Publikum gegen einen Code) ---"),
+    ("chat.intro", "Chatzeilen sehen so aus: `user: !guess 1234` (oder nur `user: 1234`).\nJedes Abstimmungsfenster nimmt den beliebtesten Tipp, sobald genug Leute abgestimmt haben."),
+    ("chat.feed_prompt", "Datei oder FIFO mit dem Chat (Enter liest den Chat aus diesem Terminal): "),
+    ("chat.feed_failed", "Der Chat ließ sich nicht öffnen: {error}"),
+    ("chat.open", "\nEin geheimer Code ist erstellt. Die Abstimmung läuft!"),
+    ("chat.voted", "🗳️ {name} stimmt für {guess}"),
+    ("chat.feed_closed", "\nDer Chat wurde beendet, bevor der Code geknackt war.\nDer Code war {secret}."),
+    ("chat.waiting", "Warte auf mehr Stimmen ({votes} von {needed} nötig)..."),
+    ("chat.crowd_guess", "TIPP DES PUBLIKUMS {number} | {guess} ({votes} von {total} Stimmen)"),
+    ("chat.feedback", "Bewertung (D,P) -> {score}"),
+    ("chat.cracked", "\n🎉🎉🎉 DER CHAT HAT DEN CODE GEKNACKT! 🎉🎉🎉"),
+    ("chat.solved", "Gelöst mit {count} Tipps in {time}."),
+    ("chat.winners", "Richtig abgestimmt haben: {names}"),
+    ("chat.next_vote", "Die nächste Abstimmung beginnt in {seconds} s."),
+    ("vote.user_cooldown", "langsam, du kannst in {seconds} s wieder abstimmen"),
+    ("vote.round_cooldown", "die Abstimmung geht in {seconds} s weiter"),
+    // Correspondence games
+    ("turn.tampered_secret", "der für {name} ausgeteilte Code"),
+    ("turn.tampered_guess", "Tipp #{number}"),
+    ("turn.tampered_score", "die Bewertung von Tipp #{number} von {name}"),
+    ("turn.read_failed", "Das Spiel {path} ließ sich nicht lesen: {error}"),
+    ("turn.unplayable", "{path} kann nicht gespielt werden: {error}."),
+    ("turn.first_turn", "{name}, das ist dein erster Zug. Wähl eine Passphrase für dieses Spiel: Sie versiegelt den Code, den du {other} austeilst, und du brauchst sie bei jedem Zug. Niemand sonst sollte sie kennen."),
+    ("turn.passphrase", "Passphrase: "),
+    ("turn.passphrase_empty", "Die Passphrase darf nicht leer sein."),
+    ("turn.passphrase_again", "Noch einmal: "),
+    ("turn.passphrase_mismatch", "Die beiden stimmen nicht überein. Noch ein Versuch."),
+    ("turn.passphrase_prompt", "{name}, deine Passphrase: "),
+    ("turn.passphrase_wrong", "Das ist nicht die Passphrase für diesen Platz."),
+    ("turn.passphrase_locked", "Zu viele falsche Passphrasen für {name}. Es wurde nichts geändert."),
+    ("turn.cracked_status", "🔓 mit {count} Tipp(s) geknackt"),
+    ("turn.guesses_status", "{count} Tipp(s)"),
+    ("turn.over", "Das Spiel ist vorbei. Endstand:"),
+    ("turn.place", "  {place}  {name}  {count} Tipp(s), Code {secret}"),
+    ("turn.title", "--- 📜 Fernspiel: {names} ---"),
+    ("turn.whose_turn", "{name} ist dran."),
+    ("turn.tampered", "{path} wurde außerhalb des Spiels geändert: {what} ist nicht das, was {name} geschrieben hat. Mit dieser Kopie geht es nicht weiter, such also eine unveränderte."),
+    ("turn.unreadable_secret", "Der für {name} ausgeteilte Code lässt sich nicht lesen."),
+    ("turn.scored", "Du hast Tipp #{number} von {name} bewertet."),
+    ("turn.cracked_other", "🔓 Damit hat {name} den Code geknackt!"),
+    ("turn.cracked_own", "🔓 Du hast deinen Code mit {count} Tipp(s) geknackt, in diesem Zug wird also nur der Tipp von {name} bewertet."),
+    ("turn.your_guesses", "Deine bisherigen Tipps:"),
+    ("turn.guess_prompt", "Dein Tipp: "),
+    ("turn.write_back_failed", "Das Spiel ließ sich nicht nach {path} zurückschreiben: {error}"),
+    ("turn.saved", "Gespeichert. Gib {path} an {name} weiter, dort geht es weiter."),
+    ("turn.no_game", "Unter {path} gibt es noch kein Spiel."),
+    ("turn.start_prompt", "Dort ein neues Fernspiel beginnen? (j/N): "),
+    ("turn.players_prompt", "Mitspielende in Zugreihenfolge, durch Kommas getrennt: "),
+    ("turn.player_count", "Ein Fernspiel hat 2 bis 8 Mitspielende."),
+    ("turn.write_failed", "Das Spiel ließ sich nicht nach {path} schreiben: {error}"),
+    ("turn.first_player", "{name} fängt an."),
+    // Tournament
+    ("tournament.final", "Finale"),
+    ("tournament.semi_finals", "Halbfinale"),
+    ("tournament.quarter_finals", "Viertelfinale"),
+    ("tournament.round", "Runde {number}"),
+    ("tournament.bracket", "|           TURNIERBAUM            |"),
+    ("tournament.bye", "  {mark} {name} (Freilos)"),
+    ("tournament.pairing", "  {mark} {home} gegen {away_mark} {away}"),
+    ("tournament.match_start", "⚔️ {home} gegen {away} — {first} beginnt."),
+    ("tournament.turn_header", "RUNDE {round} | {name} rät"),
+    ("tournament.cracked", "\n🎉 {name} hat den Code geknackt!"),
+    ("tournament.draw", "Beide haben ihren Code in Runde {round} geknackt! Mit Enter geht es mit neuen Codes noch einmal los..."),
+    ("tournament.count_prompt", "Anzahl der Teilnehmenden (2 bis 16): "),
+    ("tournament.count_invalid", "Bitte eine Zahl von 2 bis 16 eingeben."),
+    ("tournament.champion", "\n🏆🏆🏆 {name} gewinnt das Turnier! 🏆🏆🏆"),
+    ("tournament.match_prompt", "\n{round}: {home} gegen {away}. Mit Enter beginnt das Spiel..."),
+    ("tournament.advances", "\n{name} kommt weiter ({round})!"),
+    ("tournament.rematch_title", "--- 🏆 Revanche-Turnier ---"),
+    ("tournament.seeded_by", "Gesetzt nach dem heutigen Endstand:"),
+    ("tournament.seed", "  Setzplatz {number}: {name}"),
+    ("tournament.bots_out", "(Die Bots setzen diesmal aus: {names})"),
+    ("tournament.byes", "{count} Freilos(e) in der ersten Runde für die besten Setzplätze."),
+    ("tournament.title", "--- 🏆 K.-o.-Turnier ---"),
+    ("tournament.intro", "Die Teilnehmenden spielen paarweise gegeneinander. Wer gewinnt, kommt weiter, bis nur noch eine Person übrig ist."),
+    ("tournament.name_prompt", "Name für Teilnehmer {number} (Setzplatz {number}): "),
+    ("tournament.shuffle_prompt", "Setzliste auslosen? (j/N): "),
+    ("tournament.entrant_byes", "Bei {count} Teilnehmenden gibt es {byes} Freilos(e) in der ersten Runde für die besten Setzplätze."),
+    // Stats
+    ("stats.no_home", "⚠️ Kein Home-Verzeichnis gefunden; die Statistik gilt nur für diese Sitzung."),
+    ("stats.this_account", "dieses Konto"),
+    ("stats.split_stores", "📁 Solospiele werden für '{user}' gespeichert, Gruppenspiele im Vereinsspeicher."),
+    ("stats.migrated", "📁 Deine gespeicherte Statistik liegt jetzt in {path}."),
+    ("stats.personal", "persönlich"),
+    ("stats.club", "Verein"),
+    ("stats.open_failed", "⚠️ {error} (Speicher: {store}). Diese Statistik gilt nur für diese Sitzung."),
+    ("stats.needs_sqlite", "⚠️ {path} ist eine SQLite-Datenbank und braucht einen Build mit `--features sqlite` (Speicher: {store}). Diese Statistik gilt nur für diese Sitzung."),
+    ("stats.locked_title", "\n--- 🔒 Verschlüsselte Statistik ({store}) ---"),
+    ("stats.locked_intro", "Die Statistikdatei {path} ist mit einer Passphrase geschützt."),
+    ("stats.unlock_passphrase", "[1] Passphrase eingeben"),
+    ("stats.unlock_recovery", "[2] Wiederherstellungscode verwenden (danach eine neue Passphrase wählen)"),
+    ("stats.unlock_skip", "[3] In dieser Sitzung ohne Statistik spielen"),
+    ("stats.unlock_fresh", "[4] Neu anfangen (die gesperrte Datei bleibt umbenannt erhalten)"),
+    ("stats.unlock_prompt", "Deine Wahl (1-4): "),
+    ("stats.passphrase", "Passphrase: "),
+    ("stats.unlocked", "🔓 Statistik entsperrt."),
+    ("stats.failed", "❌ {error}."),
+    ("stats.recovery_prompt", "Wiederherstellungscode: "),
+    ("stats.unlocked_recovery", "🔓 Statistik mit dem Wiederherstellungscode entsperrt."),
+    ("stats.passphrase_saved", "Neue Passphrase gespeichert. Dein Wiederherstellungscode gilt weiter."),
+    ("stats.passphrase_save_failed", "⚠️ Die neue Passphrase ließ sich nicht speichern: {error}."),
+    ("stats.archived", "Die gesperrte Datei liegt jetzt in {path}."),
+    ("stats.session_only", "❌ {error}. Die Statistik gilt nur für diese Sitzung."),
+    ("stats.unlock_invalid", "Ungültige Eingabe. Bitte eine Zahl von 1 bis 4 eingeben."),
+    ("stats.new_passphrase", "Wähl eine Passphrase: "),
+    ("stats.passphrase_again", "Noch einmal: "),
+    ("stats.passphrase_empty", "Die Passphrase darf nicht leer sein."),
+    ("stats.passphrase_mismatch", "Die beiden stimmen nicht überein. Bitte noch einmal."),
+    ("stats.save_failed", "⚠️ Die Statistik ließ sich nicht speichern: {error}."),
+    ("stats.code_guessed", "🎉🎉🎉 CODE GEKNACKT! 🎉🎉🎉"),
+    ("stats.first_win", "🥇 DER ALLERERSTE SIEG für {name}! Willkommen im Club der Sieger!"),
+    ("stats.personal_best", "📈 NEUE BESTLEISTUNG für {name}! (bisher: {previous} Tipps bei {rules})"),
+    ("stats.streak_ended", "🛑 {name} beendet die Siegesserie von {rival} nach {streak} Spielen!"),
+    ("stats.no_games", "Noch keine Spiele gespeichert."),
+    ("stats.row", "| {name} | Spiele: {games} | Siege: {wins} | Tipps im Schnitt: {average} | Zuletzt: {last} | Siegesserie: {streak} (Bestwert {best_streak}) | Tage in Folge: {days} (Bestwert {best_days})"),
+    ("stats.best_row", "  {name}: {guesses} Tipps in {mode} ({rules})"),
+    ("stats.bests", "Bestleistungen:"),
+    ("stats.stored_in", "Gespeichert in {path}"),
+    ("stats.stored_in_encrypted", "Gespeichert in {path} (verschlüsselt)"),
+    ("stats.session_store", "Die Statistik gilt nur für diese Sitzung."),
+    ("stats.title_user", "STATISTIK FÜR {user}"),
+    ("stats.title_personal", "PERSÖNLICHE STATISTIK"),
+    ("stats.title_club", "VEREINSSTATISTIK"),
+    ("stats.menu_title", "\n--- Statistikmenü ---"),
+    ("stats.menu_personal", "[1] Verschlüsselung der persönlichen Statistik"),
+    ("stats.menu_club", "[2] Verschlüsselung der Vereinsstatistik"),
+    ("stats.menu_back", "[0] Zurück"),
+    ("stats.choice_prompt", "Deine Wahl: "),
+    ("stats.encryption_title", "\n--- Verschlüsselung ---"),
+    ("stats.database", "Dieser Speicher ist eine Datenbank und lässt sich nicht verschlüsseln. Speichere die Statistik in der einfachen Datei, um sie mit einer Passphrase zu schützen."),
+    ("stats.change_passphrase", "[1] Passphrase ändern"),
+    ("stats.turn_off", "[2] Verschlüsselung ausschalten"),
+    ("stats.turn_on", "[1] Diese Statistik mit einer Passphrase schützen"),
+    ("stats.encrypted", "🔒 Die Statistik ist jetzt verschlüsselt."),
+    ("stats.recovery_code", "\nWIEDERHERSTELLUNGSCODE: {code}"),
+    ("stats.recovery_advice", "Schreib ihn auf und bewahr ihn sicher auf (z. B. bei der Lehrkraft).\nEr entsperrt die Statistik, falls die Passphrase vergessen wird, und wird nicht noch einmal angezeigt."),
+    ("stats.recovery_written", "\nDrück Enter, sobald du ihn aufgeschrieben hast..."),
+    ("stats.decrypt_prompt", "Diese Statistik wieder unverschlüsselt speichern? (j/N): "),
+    ("stats.leaderboard_failed", "⚠️ Die Bestenliste in {path} ließ sich nicht aktualisieren: {error}."),
+    // Networked games
+    ("net.lost_held", "📡 {name} hat die Verbindung verloren. Der Platz wird {seconds} s freigehalten."),
+    ("net.lost", "📡 {name} hat die Verbindung verloren."),
+    ("net.no_seat", "in diesem Spiel wartet kein Platz auf dich"),
+    ("net.you_left", "du hast dieses Spiel verlassen"),
+    ("net.welcome_back", "Willkommen zurück! Deine bisherigen Tipps:"),
+    ("net.back", "🔌 {name} ist wieder da."),
+    ("net.under_way", "Das Spiel läuft schon, du siehst es ab jetzt."),
+    ("net.started", "das Spiel hat schon begonnen"),
+    ("net.not_your_turn_host", "Du bist nicht dran. /host zeigt, was du als Gastgeber tun kannst."),
+    ("net.timed_out_you", "⏳ Du hast nicht rechtzeitig geantwortet, dein Zug entfällt."),
+    ("net.timed_out", "⏳ {name} hat {minutes} Minuten lang nicht geantwortet, der Zug entfällt."),
+    ("net.waiting_rejoin", "⏳ Warte bis zu {seconds} s darauf, dass {name} zurückkommt..."),
+    ("net.not_back", "⏭ {name} ist nicht rechtzeitig zurückgekommen, die Züge entfallen, bis es so weit ist."),
+    ("net.no_discovery", "(Dieses Spiel taucht bei der Suche im lokalen Netz nicht auf: {error}.)"),
+    ("net.left_you", "Du hast das Spiel verlassen."),
+    ("net.left", "👋 {name} hat das Spiel verlassen."),
+    ("net.guess_prompt", "Runde {round}, dein Tipp (/say zum Chatten, /quit zum Verlassen): "),
+    ("net.listen_failed", "Auf Port {port} ließ sich nicht lauschen: {error}"),
+    ("net.hosting", "--- 🌐 Gastgeber: Eigene Geheimzahlen ---"),
+    ("net.join_hint", "Mitspielende finden das Spiel mit `join` oder kommen so dazu: join <Adresse dieses Rechners>:{port}"),
+    ("net.begins", "Das Spiel beginnt! Plätze: {names}. Alle knacken ihren eigenen Code."),
+    ("net.sealed", "🔒 Jeder Code ist versiegelt, und die Siegel werden am Ende mit den Codes verglichen."),
+    ("net.handover", "🌐 Fällt der Gastgeber aus, übernimmt jemand aus dem Publikum das Spiel. Dieser Rechner bekommt eine Kopie des Spiels samt aller Codes und genießt deshalb so viel Vertrauen wie der des Gastgebers. Spieler bekommen sie nie."),
+    ("net.handover_needs_spectators", "--handover übergibt das Spiel an jemanden aus dem Publikum und braucht deshalb --spectators von mindestens 1."),
+    ("net.no_successor", "🌐 Niemand schaut zu, also kann niemand das Spiel übernehmen, falls du ausfällst. Die Übergabe greift, sobald jemand zuschaut."),
+    ("net.spectator_at", "der Zuschauer unter {address}"),
+    ("net.hosting_unseated", "Du leitest dieses Spiel ohne eigenen Platz, hier gehen nur die Gastgeberbefehle. Mit /host siehst du sie."),
+    ("net.turn", "Runde {round}: {name} ist dran."),
+    ("net.watch_guess", "{name} hat {guess} getippt: {score}"),
+    ("net.cracked", "🔓 {name} hat den Code mit {count} Tipp(s) geknackt!"),
+    ("net.guessed", "{name} hat Tipp #{number} abgegeben."),
+    ("net.seed", "Seed: {seed}"),
+    ("net.host_commands", "Als Gastgeber kannst du jederzeit eingeben: /kick <Name> entfernt jemanden, /delay <Sekunden> wartet vor jedem Zug, /hold und /resume halten das Spiel an und setzen es fort, /end deckt die Codes auf und beendet es, und /host zeigt diese Liste wieder."),
+    ("net.held", "⏸ Das Spiel ist angehalten, bis der Gastgeber weitermacht."),
+    ("net.delay_off", "⏱ Der Gastgeber hat die Wartezeit vor jedem Zug abgeschafft."),
+    ("net.delay_on", "⏱ Der Gastgeber hat vor jedem Zug eine Wartezeit von {seconds} s eingestellt."),
+    ("net.delay_usage", "/delay erwartet eine Zahl von Sekunden von 0 bis {max}."),
+    ("net.already_held", "Das Spiel ist schon angehalten. Weiter geht es mit /resume."),
+    ("net.hold", "⏸ Der Gastgeber hat das Spiel angehalten."),
+    ("net.not_held", "Das Spiel ist nicht angehalten."),
+    ("net.resumed", "▶ Der Gastgeber hat das Spiel fortgesetzt."),
+    ("net.ended", "🏁 Der Gastgeber hat das Spiel hier beendet, alle Codes werden jetzt aufgedeckt."),
+    ("net.kick_unknown", "Niemand namens '{name}' sitzt am Tisch. Gib /kick <Name> ein."),
+    ("net.kick_self", "Das ist dein eigener Platz. Mit /quit in deinem Zug verlässt du das Spiel."),
+    ("net.kick_gone", "{name} hat das Spiel schon verlassen."),
+    ("net.kicked_you", "🚪 Der Gastgeber hat dich aus dem Spiel genommen."),
+    ("net.kicked", "🚪 Der Gastgeber hat {name} aus dem Spiel genommen."),
+    ("net.backup_unreadable", "Die Sicherung des Gastgebers lässt sich nicht lesen, das Spiel kann nicht weitergehen."),
+    ("net.take_over_failed", "Das Spiel ließ sich nicht übernehmen: {error}."),
+    ("net.taken_over_you", "🌐 {name} ist weg, du bist ab jetzt Gastgeber. Die Plätze der anderen werden {seconds} s freigehalten, bis sie zurück sind."),
+    ("net.searching", "Suche Spiele im lokalen Netz..."),
+    ("net.search_failed", "Das lokale Netz ließ sich nicht durchsuchen: {error}."),
+    ("net.none_found", "Keine Spiele gefunden. Frag den Gastgeber nach der Adresse und nutze: join <host>[:<port>]"),
+    ("net.games", "Spiele, die auf Mitspielende warten:"),
+    ("net.needs_newer", " (braucht einen neueren Build)"),
+    ("net.host_needs_newer", " (der Gastgeber braucht einen neueren Build)"),
+    ("net.game_row", "  {number}. Spiel von {host} unter {address} - {rules} - {seats} freie(r) Platz/Plätze{note}"),
+    ("net.pick_prompt", "Spiel wählen (Enter bricht ab): "),
+    ("net.pick_invalid", "Bitte eine Zahl von 1 bis {max} eingeben."),
+    ("net.seal_changed", "⚠️ Der Gastgeber hat für den Code von {name} ein anderes Siegel geschickt als zu Beginn. Der Code wurde womöglich geändert."),
+    ("net.no_reveals", "⚠️ Das Spiel endete, ohne dass der Gastgeber Codes aufgedeckt hat, also lässt sich keiner mit seinem Siegel vergleichen."),
+    ("net.not_revealed", "der Code von {name} wurde nicht aufgedeckt"),
+    ("net.wrong_secret", "der Code von {name}, {secret}, ist nicht der, der zu Beginn versiegelt wurde"),
+    ("net.never_sealed", "der Code von {name} wurde nie versiegelt"),
+    ("net.wrong_feedback", "die Bewertung deines Tipps {guess} passt nicht zu deinem Code"),
+    ("net.seals_ok", "✅ Jeder Code passt zu seinem Siegel vom Spielbeginn."),
+    ("net.seals_ok_feedback", "✅ Jeder Code passt zu seinem Siegel vom Spielbeginn, und alle deine Bewertungen stimmen."),
+    ("net.seals_bad", "⚠️ Die Codes des Gastgebers halten der Prüfung nicht stand:"),
+    ("net.watching", "--- 🌐 Zuschauen bei {address} ---"),
+    ("net.joining", "--- 🌐 Beitreten bei {address} ---"),
+    ("net.connection_lost", "Die Verbindung zum Gastgeber ist abgerissen ({error})."),
+    ("net.room_prompt", "Raumcode (den hat der Gastgeber): "),
+    ("net.rejoin_prompt", "Du hast hier noch einen Platz als {name}. Zurück an den Platz? (J/n): "),
+    ("net.closed", "Die Verbindung zum Gastgeber wurde geschlossen."),
+    ("net.checking_host", "📡 Verbindung zum Gastgeber verloren. Mal sehen, ob er wirklich weg ist..."),
+    ("net.reconnecting", "📡 Verbindung zum Gastgeber verloren. Versuche, wieder hineinzukommen..."),
+    ("net.taken_over", "🌐 {name} hat das Spiel übernommen."),
+    ("net.cant_join", "Diesem Spiel kann nicht beigetreten werden: {error}"),
+    ("net.seated", "Platz genommen als {name}."),
+    ("net.refused", "Der Gastgeber hat uns abgewiesen: {reason}."),
+    ("net.successor", "🌐 Wenn der Gastgeber geht, übernimmt {name} das Spiel."),
+    ("net.successor_you", "🌐 Wenn der Gastgeber geht, übernimmst du das Spiel."),
+    ("net.watch_only", "Du schaust nur zu, hier gibt es nichts einzutippen. Mit /quit hörst du auf."),
+    ("net.say_usage", "Schreib deine Nachricht hinter /say, etwa: /say viel Glück!"),
+    ("net.not_your_turn", "Du bist noch nicht dran. Mit /say <Nachricht> kannst du chatten."),
+    ("net.unreachable", "Unter {address} war kein Gastgeber zu erreichen: {error}"),
+    ("net.rejoin_failed", "Zurück ins Spiel ging es nicht. Sobald der Gastgeber erreichbar ist, bietet `join {address}` den Platz wieder an, wenn das Spiel noch läuft."),
+    // Rooms
+    ("room.version_mismatch", "der Host spricht Version {host} des Protokolls und du Version {yours}; aktualisiere die ältere"),
+    ("room.wrong_code", "das ist nicht der Code dieses Raums"),
+    ("room.no_spectators", "dieses Spiel lässt keine Zuschauer zu"),
+    ("room.spectators_full", "das Spiel hat schon seine {cap} Zuschauer"),
+    ("room.somewhere", "irgendwo"),
+    ("room.spectator_arrived", "👀 Ein Zuschauer schaut von {address} aus zu ({count} von {cap})."),
+    ("room.too_many", "der Host hat zu viele offene Verbindungen; versuch es später noch einmal"),
+    ("room.title", "--- 🚪 Raum {code} ---"),
+    ("room.game", "Spiel: Eigene Geheimzahlen, {rules}"),
+    ("room.host_seat", "  Platz 1: {name} (Host)"),
+    ("room.seat_taken", "  Platz {seat}: {name}"),
+    ("room.seat_ready", "  Platz {seat}: {name}, bereit"),
+    ("room.seat_open", "  Platz {seat}: frei"),
+    ("room.watching", "  Zuschauer: {count} von {cap}"),
+    ("room.not_started", "das Spiel hat noch nicht begonnen, also tritt mit dem Raumcode bei"),
+    ("room.left", "👋 {name} hat den Raum verlassen."),
+    ("room.full", "der Raum ist voll"),
+    ("room.joined", "🪑 {name} ist von {address} aus beigetreten und sitzt auf Platz {seat}."),
+    ("room.ready", "✅ {name} ist bereit."),
+    ("room.unready", "{name} ist doch nicht bereit."),
+    ("room.moved", "🪑 {name} hat sich auf Platz {seat} gesetzt."),
+    ("room.no_open_seats", "Es gibt keinen freien Platz, auf den du wechseln kannst."),
+    ("room.open_seats", "Freie Plätze: {seats}."),
+    ("room.left_you", "Du hast den Raum verlassen."),
+    ("room.no_answer", "{name} hat nicht rechtzeitig geantwortet und bleibt draußen."),
+    ("room.pass_code", "Gib den Raumcode {code} an die Spieler weiter, die du erwartest."),
+    ("room.start_prompt", "Alle hier sind bereit. Spiel starten? (j/N, nein wartet auf weitere Spieler): "),
+    ("room.not_all_ready", "Nicht mehr alle sind bereit."),
+    ("room.watch_help", "Du schaust zu. Jeder Tipp erscheint hier mit seiner Bewertung, sobald er abgegeben wird, aber keine Geheimzahl. Tippe /quit, um nicht mehr zuzuschauen."),
+    ("room.help", "Während du wartest, tippe `seat <n>`, um auf einen freien Platz zu wechseln, `ready`, wenn du spielbereit bist (noch einmal, um es zurückzunehmen), /say <Nachricht> zum Chatten oder /quit zum Verlassen."),
+    // Session
+    ("session.trivia", "|       QUIZ ZUR SITZUNG       |"),
+    ("session.secrets", "Geheimzahlen in dieser Sitzung: {count}"),
+    ("session.hottest", "🔥 Heißeste Ziffer: {digits} (in {count} Geheimzahl(en))"),
+    ("session.every_digit", "🧊 Jede Ziffer kam mindestens einmal vor."),
+    ("session.never", "🧊 Nie vorgekommen: {digits}"),
+    ("session.first_digit", "🚪 Häufigste erste Ziffer: {digits} ({count}-mal)"),
+    ("session.fastest", "⚡ Schnellste Lösung: {name} hat {secret} in {count} Tipp(s) geknackt ({mode})"),
+    ("session.all_time_best", "   (Bestes gewonnenes Spiel von {name} in der Statistik: {count} Tipp(s))"),
+    ("session.nobody", "⚡ In dieser Sitzung hat niemand einen Code geknackt. Nächstes Mal!"),
+    ("session.longest", "🐢 Längste Lösung: {name} brauchte {count} Tipps für {secret} ({mode})"),
+    ("session.summary", "|     SITZUNG IM ÜBERBLICK     |"),
+    ("session.games", "Gespielte Partien: {count}"),
+    ("session.placements", "Platzierungen:"),
+    ("session.did_not_finish", "nicht fertig"),
+    ("session.placement_row", "  {name}: {places}"),
+    ("session.total_guesses", "Tipps insgesamt: {count}"),
+    ("session.fastest_game", "⚡ Schnellste Lösung: {name} in {count} Tipp(s), Partie {game}"),
+    ("session.longest_game", "🐢 Längste Partie: Partie {game}, {time}"),
+    // Coach
+    ("coach.excellent", "Hervorragender Test: so aufschlussreich, wie ein Tipp hier nur sein kann."),
+    ("coach.good", "Guter Tipp: die Bewertung teilt die Möglichkeiten gut auf."),
+    ("coach.decent", "Ordentlich, aber ein anderer Tipp hätte dir mehr verraten."),
+    ("coach.weak", "Schwacher Test: viele der möglichen Codes ergäben alle dieselbe Bewertung."),
+    ("coach.poor", "Dieser Tipp kann die übrigen Codes kaum unterscheiden."),
+    ("coach.rating", "🎓 Coach: {rating}/100. {comment}"),
+    // Spectator view
+    ("spectate.write_failed", "⚠️ Der Zuschauer-Feed ließ sich nicht schreiben ({error}). Zuschauer sehen den Rest dieser Sitzung nicht."),
+    ("spectate.started", "🎲 Ein neues Spiel hat begonnen!"),
+    ("spectate.took_guess", "{name} hat Tipp Nr. {number} abgegeben."),
+    ("spectate.sat_out", "⏸️ {name} hat eine Runde ausgesetzt (Handicap)."),
+    ("spectate.timed_out", "⏰ {name} ist die Zeit ausgegangen."),
+    ("spectate.out", "🛑 {name} hat keine Tipps mehr."),
+    ("spectate.finished", "🏁 {name} hat den eigenen Code in {count} Tipps geknackt und landet auf Platz {place}!"),
+    ("spectate.over", "Das Spiel ist vorbei. Warte auf das nächste..."),
+    ("spectate.title", "--- 📺 Zuschaueransicht ---"),
+    ("spectate.waiting", "Warte auf den Beginn eines Spiels..."),
+    ("spectate.final", "Endstand"),
+    ("spectate.round", "Runde {round}"),
+    ("spectate.place", "🏁 {place}"),
+    ("spectate.out_status", "🛑 keine Tipps mehr"),
+    ("spectate.best", "bestes {score}"),
+    ("spectate.no_guesses", "noch keine Tipps"),
+    ("spectate.row", "  {name}  {count} Tipp(s)  {bar}  {status}"),
+    ("spectate.recent", "Letzte Ereignisse:"),
+    ("spectate.open_failed", "Der Zuschauer-Feed {path} ließ sich nicht öffnen: {error}"),
+    ("spectate.read_failed", "Der Zuschauer-Feed ließ sich nicht lesen: {error}"),
+    // Full-screen frontend
+    ("tui.bot_guesses", "Tipps von {name}"),
+    ("tui.your_guesses", "Deine Tipps"),
+    ("tui.no_guesses", "(noch keine Tipps)"),
+    ("tui.scoreboard", "Punktestand"),
+    ("tui.place", "🏁 {place}"),
+    ("tui.dnf", "n. b."),
+    ("tui.guesses", "{count} Tipp(s)"),
+    ("tui.title", "─ Runde {round} · {name} ist dran "),
+    ("tui.pass", "\n\n   🔒 Gib die Tastatur an {name} weiter."),
+    ("tui.pass_prompt", "   {name}, drück Enter, wenn niemand sonst hinschaut..."),
+    ("tui.screen_reader", "Die Vollbildansicht eignet sich nicht für Screenreader, deshalb gibt es stattdessen das klassische Spiel."),
+    ("tui.header", "--- 🖥️ Eigene Geheimzahlen (Vollbild) ---"),
+    ("tui.intro", "Alle versuchen um die Wette, ihren eigenen versteckten Code zu knacken. Richte unten den Tisch ein."),
+    ("tui.begin", "Das Raten kann beginnen!"),
+    ("tui.sits_out", "⏸️ {name} setzt diese Runde aus (Handicap)."),
+    ("tui.bot_guessed", "🤖 {name} hat {guess} getippt und {score} bekommen."),
+    ("tui.cracked", "🎉 In {count} Tipps geknackt! Platz {place}."),
+    ("tui.cracked_public", "🏁 {name} hat den eigenen Code geknackt und landet auf Platz {place}!"),
+    ("tui.remaining", "{count} Code(s) passen noch zu deinen Bewertungen."),
+    ("tui.end_turn", "Mit Enter deinen Zug beenden..."),
+    ("common.game_length", "Spieldauer: {time}"),
+    // Replays
+    ("replay.no_data_dir", "Es gibt kein Datenverzeichnis, deshalb werden keine Wiederholungen aufbewahrt."),
+    ("replay.none", "Noch keine Wiederholungen. Jedes Spiel mit eigenen Geheimzahlen, das bis zur Endwertung kommt, wird in {dir} aufbewahrt."),
+    ("replay.list", "Wiederholungen in {dir}:"),
+    ("replay.list_hint", "Eine ansehen mit `replay <Name>`; mit `--step` geht es mit Enter weiter."),
+    ("replay.step_prompt", "(Enter für den nächsten Zug) "),
+    ("replay.read_failed", "Die Wiederholung {path} ließ sich nicht lesen: {error}"),
+    ("replay.unplayable", "{path} lässt sich nicht abspielen: {error}."),
+    ("replay.title", "--- 🎬 Wiederholung (Seed {seed}) ---"),
+    ("replay.players", "Spieler: {names}. Jede Geheimzahl bleibt verborgen, bis sie geknackt ist."),
+    ("replay.round", "\nRunde {round}"),
+    ("replay.cracked", "  🔓 {name} hat die eigene Geheimzahl geknackt: {secret}."),
+    // Solver assistant
+    ("solve.one_left", "🔎 Nur ein Code passt: {code}."),
+    ("solve.listed", "🔎 {count} Codes passen: {codes}"),
+    ("solve.count", "🔎 {count} Codes passen."),
+    ("solve.title", "--- 🧠 Löse-Assistent ({strategy}) ---"),
+    ("solve.intro", "Tippe jeden gespielten Tipp mit seiner D,P-Bewertung ein, etwa \"0123 2,1\"."),
+    ("solve.intro_more", "Nur eine Bewertung heißt, dass du den Vorschlag gespielt hast. /undo nimmt die letzte Zeile zurück, /quit beendet."),
+    ("solve.suggestion", "\n💡 Vorgeschlagener Tipp Nr. {number}: {guess}"),
+    ("solve.prompt", "Tipp und Bewertung: "),
+    ("solve.undone", "↩️ {guess} -> {score} zurückgenommen."),
+    ("solve.nothing_to_undo", "Es gibt noch nichts zurückzunehmen."),
+    ("solve.cracked", "🎉 Mit {guess} in {count} Tipps geknackt."),
+    ("solve.no_fit", "🚩 Kein Code passt zu {guess} -> {score} und den früheren Bewertungen, deshalb wurde die Zeile ausgelassen."),
+    // Profile export and import
+    ("profile.nothing_to_export", "Hier gibt es kein Profil und kein gespeichertes Ergebnis für '{name}'."),
+    ("profile.bad_file_name", "'{name}' taugt nicht als Dateiname; gib nach dem Namen einen an."),
+    ("common.write_failed", "{path} ließ sich nicht schreiben: {error}"),
+    ("profile.exported", "Profil von {name}, {achievements} Erfolg(e) und {results} Ergebnis(se) nach {path} exportiert."),
+    ("profile.import_hint", "Auf dem anderen Rechner: profile import {path}"),
+    ("profile.other_preferences", "{name} hat hier schon ein Profil, mit anderen Einstellungen."),
+    ("profile.here", "  Hier:        {preferences}"),
+    ("profile.imported_preferences", "  Importiert:  {preferences}"),
+    ("profile.replace_prompt", "Stattdessen die importierten Einstellungen verwenden? (j/N): "),
+    ("profile.conflict", "  {when} ({mode}): hier Platz {here_place} in {here_guesses} Tipps, importiert Platz {place} in {guesses} Tipps"),
+    ("profile.save_results_failed", "Die importierten Ergebnisse ließen sich nicht speichern: {error}."),
+    ("profile.added", "{added} Ergebnis(se) hinzugefügt, {duplicates} schon vorhandene übersprungen."),
+    ("profile.conflicts", "{count} Ergebnis(se) widersprechen dem, was dieser Rechner über dasselbe Spiel weiß, deshalb bleiben die von diesem Rechner:"),
+    ("common.read_failed", "{path} ließ sich nicht lesen: {error}"),
+    ("profile.cant_import", "{path} lässt sich nicht importieren: {error}."),
+    ("profile.bad_name", "'{name}' kann hier nicht als Profil gespeichert werden; importiere es mit --as unter einem anderen Namen."),
+    ("profile.import_save_failed", "Das Profil von {name} ließ sich nicht speichern: {error}."),
+    ("profile.imported", "Profil von {name} importiert ({preferences}); insgesamt {count} Erfolg(e)."),
+    // Bot remarks
+    ("bot.digit_list", "{rest} und {last}"),
+    ("bot.solved", "Jede Ziffer an ihrem Platz. Geschafft!"),
+    ("bot.none_match", "Keine von {digits} hat gepasst, also streiche ich sie alle."),
+    ("bot.drop_one", "{digits} kann nach allem, was ich gesehen habe, nicht in meinem Code sein, also streiche ich sie."),
+    ("bot.drop_many", "{digits} können nach allem, was ich gesehen habe, nicht in meinem Code sein, also streiche ich sie."),
+    ("bot.all_matched", "Jede Ziffer hat gepasst, also sind alle anderen Ziffern raus."),
+    ("bot.rules_out", "Damit fällt auch {digits} weg."),
+    ("bot.confirmed", "{digits} muss irgendwo in meinem Code sein."),
+    ("bot.fixed", "{digit} muss an Stelle {position} stehen."),
+    ("bot.one_left", "Jetzt passt nur noch ein Code zu allem, was ich gesehen habe!"),
+    ("bot.nothing_certain", "Über einzelne Ziffern weiß ich noch nichts sicher, aber nur noch {count} Codes passen."),
+    ("bot.remark", "💬 {name}: {remark}"),
+    // Analysis
+    ("analyze.trace", "--- {strategy}-Lösung für die Geheimzahl {secret} ---"),
+    ("analyze.step", "Tipp {number}: {guess} -> {score} | {count} Kandidat(en) übrig"),
+    ("analyze.overview", "--- {strategy}-Löser über alle {count} Geheimzahlen ---"),
+    ("analyze.building", "(Der ganze Entscheidungsbaum wird gebaut, das kann einen Moment dauern...)"),
+    ("analyze.depth", "  {count} Tipp(s): {secrets} Geheimzahlen"),
+    ("analyze.summary", "Schnitt: {average} Tipps | Schlimmster Fall: {worst} Tipps"),
+    ("analyze.comparison", "--- Strategievergleich über alle {count} Geheimzahlen ---"),
+    ("analyze.building_all", "(Alle Entscheidungsbäume werden gebaut, das kann einen Moment dauern...)"),
+    ("analyze.guesses", "Tipps"),
+    ("analyze.average", "Schnitt"),
+    ("analyze.worst", "Schlimmst"),
+    ("analyze.solver_grade", "Spiel wie ein Löser"),
+    ("analyze.sharp", "Scharf"),
+    ("analyze.solid", "Solide"),
+    ("analyze.getting_there", "Auf dem Weg"),
+    ("analyze.room_to_improve", "Da geht noch was"),
+    ("analyze.review", "\n--- 🔬 Tipp-Rückblick: {name} ---"),
+    ("analyze.reviewing", "(Jeder Tipp wird mit dem besten möglichen verglichen, das kann einen Moment dauern...)"),
+    ("analyze.col_turn", "Zug"),
+    ("analyze.col_guess", "Tipp"),
+    ("analyze.col_feedback", "Bewertung"),
+    ("analyze.col_before", "Vorher"),
+    ("analyze.col_after", "Nachher"),
+    ("analyze.col_avg_left", "Ø übrig"),
+    ("analyze.col_best", "Bester"),
+    ("analyze.col_rating", "Note"),
+    ("analyze.same", "(gleich)"),
+    ("analyze.efficiency", "\nEffizienz insgesamt: {percent} % ({rating})"),
+    ("analyze.avg_left_note", "\"Ø übrig\" ist, wie viele Codes ein Tipp im Schnitt übrig lässt; 100 % heißt, er war so gut wie der beste Tipp."),
+    ("analyze.too_many", "Es gibt {count} Geheimzahlen dieser Länge, zu viele, um jeden Entscheidungsbaum zu bauen. Gib stattdessen einen Code zum Nachverfolgen an oder spiel mit --length 4 oder kürzer."),
+    ("simulate.title", "\n--- {strategy} ({count} Spiele, {seconds} s) ---"),
+    ("simulate.depth", "  {count} Tipp(s): {games}  {bar}"),
+    ("simulate.start", "Simuliere {count} Spiel(e) je Strategie (Seed {seed})."),
+    // Servers and tools
+    ("common.create_failed", "{path} ließ sich nicht anlegen: {error}"),
+    ("server.unknown_address", "einer unbekannten Adresse"),
+    ("server.full", "Der Server ist voll. Versuch es später noch einmal."),
+    ("telnet.serving", "--- 🌐 Eigene Geheimzahlen als reiner Text ---"),
+    ("telnet.connect_hint", "Spieler verbinden sich mit `nc <Adresse dieses Rechners> {port}` oder telnet. Strg+C beendet."),
+    ("telnet.connected", "🔌 Ein Spieler hat sich von {address} aus verbunden."),
+    ("telnet.disconnected", "👋 Der Spieler von {address} hat die Verbindung getrennt."),
+    ("telnet.line_too_long", "Diese Zeile ist zu lang, deshalb wurde das Spiel beendet."),
+    ("telnet.idle", "Zehn Minuten lang wurde nichts eingegeben, deshalb wurde das Spiel beendet."),
+    ("ssh.no_data_dir", "⚠ Es gibt kein Datenverzeichnis, deshalb ist der Host-Schlüssel bei jedem Serverstart neu."),
+    ("ssh.bad_host_key", "{path} ist kein Host-Schlüssel dieses Servers. Lösch ihn, um einen neuen anzulegen."),
+    ("ssh.unknown_command", "'{name}' führt dieser Server nicht aus. Versuch eins von: {names}."),
+    ("ssh.bad_option", "{name} nimmt hier '{option}' nicht an."),
+    ("ssh.serving", "--- 🔒 Eigene Geheimzahlen über SSH ---"),
+    ("ssh.connect_hint", "Spieler verbinden sich mit `ssh -p {port} play@<Adresse dieses Rechners>`. Strg+C beendet."),
+    ("ssh.fingerprint", "Der Fingerabdruck des Host-Schlüssels ist {fingerprint}."),
+    ("ssh.connected", "🔌 {name} hat sich von {address} aus verbunden."),
+    ("ssh.disconnected", "👋 {name} ({address}) hat die Verbindung getrennt."),
+    ("api.begins", "🎲 Spiel {id} beginnt: {names}."),
+    ("api.over", "🏁 Spiel {id} ist nach {count} Runde(n) vorbei."),
+    ("api.open", "🌐 Spiel {id} ist offen für {seats} Spieler."),
+    ("api.abandoned", "⏳ Spiel {id} wurde verlassen und ist weggeräumt."),
+    ("api.seated", "👋 {name} sitzt jetzt in Spiel {id} ({count} von {seats})."),
+    ("api.left", "🚪 {name} hat Spiel {id} verlassen."),
+    ("api.cracked", "🔓 {name} hat in Spiel {id} den eigenen Code geknackt."),
+    ("api.serving", "--- 🌐 Eigene Geheimzahlen über HTTP ---"),
+    ("api.hint", "Die API liegt unter http://<Adresse dieses Rechners>:{port}/games. Strg+C beendet."),
+    ("export.games", "{count} Ergebniszeilen nach {path} geschrieben."),
+    ("export.skipped", "⚠️ Eine Wiederholung übersprungen. {error}"),
+    ("export.turns", "{count} Zugzeilen nach {path} geschrieben."),
+    ("json.log_failed", "⚠️ Das Ereignisprotokoll ließ sich nicht schreiben ({error}). Der Rest dieser Sitzung wird nicht protokolliert."),
+    ("config.write_failed", "Die Datei mit den Voreinstellungen ließ sich nicht schreiben: {error}"),
+    ("config.written", "Voreinstellungen nach {path} geschrieben. Entferne die # vor einer Einstellung, um sie zu verwenden."),
+    ("config.unknown_theme", "Unbekanntes Farbschema '{name}'. Verfügbare Farbschemata: {names}"),
+    ("config.default_theme_missing", "⚠️ Das voreingestellte Farbschema wird übergangen (es gibt keins namens {name})."),
+    ("config.event_log_failed", "Das Ereignisprotokoll {path} ließ sich nicht öffnen: {error}"),
+    // Error messages
+    ("net.no_greeting", "Der Gastgeber hat uns nicht begrüßt. Er ist kein Spielgastgeber oder eine ältere Version, die aktualisiert werden muss."),
+    ("net.version_mismatch", "Der Gastgeber spricht Version {theirs} des Spielprotokolls, diese Version spricht Version {ours}. Aktualisiere die ältere."),
+    ("net.not_a_host", "Das ist kein Spielgastgeber, oder eine ältere Version, die aktualisiert werden muss."),
+    ("wire.unknown", "unbekannte Nachricht '{tag}'"),
+    ("wire.malformed", "eine '{tag}'-Nachricht, die sich nicht lesen lässt"),
+    ("save.read_failed", "Der Spielstand {path} konnte nicht gelesen werden: {error}"),
+    ("save.cant_resume", "Von {path} kann nicht weitergespielt werden: {error}."),
+    ("snapshot.damaged", "der Spielstand ist beschädigt ({reason})"),
+    ("snapshot.wrong_kind", "erwartet war ein Spielstand der Art {expected}, gefunden wurde einer der Art {found}"),
+    ("store.io", "auf die Statistikdatei konnte nicht zugegriffen werden: {error}"),
+    ("store.corrupt", "die Statistikdatei ist beschädigt ({reason})"),
+    ("store.wrong_passphrase", "diese Passphrase oder dieser Wiederherstellungscode stimmt nicht"),
+    ("store.session_only", "die Statistik wird in dieser Sitzung nicht gespeichert"),
+    ("store.unsupported", "diese Art von Statistikspeicher unterstützt keine {feature}"),
+    ("store.encryption", "Verschlüsselung"),
+    ("store.database", "die Statistikdatenbank hat ein Problem gemeldet ({reason})"),
+    ("rules.incompatible", "dieses Spiel braucht Funktionen, die diese Version nicht hat:"),
+    ("rules.needs_variant", "die Spielvariante '{id}'"),
+    ("rules.needs_variant_version", "Version {required} der Variante '{variant}' (diese Version hat Version {supported})"),
+    ("rules.needs_rules_version", "Regelversion {required} (diese Version hat Version {supported})"),
+    ("rules.needs_code_length", "{length}-stellige Codes (diese Version kann {min} bis {max})"),
+    ("rules.needs_repeats", "Codes mit wiederholten Ziffern"),
+    ("rules.needs_option", "die Regeloption '{option}'"),
+    ("rules.invalid_spec", "ungültige Spielbeschreibung: {reason}"),
+    ("engine.launch_failed", "konnte nicht gestartet werden ({reason})"),
+    ("engine.gone", "hat aufgehört"),
+    ("engine.timed_out", "hat nicht innerhalb von {seconds} Sekunden geantwortet"),
+    ("engine.invalid", "hat mit {reason} geantwortet"),
+    ("clue.violated", "Hinweis #{clue} war {code} -> {score} (D,P): "),
+    ("clue.digits", "der Geheimcode hat {digits} Ziffer(n) mit {code} gemeinsam, dein Tipp aber {would}"),
+    ("clue.places", "{places} Ziffer(n) von {code} stehen an der richtigen Stelle, dein Tipp trifft aber {would} davon"),
+];
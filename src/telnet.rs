@@ -0,0 +1,199 @@
+use std::env;
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::strings::{fill, tr};
+
+// --- Line Server ---
+// `serve --telnet` takes plain TCP connections, as `nc <server> 7777` or
+// `telnet <server> 7777` make them, for casual play on a LAN from any
+// machine with nothing installed. Each connection gets a game of its own:
+// this program runs with the connection as its input and output, so it
+// sees a pipe and plays the way it does when piped (plain text, a line at
+// a time, no escape codes). The menus, modes and stats are all the usual
+// ones, and stats go to the server's data directory.
+//
+// Lines from the client may end in CR LF or LF, and telnet's option
+// negotiation is dropped before the game sees it. Lines to the client end
+// in CR LF, which both nc and telnet show properly. Anyone who can reach
+// the port can play, so each game runs as a guest (`--guest`): nothing asks
+// for a file path, and the settings and stats encryption are shut off. A
+// connection that sends nothing for ten minutes is closed, so idle ones
+// can't hold every session open, and so is one that sends a line longer
+// than 4 KiB or stops taking the game's output for ten seconds.
+
+/// The port `serve --telnet` listens on unless told otherwise.
+pub const DEFAULT_TELNET_PORT: u16 = 7777;
+
+/// The most games the server runs at once.
+const MAX_SESSIONS: usize = 32;
+
+/// How long a connection may go without sending anything before it's closed.
+const IDLE_LIMIT: Duration = Duration::from_secs(10 * 60);
+
+/// How long the game's output may wait on a client that isn't reading it.
+const WRITE_WAIT: Duration = Duration::from_secs(10);
+
+/// The longest line a client may send.
+const MAX_LINE: usize = 4 * 1024;
+
+const IAC: u8 = 255; // Telnet's "interpret as command" byte
+const SB: u8 = 250; // Starts a subnegotiation, which runs until IAC SE
+const SE: u8 = 240;
+const WILL: u8 = 251; // WILL, WONT, DO and DONT each take an option byte
+const DONT: u8 = 254;
+
+/// Where the filter is in telnet's command syntax.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TelnetState {
+    Data,
+    Command,            // After an IAC
+    Option,             // After WILL, WONT, DO or DONT
+    Subnegotiation,     // Inside SB ... IAC SE
+    SubnegotiationIac, // An IAC inside a subnegotiation
+}
+
+/// Strips telnet commands and carriage returns from what a client sends,
+/// leaving the lines the game reads.
+struct InputFilter {
+    state: TelnetState,
+    line: usize, // What's been kept since the last line break
+}
+
+impl InputFilter {
+    /// The text in `data`, or None once a line runs past `MAX_LINE`.
+    fn filter(&mut self, data: &[u8]) -> Option<Vec<u8>> {
+        let mut text = Vec::with_capacity(data.len());
+        for &byte in data {
+            self.state = match (self.state, byte) {
+                (TelnetState::Data, IAC) => TelnetState::Command,
+                // Telnet sends Enter as CR LF or CR NUL, nc as LF
+                (TelnetState::Data, b'\r' | 0) => TelnetState::Data,
+                (TelnetState::Data, _) => {
+                    text.push(byte);
+                    TelnetState::Data
+                }
+                (TelnetState::Command, IAC) => {
+                    text.push(IAC);
+                    TelnetState::Data
+                }
+                (TelnetState::Command, SB) => TelnetState::Subnegotiation,
+                (TelnetState::Command, WILL..=DONT) => TelnetState::Option,
+                (TelnetState::Command | TelnetState::Option, _) => TelnetState::Data,
+                (TelnetState::Subnegotiation, IAC) => TelnetState::SubnegotiationIac,
+                (TelnetState::Subnegotiation, _) => TelnetState::Subnegotiation,
+                (TelnetState::SubnegotiationIac, SE) => TelnetState::Data,
+                (TelnetState::SubnegotiationIac, _) => TelnetState::Subnegotiation,
+            };
+        }
+        match text.iter().rposition(|&byte| byte == b'\n') {
+            Some(end) => self.line = text.len() - end - 1,
+            None => self.line += text.len(),
+        }
+        (self.line <= MAX_LINE).then_some(text)
+    }
+}
+
+/// Gives `text` CR LF line endings for the client.
+fn to_network_lines(text: &[u8]) -> Vec<u8> {
+    let mut lines = Vec::with_capacity(text.len() + text.len() / 16);
+    for &byte in text {
+        if byte == b'\n' {
+            lines.push(b'\r');
+        }
+        lines.push(byte);
+    }
+    lines
+}
+
+/// Plays one game session over `stream`, until the game finishes or the
+/// client goes.
+fn serve_connection(mut stream: TcpStream) -> io::Result<()> {
+    let (mut output, writer) = io::pipe()?;
+    let mut command = Command::new(env::current_exe()?);
+    command.args(["--guest", "--no-ansi", "play"]).env_remove("TERM").stdin(Stdio::piped()).stdout(writer.try_clone()?).stderr(writer);
+    let mut child = command.spawn()?;
+    // The game's end of the pipe goes with `command`, so the output ends when the game does
+    drop(command);
+
+    let mut input = child.stdin.take().expect("the game's input is piped");
+    stream.set_write_timeout(Some(WRITE_WAIT))?;
+    let mut from_client = stream.try_clone()?;
+    from_client.set_read_timeout(Some(IDLE_LIMIT))?;
+    thread::spawn(move || {
+        let mut filter = InputFilter { state: TelnetState::Data, line: 0 };
+        let mut buffer = [0; 1024];
+        // Once the client stops sending, the game sees its input run out and stops
+        loop {
+            match from_client.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(read) => {
+                    let Some(text) = filter.filter(&buffer[..read]) else {
+                        let _ = from_client.write_all(format!("\r\n{}\r\n", tr("telnet.line_too_long")).as_bytes());
+                        let _ = from_client.shutdown(Shutdown::Both);
+                        break;
+                    };
+                    if input.write_all(&text).is_err() {
+                        break;
+                    }
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) {
+                        let _ = from_client.write_all(format!("\r\n{}\r\n", tr("telnet.idle")).as_bytes());
+                        let _ = from_client.shutdown(Shutdown::Both);
+                    }
+                    break;
+                }
+            }
+        }
+    });
+
+    let mut buffer = [0; 4096];
+    let result = loop {
+        let read = match output.read(&mut buffer) {
+            Ok(0) => break Ok(()),
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => break Err(e),
+        };
+        if let Err(e) = stream.write_all(&to_network_lines(&buffer[..read])) {
+            // The client has gone, so the game has nobody to play to
+            let _ = child.kill();
+            break Err(e);
+        }
+    };
+    let _ = child.wait();
+    let _ = stream.shutdown(Shutdown::Both);
+    result
+}
+
+/// Runs `serve --telnet [--port <port>]`, taking plain text connections until stopped.
+pub fn run_telnet_serve(port: u16) -> Result<(), String> {
+    let listener = TcpListener::bind(("0.0.0.0", port)).map_err(|e| fill("net.listen_failed", &[("port", &port), ("error", &e)]))?;
+    outln!("{}", tr("telnet.serving"));
+    outln!("{}", fill("telnet.connect_hint", &[("port", &port)]));
+    let sessions = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let Ok(mut stream) = stream else { continue };
+        let address = stream.peer_addr().map(|a| a.ip().to_string()).unwrap_or_else(|_| tr("server.unknown_address").to_string());
+        if sessions.load(Ordering::SeqCst) >= MAX_SESSIONS {
+            let _ = stream.write_all(format!("{}\r\n", tr("server.full")).as_bytes());
+            continue;
+        }
+        sessions.fetch_add(1, Ordering::SeqCst);
+        let sessions = Arc::clone(&sessions);
+        thread::spawn(move || {
+            outln!("{}", fill("telnet.connected", &[("address", &address)]));
+            let _ = serve_connection(stream);
+            outln!("{}", fill("telnet.disconnected", &[("address", &address)]));
+            sessions.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,424 @@
+use std::io::{self, IsTerminal, Read, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::Duration;
+
+#[cfg(feature = "tui")]
+use crossterm::cursor::MoveTo;
+#[cfg(feature = "tui")]
+use crossterm::style::Print;
+#[cfg(feature = "tui")]
+use crossterm::terminal::{Clear, ClearType};
+
+// --- Low-Level Terminal Control ---
+
+/// Switches the terminal to unbuffered, no-echo input while alive, so single
+/// key presses can be read. The previous settings are restored on drop.
+/// Ctrl+C keeps working because signal handling is left on.
+pub struct RawMode {
+    #[cfg(unix)]
+    original: libc::termios,
+}
+
+impl RawMode {
+    /// Enables raw input on stdin, or returns None if stdin isn't a terminal
+    /// (or the platform isn't supported).
+    #[cfg(unix)]
+    pub fn enable() -> Option<RawMode> {
+        // SAFETY: tcgetattr/tcsetattr only read and write the termios struct we pass in
+        unsafe {
+            let mut settings: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut settings) != 0 {
+                return None;
+            }
+            let original = settings;
+
+            settings.c_lflag &= !(libc::ICANON | libc::ECHO);
+            settings.c_cc[libc::VMIN] = 1;
+            settings.c_cc[libc::VTIME] = 0;
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &settings) != 0 {
+                return None;
+            }
+            Some(RawMode { original })
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub fn enable() -> Option<RawMode> {
+        None
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        // SAFETY: restores the settings captured in `enable`
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original);
+        }
+    }
+}
+
+/// Stops the terminal from echoing what's typed while alive, as password
+/// prompts do; input is still read a line at a time. Echo is restored on drop.
+pub struct EchoOff {
+    #[cfg(unix)]
+    original: libc::termios,
+    #[cfg(windows)]
+    original: u32,
+}
+
+impl EchoOff {
+    /// Turns echo off for stdin, or returns None if stdin isn't a terminal.
+    #[cfg(unix)]
+    pub fn enable() -> Option<EchoOff> {
+        // SAFETY: tcgetattr/tcsetattr only read and write the termios struct we pass in
+        unsafe {
+            let mut settings: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut settings) != 0 {
+                return None;
+            }
+            let original = settings;
+            settings.c_lflag &= !libc::ECHO;
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &settings) != 0 {
+                return None;
+            }
+            Some(EchoOff { original })
+        }
+    }
+
+    #[cfg(windows)]
+    pub fn enable() -> Option<EchoOff> {
+        windows_console::disable_input_echo().map(|original| EchoOff { original })
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    pub fn enable() -> Option<EchoOff> {
+        None
+    }
+}
+
+impl Drop for EchoOff {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        // SAFETY: restores the settings captured in `enable`
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original);
+        }
+        #[cfg(windows)]
+        windows_console::restore_input_mode(self.original);
+    }
+}
+
+/// Waits for one byte of input, read straight from stdin's file descriptor.
+/// Meant for raw mode, like `read_byte_timeout`. None at end of input.
+#[cfg(unix)]
+pub fn read_byte() -> Option<u8> {
+    let mut byte = 0u8;
+    // SAFETY: read only touches the one-byte buffer we pass in
+    unsafe { (libc::read(libc::STDIN_FILENO, (&mut byte as *mut u8).cast(), 1) == 1).then_some(byte) }
+}
+
+/// Waits up to `timeout` for one byte of input, read straight from stdin's
+/// file descriptor. Meant for raw mode, where std's line buffer is empty.
+#[cfg(unix)]
+pub fn read_byte_timeout(timeout: Duration) -> Option<u8> {
+    let mut poll = libc::pollfd { fd: libc::STDIN_FILENO, events: libc::POLLIN, revents: 0 };
+    let millis = timeout.as_millis().min(i32::MAX as u128) as i32;
+    // SAFETY: poll and read only touch the struct and the one-byte buffer we pass in
+    unsafe {
+        if libc::poll(&mut poll, 1, millis) <= 0 {
+            return None;
+        }
+        let mut byte = 0u8;
+        (libc::read(libc::STDIN_FILENO, (&mut byte as *mut u8).cast(), 1) == 1).then_some(byte)
+    }
+}
+
+/// The terminal's size in (columns, rows). Falls back to the COLUMNS and
+/// LINES variables, then to 80x24, when stdout isn't a terminal.
+pub fn terminal_size() -> (usize, usize) {
+    #[cfg(feature = "tui")]
+    if io::stdout().is_terminal()
+        && let Ok((columns, rows)) = crossterm::terminal::size()
+        && columns > 0
+        && rows > 0
+    {
+        return (columns as usize, rows as usize);
+    }
+    #[cfg(all(unix, not(feature = "tui")))]
+    // SAFETY: TIOCGWINSZ only writes the winsize struct we pass in
+    unsafe {
+        let mut size: libc::winsize = std::mem::zeroed();
+        if libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) == 0 && size.ws_col > 0 && size.ws_row > 0 {
+            return (size.ws_col as usize, size.ws_row as usize);
+        }
+    }
+    #[cfg(all(windows, not(feature = "tui")))]
+    if let Some(size) = windows_console::window_size() {
+        return size;
+    }
+    let from_env = |key: &str, default: usize| std::env::var(key).ok().and_then(|v| v.parse().ok()).unwrap_or(default);
+    (from_env("COLUMNS", 80), from_env("LINES", 24))
+}
+
+/// True when stdout understands ANSI escape codes. Unix terminals always
+/// do. On Windows this switches on virtual terminal processing the first
+/// time it's asked, which legacy consoles (before Windows 10) don't support.
+#[cfg(not(feature = "tui"))]
+fn ansi_supported() -> bool {
+    static SUPPORTED: OnceLock<bool> = OnceLock::new();
+    *SUPPORTED.get_or_init(|| {
+        #[cfg(windows)]
+        return windows_console::enable_virtual_terminal();
+        #[cfg(not(windows))]
+        true
+    })
+}
+
+/// True when stdin is a terminal, so a line read from it was typed just
+/// then. Piped input is a script of answers, which nothing outside the
+/// game's own questions may take lines from.
+pub fn typed_input() -> bool {
+    static TYPED: OnceLock<bool> = OnceLock::new();
+    *TYPED.get_or_init(|| io::stdin().is_terminal())
+}
+
+/// True when both stdin and stdout are terminals. Piped or redirected runs
+/// get plain output without pauses, though input is still read a line at a time.
+pub fn interactive() -> bool {
+    static INTERACTIVE: OnceLock<bool> = OnceLock::new();
+    *INTERACTIVE.get_or_init(|| io::stdin().is_terminal() && io::stdout().is_terminal())
+}
+
+/// Clears the screen through the console API, for consoles without ANSI
+/// support. Returns false where there is no such console to clear.
+#[cfg(not(feature = "tui"))]
+fn clear_console() -> bool {
+    #[cfg(windows)]
+    return windows_console::clear();
+    #[cfg(not(windows))]
+    false
+}
+
+// --- Cursor Control ---
+// Builds with the `tui` feature leave clearing and cursor moves to crossterm,
+// which writes ANSI codes where the console takes them and falls back to the
+// console API on Windows consoles that don't. Other builds write the ANSI
+// codes themselves, after switching on virtual terminal processing on Windows.
+
+/// True when the cursor can be moved around the screen to redraw parts of it.
+pub fn cursor_control() -> bool {
+    #[cfg(feature = "tui")]
+    return cfg!(not(windows)) || io::stdout().is_terminal();
+    #[cfg(not(feature = "tui"))]
+    ansi_supported()
+}
+
+/// Blanks the screen and moves the cursor to its top left corner. Returns
+/// false where the screen can't be cleared.
+pub fn clear_terminal() -> bool {
+    let mut stdout = io::stdout().lock();
+    #[cfg(feature = "tui")]
+    return crossterm::execute!(stdout, Clear(ClearType::All), MoveTo(0, 0)).is_ok();
+    #[cfg(not(feature = "tui"))]
+    if ansi_supported() {
+        write!(stdout, "\x1b[2J\x1b[H").is_ok() && stdout.flush().is_ok()
+    } else {
+        drop(stdout);
+        clear_console()
+    }
+}
+
+/// Writes `text` over `row` (counted from 0) of the screen, erasing the rest
+/// of the row after it when `erase_rest` is set.
+pub fn write_row(out: &mut impl Write, row: usize, text: &str, erase_rest: bool) -> io::Result<()> {
+    #[cfg(feature = "tui")]
+    {
+        crossterm::queue!(out, MoveTo(0, row as u16), Print(text))?;
+        if erase_rest {
+            crossterm::queue!(out, Clear(ClearType::UntilNewLine))?;
+        }
+        Ok(())
+    }
+    #[cfg(not(feature = "tui"))]
+    write!(out, "\x1b[{};1H{}{}", row + 1, text, if erase_rest { "\x1b[K" } else { "" })
+}
+
+/// Moves the cursor to the start of `row` (counted from 0) and blanks the
+/// screen from there down.
+pub fn clear_from_row(out: &mut impl Write, row: usize) -> io::Result<()> {
+    #[cfg(feature = "tui")]
+    return crossterm::queue!(out, MoveTo(0, row as u16), Clear(ClearType::FromCursorDown));
+    #[cfg(not(feature = "tui"))]
+    write!(out, "\x1b[{};1H\x1b[J", row + 1)
+}
+
+/// The few kernel32 console calls the game needs, declared by hand. In
+/// builds with the `tui` feature crossterm sizes and clears the console.
+#[cfg(windows)]
+#[cfg_attr(feature = "tui", allow(dead_code))]
+mod windows_console {
+    use std::ffi::c_void;
+
+    type Handle = *mut c_void;
+
+    const STD_INPUT_HANDLE: u32 = -10i32 as u32;
+    const STD_OUTPUT_HANDLE: u32 = -11i32 as u32;
+    const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
+    const ENABLE_ECHO_INPUT: u32 = 0x0004;
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct Coord {
+        x: i16,
+        y: i16,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct SmallRect {
+        left: i16,
+        top: i16,
+        right: i16,
+        bottom: i16,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct ScreenBufferInfo {
+        size: Coord,
+        cursor_position: Coord,
+        attributes: u16,
+        window: SmallRect,
+        maximum_window_size: Coord,
+    }
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn GetStdHandle(std_handle: u32) -> Handle;
+        fn GetConsoleMode(console: Handle, mode: *mut u32) -> i32;
+        fn SetConsoleMode(console: Handle, mode: u32) -> i32;
+        fn GetConsoleScreenBufferInfo(console: Handle, info: *mut ScreenBufferInfo) -> i32;
+        fn FillConsoleOutputCharacterW(console: Handle, character: u16, length: u32, start: Coord, written: *mut u32) -> i32;
+        fn FillConsoleOutputAttribute(console: Handle, attribute: u16, length: u32, start: Coord, written: *mut u32) -> i32;
+        fn SetConsoleCursorPosition(console: Handle, position: Coord) -> i32;
+    }
+
+    fn screen_buffer_info() -> Option<(Handle, ScreenBufferInfo)> {
+        // SAFETY: the handle comes straight from GetStdHandle and the call only writes `info`
+        unsafe {
+            let console = GetStdHandle(STD_OUTPUT_HANDLE);
+            let mut info: ScreenBufferInfo = std::mem::zeroed();
+            (GetConsoleScreenBufferInfo(console, &mut info) != 0).then_some((console, info))
+        }
+    }
+
+    /// Stops the console echoing typed input, returning the previous input
+    /// mode to restore later, or None if stdin isn't a console.
+    pub fn disable_input_echo() -> Option<u32> {
+        // SAFETY: the console mode calls only read and write the mode value we pass in
+        unsafe {
+            let console = GetStdHandle(STD_INPUT_HANDLE);
+            let mut mode = 0;
+            if GetConsoleMode(console, &mut mode) == 0 || SetConsoleMode(console, mode & !ENABLE_ECHO_INPUT) == 0 {
+                return None;
+            }
+            Some(mode)
+        }
+    }
+
+    pub fn restore_input_mode(mode: u32) {
+        // SAFETY: as above, with a mode the console reported earlier
+        unsafe {
+            SetConsoleMode(GetStdHandle(STD_INPUT_HANDLE), mode);
+        }
+    }
+
+    /// Turns on ANSI escape handling for stdout, returning whether it took.
+    pub fn enable_virtual_terminal() -> bool {
+        // SAFETY: the console mode calls only read and write the mode value we pass in
+        unsafe {
+            let console = GetStdHandle(STD_OUTPUT_HANDLE);
+            let mut mode = 0;
+            if GetConsoleMode(console, &mut mode) == 0 {
+                return false;
+            }
+            mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0
+                || SetConsoleMode(console, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) != 0
+        }
+    }
+
+    /// Blanks the whole screen buffer, scrollback included, and homes the cursor.
+    pub fn clear() -> bool {
+        let Some((console, info)) = screen_buffer_info() else { return false };
+        let cells = info.size.x as u32 * info.size.y as u32;
+        let home = Coord { x: 0, y: 0 };
+        let mut written = 0;
+        // SAFETY: every write stays within the buffer size the console reported
+        unsafe {
+            FillConsoleOutputCharacterW(console, b' ' as u16, cells, home, &mut written) != 0
+                && FillConsoleOutputAttribute(console, info.attributes, cells, home, &mut written) != 0
+                && SetConsoleCursorPosition(console, home) != 0
+        }
+    }
+
+    /// The visible window's size in (columns, rows).
+    pub fn window_size() -> Option<(usize, usize)> {
+        let (_, info) = screen_buffer_info()?;
+        let columns = (info.window.right - info.window.left + 1) as usize;
+        let rows = (info.window.bottom - info.window.top + 1) as usize;
+        Some((columns, rows))
+    }
+}
+
+/// Watches stdin in the background and signals the first key press.
+/// With a real terminal any key counts; otherwise a full line (Enter) is needed.
+/// Keeps the terminal in raw mode until the returned listener is dropped.
+pub struct KeyListener {
+    receiver: Receiver<()>,
+    _raw: Option<RawMode>,
+}
+
+impl KeyListener {
+    pub fn start() -> KeyListener {
+        let raw = RawMode::enable();
+        let (sender, receiver) = mpsc::channel();
+        let single_key = raw.is_some();
+
+        thread::spawn(move || {
+            let pressed = if single_key {
+                let mut byte = [0u8; 1];
+                matches!(io::stdin().read(&mut byte), Ok(1))
+            } else {
+                let mut line = String::new();
+                matches!(io::stdin().read_line(&mut line), Ok(n) if n > 0)
+            };
+            // Closed input (EOF) never counts as a key press
+            if pressed {
+                let _ = sender.send(());
+            }
+        });
+
+        KeyListener { receiver, _raw: raw }
+    }
+
+    /// True once a key has been pressed.
+    pub fn pressed(&self) -> bool {
+        self.receiver.try_recv().is_ok()
+    }
+
+    /// Waits for up to `duration`, returning early (with true) on a key press.
+    pub fn wait(&self, duration: Duration) -> bool {
+        match self.receiver.recv_timeout(duration) {
+            Ok(()) => true,
+            Err(RecvTimeoutError::Timeout) => false,
+            Err(RecvTimeoutError::Disconnected) => {
+                // Input is closed, so no key can arrive; still honour the delay
+                thread::sleep(duration);
+                false
+            }
+        }
+    }
+}
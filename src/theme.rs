@@ -0,0 +1,244 @@
+use std::sync::RwLock;
+
+use crate::code::{feedback_pair, is_solved, Score};
+use crate::config::Config;
+use crate::console::plain_output;
+use crate::rankings::display_width;
+
+// --- Colour Themes ---
+// A theme colours the prompts and the feedback, and picks how section
+// banners ("--- Settings ---") are drawn. Three themes are built in; more
+// can be defined in the settings file, one `theme.<name>.<part> = <value>`
+// line per part, starting from the classic look:
+//
+//   theme = sunset
+//   theme.sunset.prompt = yellow
+//   theme.sunset.feedback = magenta
+//   theme.sunset.banner = boxed
+//
+// Nothing is coloured in plain output (`--no-ansi` or a dumb terminal).
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Color {
+    #[default]
+    Default,
+    Bold,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    pub const ALL: [Color; 8] = [Color::Default, Color::Bold, Color::Red, Color::Green, Color::Yellow, Color::Blue, Color::Magenta, Color::Cyan];
+
+    /// The name used in the settings file.
+    pub fn id(self) -> &'static str {
+        match self {
+            Color::Default => "default",
+            Color::Bold => "bold",
+            Color::Red => "red",
+            Color::Green => "green",
+            Color::Yellow => "yellow",
+            Color::Blue => "blue",
+            Color::Magenta => "magenta",
+            Color::Cyan => "cyan",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Color> {
+        Color::ALL.into_iter().find(|c| c.id() == id.to_ascii_lowercase())
+    }
+
+    /// The SGR parameter that switches the colour on, if it changes anything.
+    fn sgr(self) -> Option<&'static str> {
+        match self {
+            Color::Default => None,
+            Color::Bold => Some("1"),
+            Color::Red => Some("31"),
+            Color::Green => Some("32"),
+            Color::Yellow => Some("33"),
+            Color::Blue => Some("34"),
+            Color::Magenta => Some("35"),
+            Color::Cyan => Some("36"),
+        }
+    }
+
+    /// `text` in this colour, unless output is plain.
+    pub fn paint(self, text: &str) -> String {
+        match self.sgr() {
+            Some(sgr) if !plain_output() => format!("\x1b[{}m{}\x1b[0m", sgr, text),
+            _ => text.to_string(),
+        }
+    }
+}
+
+/// How section banners are drawn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BannerStyle {
+    #[default]
+    Dashes, // --- Title ---, as written
+    Boxed,  // The title in a box
+    Bold,   // --- TITLE --- in bold
+}
+
+impl BannerStyle {
+    pub const ALL: [BannerStyle; 3] = [BannerStyle::Dashes, BannerStyle::Boxed, BannerStyle::Bold];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            BannerStyle::Dashes => "dashes",
+            BannerStyle::Boxed => "boxed",
+            BannerStyle::Bold => "bold",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<BannerStyle> {
+        BannerStyle::ALL.into_iter().find(|b| b.id() == id.to_ascii_lowercase())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Theme {
+    pub name: String,
+    pub prompt: Color,   // Every question the game asks
+    pub feedback: Color, // Feedback with at least one digit found
+    pub miss: Color,     // Feedback with no digits found
+    pub cracked: Color,  // The winning feedback
+    pub banner: BannerStyle,
+}
+
+/// Set by `--theme`, which wins over the settings file.
+static OVERRIDE: RwLock<Option<String>> = RwLock::new(None);
+
+/// The theme in the profile of the player whose turn it is, which wins over both.
+static PLAYER: RwLock<Option<String>> = RwLock::new(None);
+
+impl Theme {
+    /// The themes that ship with the game; the first is the default.
+    pub fn builtin() -> Vec<Theme> {
+        vec![
+            Theme { name: "classic".to_string(), ..Theme::default() },
+            Theme {
+                name: "bright".to_string(),
+                prompt: Color::Cyan,
+                feedback: Color::Yellow,
+                miss: Color::Red,
+                cracked: Color::Green,
+                banner: BannerStyle::Boxed,
+            },
+            Theme {
+                name: "mono".to_string(),
+                prompt: Color::Bold,
+                cracked: Color::Bold,
+                banner: BannerStyle::Bold,
+                ..Theme::default()
+            },
+        ]
+    }
+
+    /// The built-in themes followed by those defined in the settings (which
+    /// replace a built-in theme of the same name).
+    pub fn all(config: &Config) -> Vec<Theme> {
+        let mut themes: Vec<Theme> = Theme::builtin().into_iter().filter(|b| !config.themes.iter().any(|t| t.name == b.name)).collect();
+        themes.extend(config.themes.iter().cloned());
+        themes
+    }
+
+    pub fn by_name(config: &Config, name: &str) -> Option<Theme> {
+        Theme::all(config).into_iter().find(|t| t.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The theme in use: the current player's profile, then `--theme`, then
+    /// the settings, then classic.
+    pub fn current() -> Theme {
+        let config = Config::current();
+        let player = PLAYER.read().unwrap().clone().and_then(|name| Theme::by_name(&config, &name));
+        player.unwrap_or_else(|| {
+            let name = OVERRIDE.read().unwrap().clone().unwrap_or_else(|| config.theme.clone());
+            Theme::by_name(&config, &name).unwrap_or_else(|| Theme::builtin().remove(0))
+        })
+    }
+
+    /// Overrides the theme for the rest of the run.
+    pub fn set_current(name: &str) {
+        *OVERRIDE.write().unwrap() = Some(name.to_string());
+    }
+
+    /// Uses a player's own theme until it's changed again (None for nobody's).
+    pub fn set_player(name: Option<String>) {
+        *PLAYER.write().unwrap() = name;
+    }
+
+    /// Sets one part of the theme from a settings value, returning false if
+    /// the part or value isn't known.
+    pub fn set_part(&mut self, part: &str, value: &str) -> bool {
+        let color = Color::from_id(value);
+        match (part, color) {
+            ("prompt", Some(color)) => self.prompt = color,
+            ("feedback", Some(color)) => self.feedback = color,
+            ("miss", Some(color)) => self.miss = color,
+            ("cracked", Some(color)) => self.cracked = color,
+            ("banner", _) => match BannerStyle::from_id(value) {
+                Some(banner) => self.banner = banner,
+                None => return false,
+            },
+            _ => return false,
+        }
+        true
+    }
+
+    /// The theme's parts as settings-file lines.
+    pub fn to_lines(&self) -> String {
+        let parts = [
+            ("prompt", self.prompt.id()),
+            ("feedback", self.feedback.id()),
+            ("miss", self.miss.id()),
+            ("cracked", self.cracked.id()),
+            ("banner", self.banner.id()),
+        ];
+        parts.iter().map(|(part, value)| format!("theme.{}.{} = {}\n", self.name, part, value)).collect()
+    }
+
+    /// `text` (a rendered score) in the colour for how good `score` is.
+    pub fn paint_feedback(&self, score: Score, text: &str) -> String {
+        let color = if is_solved(score) {
+            self.cracked
+        } else if feedback_pair(score).0 == 0 {
+            self.miss
+        } else {
+            self.feedback
+        };
+        color.paint(text)
+    }
+
+    /// Redraws any `--- Title ---` banner lines in `text` in the theme's
+    /// banner style. Everything else is left as it is.
+    pub fn style_banners(&self, text: &str) -> String {
+        if self.banner == BannerStyle::Dashes || plain_output() || !text.contains("--- ") {
+            return text.to_string();
+        }
+        let lines: Vec<String> = text
+            .split('\n')
+            .map(|line| {
+                let Some(title) = line.strip_prefix("--- ").and_then(|rest| rest.strip_suffix(" ---")) else { return line.to_string() };
+                match self.banner {
+                    BannerStyle::Boxed => {
+                        let rule = "─".repeat(display_width(title) + 2);
+                        format!("┌{}┐\n│ {} │\n└{}┘", rule, title, rule)
+                    }
+                    BannerStyle::Bold => Color::Bold.paint(&format!("--- {} ---", title.to_uppercase())),
+                    BannerStyle::Dashes => line.to_string(),
+                }
+            })
+            .collect();
+        lines.join("\n")
+    }
+}
+
+/// `text` (a rendered score) coloured by the current theme.
+pub fn paint_feedback(score: Score, text: &str) -> String {
+    Theme::current().paint_feedback(score, text)
+}
@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+use crate::coach::coach_guess;
+use crate::code::{calculate_score, format_code, generate_secret, is_solved, Guess, Score};
+use crate::console::{clear_screen, prompt, Countdown};
+use crate::feedback::player_notation;
+use crate::game::{get_consistent_guess, track_candidates};
+use crate::locale::Locale;
+use crate::session::{Session, Solve};
+use crate::solver::all_codes;
+use crate::strings::{fill, tr};
+use crate::theme::paint_feedback;
+
+// --- Solo Time Attack ---
+// Crack as many codes as possible before the clock runs out. The clock is
+// checked whenever a guess is submitted, so a guess typed after time is up
+// doesn't count.
+
+/// Asks how long the run should last.
+fn get_time_limit() -> Duration {
+    loop {
+        outln!("{}", tr("time_attack.limit_title"));
+        outln!("{}", tr("time_attack.limit_2"));
+        outln!("{}", tr("time_attack.limit_5"));
+        outln!("{}", tr("time_attack.limit_10"));
+
+        match prompt(tr("time_attack.limit_prompt")).as_str() {
+            "1" => return Duration::from_secs(2 * 60),
+            "2" => return Duration::from_secs(5 * 60),
+            "3" => return Duration::from_secs(10 * 60),
+            _ => outln!("{}", tr("time_attack.limit_invalid")),
+        }
+    }
+}
+
+/// Runs one timed practice session and prints the per-code breakdown.
+pub fn run_time_attack(session: &mut Session) {
+    clear_screen();
+    outln!("{}", tr("time_attack.title"));
+    outln!("{}", tr("time_attack.intro"));
+
+    let player = prompt(tr("common.name_prompt"));
+    let limit = get_time_limit();
+    let locale = Locale::current();
+
+    prompt(&fill("time_attack.start_prompt", &[("time", &locale.duration(limit))]));
+    clear_screen();
+
+    let deadline = Countdown::new(limit);
+    let mut solved_counts: Vec<u32> = Vec::new(); // Guesses used for each cracked code
+    let mut current_guesses: u32 = 0;
+    let mut secret = generate_secret();
+    let mut candidates = all_codes();
+    let mut clues: Vec<(Guess, Score)> = Vec::new(); // Feedback on the current code
+
+    loop {
+        outln!("{}", fill("time_attack.status", &[
+            ("time", &locale.duration(deadline.left())),
+            ("cracked", &solved_counts.len()),
+            ("guesses", &current_guesses),
+        ]));
+
+        let Some(guess) = get_consistent_guess(&player, &clues) else { continue };
+
+        if deadline.expired() {
+            outln!("{}", tr("time_attack.too_late"));
+            break;
+        }
+
+        current_guesses += 1;
+        let score = calculate_score(&guess, &secret);
+        outln!("{}", fill("game.feedback", &[("guess", &format_code(&guess)), ("score", &paint_feedback(score, &player_notation().render(score)))]));
+
+        if is_solved(score) {
+            solved_counts.push(current_guesses);
+            session.record_secret("time attack", secret, Some(Solve { player: player.clone(), guesses: current_guesses }));
+            outln!("{}", fill("time_attack.cracked", &[("number", &solved_counts.len()), ("guesses", &current_guesses)]));
+            current_guesses = 0;
+            secret = generate_secret();
+            candidates = all_codes();
+            clues.clear();
+        } else {
+            coach_guess(&guess, &candidates);
+            track_candidates(&mut candidates, &guess, score);
+            clues.push((guess, score));
+        }
+    }
+
+    session.record_secret("time attack", secret, None);
+
+    // --- TIME ATTACK RESULTS ---
+    outln!("\n======================================");
+    outln!("{}", tr("time_attack.results"));
+    outln!("======================================");
+    outln!("{}", fill("time_attack.summary", &[
+        ("name", &player),
+        ("count", &locale.count(solved_counts.len() as u64)),
+        ("time", &locale.duration(limit)),
+    ]));
+    for (i, guesses) in solved_counts.iter().enumerate() {
+        outln!("{}", fill("time_attack.code_row", &[("number", &format!("{:<3}", i + 1)), ("guesses", &format!("{:>3}", guesses))]));
+    }
+    if !solved_counts.is_empty() {
+        let average = solved_counts.iter().sum::<u32>() as f64 / solved_counts.len() as f64;
+        outln!("{}", fill("time_attack.average", &[("average", &locale.decimal(average, 1))]));
+    }
+    outln!("{}", fill("time_attack.unfinished", &[("secret", &format_code(&secret)), ("guesses", &current_guesses)]));
+    outln!("======================================");
+}
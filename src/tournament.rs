@@ -0,0 +1,392 @@
+use rand::seq::SliceRandom;
+
+use crate::coach::coach_guess;
+use crate::code::{calculate_score, format_code, game_rng, generate_secret, is_solved, Guess, Score};
+use crate::console::{clear_screen, hand_over, prompt};
+use crate::feedback::player_notation;
+use crate::game::{get_consistent_guess, track_candidates};
+use crate::locale::Locale;
+use crate::rankings::{sort_standings, Standing};
+use crate::session::{Session, Solve};
+use crate::solver::all_codes;
+use crate::strings::{fill, tr};
+use crate::theme::paint_feedback;
+
+// --- Bracket Data ---
+
+/// One pairing in the bracket. `away` is None when `home` has a bye.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    pub home: String,
+    pub away: Option<String>,
+    pub winner: Option<String>,
+}
+
+impl Match {
+    fn new(home: String, away: Option<String>) -> Self {
+        // A bye advances automatically
+        let winner = if away.is_none() { Some(home.clone()) } else { None };
+        Match { home, away, winner }
+    }
+
+    pub fn is_bye(&self) -> bool {
+        self.away.is_none()
+    }
+}
+
+/// A single-elimination bracket. Round 0 is the opening round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bracket {
+    pub rounds: Vec<Vec<Match>>,
+}
+
+impl Bracket {
+    /// Builds the opening round from players listed in seed order (best first).
+    /// When the field isn't a power of two, the top seeds receive byes.
+    pub fn new(seeds: Vec<String>) -> Self {
+        let size = seeds.len().next_power_of_two();
+        let mut slots: Vec<Option<String>> = seeds.into_iter().map(Some).collect();
+        slots.resize(size, None);
+
+        // Standard seeding: 1 vs N, 2 vs N-1, ... so empty slots face the top seeds.
+        // Matches are laid out so the top two seeds can only meet in the final.
+        let matches = seed_order(size)
+            .chunks(2)
+            .map(|pair| {
+                let home = slots[pair[0]].take().expect("top half of the seeding is always filled");
+                Match::new(home, slots[pair[1]].take())
+            })
+            .collect();
+
+        Bracket { rounds: vec![matches] }
+    }
+
+    /// The round currently being played.
+    pub fn current_round(&self) -> &[Match] {
+        self.rounds.last().map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// The champion, once the final has been decided.
+    pub fn champion(&self) -> Option<&str> {
+        match self.current_round() {
+            [final_match] => final_match.winner.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Records the winner of a match in the current round.
+    pub fn record_winner(&mut self, match_index: usize, winner: String) {
+        if let Some(m) = self.rounds.last_mut().and_then(|r| r.get_mut(match_index)) {
+            m.winner = Some(winner);
+        }
+    }
+
+    /// Once every match in the current round has a winner, pairs those
+    /// winners (in bracket order) into the next round. Returns false if the
+    /// round isn't finished or the tournament is already over.
+    pub fn advance(&mut self) -> bool {
+        let round = self.current_round();
+        if round.len() <= 1 || round.iter().any(|m| m.winner.is_none()) {
+            return false;
+        }
+
+        let winners: Vec<String> = round.iter().filter_map(|m| m.winner.clone()).collect();
+        let next = winners
+            .chunks(2)
+            .map(|pair| Match::new(pair[0].clone(), pair.get(1).cloned()))
+            .collect();
+        self.rounds.push(next);
+        true
+    }
+
+    /// A readable name for a round, counted back from the final.
+    pub fn round_name(&self, round_index: usize) -> String {
+        let total_rounds = self.rounds[0].len().trailing_zeros() as usize + 1;
+        match total_rounds - round_index {
+            1 => tr("tournament.final").to_string(),
+            2 => tr("tournament.semi_finals").to_string(),
+            3 => tr("tournament.quarter_finals").to_string(),
+            _ => fill("tournament.round", &[("number", &(round_index + 1))]),
+        }
+    }
+
+    /// Prints every round played so far.
+    pub fn display(&self) {
+        outln!("\n======================================");
+        outln!("{}", tr("tournament.bracket"));
+        outln!("======================================");
+        for (i, round) in self.rounds.iter().enumerate() {
+            outln!("--- {} ---", self.round_name(i));
+            for m in round {
+                let mark = |name: &str| if m.winner.as_deref() == Some(name) { "🏆" } else { "  " };
+                match &m.away {
+                    None => outln!("{}", fill("tournament.bye", &[("mark", &mark(&m.home)), ("name", &format!("{:<15}", m.home))])),
+                    Some(away) => outln!("{}", fill("tournament.pairing", &[
+                        ("mark", &mark(&m.home)),
+                        ("home", &format!("{:<15}", m.home)),
+                        ("away_mark", &mark(away)),
+                        ("away", away),
+                    ])),
+                }
+            }
+        }
+        outln!("======================================");
+    }
+}
+
+/// Returns the 0-based seed in each bracket slot, e.g. [0, 7, 3, 4, 1, 6, 2, 5] for 8.
+fn seed_order(size: usize) -> Vec<usize> {
+    let mut order = vec![0];
+    while order.len() < size {
+        let n = order.len() * 2;
+        order = order.iter().flat_map(|&s| [s, n - 1 - s]).collect();
+    }
+    order
+}
+
+// --- Head-to-Head Matches ---
+
+/// Plays one head-to-head game: each player cracks their own secret, taking
+/// turns. If the first player to move cracks their code, the second player
+/// still gets the rest of that round to draw level; a draw is replayed with
+/// fresh secrets and the other player moving first.
+/// Returns the index (0 or 1) of the winner in `names`.
+pub fn play_head_to_head(names: [&str; 2], session: &mut Session) -> usize {
+    let mut first = 0;
+
+    loop {
+        let secrets: [Guess; 2] = [generate_secret(), generate_secret()];
+        let mut round_number: u32 = 1;
+        let mut guesses = [0u32; 2];
+        let mut candidates = [all_codes(), all_codes()];
+        let mut clues: [Vec<(Guess, Score)>; 2] = [Vec::new(), Vec::new()];
+
+        clear_screen();
+        outln!("{}", fill("tournament.match_start", &[("home", &names[0]), ("away", &names[1]), ("first", &names[first])]));
+
+        loop {
+            let mut solved = [false; 2];
+
+            for seat in [first, 1 - first] {
+                outln!("\n======================================");
+                outln!("{}", fill("tournament.turn_header", &[("round", &round_number), ("name", &names[seat])]));
+                outln!("======================================");
+
+                let Some(guess) = get_consistent_guess(names[seat], &clues[seat]) else { continue };
+                guesses[seat] += 1;
+                let score = calculate_score(&guess, &secrets[seat]);
+
+                outln!("--------------------------------------");
+                outln!("{}", fill("game.feedback", &[("guess", &format_code(&guess)), ("score", &paint_feedback(score, &player_notation().render(score)))]));
+                outln!("--------------------------------------");
+                coach_guess(&guess, &candidates[seat]);
+                track_candidates(&mut candidates[seat], &guess, score);
+                clues[seat].push((guess, score));
+
+                if is_solved(score) {
+                    solved[seat] = true;
+                    outln!("{}", fill("tournament.cracked", &[("name", &names[seat])]));
+                }
+
+                hand_over(Some(names[1 - seat]));
+            }
+
+            if solved != [false, false] {
+                for seat in 0..2 {
+                    let solve = solved[seat].then(|| Solve { player: names[seat].to_string(), guesses: guesses[seat] });
+                    session.record_secret("tournament", secrets[seat], solve);
+                }
+            }
+
+            match solved {
+                [true, true] => {
+                    prompt(&fill("tournament.draw", &[("round", &round_number)]));
+                    first = 1 - first;
+                    break;
+                }
+                [true, false] => return 0,
+                [false, true] => return 1,
+                [false, false] => round_number += 1,
+            }
+        }
+    }
+}
+
+// --- Tournament Flow ---
+
+/// Gets the number of tournament entrants.
+fn get_entrant_count() -> usize {
+    loop {
+        match prompt(tr("tournament.count_prompt")).parse::<usize>() {
+            Ok(n) if (2..=16).contains(&n) => return n,
+            _ => outln!("{}", tr("tournament.count_invalid")),
+        }
+    }
+}
+
+/// Plays every match of a bracket until a champion is crowned.
+pub fn run_bracket(mut bracket: Bracket, session: &mut Session) {
+    loop {
+        bracket.display();
+
+        if let Some(champion) = bracket.champion() {
+            outln!("{}", fill("tournament.champion", &[("name", &champion)]));
+            return;
+        }
+
+        let round_index = bracket.rounds.len() - 1;
+        let round_name = bracket.round_name(round_index);
+        let pending: Vec<(usize, String, String)> = bracket
+            .current_round()
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.winner.is_none())
+            .filter_map(|(i, m)| m.away.clone().map(|away| (i, m.home.clone(), away)))
+            .collect();
+
+        for (match_index, home, away) in pending {
+            prompt(&fill("tournament.match_prompt", &[("round", &round_name), ("home", &home), ("away", &away)]));
+
+            let winner = if play_head_to_head([&home, &away], session) == 0 { home } else { away };
+            outln!("{}", fill("tournament.advances", &[("name", &winner), ("round", &round_name), ("round_lower", &round_name.to_lowercase())]));
+            bracket.record_winner(match_index, winner);
+        }
+
+        bracket.advance();
+    }
+}
+
+/// Turns a finished casual game into a bracket, seeding players by how they
+/// finished. Bots sit it out, since tournament matches are played by people.
+pub fn run_rematch_bracket(standings: &[Standing], bots: &[&str], session: &mut Session) {
+    let mut standings = standings.to_vec();
+    sort_standings(&mut standings);
+    let seeds: Vec<String> = standings.into_iter().map(|s| s.name).filter(|name| !bots.contains(&name.as_str())).collect();
+
+    clear_screen();
+    outln!("{}", tr("tournament.rematch_title"));
+    outln!("{}", tr("tournament.seeded_by"));
+    for (i, name) in seeds.iter().enumerate() {
+        outln!("{}", fill("tournament.seed", &[("number", &(i + 1)), ("name", name)]));
+    }
+    if !bots.is_empty() {
+        outln!("{}", fill("tournament.bots_out", &[("names", &bots.join(", "))]));
+    }
+
+    let byes = seeds.len().next_power_of_two() - seeds.len();
+    if byes > 0 {
+        outln!("{}", fill("tournament.byes", &[("count", &Locale::current().count(byes as u64))]));
+    }
+    run_bracket(Bracket::new(seeds), session);
+}
+
+/// Sets up a knockout tournament and plays it to the end.
+pub fn run_tournament(session: &mut Session) {
+    clear_screen();
+    outln!("{}", tr("tournament.title"));
+    outln!("{}", tr("tournament.intro"));
+
+    let count = get_entrant_count();
+    let mut names: Vec<String> = (0..count)
+        .map(|i| prompt(&fill("tournament.name_prompt", &[("number", &(i + 1))])))
+        .collect();
+
+    if prompt(tr("tournament.shuffle_prompt")).eq_ignore_ascii_case(tr("common.yes")) {
+        names.shuffle(&mut game_rng());
+    }
+
+    let byes = count.next_power_of_two() - count;
+    if byes > 0 {
+        outln!("{}", fill("tournament.entrant_byes", &[("count", &count), ("byes", &Locale::current().count(byes as u64))]));
+    }
+
+    run_bracket(Bracket::new(names), session);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bracket(count: usize) -> Bracket {
+        Bracket::new((1..=count).map(|seed| format!("P{}", seed)).collect())
+    }
+
+    /// Each match of the current round as (home, away), "-" standing for a bye.
+    fn pairings(bracket: &Bracket) -> Vec<(&str, &str)> {
+        bracket.current_round().iter().map(|m| (m.home.as_str(), m.away.as_deref().unwrap_or("-"))).collect()
+    }
+
+    /// Lets the home player win every match still to be played in the current round.
+    fn home_wins(bracket: &mut Bracket) {
+        for i in 0..bracket.current_round().len() {
+            if bracket.current_round()[i].winner.is_none() {
+                let home = bracket.current_round()[i].home.clone();
+                bracket.record_winner(i, home);
+            }
+        }
+    }
+
+    #[test]
+    fn seeds_are_spread_so_the_top_two_meet_last() {
+        assert_eq!(seed_order(2), [0, 1]);
+        assert_eq!(seed_order(4), [0, 3, 1, 2]);
+        assert_eq!(seed_order(8), [0, 7, 3, 4, 1, 6, 2, 5]);
+    }
+
+    #[test]
+    fn a_full_bracket_of_eight_plays_three_rounds() {
+        let mut bracket = bracket(8);
+        assert_eq!(pairings(&bracket), [("P1", "P8"), ("P4", "P5"), ("P2", "P7"), ("P3", "P6")]);
+        assert!(bracket.current_round().iter().all(|m| !m.is_bye() && m.winner.is_none()));
+        assert!(!bracket.advance(), "a round with matches still to play doesn't advance");
+
+        home_wins(&mut bracket);
+        assert!(bracket.advance());
+        assert_eq!(pairings(&bracket), [("P1", "P4"), ("P2", "P3")]);
+        assert_eq!(bracket.round_name(1), tr("tournament.semi_finals"));
+
+        bracket.record_winner(0, "P4".to_string());
+        bracket.record_winner(1, "P2".to_string());
+        assert!(bracket.advance());
+        assert_eq!(pairings(&bracket), [("P4", "P2")]);
+        assert_eq!(bracket.champion(), None);
+
+        bracket.record_winner(0, "P2".to_string());
+        assert_eq!(bracket.champion(), Some("P2"));
+        assert!(!bracket.advance(), "there is nothing after the final");
+        assert_eq!(bracket.rounds.len(), 3);
+    }
+
+    #[test]
+    fn with_three_entrants_the_top_seed_has_a_bye_into_the_final() {
+        let mut bracket = bracket(3);
+        assert_eq!(pairings(&bracket), [("P1", "-"), ("P2", "P3")]);
+        assert_eq!(bracket.current_round()[0].winner.as_deref(), Some("P1"));
+        assert_eq!(bracket.round_name(0), tr("tournament.semi_finals"));
+
+        bracket.record_winner(1, "P3".to_string());
+        assert!(bracket.advance());
+        assert_eq!(pairings(&bracket), [("P1", "P3")]);
+        assert_eq!(bracket.round_name(1), tr("tournament.final"));
+        bracket.record_winner(0, "P1".to_string());
+        assert_eq!(bracket.champion(), Some("P1"));
+    }
+
+    #[test]
+    fn with_five_entrants_the_top_three_have_byes() {
+        let mut bracket = bracket(5);
+        assert_eq!(pairings(&bracket), [("P1", "-"), ("P4", "P5"), ("P2", "-"), ("P3", "-")]);
+        assert_eq!(bracket.current_round().iter().filter(|m| m.is_bye()).count(), 3);
+        assert!(!bracket.advance());
+
+        bracket.record_winner(1, "P5".to_string());
+        assert!(bracket.advance());
+        assert_eq!(pairings(&bracket), [("P1", "P5"), ("P2", "P3")]);
+
+        home_wins(&mut bracket);
+        assert!(bracket.advance());
+        assert_eq!(pairings(&bracket), [("P1", "P2")]);
+        bracket.record_winner(0, "P2".to_string());
+        assert_eq!(bracket.champion(), Some("P2"));
+    }
+}
@@ -0,0 +1,123 @@
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use crate::code::{format_code, Guess, Score};
+use crate::console::{guest_session, prompt};
+use crate::feedback::player_notation;
+use crate::locale::Locale;
+use crate::rankings::{display_width, pad, render_rankings, Standing};
+use crate::strings::{fill, tr};
+
+// --- Game Transcripts ---
+// After the final rankings of an Individual Secrets game, the table can keep
+// a record of it: every guess with its feedback and how far into the game it
+// came, then the rankings with everyone's secret. A file whose name ends in
+// `.md` is written as Markdown; anything else is plain text.
+
+/// One guess, as the transcript lists it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Move {
+    pub round: u32,
+    pub player: String,
+    pub guess: Guess,
+    pub score: Score,
+    pub at: Duration, // Time since the game started
+}
+
+/// Every guess of a game so far, in the order they were made.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Transcript {
+    pub moves: Vec<Move>,
+}
+
+/// Formats a time into the game as `+mm:ss`, with hours once there are any.
+pub fn clock(at: Duration) -> String {
+    let total = at.as_secs();
+    match total / 3600 {
+        0 => format!("+{:02}:{:02}", total / 60, total % 60),
+        hours => format!("+{}:{:02}:{:02}", hours, (total / 60) % 60, total % 60),
+    }
+}
+
+impl Transcript {
+    pub fn record(&mut self, round: u32, player: &str, guess: Guess, score: Score, at: Duration) {
+        self.moves.push(Move { round, player: player.to_string(), guess, score, at });
+    }
+
+    /// Drops the latest guess, after an `/undo`.
+    pub fn take_back(&mut self) {
+        self.moves.pop();
+    }
+
+    /// The whole transcript as one document. `duration` is how long the game
+    /// took, which also dates its start.
+    pub fn render(&self, standings: &[Standing], seed: u64, duration: Duration, markdown: bool) -> String {
+        let locale = Locale::current();
+        let started = SystemTime::now().checked_sub(duration).unwrap_or_else(SystemTime::now);
+        let played = fill("transcript.played", &[("time", &locale.timestamp(started)), ("length", &locale.duration(duration)), ("seed", &seed)]);
+        let headers = ["transcript.round", "transcript.time", "rankings.player", "transcript.guess", "transcript.feedback"].map(tr);
+        let rows: Vec<[String; 5]> = self
+            .moves
+            .iter()
+            .map(|m| [m.round.to_string(), clock(m.at), m.player.clone(), format_code(&m.guess), player_notation().render(m.score)])
+            .collect();
+
+        let mut out = Vec::new();
+        if markdown {
+            out.push(format!("# {}", tr("transcript.title")));
+            out.push(String::new());
+            out.push(played);
+            out.push(String::new());
+            out.push(format!("## {}", tr("transcript.guesses")));
+            out.push(String::new());
+            out.push(format!("| {} |", headers.join(" | ")));
+            out.push(format!("|{}|", headers.map(|_| "---").join("|")));
+            // A | in a name would start a new cell
+            out.extend(rows.iter().map(|row| format!("| {} |", row.clone().map(|cell| cell.replace('|', "\\|")).join(" | "))));
+            out.push(String::new());
+            out.push(format!("## {}", tr("transcript.rankings")));
+            out.push(String::new());
+            out.push("```".to_string());
+            out.extend(render_rankings(standings));
+            out.push("```".to_string());
+        } else {
+            out.push(tr("transcript.title").to_string());
+            out.push(played);
+            out.push(String::new());
+            out.push(tr("transcript.guesses").to_string());
+            let mut widths = headers.map(display_width);
+            for row in &rows {
+                for (width, cell) in widths.iter_mut().zip(row) {
+                    *width = (*width).max(display_width(cell));
+                }
+            }
+            let line = |cells: &[String]| cells.iter().zip(widths).map(|(cell, width)| pad(cell, width)).collect::<Vec<_>>().join("  ").trim_end().to_string();
+            out.push(line(&headers.map(str::to_string)));
+            out.extend(rows.iter().map(|row| line(row)));
+            out.push(String::new());
+            out.push(tr("transcript.rankings").to_string());
+            out.extend(render_rankings(standings));
+        }
+        out.push(String::new());
+        out.join("\n")
+    }
+}
+
+/// Offers to write the transcript of a finished game to a file of the players' choosing.
+/// A guest session has no files of its own, so it isn't asked.
+pub fn offer_transcript(transcript: &Transcript, standings: &[Standing], seed: u64, duration: Duration) {
+    if guest_session() {
+        return;
+    }
+    let path = prompt(tr("transcript.prompt"));
+    if path.is_empty() {
+        return;
+    }
+    let path = Path::new(&path);
+    let markdown = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("md"));
+    match fs::write(path, transcript.render(standings, seed, duration, markdown)) {
+        Ok(()) => outln!("{}", fill("transcript.saved", &[("path", &path.display())])),
+        Err(e) => outln!("{}", fill("transcript.failed", &[("path", &path.display()), ("error", &e)])),
+    }
+}
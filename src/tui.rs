@@ -0,0 +1,448 @@
+use std::collections::BTreeMap;
+use std::io::{self, Stdout, Write};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use ratatui::backend::CrosstermBackend;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::symbols::border;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Padding, Paragraph, Widget};
+use ratatui::{Terminal, TerminalOptions, Viewport};
+
+use crate::coach::coach_guess;
+use crate::code::{calculate_score, format_code, is_solved, start_seeded_game};
+use crate::config::Config;
+use crate::console::{clear_screen, draw_frame, frame_still_drawn, note_frame, pause, plain_output, prompt};
+use crate::feedback::{player_notation, FeedbackRenderer, PegNotation};
+use crate::glyphs::{decorate, OutputProfile};
+use crate::game::{get_player_guess_with_hints, get_starting_player_index, greet_profiles, run_game, use_preferred_length, Player, TurnChoice};
+use crate::profile::Profile;
+use crate::lobby::{run_lobby, take_preset_seats};
+use crate::locale::Locale;
+use crate::rankings::{display_width, pad, print_rankings, Finish, Standing};
+use crate::rating::Ratings;
+use crate::rules::Rules;
+use crate::session::{GameRecord, Placement, Session, Solve};
+use crate::solver::filter_candidates;
+use crate::sound::{self, Cue};
+use crate::stats::{print_leaderboard, record_results};
+use crate::store::{new_game_id, now_timestamp, GameResult, StoreSet};
+use crate::strings::{fill, tr};
+use crate::json;
+use crate::terminal::{clear_from_row, clear_terminal, cursor_control, terminal_size};
+
+// --- Full-Screen Frontend ---
+// An optional frontend (the `tui` feature) for Individual Secrets games. Each
+// turn redraws one fixed layout in place through ratatui, which only rewrites
+// the cells that changed, instead of clearing and reprinting:
+//
+//   ┌─ Round 3 · Alice's turn ────────────┬────────────────────────┐
+//   │ Your guesses                        │ Scoreboard             │
+//   │  1. 0123 -> ●○··                    │ ▶ Alice      2 guesses │
+//   │  2. 4501 -> ○···                    │   Bob        🏁 1st    │
+//   ├─────────────────────────────────────┴────────────────────────┤
+//   │ 40 code(s) still fit your feedback.                          │
+//   └──────────────────────────────────────────────────────────────┘
+//   Alice, enter your 4-digit guess (or a command like /hint):
+//
+// Only the player whose turn it is sees their own guesses; between human
+// turns the screen is blanked until the next player is at the keyboard.
+
+/// The left panel: the current player's clues and guesses, latest at the bottom.
+fn history_panel(player: &Player, height: usize) -> Vec<String> {
+    let mut lines = vec![if player.is_bot() { fill("tui.bot_guesses", &[("name", &player.name)]) } else { tr("tui.your_guesses").to_string() }];
+    let mut entries: Vec<String> = player
+        .head_start
+        .iter()
+        .map(|(clue, score)| format!("🎁  {} -> {}", format_code(clue), PegNotation.render(*score)))
+        .collect();
+    entries.extend(
+        player
+            .history
+            .iter()
+            .enumerate()
+            .map(|(i, (guess, score))| format!("{:>2}. {} -> {}", i + 1, format_code(guess), PegNotation.render(*score))),
+    );
+    if entries.is_empty() {
+        entries.push(tr("tui.no_guesses").to_string());
+    }
+    let skip = entries.len().saturating_sub(height - 1);
+    lines.extend(entries.into_iter().skip(skip));
+    lines
+}
+
+/// The right panel: everyone's progress, with the current player marked.
+fn scoreboard_panel(players: &[Player], current: usize) -> Vec<String> {
+    let locale = Locale::current();
+    let width = players.iter().map(|p| display_width(&p.name)).max().unwrap_or(0);
+    let mut lines = vec![tr("tui.scoreboard").to_string()];
+    for (i, p) in players.iter().enumerate() {
+        let marker = if i == current { "▶" } else { " " };
+        let status = match p.rank {
+            Some(rank) if p.cracked => fill("tui.place", &[("place", &locale.ordinal(rank))]),
+            Some(_) => tr("tui.dnf").to_string(),
+            None => fill("tui.guesses", &[("count", &locale.count(p.guesses as u64))]),
+        };
+        lines.push(format!("{} {} {}", marker, pad(&p.name, width), status));
+    }
+    lines
+}
+
+/// The terminal the layout is drawn on, kept between turns (with the area
+/// it was drawn in) so each draw only rewrites the cells that changed.
+type Screen = Terminal<CrosstermBackend<Stdout>>;
+
+static SCREEN: Mutex<Option<(Rect, Screen)>> = Mutex::new(None);
+
+/// Draws the screen for `players[current]`'s turn, with `notes` in the message area.
+fn draw(players: &[Player], current: usize, round: u32, notes: &[String]) {
+    if json::active() {
+        return;
+    }
+    let size = terminal_size();
+    let (columns, rows) = size;
+    let width = columns.clamp(60, 120);
+    let inner = width - 3; // Three border columns around the two panels
+    let left_width = inner * 3 / 5;
+    let notes_height = notes.len().max(1);
+    let body_height = rows.saturating_sub(8 + notes_height).max(players.len() + 1);
+    let sizes = Sizes { left_width, body_height };
+    let area = Rect::new(0, 0, width as u16, (body_height + notes_height + 3) as u16);
+
+    // Plain output and screens too small for the layout get it printed in full
+    if plain_output() || !cursor_control() || width > columns || usize::from(area.height) >= rows {
+        let mut buffer = Buffer::empty(area);
+        render(&mut buffer, sizes, players, current, round, notes);
+        draw_frame(&buffer_rows(&buffer));
+        return;
+    }
+    let mut screen = SCREEN.lock().unwrap();
+    // Anything that scrolled or cleared the screen leaves nothing to draw over
+    let kept = screen.take().filter(|(drawn, _)| *drawn == area && frame_still_drawn(size));
+    let mut terminal = match kept {
+        Some((_, terminal)) => terminal,
+        None => {
+            clear_terminal();
+            Terminal::with_options(CrosstermBackend::new(io::stdout()), TerminalOptions { viewport: Viewport::Fixed(area) })
+                .expect("failed opening the terminal")
+        }
+    };
+    terminal
+        .draw(|frame| {
+            render(frame.buffer_mut(), sizes, players, current, round, notes);
+            frame.set_cursor_position((0, area.height));
+        })
+        .expect("failed drawing the screen");
+    // Leave the cursor under the layout, with everything below it blanked
+    let mut out = io::stdout().lock();
+    clear_from_row(&mut out, area.height.into()).and_then(|_| out.flush()).expect("failed drawing the screen");
+    note_frame(area.height.into(), size);
+    *screen = Some((area, terminal));
+}
+
+/// Where the layout's panels go: the width of the history panel and the
+/// height of both panels, borders left out. The message area gets the rest.
+#[derive(Clone, Copy)]
+struct Sizes {
+    left_width: usize,
+    body_height: usize,
+}
+
+/// Renders the layout into `buffer`, which covers exactly the screen it takes up.
+fn render(buffer: &mut Buffer, sizes: Sizes, players: &[Player], current: usize, round: u32, notes: &[String]) {
+    let ascii = OutputProfile::current() == OutputProfile::Ascii;
+    let glyph = |unicode: &'static str, ascii_form: &'static str| if ascii { ascii_form } else { unicode };
+    let edges = border::Set {
+        top_left: glyph("┌", "+"),
+        top_right: glyph("┐", "+"),
+        bottom_left: glyph("└", "+"),
+        bottom_right: glyph("┘", "+"),
+        vertical_left: glyph("│", "|"),
+        vertical_right: glyph("│", "|"),
+        horizontal_top: glyph("─", "-"),
+        horizontal_bottom: glyph("─", "-"),
+    };
+    let text = |lines: Vec<String>| Paragraph::new(lines.iter().map(|line| Line::from(decorate(line).into_owned())).collect::<Vec<_>>());
+
+    let [body, message] = Layout::vertical([Constraint::Length(sizes.body_height as u16 + 1), Constraint::Fill(1)]).areas(buffer.area);
+    let [left, right] = Layout::horizontal([Constraint::Length(sizes.left_width as u16 + 1), Constraint::Fill(1)]).areas(body);
+
+    let title = fill("tui.title", &[("round", &round), ("name", &players[current].name)]);
+    text(history_panel(&players[current], sizes.body_height))
+        .block(Block::new().borders(Borders::TOP | Borders::LEFT).border_set(edges).title(decorate(&title).into_owned()).padding(Padding::left(1)))
+        .render(left, buffer);
+    text(scoreboard_panel(players, current))
+        .block(
+            Block::new()
+                .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+                .border_set(border::Set { top_left: glyph("┬", "+"), ..edges })
+                .padding(Padding::left(1)),
+        )
+        .render(right, buffer);
+    let notes = if notes.is_empty() { vec![String::new()] } else { notes.to_vec() };
+    text(notes)
+        .block(
+            Block::bordered()
+                .border_set(border::Set { top_left: glyph("├", "+"), top_right: glyph("┤", "+"), ..edges })
+                .padding(Padding::left(1)),
+        )
+        .render(message, buffer);
+    // Where the panels' divider meets the message area
+    buffer[(right.x, message.y)].set_symbol(glyph("┴", "+"));
+}
+
+/// The rows of `buffer` as text, for printing the layout in full.
+fn buffer_rows(buffer: &Buffer) -> Vec<String> {
+    let area = buffer.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            let mut row = String::new();
+            let mut covered = 0; // Columns still taken up by a wide character
+            for x in area.left()..area.right() {
+                let symbol = buffer[(x, y)].symbol();
+                if covered > 0 {
+                    covered -= 1;
+                    continue;
+                }
+                row.push_str(symbol);
+                covered = display_width(symbol).saturating_sub(1);
+            }
+            row
+        })
+        .collect()
+}
+
+/// Blanks the screen and waits until the next player has the keyboard.
+fn hand_over(name: &str) {
+    clear_screen();
+    outln!("{}", fill("tui.pass", &[("name", &name)]));
+    prompt(&fill("tui.pass_prompt", &[("name", &name)]));
+}
+
+/// Plays an Individual Secrets game in the full-screen layout.
+pub fn run_tui(stores: &mut StoreSet, session: &mut Session) {
+    // A screen drawn with boxes reads badly aloud; the classic game reads well
+    if Config::current().screen_reader {
+        outln!("{}", tr("tui.screen_reader"));
+        return run_game(stores, session);
+    }
+    clear_screen();
+    outln!("{}", tr("tui.header"));
+    outln!("{}", tr("tui.intro"));
+
+    let preset = take_preset_seats();
+    let quick_start = preset.is_some();
+    let seats = preset.unwrap_or_else(|| run_lobby(&Ratings::from_results(stores.store_for(2).results())));
+    use_preferred_length(&seats);
+    let num_players = seats.len();
+    let seed = start_seeded_game();
+    let mut players: Vec<Player> = seats.into_iter().map(Player::from_seat).collect();
+    greet_profiles(&players, stores.store_for(num_players).results());
+    let start = if quick_start { 0 } else { get_starting_player_index(&players, stores.store_for(num_players).results()) };
+    let humans = players.iter().filter(|p| !p.is_bot()).count();
+
+    let started_at = Instant::now();
+    let mut round: u32 = 1;
+    let mut seat = start;
+    let mut rank_to_assign: usize = 1; // Same round-based tie ranking as the classic game
+    let mut last_ranked_round: u32 = 0;
+    let mut public_notes: Vec<String> = vec![tr("tui.begin").to_string()];
+    let mut ended_early = false; // Someone typed /quit
+
+    loop {
+        // Players out of guesses have finished without a rank
+        let active = players.iter().filter(|p| p.rank.is_none() && p.finished.is_none()).count();
+        if active == 0 || (num_players > 1 && active == 1) {
+            break;
+        }
+
+        if players[seat].rank.is_none() && players[seat].finished.is_none() {
+            if players[seat].sits_out > 0 {
+                players[seat].sits_out -= 1;
+                public_notes.push(fill("tui.sits_out", &[("name", &players[seat].name)]));
+            } else {
+                if !players[seat].is_bot() && humans > 1 {
+                    hand_over(&players[seat].name);
+                }
+                Profile::apply(players[seat].profile.as_ref());
+                if !players[seat].is_bot() {
+                    sound::play(Cue::TurnStart);
+                }
+                match play_turn(&mut players, seat, round, &public_notes, &mut rank_to_assign, &mut last_ranked_round, started_at) {
+                    TurnEnd::Played(Some(note)) => public_notes = vec![note],
+                    TurnEnd::Played(None) => public_notes.clear(),
+                    TurnEnd::Quit => {
+                        ended_early = true;
+                        break;
+                    }
+                }
+            }
+        }
+
+        // Step to the next seat; passing the first player again starts a new round
+        seat = (seat + 1) % num_players;
+        if seat == start {
+            round += 1;
+        }
+    }
+
+    Profile::apply(None);
+    sound::play(Cue::GameEnd);
+
+    // Whoever is left never cracked their code
+    for p in players.iter_mut().filter(|p| p.rank.is_none()) {
+        p.rank = Some(rank_to_assign);
+        p.finished.get_or_insert((round, started_at.elapsed()));
+    }
+
+    clear_screen();
+    let rating_changes = if ended_early {
+        BTreeMap::new()
+    } else {
+        let finishes: Vec<(&str, usize)> = players.iter().map(|p| (p.name.as_str(), p.rank.unwrap_or(num_players))).collect();
+        Ratings::from_results(stores.store_for(num_players).results()).changes(&finishes)
+    };
+    let standings: Vec<Standing> = players
+        .iter()
+        .map(|p| {
+            let (round, time) = p.finished.unwrap_or((round, started_at.elapsed()));
+            let finish = if p.cracked { Finish::Placed(p.rank.unwrap_or(num_players)) } else { Finish::DidNotFinish { round } };
+            Standing { name: p.name.clone(), finish, guesses: p.guesses, time, secret: p.secret_code, rating: rating_changes.get(&p.name).copied() }
+        })
+        .collect();
+    print_rankings(&standings);
+    outln!("{}", fill("common.game_length", &[("time", &Locale::current().duration(started_at.elapsed()))]));
+    outln!("{}", fill("game.seed", &[("seed", &seed)]));
+
+    for p in &players {
+        let solve = p.cracked.then(|| Solve { player: p.name.clone(), guesses: p.guesses });
+        session.record_secret("individual", p.secret_code, solve);
+    }
+    // The table is sorted into finishing order for the summary
+    let mut placements: Vec<Placement> =
+        players.iter().map(|p| Placement { player: p.name.clone(), place: p.rank.filter(|_| p.cracked), guesses: p.guesses }).collect();
+    placements.sort_by_key(|p| p.place.unwrap_or(usize::MAX));
+    session.record_game(GameRecord { duration: started_at.elapsed(), total_guesses: players.iter().map(|p| p.guesses).sum(), placements });
+    // As in the classic game, only complete games count towards stats
+    if ended_early {
+        outln!("{}", tr("game.ended_early"));
+        return;
+    }
+    let (game, timestamp) = (new_game_id(), now_timestamp());
+    let results = players
+        .iter()
+        .map(|p| GameResult {
+            game,
+            timestamp,
+            mode: "individual".to_string(),
+            player: p.name.clone(),
+            rank: p.rank.unwrap_or(num_players),
+            field_size: num_players,
+            guesses: p.guesses,
+            rules: Rules::default().record_key(),
+        })
+        .collect();
+    record_results(stores, results, &[]);
+    if Config::current().show_leaderboard {
+        print_leaderboard(stores.store_for(num_players));
+    }
+}
+
+/// How a turn in the full-screen game ended.
+enum TurnEnd {
+    Played(Option<String>), // With a note everyone may see, if there is one
+    Quit,
+}
+
+/// Plays one turn for `players[seat]`, which ends with a note everyone may
+/// see afterwards (a bot's move or a finish) if there is one.
+fn play_turn(
+    players: &mut [Player],
+    seat: usize,
+    round: u32,
+    public_notes: &[String],
+    rank_to_assign: &mut usize,
+    last_ranked_round: &mut u32,
+    started_at: Instant,
+) -> TurnEnd {
+    let scoreboard = scoreboard_panel(players, seat);
+    draw(players, seat, round, public_notes);
+
+    let player = &mut players[seat];
+    let engine_turn = player.engine_turn(round);
+    let guess = match &player.bot {
+        Some(bot) => {
+            let guess = bot.next_guess();
+            outln!("{}", fill("game.bot_thinking", &[("name", &player.name)]));
+            pause(Duration::from_secs(1));
+            guess
+        }
+        None => match engine_turn {
+            Some(Ok(guess)) => guess,
+            Some(Err(note)) => return TurnEnd::Played(Some(note)),
+            None => {
+                let attempt = player.guesses + 1;
+                loop {
+                    match get_player_guess_with_hints(player, attempt, &scoreboard) {
+                        TurnChoice::Guess(guess) => break guess,
+                        // Out of time or passed: the lost turn is the only thing to report
+                        TurnChoice::TimedOut => return TurnEnd::Played(Some(fill("guess.time_up", &[("name", &player.name)]))),
+                        TurnChoice::Skip => return TurnEnd::Played(Some(fill("guess.skipped", &[("name", &player.name)]))),
+                        TurnChoice::Quit => return TurnEnd::Quit,
+                        TurnChoice::Save(_) => outln!("{}", tr("save.unavailable")),
+                    }
+                }
+            }
+        },
+    };
+
+    let score = calculate_score(&guess, &player.secret_code);
+    let before = player.candidates.clone();
+    if let Some(bot) = &mut player.bot {
+        bot.observe(&guess, score);
+    }
+    player.guesses += 1;
+    player.history.push((guess, score));
+    filter_candidates(&mut player.candidates, &guess, score);
+
+    let mut notes = vec![format!("{} -> {}", format_code(&guess), player_notation().render(score))];
+    let mut public_note = player.is_bot().then(|| fill("tui.bot_guessed", &[("name", &player.name), ("guess", &format_code(&guess)), ("score", &player_notation().render(score))]));
+
+    if is_solved(score) {
+        // A new round gets the next rank; another finish in the same round ties with it
+        let rank = if round > *last_ranked_round {
+            *rank_to_assign += 1;
+            *rank_to_assign - 1
+        } else {
+            rank_to_assign.saturating_sub(1).max(1)
+        };
+        *last_ranked_round = round;
+        player.rank = Some(rank);
+        player.cracked = true;
+        player.finished = Some((round, started_at.elapsed()));
+
+        sound::play(Cue::Cracked);
+        let place = Locale::current().ordinal(rank);
+        notes.push(fill("tui.cracked", &[("count", &player.guesses), ("place", &place)]));
+        public_note = Some(fill("tui.cracked_public", &[("name", &player.name), ("place", &place)]));
+    } else if Config::current().guess_limit > 0 && player.guesses >= Config::current().guess_limit {
+        player.finished = Some((round, started_at.elapsed()));
+        let out = fill("game.out_of_guesses", &[("name", &player.name), ("limit", &player.guesses), ("secret", &format_code(&player.secret_code))]);
+        notes.push(out.clone());
+        public_note = Some(out);
+    } else {
+        notes.push(fill("tui.remaining", &[("count", &player.candidates.len())]));
+    }
+
+    let is_bot = player.is_bot();
+    draw(players, seat, round, &notes);
+    if is_bot {
+        pause(Duration::from_secs(2));
+    } else {
+        coach_guess(&guess, &before);
+        prompt(tr("tui.end_turn"));
+    }
+    TurnEnd::Played(public_note)
+}
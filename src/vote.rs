@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::code::Guess;
+use crate::strings::fill;
+
+// --- Vote Aggregation ---
+// Turns a stream of chat votes into one guess at a time. Each user gets one
+// vote per window (re-voting replaces their earlier vote), users must wait out
+// a cooldown between messages, and a window only closes once it has enough
+// voters, so a single spammer can't dictate the guess. The caller supplies the
+// clock, which keeps the aggregator independent of any chat transport.
+
+/// Tuning knobs for a `VoteAggregator`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VoteConfig {
+    pub window: Duration,        // How long votes are collected before a guess is picked
+    pub min_votes: usize,        // Distinct voters needed before a window may close
+    pub user_cooldown: Duration, // Minimum gap between two votes from the same user
+    pub round_cooldown: Duration, // Pause after a guess is played before voting reopens
+}
+
+impl Default for VoteConfig {
+    fn default() -> Self {
+        VoteConfig {
+            window: Duration::from_secs(20),
+            min_votes: 3,
+            user_cooldown: Duration::from_secs(5),
+            round_cooldown: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Why a vote was not counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoteRejected {
+    UserCooldown(Duration),  // The user voted too recently; remaining wait
+    RoundCooldown(Duration), // Voting hasn't reopened yet; remaining wait
+}
+
+impl fmt::Display for VoteRejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VoteRejected::UserCooldown(left) => write!(f, "{}", fill("vote.user_cooldown", &[("seconds", &(left.as_secs() + 1))])),
+            VoteRejected::RoundCooldown(left) => write!(f, "{}", fill("vote.round_cooldown", &[("seconds", &(left.as_secs() + 1))])),
+        }
+    }
+}
+
+/// The result of a closed voting window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tally {
+    pub winner: Guess,
+    pub winner_votes: usize,
+    pub total_votes: usize,
+    pub voters: Vec<String>, // Users who voted for the winning guess, in voting order
+}
+
+/// What `poll` found when checked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PollResult {
+    Idle,                       // No window is open yet
+    Collecting { votes: usize, remaining: Duration },
+    NeedMoreVotes { votes: usize, needed: usize }, // Window expired but the threshold isn't met
+    Closed(Tally),
+}
+
+/// Collects chat votes and decides which guess gets played.
+#[derive(Debug)]
+pub struct VoteAggregator {
+    config: VoteConfig,
+    window_opened: Option<Instant>,
+    votes: Vec<(String, Guess)>,            // One entry per user, in the order of their current votes
+    last_vote_at: HashMap<String, Instant>, // For the per-user cooldown
+    reopens_at: Option<Instant>,            // Set after a window closes
+}
+
+impl VoteAggregator {
+    pub fn new(config: VoteConfig) -> Self {
+        VoteAggregator {
+            config,
+            window_opened: None,
+            votes: Vec::new(),
+            last_vote_at: HashMap::new(),
+            reopens_at: None,
+        }
+    }
+
+    pub fn config(&self) -> &VoteConfig {
+        &self.config
+    }
+
+    /// Records a vote. The first vote after a quiet period opens the window.
+    pub fn cast(&mut self, user: &str, guess: Guess, now: Instant) -> Result<(), VoteRejected> {
+        if let Some(reopens_at) = self.reopens_at {
+            if now < reopens_at {
+                return Err(VoteRejected::RoundCooldown(reopens_at - now));
+            }
+            self.reopens_at = None;
+        }
+
+        if let Some(&last) = self.last_vote_at.get(user) {
+            let since = now.saturating_duration_since(last);
+            if since < self.config.user_cooldown {
+                return Err(VoteRejected::UserCooldown(self.config.user_cooldown - since));
+            }
+        }
+        self.last_vote_at.insert(user.to_string(), now);
+
+        // A user changing their mind replaces their vote rather than adding
+        // one, and the new vote counts from when it was cast for tie-breaks
+        self.votes.retain(|(voter, _)| voter != user);
+        self.votes.push((user.to_string(), guess));
+
+        self.window_opened.get_or_insert(now);
+        Ok(())
+    }
+
+    /// Checks whether the current window can close, returning the winner if so.
+    pub fn poll(&mut self, now: Instant) -> PollResult {
+        let Some(opened) = self.window_opened else {
+            return PollResult::Idle;
+        };
+
+        let elapsed = now.saturating_duration_since(opened);
+        if elapsed < self.config.window {
+            return PollResult::Collecting { votes: self.votes.len(), remaining: self.config.window - elapsed };
+        }
+
+        if self.votes.len() < self.config.min_votes {
+            // Keep the window open until enough different people have voted
+            return PollResult::NeedMoreVotes { votes: self.votes.len(), needed: self.config.min_votes };
+        }
+
+        let tally = self.tally();
+        self.window_opened = None;
+        self.votes.clear();
+        self.reopens_at = Some(now + self.config.round_cooldown);
+        PollResult::Closed(tally)
+    }
+
+    /// Counts the current votes. Ties go to the guess with the earliest standing vote.
+    fn tally(&self) -> Tally {
+        let mut counts: Vec<(Guess, usize)> = Vec::new();
+        for (_, guess) in &self.votes {
+            match counts.iter_mut().find(|(g, _)| g == guess) {
+                Some(entry) => entry.1 += 1,
+                None => counts.push((*guess, 1)),
+            }
+        }
+
+        // `max_by_key` keeps the last maximum, so walk in reverse to favour the earliest
+        let (winner, winner_votes) = counts.iter().rev().max_by_key(|(_, n)| *n).copied().unwrap();
+        let voters = self.votes.iter().filter(|(_, g)| *g == winner).map(|(u, _)| u.clone()).collect();
+
+        Tally { winner, winner_votes, total_votes: self.votes.len(), voters }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: VoteConfig = VoteConfig {
+        window: Duration::from_secs(20),
+        min_votes: 2,
+        user_cooldown: Duration::from_secs(5),
+        round_cooldown: Duration::from_secs(5),
+    };
+
+    fn code(digits: &str) -> Guess {
+        Guess::from_digits(&digits.bytes().map(|b| b - b'0').collect::<Vec<u8>>())
+    }
+
+    fn secs(seconds: u64) -> Duration {
+        Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn a_user_waits_out_the_cooldown_between_votes() {
+        let (start, mut votes) = (Instant::now(), VoteAggregator::new(CONFIG));
+        assert_eq!(votes.cast("ann", code("0123"), start), Ok(()));
+        assert_eq!(votes.cast("ann", code("4567"), start + secs(2)), Err(VoteRejected::UserCooldown(secs(3))));
+        assert_eq!(votes.cast("bob", code("4567"), start + secs(2)), Ok(()));
+        assert_eq!(votes.cast("ann", code("4567"), start + secs(5)), Ok(()));
+    }
+
+    #[test]
+    fn a_second_vote_replaces_the_first() {
+        let (start, mut votes) = (Instant::now(), VoteAggregator::new(CONFIG));
+        votes.cast("ann", code("0123"), start).unwrap();
+        votes.cast("bob", code("0123"), start + secs(1)).unwrap();
+        votes.cast("ann", code("4567"), start + secs(10)).unwrap();
+        let PollResult::Closed(tally) = votes.poll(start + secs(20)) else { panic!("the window should close") };
+        assert_eq!(tally.total_votes, 2);
+        assert_eq!((tally.winner, tally.winner_votes), (code("0123"), 1));
+        assert_eq!(tally.voters, ["bob"]);
+    }
+
+    #[test]
+    fn a_window_stays_open_until_enough_users_vote() {
+        let (start, mut votes) = (Instant::now(), VoteAggregator::new(CONFIG));
+        assert_eq!(votes.poll(start), PollResult::Idle);
+        votes.cast("ann", code("0123"), start).unwrap();
+        assert_eq!(votes.poll(start + secs(5)), PollResult::Collecting { votes: 1, remaining: secs(15) });
+        assert_eq!(votes.poll(start + secs(30)), PollResult::NeedMoreVotes { votes: 1, needed: 2 });
+        votes.cast("ann", code("4567"), start + secs(40)).unwrap();
+        assert_eq!(votes.poll(start + secs(41)), PollResult::NeedMoreVotes { votes: 1, needed: 2 });
+
+        votes.cast("bob", code("4567"), start + secs(42)).unwrap();
+        let PollResult::Closed(tally) = votes.poll(start + secs(42)) else { panic!("the window should close") };
+        assert_eq!((tally.winner, tally.winner_votes, tally.total_votes), (code("4567"), 2, 2));
+
+        // Voting reopens after the round cooldown, with an empty window
+        assert_eq!(votes.cast("cat", code("0123"), start + secs(44)), Err(VoteRejected::RoundCooldown(secs(3))));
+        assert_eq!(votes.poll(start + secs(44)), PollResult::Idle);
+        assert_eq!(votes.cast("cat", code("0123"), start + secs(47)), Ok(()));
+        assert_eq!(votes.poll(start + secs(48)), PollResult::Collecting { votes: 1, remaining: secs(19) });
+    }
+
+    #[test]
+    fn a_tie_goes_to_the_guess_with_the_earliest_vote() {
+        let (start, mut votes) = (Instant::now(), VoteAggregator::new(CONFIG));
+        votes.cast("ann", code("4567"), start).unwrap();
+        votes.cast("bob", code("0123"), start + secs(1)).unwrap();
+        votes.cast("cat", code("0123"), start + secs(2)).unwrap();
+        votes.cast("dan", code("4567"), start + secs(3)).unwrap();
+        let PollResult::Closed(tally) = votes.poll(start + secs(20)) else { panic!("the window should close") };
+        assert_eq!((tally.winner, tally.winner_votes, tally.total_votes), (code("4567"), 2, 4));
+        assert_eq!(tally.voters, ["ann", "dan"]);
+    }
+}